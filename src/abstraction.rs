@@ -1,8 +1,18 @@
 use std::error::Error;
 use winit::event_loop::{ActiveEventLoop, EventLoop};
-use winit::window::WindowId;
+use winit::window::{Window, WindowId};
 use crate::color::Color;
-use crate::data::Size;
+use crate::commands::CommandBinding;
+use crate::components::ContextMenu;
+use crate::data::{ComponentId, Orientation, Rectangle, Size, Transform};
+use crate::focus::{FocusScope, Focusable};
+use crate::image::{Image, ImageInterpolation};
+use crate::input::{EventContext, InputEvent};
+use crate::overlay::PopupPlacement;
+use crate::text::{FontMetrics, TextStyle};
+use crate::tool_tip::ToolTip;
+use crate::visuals::PathCommand;
+use std::rc::Rc;
 
 pub trait RenderSurface : Renderer {
     fn create(
@@ -12,6 +22,8 @@ pub trait RenderSurface : Renderer {
 
 
     fn window_id(&self) -> WindowId;
+    fn window(&self) -> &Window;
+    fn text_metrics(&self) -> Rc<dyn TextMetrics>;
     fn start_rendering(&mut self);
     fn renderer(&mut self) -> &mut dyn Renderer;
     fn finish_rendering(&mut self);
@@ -19,13 +31,66 @@ pub trait RenderSurface : Renderer {
 
 pub trait Renderer {
     fn draw_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, fill: Color);
+    fn stroke_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, stroke: Color, thickness: f32);
+    fn draw_rounded_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32, fill: Color);
+    fn stroke_rounded_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32, stroke: Color, thickness: f32);
+    /// Draws *text* in a single line, starting at *x* with its baseline at *y*.
+    fn draw_text(&mut self, x: f32, y: f32, text: &str, style: &TextStyle);
+    fn fill_path(&mut self, commands: &[PathCommand], fill: Color);
+    fn stroke_path(&mut self, commands: &[PathCommand], stroke: Color, thickness: f32);
+    /// Fills the rectangle with a gradient through *colors*, spaced evenly from the left to the
+    /// right edge for [`Orientation::Horizontal`] or from the top to the bottom edge for
+    /// [`Orientation::Vertical`].
+    fn draw_linear_gradient(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        orientation: Orientation,
+        colors: &[Color],
+    );
+    /// Draws *image* stretched to the rectangle.
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        interpolation: ImageInterpolation,
+    );
     fn translate(&mut self, x: f32, y: f32);
+    /// Applies *transform* to everything drawn until the matching *pop_transform*, before the
+    /// transformations already applied. Translations must be undone before calling
+    /// *pop_transform*.
+    fn push_transform(&mut self, transform: Transform);
+    fn pop_transform(&mut self);
+    /// Restricts drawing to the intersection of the current clip and the given rectangle,
+    /// until the matching *pop_clip*. Translations must be undone before calling *pop_clip*.
+    fn push_clip(&mut self, x: f32, y: f32, width: f32, height: f32);
+    fn pop_clip(&mut self);
 }
+/// # Description
+/// Measures text, as it would be drawn by the corresponding [`Renderer`].
+pub trait TextMetrics {
+    fn font_metrics(&self, style: &TextStyle) -> FontMetrics;
+    /// Measures the advance width of *text* drawn in a single line.
+    fn measure_text(&self, text: &str, style: &TextStyle) -> f32;
+}
+
 pub trait Visual {
     fn draw(&self, renderer: &mut dyn Renderer);
 }
 
 pub trait Component {
+    /// # Description
+    /// The id of this component instance.
+    ///
+    /// # Remarks
+    /// The id must never change during the lifetime of the component.
+    fn id(&self) -> ComponentId;
+
     /// # Description
     /// Measures the size in layout required to render this.
     ///
@@ -77,6 +142,18 @@ pub trait Component {
     /// Make sure to always call every *arrange* of every child!
     fn arrange(&mut self, given: Size<f32>) -> Size<f32>;
 
+    /// # Description
+    /// Informs this component about the part of it visible in a scroll area, eg. the
+    /// [`crate::components::ScrollViewer`] it is the content of.
+    ///
+    /// # Parameters
+    /// - **viewport**: The visible area, relative to this component.
+    ///
+    /// # Remarks
+    /// Called before *arrange*, allowing virtualizing components to only create and arrange the
+    /// children that are visible. The default implementation ignores the viewport.
+    fn set_viewport(&mut self, _viewport: Rectangle<f32>) {}
+
     /// # Description
     /// Handles the rendering of the components visuals.
     ///
@@ -93,8 +170,156 @@ pub trait Component {
     /// With this must be fast, i, the doc author, do mean that this must be fast.
     /// This is the "rendering loop".
     fn render(&self, renderer: &mut dyn Renderer);
+
+    /// # Description
+    /// Handles an input event dispatched to this component.
+    ///
+    /// # Parameters
+    /// - **event**: The event to handle. Positions are relative to this component.
+    /// - **context**: The context of the dispatch, used to eg. capture the pointer.
+    ///
+    /// # Returns
+    /// Whether the event was handled by this component.
+    ///
+    /// # Remarks
+    /// Pointer events are only dispatched to a component if the pointer is within the bounds
+    /// of the component or if the component captured the pointer.
+    /// The default implementation ignores every event.
+    fn handle_event(&mut self, _event: &InputEvent, _context: &mut EventContext) -> bool {
+        false
+    }
+
+    /// # Description
+    /// Visits the child components of this component.
+    ///
+    /// # Parameters
+    /// - **visitor**: Callback receiving every child, together with the bounds it was arranged
+    ///                in, relative to this component.
+    ///
+    /// # Remarks
+    /// Children have to be visited in the order they are rendered in. The bounds passed must
+    /// match the translation applied to the renderer when rendering the child.
+    /// Components not visiting their children here will not have them take part in eg. event
+    /// dispatching or focus navigation.
+    /// The default implementation visits nothing.
+    fn visit_children(&self, _visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {}
+
+    /// # Description
+    /// Visits the child components of this component mutably.
+    ///
+    /// # Remarks
+    /// See *visit_children*. Both methods have to visit the same children in the same order.
+    fn visit_children_mut(
+        &mut self,
+        _visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+    }
+
+    /// # Description
+    /// Visits the popups this component currently shows, eg. the drop-down list of a combo box,
+    /// along with where to place them.
+    ///
+    /// # Remarks
+    /// Popups are measured, arranged and placed by the window after this component was
+    /// arranged, and rendered on top of all other content, without being clipped by their
+    /// parents. Events not handled by a popup bubble up to this component.
+    /// Pressing the pointer outside of this component and its popups dispatches
+    /// [`InputEvent::LightDismiss`] to this component.
+    /// The default implementation visits nothing.
+    fn visit_popups(&self, _visitor: &mut dyn FnMut(PopupPlacement, &dyn Component)) {}
+
+    /// # Description
+    /// Visits the popups of this component mutably.
+    ///
+    /// # Remarks
+    /// See *visit_popups*. Both methods have to visit the same popups in the same order.
+    fn visit_popups_mut(&mut self, _visitor: &mut dyn FnMut(PopupPlacement, &mut dyn Component)) {}
+
+    /// # Description
+    /// Whether and how this component can receive the keyboard focus.
+    ///
+    /// # Remarks
+    /// The default implementation is not focusable.
+    fn focusable(&self) -> Option<Focusable> {
+        None
+    }
+
+    /// # Description
+    /// Whether this component groups the focusable components below it into a focus scope.
+    ///
+    /// # Remarks
+    /// The default implementation is no focus scope.
+    fn focus_scope(&self) -> Option<FocusScope> {
+        None
+    }
+
+    /// # Description
+    /// The key gestures bound to commands at the scope of this component.
+    ///
+    /// # Remarks
+    /// The bindings are active while this component or one of its children has the keyboard
+    /// focus and the key press was not handled by the focused component.
+    /// Bindings of inner components take precedence over the ones of outer components and
+    /// the ones of the window.
+    /// The default implementation has no bindings.
+    fn command_bindings(&self) -> &[CommandBinding] {
+        &[]
+    }
+
+    /// # Description
+    /// Whether this component accepts text from input methods while focused, and where.
+    ///
+    /// # Returns
+    /// The area text currently is inserted at (eg. the caret or the text being composed),
+    /// relative to this component, or `None` if no text input is accepted.
+    ///
+    /// # Remarks
+    /// The window enables input methods while the focused component returns an area and
+    /// positions the candidate window of the input method next to it.
+    /// The default implementation accepts no text input.
+    fn text_input_area(&self) -> Option<Rectangle<f32>> {
+        None
+    }
+
+    /// # Description
+    /// Whether this component currently plays an animation.
+    ///
+    /// # Remarks
+    /// While any component of a window is animating, the window is rendered continuously and
+    /// dispatches an [`InputEvent::Tick`] every frame, which animations advance on.
+    /// The default implementation never animates.
+    fn is_animating(&self) -> bool {
+        false
+    }
+
+    /// # Description
+    /// The tool tip shown while the pointer hovers this component.
+    ///
+    /// # Remarks
+    /// The window shows the tool tip of the innermost hovered component having one.
+    /// The default implementation has no tool tip.
+    fn tool_tip(&self) -> Option<&ToolTip> {
+        None
+    }
+
+    /// # Description
+    /// Creates the context menu opened by right-clicking this component, or by pressing the
+    /// context menu key while it has the keyboard focus.
+    ///
+    /// # Remarks
+    /// Called every time the context menu is opened. The window opens the context menu of the
+    /// innermost component having one, unless the component handled the click itself.
+    /// The default implementation has no context menu.
+    fn context_menu(&self) -> Option<ContextMenu> {
+        None
+    }
 }
 
+/// # Description
+/// A component hosting children added to it after construction.
+///
+/// # Remarks
+/// Components hosting a single child replace it when another child is added.
 pub trait ContainerComponent : Component {
     fn add_child(&mut self, child: Box<dyn Component>);
 }
\ No newline at end of file
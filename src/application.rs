@@ -1,14 +1,19 @@
 use crate::abstraction::{RenderSurface, Renderer};
+use crate::clipboard::{self, Clipboard, MemoryClipboard};
+use crate::data::Point;
+use crate::input::{InputEvent, PIXELS_PER_WHEEL_LINE};
 use crate::renderers::SkiaRenderer;
 use crate::window::AppWindow;
 use log::{error, info, log, warn};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::rc::Rc;
 use winit::application::ApplicationHandler;
 use winit::error::{EventLoopError, OsError};
-use winit::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
+use std::time::Instant;
+use winit::event::{DeviceEvent, DeviceId, ElementState, Ime, MouseScrollDelta, StartCause, WindowEvent};
 use winit::event_loop;
-use winit::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopBuilder};
 use winit::window::{Window, WindowAttributes, WindowId};
 
 #[derive(Debug)]
@@ -44,14 +49,32 @@ impl Error for AppErrors {}
 #[derive(Debug)]
 pub enum AppEvents {}
 
-pub struct AppBuilder {}
+pub struct AppBuilder {
+    clipboard: Option<Rc<dyn Clipboard>>,
+    content: Option<Rc<dyn Fn(&mut AppWindow)>>,
+}
 
 type EventLoopAttributes = AppEvents;
 
 impl AppBuilder {
     pub fn new() -> Self {
-        AppBuilder {}
+        AppBuilder { clipboard: None, content: None }
+    }
+
+    /// # Description
+    /// Replaces the clipboard of the platform, eg. with a [`MemoryClipboard`] for tests.
+    pub fn with_clipboard(mut self, clipboard: Rc<dyn Clipboard>) -> Self {
+        self.clipboard = Some(clipboard);
+        self
+    }
+
+    /// # Description
+    /// Fills every window created with content, eg. by calling [`AppWindow::add_content`].
+    pub fn with_content<F: Fn(&mut AppWindow) + 'static>(mut self, f: F) -> Self {
+        self.content = Some(Rc::new(f));
+        self
     }
+
     pub fn run<
         F: Fn(&ActiveEventLoop) -> Result<Box<dyn RenderSurface>, Box<dyn Error>> + 'static,
     >(
@@ -62,7 +85,11 @@ impl AppBuilder {
             Ok(d) => d,
             Err(e) => return Err(AppErrors::WinitEventLoopError(e)),
         };
-        let mut app = App::new(f);
+        let clipboard = match self.clipboard.as_ref() {
+            Some(d) => d.clone(),
+            None => App::system_clipboard(),
+        };
+        let mut app = App::new(f, clipboard, self.content.clone());
         match event_loop.run_app(&mut app) {
             Ok(_) => match app.last_error {
                 None => Ok(app),
@@ -82,6 +109,9 @@ pub struct App {
     last_error: Option<AppErrors>,
     surface_factory:
         Box<dyn Fn(&ActiveEventLoop) -> Result<Box<dyn RenderSurface>, Box<dyn Error>>>,
+    clipboard: Rc<dyn Clipboard>,
+    /// Fills the windows created with content.
+    content_factory: Option<Rc<dyn Fn(&mut AppWindow)>>,
 }
 
 enum AppFindWindowResult {
@@ -93,12 +123,45 @@ enum AppFindWindowResult {
 impl App {
     fn new<F: Fn(&ActiveEventLoop) -> Result<Box<dyn RenderSurface>, Box<dyn Error>> + 'static>(
         renderer_factory: F,
+        clipboard: Rc<dyn Clipboard>,
+        content_factory: Option<Rc<dyn Fn(&mut AppWindow)>>,
     ) -> Self {
+        clipboard::set_clipboard(clipboard.clone());
         App {
             windows: vec![],
             last_error: None,
             surface_factory: Box::new(renderer_factory),
+            clipboard,
+            content_factory,
+        }
+    }
+
+    /// # Description
+    /// The clipboard of the platform, falling back to a [`MemoryClipboard`] if it is not
+    /// available.
+    fn system_clipboard() -> Rc<dyn Clipboard> {
+        #[cfg(feature = "system-clipboard")]
+        match clipboard::SystemClipboard::new() {
+            Ok(d) => return Rc::new(d),
+            Err(e) => warn!("{}, falling back to an in-memory clipboard", e),
         }
+        Rc::new(MemoryClipboard::new())
+    }
+
+    /// # Description
+    /// The clipboard used by this application.
+    ///
+    /// # Remarks
+    /// Components access the same clipboard through [`clipboard::clipboard`].
+    pub fn clipboard(&self) -> Rc<dyn Clipboard> {
+        self.clipboard.clone()
+    }
+
+    /// # Description
+    /// Replaces the clipboard used by this application and its components.
+    pub fn set_clipboard(&mut self, clipboard: Rc<dyn Clipboard>) {
+        clipboard::set_clipboard(clipboard.clone());
+        self.clipboard = clipboard;
     }
 
     pub(crate) fn count_active_windows(&self) -> usize {
@@ -152,6 +215,7 @@ impl App {
                         Some(render_surface),
                         generation,
                     );
+                    self.fill_window(i);
                     self.windows[i].render();
                     return Ok(AppWindowId {
                         index: i,
@@ -171,6 +235,7 @@ impl App {
         }
         let app_window = AppWindow::new(Some(render_surface.window_id()), Some(render_surface), 1);
         self.windows.push(app_window);
+        self.fill_window(index);
         let app_window = self.windows.last_mut().unwrap();
         app_window.render();
         Ok(AppWindowId {
@@ -178,13 +243,27 @@ impl App {
             generation: 1,
         })
     }
+
+    /// Adds the content to the window at *index*, which was just created.
+    fn fill_window(&mut self, index: usize) {
+        if let Some(content_factory) = self.content_factory.as_ref() {
+            content_factory(&mut self.windows[index]);
+        }
+    }
 }
 
 impl ApplicationHandler<EventLoopAttributes> for App {
     fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
         info!("Application received new events with cause {:?}", cause);
         match cause {
-            StartCause::ResumeTimeReached { .. } => {}
+            StartCause::ResumeTimeReached { .. } => {
+                let now = Instant::now();
+                for window in self.windows.iter_mut() {
+                    if window.render_surface.is_some() {
+                        window.wake_up(now);
+                    }
+                }
+            }
             StartCause::WaitCancelled { .. } => {}
             StartCause::Poll => {}
             StartCause::Init => match self.create_window(event_loop) {
@@ -255,15 +334,83 @@ impl ApplicationHandler<EventLoopAttributes> for App {
             WindowEvent::DroppedFile(_) => {}
             WindowEvent::HoveredFile(_) => {}
             WindowEvent::HoveredFileCancelled => {}
-            WindowEvent::Focused(_) => {}
-            WindowEvent::KeyboardInput { .. } => {}
-            WindowEvent::ModifiersChanged(_) => {}
-            WindowEvent::Ime(_) => {}
-            WindowEvent::CursorLeft { .. } => {}
-            WindowEvent::CursorMoved { .. } => {}
+            WindowEvent::Focused(focused) => {
+                self.windows[app_window_id.index].set_window_focused(focused);
+            }
+            WindowEvent::KeyboardInput {
+                event,
+                is_synthetic,
+                ..
+            } => {
+                // Synthetic events are issued for keys held while the focus changes
+                if !is_synthetic {
+                    let window = &mut self.windows[app_window_id.index];
+                    let modifiers = window.modifiers;
+                    window.dispatch(match event.state {
+                        ElementState::Pressed => InputEvent::KeyPressed {
+                            key: event.logical_key,
+                            text: event.text,
+                            modifiers,
+                            repeat: event.repeat,
+                        },
+                        ElementState::Released => InputEvent::KeyReleased {
+                            key: event.logical_key,
+                            modifiers,
+                        },
+                    });
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.windows[app_window_id.index].modifiers = modifiers.state();
+            }
+            WindowEvent::Ime(Ime::Commit(text)) => {
+                self.windows[app_window_id.index].dispatch(InputEvent::ImeCommit { text });
+            }
+            WindowEvent::Ime(Ime::Preedit(text, cursor)) => {
+                self.windows[app_window_id.index].dispatch(InputEvent::ImePreedit { text, cursor });
+            }
+            // Composing ends with the input method, without committing any text
+            WindowEvent::Ime(Ime::Disabled) => {
+                self.windows[app_window_id.index].dispatch(InputEvent::ImePreedit {
+                    text: String::new(),
+                    cursor: None,
+                });
+            }
+            WindowEvent::Ime(Ime::Enabled) => {}
+            WindowEvent::CursorLeft { .. } => {
+                self.windows[app_window_id.index].pointer_left();
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.windows[app_window_id.index].dispatch(InputEvent::PointerMoved {
+                    position: Point {
+                        x: position.x as f32,
+                        y: position.y as f32,
+                    },
+                });
+            }
             WindowEvent::CursorEntered { .. } => {}
-            WindowEvent::MouseWheel { .. } => {}
-            WindowEvent::MouseInput { .. } => {}
+            WindowEvent::MouseWheel { delta, .. } => {
+                let window = &mut self.windows[app_window_id.index];
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => Point { x, y },
+                    MouseScrollDelta::PixelDelta(d) => Point {
+                        x: d.x as f32 / PIXELS_PER_WHEEL_LINE,
+                        y: d.y as f32 / PIXELS_PER_WHEEL_LINE,
+                    },
+                };
+                window.dispatch(InputEvent::PointerWheel {
+                    position: window.pointer.position(),
+                    delta,
+                });
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let window = &mut self.windows[app_window_id.index];
+                let position = window.pointer.position();
+                window.dispatch(match state {
+                    ElementState::Pressed => InputEvent::PointerPressed { position, button },
+                    ElementState::Released => InputEvent::PointerReleased { position, button },
+                });
+            }
             WindowEvent::PinchGesture { .. } => {}
             WindowEvent::PanGesture { .. } => {}
             WindowEvent::DoubleTapGesture { .. } => {}
@@ -291,9 +438,18 @@ impl ApplicationHandler<EventLoopAttributes> for App {
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let mut next_wake_up: Option<Instant> = None;
         for window in self.windows.iter_mut() {
             window.render();
+            next_wake_up = match (next_wake_up, window.next_wake_up) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
         }
+        event_loop.set_control_flow(match next_wake_up {
+            None => ControlFlow::Wait,
+            Some(at) => ControlFlow::WaitUntil(at),
+        });
         info!("Application is waiting for new events");
     }
 
@@ -0,0 +1,361 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// # Description
+/// The clipboards a [`Clipboard`] provides access to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ClipboardKind {
+    /// The regular clipboard, used by copy and paste.
+    Clipboard,
+    /// The primary selection, holding the last selected text and pasted with the middle
+    /// mouse button. Only available on Linux.
+    Primary,
+}
+
+/// # Description
+/// An image on a clipboard.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClipboardImage {
+    pub width: usize,
+    pub height: usize,
+    /// The pixels of the image, row by row, 4 bytes (red, green, blue, alpha) per pixel.
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum ClipboardErrors {
+    /// The clipboard holds no content in the requested format.
+    Empty,
+    /// The clipboard or the format is not supported on this platform.
+    Unsupported,
+    /// The platform failed to access the clipboard.
+    Platform(String),
+}
+
+impl Display for ClipboardErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardErrors::Empty => write!(f, "Clipboard is empty"),
+            ClipboardErrors::Unsupported => write!(f, "Clipboard is not supported"),
+            ClipboardErrors::Platform(d) => write!(f, "Clipboard access failed ({})", d),
+        }
+    }
+}
+
+impl Error for ClipboardErrors {}
+
+/// # Description
+/// Provides access to the clipboards of the platform.
+///
+/// # Remarks
+/// Setting content in one format replaces the content in all other formats.
+pub trait Clipboard {
+    fn text(&self, kind: ClipboardKind) -> Result<String, ClipboardErrors>;
+    fn set_text(&self, kind: ClipboardKind, text: &str) -> Result<(), ClipboardErrors>;
+    fn html(&self, kind: ClipboardKind) -> Result<String, ClipboardErrors>;
+
+    /// # Description
+    /// Replaces the content of the clipboard with *html*.
+    ///
+    /// # Parameters
+    /// - **alt_text**: The plain text offered to applications not accepting HTML.
+    fn set_html(
+        &self,
+        kind: ClipboardKind,
+        html: &str,
+        alt_text: Option<&str>,
+    ) -> Result<(), ClipboardErrors>;
+    fn image(&self, kind: ClipboardKind) -> Result<ClipboardImage, ClipboardErrors>;
+    fn set_image(&self, kind: ClipboardKind, image: &ClipboardImage) -> Result<(), ClipboardErrors>;
+}
+
+#[derive(Clone, Debug)]
+struct ClipboardContent {
+    text: Option<String>,
+    html: Option<String>,
+    image: Option<ClipboardImage>,
+}
+
+/// # Description
+/// A clipboard only known to this process, eg. for tests.
+pub struct MemoryClipboard {
+    contents: RefCell<HashMap<ClipboardKind, ClipboardContent>>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> MemoryClipboard {
+        MemoryClipboard {
+            contents: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn get<T, F: Fn(&ClipboardContent) -> Option<T>>(
+        &self,
+        kind: ClipboardKind,
+        f: F,
+    ) -> Result<T, ClipboardErrors> {
+        match self.contents.borrow().get(&kind).and_then(f) {
+            None => Err(ClipboardErrors::Empty),
+            Some(d) => Ok(d),
+        }
+    }
+
+    fn set(&self, kind: ClipboardKind, content: ClipboardContent) -> Result<(), ClipboardErrors> {
+        self.contents.borrow_mut().insert(kind, content);
+        Ok(())
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn text(&self, kind: ClipboardKind) -> Result<String, ClipboardErrors> {
+        self.get(kind, |d| d.text.clone())
+    }
+
+    fn set_text(&self, kind: ClipboardKind, text: &str) -> Result<(), ClipboardErrors> {
+        self.set(
+            kind,
+            ClipboardContent {
+                text: Some(text.to_string()),
+                html: None,
+                image: None,
+            },
+        )
+    }
+
+    fn html(&self, kind: ClipboardKind) -> Result<String, ClipboardErrors> {
+        self.get(kind, |d| d.html.clone())
+    }
+
+    fn set_html(
+        &self,
+        kind: ClipboardKind,
+        html: &str,
+        alt_text: Option<&str>,
+    ) -> Result<(), ClipboardErrors> {
+        self.set(
+            kind,
+            ClipboardContent {
+                text: alt_text.map(|d| d.to_string()),
+                html: Some(html.to_string()),
+                image: None,
+            },
+        )
+    }
+
+    fn image(&self, kind: ClipboardKind) -> Result<ClipboardImage, ClipboardErrors> {
+        self.get(kind, |d| d.image.clone())
+    }
+
+    fn set_image(&self, kind: ClipboardKind, image: &ClipboardImage) -> Result<(), ClipboardErrors> {
+        self.set(
+            kind,
+            ClipboardContent {
+                text: None,
+                html: None,
+                image: Some(image.clone()),
+            },
+        )
+    }
+}
+
+/// # Description
+/// The clipboard of the operating system.
+///
+/// # Remarks
+/// Images are only exchanged with the `clipboard-images` feature, enabled by default.
+/// Without it, reading or writing an image fails with [`ClipboardErrors::Unsupported`].
+#[cfg(feature = "system-clipboard")]
+pub struct SystemClipboard {
+    clipboard: RefCell<arboard::Clipboard>,
+}
+
+#[cfg(feature = "system-clipboard")]
+impl SystemClipboard {
+    pub fn new() -> Result<SystemClipboard, ClipboardErrors> {
+        match arboard::Clipboard::new() {
+            Ok(d) => Ok(SystemClipboard {
+                clipboard: RefCell::new(d),
+            }),
+            Err(e) => Err(ClipboardErrors::from(e)),
+        }
+    }
+
+    fn get<T, F: FnOnce(arboard::Get) -> Result<T, arboard::Error>>(
+        &self,
+        kind: ClipboardKind,
+        f: F,
+    ) -> Result<T, ClipboardErrors> {
+        let mut clipboard = self.clipboard.borrow_mut();
+        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+        let get = {
+            use arboard::GetExtLinux;
+            clipboard.get().clipboard(linux_kind(kind))
+        };
+        #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten")))))]
+        let get = match kind {
+            ClipboardKind::Clipboard => clipboard.get(),
+            ClipboardKind::Primary => return Err(ClipboardErrors::Unsupported),
+        };
+        f(get).map_err(ClipboardErrors::from)
+    }
+
+    fn set<F: FnOnce(arboard::Set) -> Result<(), arboard::Error>>(
+        &self,
+        kind: ClipboardKind,
+        f: F,
+    ) -> Result<(), ClipboardErrors> {
+        let mut clipboard = self.clipboard.borrow_mut();
+        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+        let set = {
+            use arboard::SetExtLinux;
+            clipboard.set().clipboard(linux_kind(kind))
+        };
+        #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten")))))]
+        let set = match kind {
+            ClipboardKind::Clipboard => clipboard.set(),
+            ClipboardKind::Primary => return Err(ClipboardErrors::Unsupported),
+        };
+        f(set).map_err(ClipboardErrors::from)
+    }
+}
+
+#[cfg(all(
+    feature = "system-clipboard",
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+))]
+fn linux_kind(kind: ClipboardKind) -> arboard::LinuxClipboardKind {
+    match kind {
+        ClipboardKind::Clipboard => arboard::LinuxClipboardKind::Clipboard,
+        ClipboardKind::Primary => arboard::LinuxClipboardKind::Primary,
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+impl From<arboard::Error> for ClipboardErrors {
+    fn from(error: arboard::Error) -> Self {
+        match error {
+            arboard::Error::ContentNotAvailable => ClipboardErrors::Empty,
+            arboard::Error::ClipboardNotSupported => ClipboardErrors::Unsupported,
+            error => ClipboardErrors::Platform(error.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+impl Clipboard for SystemClipboard {
+    fn text(&self, kind: ClipboardKind) -> Result<String, ClipboardErrors> {
+        self.get(kind, |get| get.text())
+    }
+
+    fn set_text(&self, kind: ClipboardKind, text: &str) -> Result<(), ClipboardErrors> {
+        self.set(kind, |set| set.text(text))
+    }
+
+    fn html(&self, kind: ClipboardKind) -> Result<String, ClipboardErrors> {
+        self.get(kind, |get| get.html())
+    }
+
+    fn set_html(
+        &self,
+        kind: ClipboardKind,
+        html: &str,
+        alt_text: Option<&str>,
+    ) -> Result<(), ClipboardErrors> {
+        self.set(kind, |set| set.html(html, alt_text))
+    }
+
+    #[cfg(feature = "clipboard-images")]
+    fn image(&self, kind: ClipboardKind) -> Result<ClipboardImage, ClipboardErrors> {
+        self.get(kind, |get| get.image()).map(|image| ClipboardImage {
+            width: image.width,
+            height: image.height,
+            bytes: image.bytes.into_owned(),
+        })
+    }
+
+    #[cfg(not(feature = "clipboard-images"))]
+    fn image(&self, _kind: ClipboardKind) -> Result<ClipboardImage, ClipboardErrors> {
+        Err(ClipboardErrors::Unsupported)
+    }
+
+    #[cfg(feature = "clipboard-images")]
+    fn set_image(&self, kind: ClipboardKind, image: &ClipboardImage) -> Result<(), ClipboardErrors> {
+        self.set(kind, |set| {
+            set.image(arboard::ImageData {
+                width: image.width,
+                height: image.height,
+                bytes: image.bytes.as_slice().into(),
+            })
+        })
+    }
+
+    #[cfg(not(feature = "clipboard-images"))]
+    fn set_image(&self, _kind: ClipboardKind, _image: &ClipboardImage) -> Result<(), ClipboardErrors> {
+        Err(ClipboardErrors::Unsupported)
+    }
+}
+
+thread_local! {
+    static CLIPBOARD: RefCell<Rc<dyn Clipboard>> = RefCell::new(Rc::new(MemoryClipboard::new()));
+}
+
+/// # Description
+/// The clipboard used by components, eg. to copy and paste text.
+///
+/// # Remarks
+/// This is the clipboard of the running [`crate::application::App`].
+pub fn clipboard() -> Rc<dyn Clipboard> {
+    CLIPBOARD.with(|clipboard| clipboard.borrow().clone())
+}
+
+pub(crate) fn set_clipboard(clipboard: Rc<dyn Clipboard>) {
+    CLIPBOARD.with(|d| *d.borrow_mut() = clipboard);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_clipboard_text() {
+        let clipboard = MemoryClipboard::new();
+        assert!(matches!(clipboard.text(ClipboardKind::Clipboard), Err(ClipboardErrors::Empty)));
+        clipboard.set_text(ClipboardKind::Clipboard, "Hello").unwrap();
+        clipboard.set_text(ClipboardKind::Primary, "World").unwrap();
+        assert_eq!(clipboard.text(ClipboardKind::Clipboard).unwrap(), "Hello");
+        assert_eq!(clipboard.text(ClipboardKind::Primary).unwrap(), "World");
+        assert!(matches!(clipboard.html(ClipboardKind::Clipboard), Err(ClipboardErrors::Empty)));
+    }
+
+    #[test]
+    fn test_memory_clipboard_html() {
+        let clipboard = MemoryClipboard::new();
+        clipboard.set_text(ClipboardKind::Clipboard, "Plain").unwrap();
+        clipboard.set_html(ClipboardKind::Clipboard, "<b>Bold</b>", Some("Bold")).unwrap();
+        assert_eq!(clipboard.html(ClipboardKind::Clipboard).unwrap(), "<b>Bold</b>");
+        assert_eq!(clipboard.text(ClipboardKind::Clipboard).unwrap(), "Bold");
+        // Without an alternative text, the previous text is replaced as well
+        clipboard.set_html(ClipboardKind::Clipboard, "<i>Italic</i>", None).unwrap();
+        assert_eq!(clipboard.html(ClipboardKind::Clipboard).unwrap(), "<i>Italic</i>");
+        assert!(matches!(clipboard.text(ClipboardKind::Clipboard), Err(ClipboardErrors::Empty)));
+    }
+
+    #[test]
+    fn test_memory_clipboard_image() {
+        let clipboard = MemoryClipboard::new();
+        let image = ClipboardImage {
+            width: 2,
+            height: 1,
+            bytes: vec![255, 0, 0, 255, 0, 0, 255, 128],
+        };
+        clipboard.set_text(ClipboardKind::Clipboard, "Replaced").unwrap();
+        clipboard.set_image(ClipboardKind::Clipboard, &image).unwrap();
+        assert_eq!(clipboard.image(ClipboardKind::Clipboard).unwrap(), image);
+        assert!(matches!(clipboard.text(ClipboardKind::Clipboard), Err(ClipboardErrors::Empty)));
+        assert!(matches!(clipboard.image(ClipboardKind::Primary), Err(ClipboardErrors::Empty)));
+    }
+}
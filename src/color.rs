@@ -1,4 +1,22 @@
-#[derive(Copy, Clone, Debug)]
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum ColorErrors {
+    InvalidHex(String),
+}
+
+impl Display for ColorErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorErrors::InvalidHex(d) => write!(f, "Invalid hex color ({})", d),
+        }
+    }
+}
+
+impl Error for ColorErrors {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Color {
     rgba: u32,
 }
@@ -27,13 +45,112 @@ impl Color {
             | 0xFF;
         Color { rgba }
     }
+
+    pub fn from_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        let rgba = ((red as u32) << (8 * 3))
+            | ((green as u32) << (8 * 2))
+            | ((blue as u32) << (8 * 1))
+            | alpha as u32;
+        Color { rgba }
+    }
+
+    /// # Description
+    /// This color with its alpha replaced by *alpha*.
+    pub fn with_alpha(&self, alpha: u8) -> Self {
+        Color { rgba: (self.rgba & 0xFFFFFF00) | alpha as u32 }
+    }
+
+    /// # Description
+    /// Creates an opaque color from its hue, saturation and value.
+    ///
+    /// # Parameters
+    /// - **hue**: The hue in degrees, wrapped into `0` to `360`.
+    /// - **saturation**: The saturation, from `0` to `1`.
+    /// - **value**: The value (brightness), from `0` to `1`.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let hue = hue.rem_euclid(360f32) / 60f32;
+        let saturation = f32::max(0f32, f32::min(saturation, 1f32));
+        let value = f32::max(0f32, f32::min(value, 1f32));
+        let chroma = value * saturation;
+        let x = chroma * (1f32 - (hue % 2f32 - 1f32).abs());
+        let (red, green, blue) = match hue as u32 {
+            0 => (chroma, x, 0f32),
+            1 => (x, chroma, 0f32),
+            2 => (0f32, chroma, x),
+            3 => (0f32, x, chroma),
+            4 => (x, 0f32, chroma),
+            _ => (chroma, 0f32, x),
+        };
+        let channel = |d: f32| ((d + value - chroma) * 255f32).round() as u8;
+        Color::from_rgb(channel(red), channel(green), channel(blue))
+    }
+
+    /// # Description
+    /// The hue, saturation and value of this color, ignoring its alpha.
+    ///
+    /// # Returns
+    /// The hue in degrees from `0` to `360` (exclusive), the saturation and the value from
+    /// `0` to `1`. The hue of grays is `0`.
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let red = self.red() as f32 / 255f32;
+        let green = self.green() as f32 / 255f32;
+        let blue = self.blue() as f32 / 255f32;
+        let max = f32::max(red, f32::max(green, blue));
+        let min = f32::min(red, f32::min(green, blue));
+        let chroma = max - min;
+        let hue = if chroma == 0f32 {
+            0f32
+        } else if max == red {
+            60f32 * ((green - blue) / chroma).rem_euclid(6f32)
+        } else if max == green {
+            60f32 * ((blue - red) / chroma + 2f32)
+        } else {
+            60f32 * ((red - green) / chroma + 4f32)
+        };
+        let saturation = if max == 0f32 { 0f32 } else { chroma / max };
+        (hue % 360f32, saturation, max)
+    }
+
+    /// # Description
+    /// Formats this color as `#RRGGBB`, or as `#RRGGBBAA` if it is not opaque.
+    pub fn to_hex(&self) -> String {
+        if self.alpha() == 0xFF {
+            format!("#{:06X}", self.rgba >> 8)
+        } else {
+            format!("#{:08X}", self.rgba)
+        }
+    }
+
+    /// # Description
+    /// Parses a color in the form of `#RGB`, `#RRGGBB` or `#RRGGBBAA`.
+    ///
+    /// # Remarks
+    /// The leading `#` is optional and surrounding whitespace is ignored.
+    pub fn parse_hex(text: &str) -> Result<Color, ColorErrors> {
+        let trimmed = text.trim();
+        let digits = trimmed.strip_prefix('#').unwrap_or(trimmed);
+        let invalid = || ColorErrors::InvalidHex(text.to_string());
+        if !digits.chars().all(|d| d.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let value = u32::from_str_radix(digits, 16).map_err(|_| invalid())?;
+        match digits.len() {
+            3 => {
+                let channel = |shift: u32| ((value >> shift) & 0xF) as u8 * 0x11;
+                Ok(Color::from_rgb(channel(8), channel(4), channel(0)))
+            }
+            6 => Ok(Color { rgba: (value << 8) | 0xFF }),
+            8 => Ok(Color { rgba: value }),
+            _ => Err(invalid()),
+        }
+    }
 }
 
 impl Color {
     pub const BLACK: Color = Color{rgba: 0x000000FF};
     pub const WHITE: Color = Color{rgba: 0xFFFFFFFF};
     pub const RED: Color = Color{rgba: 0xFF0000FF};
-    pub const GREEN: Color = Color{rgba: 0x0000FFFF};
+    pub const GREEN: Color = Color{rgba: 0x008000FF};
     pub const BLUE: Color = Color{rgba: 0x0000FFFF};
     pub const TRANSPARENT: Color = Color { rgba: 0x00000000 };
     pub const YELLOW: Color = Color { rgba: 0xFFFF00FF };
@@ -113,6 +230,15 @@ mod tests {
     use super::*;
     
 
+    #[test]
+    fn test_named_colors() {
+        // GREEN used to have the value of BLUE
+        assert_eq!(Color::GREEN.to_rgb(), (0x00, 0x80, 0x00));
+        assert_eq!(Color::BLUE.to_rgb(), (0x00, 0x00, 0xFF));
+        assert_eq!(Color::LIME.to_rgb(), (0x00, 0xFF, 0x00));
+        assert_ne!(Color::GREEN, Color::BLUE);
+    }
+
     #[test]
     fn test_to_rgb() {
         let color = Color { rgba: 0xFF00AA00 }; // Red = 255, Green = 0, Blue = 170
@@ -137,6 +263,29 @@ mod tests {
         assert_eq!(color.rgba, 0x004D4DFF);
     }
 
+    #[test]
+    fn test_hsv() {
+        assert_eq!(Color::from_hsv(0f32, 1f32, 1f32), Color::RED);
+        assert_eq!(Color::from_hsv(120f32, 1f32, 1f32), Color::LIME);
+        assert_eq!(Color::from_hsv(-120f32, 1f32, 1f32), Color::BLUE);
+        assert_eq!(Color::from_hsv(300f32, 0f32, 0.5f32), Color::from_rgb(128, 128, 128));
+        assert_eq!(Color::ORANGE.to_hsv(), (38.823532f32, 1f32, 1f32));
+        let (hue, saturation, value) = Color::CORNFLOWER_BLUE.to_hsv();
+        assert_eq!(Color::from_hsv(hue, saturation, value), Color::CORNFLOWER_BLUE);
+        assert_eq!(Color::GRAY.to_hsv().0, 0f32);
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Color::CORNFLOWER_BLUE.to_hex(), "#6495ED");
+        assert_eq!(Color::CORNFLOWER_BLUE.with_alpha(0x80).to_hex(), "#6495ED80");
+        assert_eq!(Color::parse_hex(" #6495ed ").unwrap(), Color::CORNFLOWER_BLUE);
+        assert_eq!(Color::parse_hex("f0c").unwrap(), Color::from_rgb(0xFF, 0x00, 0xCC));
+        assert_eq!(Color::parse_hex("#00000000").unwrap(), Color::TRANSPARENT);
+        assert!(Color::parse_hex("#12345").is_err());
+        assert!(Color::parse_hex("#+12345").is_err());
+    }
+
     #[test]
     fn test_red() {
         let color = Color { rgba: 0xFF000000 }; // Red component is 255
@@ -0,0 +1,298 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+/// # Description
+/// An action which can be invoked by the user, eg. using a button, a menu item or a
+/// key gesture.
+///
+/// # Remarks
+/// Commands are shared between all sources invoking them using [`Rc`].
+/// Sources query *can_execute* whenever they are laid out, disabling themselves if the
+/// command cannot be executed.
+pub trait Command {
+    /// # Description
+    /// The human readable name of the command, eg. `Save`.
+    fn name(&self) -> &str;
+
+    /// # Description
+    /// Whether the command can currently be executed.
+    fn can_execute(&self) -> bool;
+
+    /// # Description
+    /// Executes the command.
+    ///
+    /// # Remarks
+    /// Only called if *can_execute* returned `true`.
+    fn execute(&self);
+}
+
+/// # Description
+/// A [`Command`] delegating to closures.
+pub struct DelegateCommand {
+    name: String,
+    can_execute: Box<dyn Fn() -> bool>,
+    execute: RefCell<Box<dyn FnMut()>>,
+}
+
+impl DelegateCommand {
+    /// # Description
+    /// Creates a command which can always be executed.
+    pub fn new<E: FnMut() + 'static>(name: &str, execute: E) -> DelegateCommand {
+        DelegateCommand {
+            name: name.to_string(),
+            can_execute: Box::new(|| true),
+            execute: RefCell::new(Box::new(execute)),
+        }
+    }
+
+    /// # Description
+    /// Creates a command which can only be executed if *can_execute* returns `true`.
+    pub fn with_can_execute<C: Fn() -> bool + 'static, E: FnMut() + 'static>(
+        name: &str,
+        can_execute: C,
+        execute: E,
+    ) -> DelegateCommand {
+        DelegateCommand {
+            name: name.to_string(),
+            can_execute: Box::new(can_execute),
+            execute: RefCell::new(Box::new(execute)),
+        }
+    }
+}
+
+impl Command for DelegateCommand {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn can_execute(&self) -> bool {
+        (self.can_execute)()
+    }
+
+    fn execute(&self) {
+        // A command executing itself again (eg. by pumping events) is ignored
+        if let Ok(mut execute) = self.execute.try_borrow_mut() {
+            (execute)();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum KeyGestureErrors {
+    Empty,
+    UnknownModifier(String),
+    UnknownKey(String),
+}
+
+impl Display for KeyGestureErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyGestureErrors::Empty => write!(f, "Key gesture is empty"),
+            KeyGestureErrors::UnknownModifier(d) => write!(f, "Unknown modifier ({})", d),
+            KeyGestureErrors::UnknownKey(d) => write!(f, "Unknown key ({})", d),
+        }
+    }
+}
+
+impl Error for KeyGestureErrors {}
+
+/// The names used to parse and display named keys of a [`KeyGesture`].
+const NAMED_KEYS: &[(&str, NamedKey)] = &[
+    ("Enter", NamedKey::Enter),
+    ("Esc", NamedKey::Escape),
+    ("Escape", NamedKey::Escape),
+    ("Tab", NamedKey::Tab),
+    ("Space", NamedKey::Space),
+    ("Backspace", NamedKey::Backspace),
+    ("Del", NamedKey::Delete),
+    ("Delete", NamedKey::Delete),
+    ("Ins", NamedKey::Insert),
+    ("Insert", NamedKey::Insert),
+    ("Home", NamedKey::Home),
+    ("End", NamedKey::End),
+    ("PageUp", NamedKey::PageUp),
+    ("PageDown", NamedKey::PageDown),
+    ("Up", NamedKey::ArrowUp),
+    ("Down", NamedKey::ArrowDown),
+    ("Left", NamedKey::ArrowLeft),
+    ("Right", NamedKey::ArrowRight),
+    ("F1", NamedKey::F1),
+    ("F2", NamedKey::F2),
+    ("F3", NamedKey::F3),
+    ("F4", NamedKey::F4),
+    ("F5", NamedKey::F5),
+    ("F6", NamedKey::F6),
+    ("F7", NamedKey::F7),
+    ("F8", NamedKey::F8),
+    ("F9", NamedKey::F9),
+    ("F10", NamedKey::F10),
+    ("F11", NamedKey::F11),
+    ("F12", NamedKey::F12),
+];
+
+/// # Description
+/// A combination of modifier keys and a single key, eg. `Ctrl+S`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyGesture {
+    pub key: Key,
+    pub modifiers: ModifiersState,
+}
+
+impl KeyGesture {
+    pub fn new(key: Key, modifiers: ModifiersState) -> KeyGesture {
+        KeyGesture {
+            key: normalize(&key),
+            modifiers,
+        }
+    }
+
+    /// # Description
+    /// Parses a key gesture in the form of `Ctrl+Shift+S`.
+    ///
+    /// # Remarks
+    /// Modifiers (`Ctrl`, `Shift`, `Alt` and `Super`) and keys are case-insensitive.
+    /// Keys are either a single character or the name of a named key, eg. `F5` or `PageUp`.
+    pub fn parse(text: &str) -> Result<KeyGesture, KeyGestureErrors> {
+        let parts: Vec<&str> = text.split('+').map(|part| part.trim()).collect();
+        // A trailing '+' is the plus key itself, eg. `Ctrl++`
+        let (modifier_parts, key_part) = match parts.as_slice() {
+            [.., "", ""] => (&parts[..parts.len() - 2], "+"),
+            [modifiers @ .., key] => (modifiers, *key),
+            [] => return Err(KeyGestureErrors::Empty),
+        };
+        if key_part.is_empty() {
+            return Err(KeyGestureErrors::Empty);
+        }
+        let mut modifiers = ModifiersState::empty();
+        for part in modifier_parts {
+            modifiers |= match part.to_lowercase().as_str() {
+                "ctrl" | "control" => ModifiersState::CONTROL,
+                "shift" => ModifiersState::SHIFT,
+                "alt" => ModifiersState::ALT,
+                "super" | "win" | "cmd" | "meta" => ModifiersState::SUPER,
+                _ => return Err(KeyGestureErrors::UnknownModifier(part.to_string())),
+            };
+        }
+        let key = match NAMED_KEYS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key_part))
+        {
+            Some((_, named)) => Key::Named(*named),
+            None if key_part.chars().count() == 1 => Key::Character(key_part.into()),
+            None => return Err(KeyGestureErrors::UnknownKey(key_part.to_string())),
+        };
+        Ok(KeyGesture::new(key, modifiers))
+    }
+
+    /// # Description
+    /// Whether pressing *key* while *modifiers* are held matches this gesture.
+    pub fn matches(&self, key: &Key, modifiers: ModifiersState) -> bool {
+        self.modifiers == modifiers && self.key == normalize(key)
+    }
+}
+
+impl Display for KeyGesture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.control_key() {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.shift_key() {
+            write!(f, "Shift+")?;
+        }
+        if self.modifiers.alt_key() {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.super_key() {
+            write!(f, "Super+")?;
+        }
+        match &self.key {
+            Key::Character(d) => write!(f, "{}", d.to_uppercase()),
+            Key::Named(named) => match NAMED_KEYS.iter().rev().find(|(_, d)| d == named) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => write!(f, "{:?}", named),
+            },
+            key => write!(f, "{:?}", key),
+        }
+    }
+}
+
+/// Character keys are compared case-insensitive, as Shift changes the case of the
+/// logical key.
+fn normalize(key: &Key) -> Key {
+    match key {
+        Key::Character(d) => Key::Character(d.to_lowercase().into()),
+        key => key.clone(),
+    }
+}
+
+/// # Description
+/// Binds a [`KeyGesture`] to a [`Command`].
+#[derive(Clone)]
+pub struct CommandBinding {
+    pub gesture: KeyGesture,
+    pub command: Rc<dyn Command>,
+}
+
+impl CommandBinding {
+    pub fn new(gesture: KeyGesture, command: Rc<dyn Command>) -> CommandBinding {
+        CommandBinding { gesture, command }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let gesture = KeyGesture::parse("Ctrl+Shift+S").unwrap();
+        assert_eq!(gesture.key, Key::Character("s".into()));
+        assert_eq!(gesture.modifiers, ModifiersState::CONTROL | ModifiersState::SHIFT);
+        assert_eq!(KeyGesture::parse(" ctrl + s ").unwrap(), KeyGesture::parse("CTRL+S").unwrap());
+        assert_eq!(KeyGesture::parse("Cmd+Alt+pageup").unwrap().key, Key::Named(NamedKey::PageUp));
+        assert_eq!(KeyGesture::parse("Esc").unwrap().key, Key::Named(NamedKey::Escape));
+        assert_eq!(KeyGesture::parse("F12").unwrap().key, Key::Named(NamedKey::F12));
+
+        let plus = KeyGesture::parse("Ctrl++").unwrap();
+        assert_eq!(plus.key, Key::Character("+".into()));
+        assert_eq!(plus.modifiers, ModifiersState::CONTROL);
+        assert_eq!(KeyGesture::parse("+").unwrap().modifiers, ModifiersState::empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(KeyGesture::parse(""), Err(KeyGestureErrors::Empty)));
+        assert!(matches!(KeyGesture::parse("Ctrl+"), Err(KeyGestureErrors::Empty)));
+        assert!(matches!(KeyGesture::parse("Ctrl++S"), Err(KeyGestureErrors::UnknownModifier(_))));
+        assert!(matches!(KeyGesture::parse("Hyper+S"), Err(KeyGestureErrors::UnknownModifier(d)) if d == "Hyper"));
+        assert!(matches!(KeyGesture::parse("Ctrl+Foo"), Err(KeyGestureErrors::UnknownKey(d)) if d == "Foo"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let gesture = KeyGesture::parse("Ctrl+S").unwrap();
+        assert!(gesture.matches(&Key::Character("s".into()), ModifiersState::CONTROL));
+        assert!(gesture.matches(&Key::Character("S".into()), ModifiersState::CONTROL));
+        assert!(!gesture.matches(&Key::Character("s".into()), ModifiersState::CONTROL | ModifiersState::SHIFT));
+        assert!(!gesture.matches(&Key::Character("s".into()), ModifiersState::empty()));
+        assert!(!gesture.matches(&Key::Character("d".into()), ModifiersState::CONTROL));
+        let gesture = KeyGesture::parse("Shift+Delete").unwrap();
+        assert!(gesture.matches(&Key::Named(NamedKey::Delete), ModifiersState::SHIFT));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(KeyGesture::parse("shift+ctrl+s").unwrap().to_string(), "Ctrl+Shift+S");
+        assert_eq!(KeyGesture::parse("Super+Alt+Up").unwrap().to_string(), "Alt+Super+Up");
+        // Aliases are displayed using their full name
+        assert_eq!(KeyGesture::parse("Esc").unwrap().to_string(), "Escape");
+        for text in ["Ctrl++", "Ctrl+Shift+Delete", "Alt+F4", "PageDown", "Ctrl+1", "Space"].iter() {
+            let gesture = KeyGesture::parse(text).unwrap();
+            assert_eq!(gesture.to_string(), *text);
+            assert_eq!(KeyGesture::parse(&gesture.to_string()).unwrap(), gesture);
+        }
+    }
+}
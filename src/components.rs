@@ -1,4 +1,63 @@
+mod border;
+mod button;
+mod calendar;
+mod check_box;
+mod color_picker;
+mod combo_box;
+mod context_menu;
+mod data_grid;
+mod date_picker;
+mod expander;
+mod glyph_label;
+mod group_box;
+mod image_view;
+mod list_view;
+mod menu;
+mod menu_bar;
+mod numeric_up_down;
+mod press_state;
+mod progress_bar;
+mod radio_button;
+mod range_value;
+mod scroll_viewer;
 mod scrollbar;
-// mod button;
+mod selection_model;
+mod slider;
+mod split_panel;
+mod tab_control;
+mod text_block;
+mod text_box;
+mod toggle_switch;
+mod tree_view;
+mod virtualizing_panel;
 
-pub use self::scrollbar::*;
\ No newline at end of file
+pub use self::border::*;
+pub use self::button::*;
+pub use self::calendar::*;
+pub use self::check_box::*;
+pub use self::color_picker::*;
+pub use self::combo_box::*;
+pub use self::context_menu::*;
+pub use self::data_grid::*;
+pub use self::date_picker::*;
+pub use self::expander::*;
+pub use self::group_box::*;
+pub use self::image_view::*;
+pub use self::list_view::*;
+pub use self::menu::*;
+pub use self::menu_bar::*;
+pub use self::numeric_up_down::*;
+pub use self::progress_bar::*;
+pub use self::radio_button::*;
+pub use self::range_value::*;
+pub use self::scroll_viewer::*;
+pub use self::scrollbar::*;
+pub use self::selection_model::*;
+pub use self::slider::*;
+pub use self::split_panel::*;
+pub use self::tab_control::*;
+pub use self::text_block::*;
+pub use self::text_box::*;
+pub use self::toggle_switch::*;
+pub use self::tree_view::*;
+pub use self::virtualizing_panel::*;
@@ -0,0 +1,141 @@
+use crate::abstraction::{Component, ContainerComponent, Renderer, Visual};
+use crate::color::Color;
+use crate::data::{ComponentId, Rectangle, Size, Thickness};
+use crate::visuals;
+
+/// # Description
+/// Draws a background and a border around a single child.
+///
+/// # Remarks
+/// Adding a child using [`ContainerComponent::add_child`] replaces the previous child.
+pub struct Border {
+    // #[property]
+    pub child: Option<Box<dyn Component>>,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub border_brush: Color,
+    // #[property]
+    pub border_thickness: f32,
+    // #[property]
+    pub corner_radius: f32,
+    /// The space between the border and the child.
+    // #[property]
+    pub padding: Thickness,
+
+    id: ComponentId,
+    size: Size<f32>,
+}
+
+impl Component for Border {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let (horizontal, vertical) = self.chrome();
+        let desired = match &self.child {
+            None => Size { width: 0f32, height: 0f32 },
+            Some(child) => child.measure(Size {
+                width: f32::max(0f32, available.width - horizontal),
+                height: f32::max(0f32, available.height - vertical),
+            }),
+        };
+        Size { width: desired.width + horizontal, height: desired.height + vertical }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let bounds = self.child_bounds();
+        if let Some(child) = self.child.as_mut() {
+            let size = Size { width: bounds.width, height: bounds.height };
+            child.measure(size);
+            child.arrange(size);
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        // The stroke is centered on the outline, so it is inset by half its thickness
+        let inset = self.border_thickness / 2f32;
+        visuals::RoundedRectangle {
+            x: inset,
+            y: inset,
+            width: f32::max(0f32, self.size.width - self.border_thickness),
+            height: f32::max(0f32, self.size.height - self.border_thickness),
+            radius: self.corner_radius,
+            fill: self.background,
+            stroke: self.border_brush,
+            thickness: self.border_thickness,
+        }
+        .draw(renderer);
+        if let Some(child) = &self.child {
+            let bounds = self.child_bounds();
+            renderer.push_clip(bounds.x, bounds.y, bounds.width, bounds.height);
+            renderer.translate(bounds.x, bounds.y);
+            child.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+            renderer.pop_clip();
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if let Some(child) = &self.child {
+            visitor(self.child_bounds(), &**child);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.child_bounds();
+        if let Some(child) = self.child.as_mut() {
+            visitor(bounds, &mut **child);
+        }
+    }
+}
+
+impl ContainerComponent for Border {
+    fn add_child(&mut self, child: Box<dyn Component>) {
+        self.child = Some(child);
+    }
+}
+
+impl Border {
+    pub fn new() -> Border {
+        Border {
+            id: ComponentId::new(),
+            child: None,
+            background: Color::TRANSPARENT,
+            border_brush: Color::GRAY,
+            border_thickness: 1f32,
+            corner_radius: 0f32,
+            padding: Thickness::uniform(0f32),
+            size: Size { width: 0f32, height: 0f32 },
+        }
+    }
+
+    pub fn with_child(mut self, child: Box<dyn Component>) -> Self {
+        self.child = Some(child);
+        self
+    }
+
+    /// The horizontal and vertical space taken by the border and the padding.
+    fn chrome(&self) -> (f32, f32) {
+        (
+            self.padding.horizontal() + 2f32 * self.border_thickness,
+            self.padding.vertical() + 2f32 * self.border_thickness,
+        )
+    }
+
+    fn child_bounds(&self) -> Rectangle<f32> {
+        let (horizontal, vertical) = self.chrome();
+        Rectangle {
+            x: self.border_thickness + self.padding.left,
+            y: self.border_thickness + self.padding.top,
+            width: f32::max(0f32, self.size.width - horizontal),
+            height: f32::max(0f32, self.size.height - vertical),
+        }
+    }
+}
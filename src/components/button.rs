@@ -1,23 +1,380 @@
-use crate::abstraction::{Component, Visual};
-use crate::data::Size;
+use std::rc::Rc;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::commands::Command;
+use crate::components::ContextMenu;
+use crate::components::press_state::{PressResult, PressState};
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::{self, TextStyle};
+use crate::tool_tip::ToolTip;
+use crate::visuals;
 
-pub struct button {
-    
+pub struct Button {
+    /// The text displayed on the button.
+    // #[property]
+    pub text: String,
+    /// An optional icon, displayed in front of the text.
+    // #[property]
+    pub icon: Option<Box<dyn Component>>,
+    /// The style the text is drawn in.
+    // #[property]
+    pub text_style: TextStyle,
+    /// The space between the icon and the text.
+    // #[property]
+    pub spacing: f32,
+    /// The space between the border and the content.
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub corner_radius: f32,
+    /// Whether the button can be interacted with.
+    // #[property]
+    pub enabled: bool,
+    /// The command executed when the button is clicked.
+    /// The button is disabled while the command cannot be executed.
+    // #[property]
+    pub command: Option<Rc<dyn Command>>,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub hover_background: Color,
+    // #[property]
+    pub pressed_background: Color,
+    // #[property]
+    pub disabled_background: Color,
+    // #[property]
+    pub disabled_foreground: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    /// Called when the button was clicked.
+    // #[event]
+    pub on_click: Option<Box<dyn FnMut()>>,
+    /// The tool tip shown while the pointer hovers the button.
+    // #[property]
+    pub tool_tip: Option<ToolTip>,
+    /// Creates the context menu opened by right-clicking the button.
+    // #[property]
+    pub context_menu: Option<Rc<dyn Fn() -> ContextMenu>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    icon_bounds: Rectangle<f32>,
+    text_origin: Point<f32>,
+    render_style: TextStyle,
+    render_enabled: bool,
+    press: PressState,
 }
 
-impl Component for button {
+impl Component for Button {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
     fn measure(&self, available: Size<f32>) -> Size<f32> {
-        todo!()
+        let inner = Size {
+            width: available.width - self.padding.horizontal(),
+            height: available.height - self.padding.vertical(),
+        };
+        let (icon, text_width, line_height) = self.measure_content(inner);
+        let spacing = self.content_spacing();
+        Size {
+            width: icon.width + spacing + text_width + self.padding.horizontal(),
+            height: f32::max(icon.height, line_height) + self.padding.vertical(),
+        }
     }
 
     fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
-        todo!()
+        let inner = Size {
+            width: given.width - self.padding.horizontal(),
+            height: given.height - self.padding.vertical(),
+        };
+        let (icon, text_width, _) = self.measure_content(inner);
+        let spacing = self.content_spacing();
+        let content_width = icon.width + spacing + text_width;
+        let x = self.padding.left + f32::max(0f32, inner.width - content_width) / 2f32;
+
+        self.icon_bounds = Rectangle {
+            x,
+            y: self.padding.top + f32::max(0f32, inner.height - icon.height) / 2f32,
+            width: icon.width,
+            height: f32::min(icon.height, inner.height),
+        };
+        if let Some(component) = self.icon.as_mut() {
+            component.arrange(Size {
+                width: self.icon_bounds.width,
+                height: self.icon_bounds.height,
+            });
+        }
+
+        let font = text::text_metrics().font_metrics(&self.text_style);
+        self.text_origin = Point {
+            x: x + icon.width + spacing,
+            y: self.padding.top + (inner.height - font.line_height) / 2f32 + font.ascent,
+        };
+        self.render_enabled = self.is_enabled();
+        self.render_style = self.text_style.clone();
+        if !self.render_enabled {
+            self.render_style.color = self.disabled_foreground;
+        }
+        self.size = given;
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let background = if !self.render_enabled {
+            self.disabled_background
+        } else if self.press.is_pressed() {
+            self.pressed_background
+        } else if self.press.hovered {
+            self.hover_background
+        } else {
+            self.background
+        };
+        visuals::RoundedRectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            radius: self.corner_radius,
+            fill: background,
+            stroke: if self.press.focused { self.focused_border } else { self.border },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        if let Some(icon) = self.icon.as_ref() {
+            renderer.translate(self.icon_bounds.x, self.icon_bounds.y);
+            icon.render(renderer);
+            renderer.translate(-self.icon_bounds.x, -self.icon_bounds.y);
+        }
+        if !self.text.is_empty() {
+            visuals::Text {
+                x: self.text_origin.x,
+                y: self.text_origin.y,
+                text: &self.text,
+                style: &self.render_style,
+            }
+            .draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        // Holding Enter clicks only once, and Enter with modifiers is left to key gestures
+        if let InputEvent::KeyPressed { key: Key::Named(NamedKey::Enter), modifiers, repeat: false, .. } = event {
+            if !self.is_enabled() || !modifiers.is_empty() {
+                return false;
+            }
+            self.click();
+            return true;
+        }
+        match self.press.handle_event(event, context, self.bounds(), self.is_enabled()) {
+            PressResult::Unhandled => false,
+            PressResult::Handled => true,
+            PressResult::Clicked => {
+                self.click();
+                true
+            }
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if let Some(icon) = self.icon.as_ref() {
+            visitor(self.icon_bounds, icon.as_ref());
+        }
+    }
+
+    fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component)) {
+        if let Some(icon) = self.icon.as_mut() {
+            visitor(self.icon_bounds, icon.as_mut());
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.is_enabled() {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+
+    fn tool_tip(&self) -> Option<&ToolTip> {
+        self.tool_tip.as_ref()
+    }
+
+    fn context_menu(&self) -> Option<ContextMenu> {
+        self.context_menu.as_ref().map(|create| create())
+    }
+}
+
+impl Button {
+    pub fn new() -> Button {
+        Button {
+            id: ComponentId::new(),
+            text: String::new(),
+            icon: None,
+            text_style: TextStyle::new(),
+            spacing: 4f32,
+            padding: Thickness { left: 8f32, top: 4f32, right: 8f32, bottom: 4f32 },
+            corner_radius: 3f32,
+            enabled: true,
+            command: None,
+            background: Color::GAINSBORO,
+            hover_background: Color::LIGHT_GRAY,
+            pressed_background: Color::DARK_GRAY,
+            disabled_background: Color::MINT_CREAM,
+            disabled_foreground: Color::GRAY,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            on_click: None,
+            tool_tip: None,
+            context_menu: None,
+            size: Size { width: 0f32, height: 0f32 },
+            icon_bounds: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+            text_origin: Point { x: 0f32, y: 0f32 },
+            render_style: TextStyle::new(),
+            render_enabled: true,
+            press: PressState::new(),
+        }
+    }
+
+    /// # Description
+    /// Whether the button is enabled and its command, if any, can be executed.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && self.command.as_ref().map_or(true, |command| command.can_execute())
     }
 
-    fn render<F>(&self, dispatch: F)
-    where
-        F: FnMut(&dyn Visual)
-    {
-        todo!()
+    /// # Description
+    /// Clicks the button, executing its command and raising *on_click*.
+    ///
+    /// # Remarks
+    /// Does nothing if the button is disabled.
+    pub fn click(&mut self) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Some(command) = self.command.as_ref() {
+            command.execute();
+        }
+        if let Some(callback) = self.on_click.as_mut() {
+            callback();
+        }
     }
-}
\ No newline at end of file
+
+    fn bounds(&self) -> Rectangle<f32> {
+        Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height }
+    }
+
+    fn content_spacing(&self) -> f32 {
+        if self.icon.is_some() && !self.text.is_empty() {
+            self.spacing
+        } else {
+            0f32
+        }
+    }
+
+    /// Measures the icon, the width of the text and the height of the text.
+    fn measure_content(&self, available: Size<f32>) -> (Size<f32>, f32, f32) {
+        let icon = match self.icon.as_ref() {
+            None => Size { width: 0f32, height: 0f32 },
+            Some(d) => d.measure(available),
+        };
+        if self.text.is_empty() {
+            return (icon, 0f32, 0f32);
+        }
+        let metrics = text::text_metrics();
+        let text_width = metrics.measure_text(&self.text, &self.text_style);
+        let line_height = metrics.font_metrics(&self.text_style).line_height;
+        (icon, text_width, line_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::DelegateCommand;
+    use crate::pointer::PointerState;
+    use std::cell::Cell;
+    use std::time::Instant;
+    use winit::event::MouseButton;
+    use winit::keyboard::ModifiersState;
+
+    /// A button counting its clicks in the returned cell.
+    fn button() -> (Button, Rc<Cell<usize>>) {
+        let clicks = Rc::new(Cell::new(0));
+        let mut button = Button::new();
+        let counter = clicks.clone();
+        button.on_click = Some(Box::new(move || counter.set(counter.get() + 1)));
+        button.arrange(Size { width: 80f32, height: 24f32 });
+        (button, clicks)
+    }
+
+    fn send(button: &mut Button, event: InputEvent) -> bool {
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+        button.handle_event(&event, &mut context)
+    }
+
+    fn key(key: NamedKey, modifiers: ModifiersState, repeat: bool) -> InputEvent {
+        InputEvent::KeyPressed { key: Key::Named(key), text: None, modifiers, repeat }
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let (mut button, clicks) = button();
+        assert!(send(&mut button, key(NamedKey::Enter, ModifiersState::empty(), false)));
+        assert!(!send(&mut button, key(NamedKey::Enter, ModifiersState::empty(), true)));
+        assert_eq!(clicks.get(), 1);
+        assert!(!send(&mut button, key(NamedKey::Enter, ModifiersState::CONTROL, false)));
+        assert!(!send(&mut button, key(NamedKey::Enter, ModifiersState::ALT, false)));
+        assert_eq!(clicks.get(), 1);
+
+        // Space clicks once released
+        send(&mut button, key(NamedKey::Space, ModifiersState::empty(), false));
+        assert_eq!(clicks.get(), 1);
+        send(&mut button, InputEvent::KeyReleased { key: Key::Named(NamedKey::Space), modifiers: ModifiersState::empty() });
+        assert_eq!(clicks.get(), 2);
+    }
+
+    #[test]
+    fn test_pointer() {
+        let (mut button, clicks) = button();
+        let inside = Point { x: 40f32, y: 12f32 };
+        let outside = Point { x: 120f32, y: 12f32 };
+        send(&mut button, InputEvent::PointerPressed { position: inside, button: MouseButton::Left });
+        assert_eq!(clicks.get(), 0);
+        send(&mut button, InputEvent::PointerReleased { position: inside, button: MouseButton::Left });
+        assert_eq!(clicks.get(), 1);
+
+        // Releasing outside of the button cancels the click
+        send(&mut button, InputEvent::PointerPressed { position: inside, button: MouseButton::Left });
+        send(&mut button, InputEvent::PointerReleased { position: outside, button: MouseButton::Left });
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn test_command() {
+        let (mut button, clicks) = button();
+        let executable = Rc::new(Cell::new(false));
+        let executed = Rc::new(Cell::new(0));
+        let (can_execute, counter) = (executable.clone(), executed.clone());
+        button.command = Some(Rc::new(DelegateCommand::with_can_execute(
+            "Test",
+            move || can_execute.get(),
+            move || counter.set(counter.get() + 1),
+        )));
+        assert!(!button.is_enabled());
+        assert!(button.focusable().is_none());
+        assert!(!send(&mut button, key(NamedKey::Enter, ModifiersState::empty(), false)));
+        button.click();
+        assert_eq!((clicks.get(), executed.get()), (0, 0));
+
+        executable.set(true);
+        assert!(button.is_enabled());
+        send(&mut button, key(NamedKey::Enter, ModifiersState::empty(), false));
+        assert_eq!((clicks.get(), executed.get()), (1, 1));
+    }
+}
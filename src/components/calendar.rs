@@ -0,0 +1,638 @@
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::date::{Date, DateFormat, Weekday};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::{self, FontWeight, TextStyle};
+use crate::visuals::{self, PathCommand};
+
+/// The number of weeks shown, enough for every month regardless of the first day of the week.
+const WEEKS: usize = 6;
+
+/// The parts of a calendar the pointer can interact with.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Part {
+    Previous,
+    Next,
+    Day(Date),
+}
+
+/// # Description
+/// Shows the days of a month, of which the user selects one.
+///
+/// # Remarks
+/// The buttons next to the title and the mouse wheel change the month shown. While the
+/// calendar has the keyboard focus, the arrow keys move by a day or a week, Page Up and
+/// Page Down by a month (by a year if Ctrl is held) and Home and End to the first and the
+/// last day of the month. Space and Enter select the day moved to.
+/// Days before *min_date*, after *max_date* or in *blackout_dates* cannot be selected.
+/// Selecting a day by clicking it or pressing Enter leaves the event unhandled, so a
+/// component hosting the calendar can react to it.
+pub struct Calendar {
+    // #[property]
+    pub selected_date: Option<Date>,
+    /// Any day of the month shown.
+    // #[property]
+    pub display_date: Date,
+    /// The earliest day that can be selected.
+    // #[property]
+    pub min_date: Option<Date>,
+    /// The latest day that can be selected.
+    // #[property]
+    pub max_date: Option<Date>,
+    /// Days that cannot be selected, although they are within the limits.
+    // #[property]
+    pub blackout_dates: Vec<Date>,
+    /// The day each row starts with. Defaults to the one of the system locale.
+    // #[property]
+    pub first_day_of_week: Weekday,
+    /// The names of the months and the days of the week.
+    // #[property]
+    pub format: DateFormat,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub text_style: TextStyle,
+    /// The padding of a day around its number.
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub hover_background: Color,
+    // #[property]
+    pub selected_background: Color,
+    // #[property]
+    pub selected_foreground: Color,
+    /// The color of the frame around the current day.
+    // #[property]
+    pub today_border: Color,
+    /// The color of the days of the previous and the next month as well as the weekday names.
+    // #[property]
+    pub other_month_foreground: Color,
+    /// The color of the days that cannot be selected.
+    // #[property]
+    pub disabled_foreground: Color,
+    /// The color of the buttons changing the month.
+    // #[property]
+    pub glyph: Color,
+    /// Called with the new selected date whenever the user selected a day.
+    // #[event]
+    pub on_selected_date_changed: Option<Box<dyn FnMut(Option<Date>)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    focused: bool,
+    hovered: Option<Part>,
+    /// The day moved to using the keyboard.
+    current: Date,
+    today: Date,
+    /// The day the pointer was pressed on, committed if it is released on it as well.
+    pressed: Option<Date>,
+    /// Whether the user selected a day, to be taken by a hosting component.
+    committed: bool,
+    header_height: f32,
+    names_height: f32,
+    cell_width: f32,
+    cell_height: f32,
+    baseline: f32,
+    title: String,
+    title_style: TextStyle,
+}
+
+impl Component for Calendar {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, _available: Size<f32>) -> Size<f32> {
+        let metrics = text::text_metrics();
+        let line_height = metrics.font_metrics(&self.text_style).line_height;
+        let (header_height, names_height) = self.row_heights(line_height);
+        let widest_name = self
+            .format
+            .day_names
+            .iter()
+            .map(|d| metrics.measure_text(d, &self.text_style))
+            .fold(metrics.measure_text("00", &self.text_style), f32::max);
+        let cell_width = f32::max(widest_name + self.padding.horizontal(), line_height + self.padding.vertical());
+        let cell_height = line_height + self.padding.vertical();
+        Size {
+            width: 7f32 * cell_width + 2f32,
+            height: header_height + names_height + WEEKS as f32 * cell_height + 2f32,
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let font = text::text_metrics().font_metrics(&self.text_style);
+        let (header_height, names_height) = self.row_heights(font.line_height);
+        self.header_height = header_height;
+        self.names_height = names_height;
+        self.cell_width = f32::max(0f32, given.width - 2f32) / 7f32;
+        self.cell_height = f32::max(0f32, given.height - 2f32 - header_height - names_height) / WEEKS as f32;
+        self.baseline = (self.cell_height - font.line_height) / 2f32 + font.ascent;
+        self.today = Date::today();
+        if self.current.first_of_month() != self.display_date.first_of_month() {
+            self.current = self.display_date.first_of_month();
+        }
+        self.title = format!(
+            "{} {}",
+            self.format.month_names[self.display_date.month() as usize - 1],
+            self.display_date.year()
+        );
+        self.title_style = self.text_style.clone();
+        self.title_style.weight = FontWeight::SemiBold;
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let metrics = text::text_metrics();
+        visuals::Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height, fill: self.background }
+            .draw(renderer);
+        visuals::Outline {
+            x: 0.5f32,
+            y: 0.5f32,
+            width: self.size.width - 1f32,
+            height: self.size.height - 1f32,
+            stroke: if self.focused { self.focused_border } else { self.border },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        // The header with the title between the buttons changing the month
+        for (part, direction) in [(Part::Previous, -1f32), (Part::Next, 1f32)].iter().copied() {
+            let bounds = self.part_bounds(part);
+            let enabled = self.enabled && self.can_show(direction as i32);
+            if enabled && self.hovered == Some(part) {
+                visuals::Rectangle {
+                    x: bounds.x,
+                    y: bounds.y,
+                    width: bounds.width,
+                    height: bounds.height,
+                    fill: self.hover_background,
+                }
+                .draw(renderer);
+            }
+            let center = Point { x: bounds.x + bounds.width / 2f32, y: bounds.y + bounds.height / 2f32 };
+            let point = |x: f32, y: f32| Point { x: center.x + x * direction, y: center.y + y };
+            visuals::Path {
+                commands: &[
+                    PathCommand::MoveTo(point(-2f32, -4f32)),
+                    PathCommand::LineTo(point(2f32, 0f32)),
+                    PathCommand::LineTo(point(-2f32, 4f32)),
+                ],
+                fill: Color::TRANSPARENT,
+                stroke: if enabled { self.glyph } else { self.disabled_foreground },
+                thickness: 1.5f32,
+            }
+            .draw(renderer);
+        }
+        let title_width = metrics.measure_text(&self.title, &self.title_style);
+        visuals::Text {
+            x: (self.size.width - title_width) / 2f32,
+            y: 1f32 + (self.header_height - self.cell_height) / 2f32 + self.baseline,
+            text: &self.title,
+            style: &self.title_style,
+        }
+        .draw(renderer);
+
+        let mut name_style = self.text_style.clone();
+        name_style.color = self.other_month_foreground;
+        for column in 0..7 {
+            let name = self.format.day_names[(self.first_day_of_week.index() as usize + column) % 7];
+            let width = metrics.measure_text(name, &name_style);
+            visuals::Text {
+                x: 1f32 + (column as f32 + 0.5f32) * self.cell_width - width / 2f32,
+                y: 1f32 + self.header_height + (self.names_height - self.cell_height) / 2f32 + self.baseline,
+                text: name,
+                style: &name_style,
+            }
+            .draw(renderer);
+        }
+
+        let first = self.first_shown();
+        let mut style = self.text_style.clone();
+        for index in 0..7 * WEEKS as i64 {
+            let date = first.add_days(index);
+            let bounds = self.part_bounds(Part::Day(date));
+            let selectable = self.is_selectable(date);
+            let selected = self.selected_date == Some(date);
+            let fill = if selected {
+                self.selected_background
+            } else if self.enabled && selectable && self.hovered == Some(Part::Day(date)) {
+                self.hover_background
+            } else {
+                Color::TRANSPARENT
+            };
+            visuals::Rectangle { x: bounds.x, y: bounds.y, width: bounds.width, height: bounds.height, fill }
+                .draw(renderer);
+            if date == self.today || self.focused && date == self.current {
+                visuals::Outline {
+                    x: bounds.x + 1.5f32,
+                    y: bounds.y + 1.5f32,
+                    width: bounds.width - 3f32,
+                    height: bounds.height - 3f32,
+                    stroke: if self.focused && date == self.current { self.focused_border } else { self.today_border },
+                    thickness: 1f32,
+                }
+                .draw(renderer);
+            }
+            style.color = if !self.enabled || !selectable {
+                self.disabled_foreground
+            } else if selected {
+                self.selected_foreground
+            } else if date.month() != self.display_date.month() {
+                self.other_month_foreground
+            } else {
+                self.text_style.color
+            };
+            let number = date.day().to_string();
+            let width = metrics.measure_text(&number, &style);
+            let x = bounds.x + (bounds.width - width) / 2f32;
+            visuals::Text { x, y: bounds.y + self.baseline, text: &number, style: &style }.draw(renderer);
+            // Blacked out days are struck through
+            if self.blackout_dates.contains(&date) {
+                let y = bounds.y + bounds.height / 2f32;
+                visuals::Path {
+                    commands: &[
+                        PathCommand::MoveTo(Point { x: x - 2f32, y }),
+                        PathCommand::LineTo(Point { x: x + width + 2f32, y }),
+                    ],
+                    fill: Color::TRANSPARENT,
+                    stroke: self.disabled_foreground,
+                    thickness: 1f32,
+                }
+                .draw(renderer);
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                true
+            }
+            InputEvent::PointerMoved { position } => {
+                self.hovered = self.part_at(*position);
+                false
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = None;
+                false
+            }
+            _ if !self.enabled => false,
+            InputEvent::PointerPressed { position, button: MouseButton::Left } => {
+                match self.part_at(*position) {
+                    Some(Part::Previous) => self.show_month(-1),
+                    Some(Part::Next) => self.show_month(1),
+                    Some(Part::Day(date)) if self.is_selectable(date) => {
+                        self.move_to(date);
+                        self.select(Some(date));
+                        self.pressed = Some(date);
+                    }
+                    _ => {}
+                }
+                context.request_focus(self.id);
+                true
+            }
+            InputEvent::PointerReleased { position, button: MouseButton::Left } => {
+                if let Some(date) = self.pressed.take() {
+                    self.committed = self.part_at(*position) == Some(Part::Day(date));
+                }
+                false
+            }
+            InputEvent::PointerWheel { delta, .. } if delta.y != 0f32 => {
+                self.show_month(if delta.y > 0f32 { -1 } else { 1 });
+                true
+            }
+            InputEvent::KeyPressed { key, modifiers, .. } if self.focused => {
+                let current = self.current;
+                let target = match key {
+                    Key::Named(NamedKey::ArrowLeft) => current.add_days(-1),
+                    Key::Named(NamedKey::ArrowRight) => current.add_days(1),
+                    Key::Named(NamedKey::ArrowUp) => current.add_days(-7),
+                    Key::Named(NamedKey::ArrowDown) => current.add_days(7),
+                    Key::Named(NamedKey::PageUp) if modifiers.control_key() => current.add_months(-12),
+                    Key::Named(NamedKey::PageDown) if modifiers.control_key() => current.add_months(12),
+                    Key::Named(NamedKey::PageUp) => current.add_months(-1),
+                    Key::Named(NamedKey::PageDown) => current.add_months(1),
+                    Key::Named(NamedKey::Home) => current.first_of_month(),
+                    Key::Named(NamedKey::End) => current.last_of_month(),
+                    Key::Named(NamedKey::Space) | Key::Named(NamedKey::Enter) => {
+                        if !self.is_selectable(current) {
+                            return true;
+                        }
+                        self.select(Some(current));
+                        self.committed = true;
+                        return *key == Key::Named(NamedKey::Space);
+                    }
+                    _ => return false,
+                };
+                self.move_to(target);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+}
+
+impl Calendar {
+    pub fn new() -> Calendar {
+        let today = Date::today();
+        let format = DateFormat::system();
+        Calendar {
+            id: ComponentId::new(),
+            selected_date: None,
+            display_date: today,
+            min_date: None,
+            max_date: None,
+            blackout_dates: vec![],
+            first_day_of_week: format.first_day_of_week,
+            format,
+            enabled: true,
+            text_style: TextStyle::new(),
+            padding: Thickness::uniform(4f32),
+            background: Color::WHITE,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            hover_background: Color::GAINSBORO,
+            selected_background: Color::CORNFLOWER_BLUE,
+            selected_foreground: Color::WHITE,
+            today_border: Color::DIM_GRAY,
+            other_month_foreground: Color::GRAY,
+            disabled_foreground: Color::LIGHT_GRAY,
+            glyph: Color::DIM_GRAY,
+            on_selected_date_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            focused: false,
+            hovered: None,
+            current: today,
+            today,
+            pressed: None,
+            committed: false,
+            header_height: 0f32,
+            names_height: 0f32,
+            cell_width: 0f32,
+            cell_height: 0f32,
+            baseline: 0f32,
+            title: String::new(),
+            title_style: TextStyle::new(),
+        }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// # Description
+    /// Whether *date* is within the limits and not blacked out.
+    pub fn is_selectable(&self, date: Date) -> bool {
+        self.min_date.map_or(true, |d| date >= d)
+            && self.max_date.map_or(true, |d| date <= d)
+            && !self.blackout_dates.contains(&date)
+    }
+
+    /// # Description
+    /// Selects *date*, raising *on_selected_date_changed* if the selection changed.
+    ///
+    /// # Remarks
+    /// The month of *date* is shown. Dates that cannot be selected are ignored.
+    pub fn select(&mut self, date: Option<Date>) {
+        if let Some(date) = date {
+            if !self.is_selectable(date) {
+                return;
+            }
+            self.move_to(date);
+        }
+        if date == self.selected_date {
+            return;
+        }
+        self.selected_date = date;
+        if let Some(callback) = self.on_selected_date_changed.as_mut() {
+            callback(date);
+        }
+    }
+
+    /// # Description
+    /// Moves the keyboard to *date*, showing its month.
+    pub(crate) fn move_to(&mut self, date: Date) {
+        let date = self.clamp(date);
+        self.current = date;
+        self.display_date = date;
+    }
+
+    /// Whether the user selected a day since the last call.
+    pub(crate) fn take_committed(&mut self) -> bool {
+        std::mem::replace(&mut self.committed, false)
+    }
+
+    fn clamp(&self, date: Date) -> Date {
+        match (self.min_date, self.max_date) {
+            (Some(min), _) if date < min => min,
+            (_, Some(max)) if date > max => max,
+            _ => date,
+        }
+    }
+
+    /// Whether the month *months* months after the one shown contains days within the limits.
+    fn can_show(&self, months: i32) -> bool {
+        let month = self.display_date.add_months(months);
+        self.min_date.map_or(true, |d| month.last_of_month() >= d)
+            && self.max_date.map_or(true, |d| month.first_of_month() <= d)
+    }
+
+    fn show_month(&mut self, months: i32) {
+        if self.can_show(months) {
+            let date = self.current.add_months(months);
+            self.move_to(date);
+        }
+    }
+
+    /// The first day shown, which is in the previous month unless the month starts a week.
+    fn first_shown(&self) -> Date {
+        let first = self.display_date.first_of_month();
+        first.add_days(-(first.weekday().days_since(self.first_day_of_week) as i64))
+    }
+
+    fn row_heights(&self, line_height: f32) -> (f32, f32) {
+        (line_height + 2f32 * self.padding.vertical(), line_height + self.padding.vertical())
+    }
+
+    fn part_bounds(&self, part: Part) -> Rectangle<f32> {
+        match part {
+            Part::Previous | Part::Next => {
+                let width = f32::min(self.header_height, self.size.width / 2f32);
+                Rectangle {
+                    x: if part == Part::Previous { 1f32 } else { self.size.width - 1f32 - width },
+                    y: 1f32,
+                    width,
+                    height: self.header_height,
+                }
+            }
+            Part::Day(date) => {
+                let index = date.days() - self.first_shown().days();
+                Rectangle {
+                    x: 1f32 + (index % 7) as f32 * self.cell_width,
+                    y: 1f32 + self.header_height + self.names_height + (index / 7) as f32 * self.cell_height,
+                    width: self.cell_width,
+                    height: self.cell_height,
+                }
+            }
+        }
+    }
+
+    fn part_at(&self, position: Point<f32>) -> Option<Part> {
+        if let Some(part) = [Part::Previous, Part::Next]
+            .iter()
+            .copied()
+            .find(|d| self.part_bounds(*d).contains(position))
+        {
+            return Some(part);
+        }
+        let top = 1f32 + self.header_height + self.names_height;
+        if position.x < 1f32 || position.y < top || self.cell_width <= 0f32 || self.cell_height <= 0f32 {
+            return None;
+        }
+        let column = ((position.x - 1f32) / self.cell_width) as i64;
+        let row = ((position.y - top) / self.cell_height) as i64;
+        if column >= 7 || row >= WEEKS as i64 {
+            return None;
+        }
+        Some(Part::Day(self.first_shown().add_days(row * 7 + column)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Instant;
+    use winit::keyboard::ModifiersState;
+
+    fn date(month: u32, day: u32) -> Date {
+        Date::new(2024, month, day).unwrap()
+    }
+
+    /// A calendar limited to the 10th of March to the 20th of April 2024 without the 15th of
+    /// March, recording its selection changes in the returned vector.
+    fn calendar() -> (Calendar, Rc<RefCell<Vec<Option<Date>>>>) {
+        let changes = Rc::new(RefCell::new(vec![]));
+        let mut calendar = Calendar::new();
+        calendar.min_date = Some(date(3, 10));
+        calendar.max_date = Some(date(4, 20));
+        calendar.blackout_dates = vec![date(3, 15)];
+        let recorder = changes.clone();
+        calendar.on_selected_date_changed = Some(Box::new(move |d| recorder.borrow_mut().push(d)));
+        calendar.move_to(date(3, 12));
+        let size = calendar.measure(Size { width: 300f32, height: 300f32 });
+        calendar.arrange(size);
+        (calendar, changes)
+    }
+
+    fn send(calendar: &mut Calendar, event: InputEvent, modifiers: ModifiersState) -> bool {
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, modifiers);
+        calendar.handle_event(&event, &mut context)
+    }
+
+    fn click(calendar: &mut Calendar, part: Part) {
+        let bounds = calendar.part_bounds(part);
+        let position = Point { x: bounds.x + bounds.width / 2f32, y: bounds.y + bounds.height / 2f32 };
+        send(calendar, InputEvent::PointerPressed { position, button: MouseButton::Left }, ModifiersState::empty());
+        send(calendar, InputEvent::PointerReleased { position, button: MouseButton::Left }, ModifiersState::empty());
+    }
+
+    fn press(calendar: &mut Calendar, key: NamedKey, modifiers: ModifiersState) -> bool {
+        let event = InputEvent::KeyPressed { key: Key::Named(key), text: None, modifiers, repeat: false };
+        send(calendar, event, modifiers)
+    }
+
+    #[test]
+    fn test_select() {
+        let (mut calendar, changes) = calendar();
+        calendar.select(Some(date(3, 9)));
+        calendar.select(Some(date(3, 15)));
+        calendar.select(Some(date(4, 21)));
+        assert_eq!(calendar.selected_date, None);
+        calendar.select(Some(date(4, 20)));
+        assert_eq!(calendar.display_date, date(4, 20));
+        calendar.select(None);
+        assert_eq!(*changes.borrow(), vec![Some(date(4, 20)), None]);
+    }
+
+    #[test]
+    fn test_click() {
+        let (mut calendar, changes) = calendar();
+        click(&mut calendar, Part::Day(date(3, 9)));
+        click(&mut calendar, Part::Day(date(3, 15)));
+        assert!(!calendar.take_committed());
+        assert_eq!(calendar.selected_date, None);
+        click(&mut calendar, Part::Day(date(3, 16)));
+        assert!(calendar.take_committed());
+        assert_eq!(*changes.borrow(), vec![Some(date(3, 16))]);
+
+        // The months outside of the limits are not shown
+        click(&mut calendar, Part::Previous);
+        assert_eq!(calendar.display_date, date(3, 16));
+        click(&mut calendar, Part::Next);
+        assert_eq!(calendar.display_date, date(4, 16));
+        click(&mut calendar, Part::Next);
+        let wheel = InputEvent::PointerWheel {
+            position: Point { x: 10f32, y: 10f32 },
+            delta: Point { x: 0f32, y: -1f32 },
+        };
+        send(&mut calendar, wheel, ModifiersState::empty());
+        assert_eq!(calendar.display_date, date(4, 16));
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let (mut calendar, changes) = calendar();
+        assert!(!press(&mut calendar, NamedKey::ArrowLeft, ModifiersState::empty()));
+        send(&mut calendar, InputEvent::FocusGained, ModifiersState::empty());
+
+        // Moving is clamped to the limits
+        press(&mut calendar, NamedKey::ArrowLeft, ModifiersState::empty());
+        press(&mut calendar, NamedKey::ArrowLeft, ModifiersState::empty());
+        press(&mut calendar, NamedKey::ArrowLeft, ModifiersState::empty());
+        assert_eq!(calendar.current, date(3, 10));
+        press(&mut calendar, NamedKey::PageDown, ModifiersState::CONTROL);
+        assert_eq!(calendar.current, date(4, 20));
+        press(&mut calendar, NamedKey::PageUp, ModifiersState::empty());
+        assert_eq!(calendar.current, date(3, 20));
+
+        // Blacked out days are moved over but not selected
+        for _ in 0..5 {
+            press(&mut calendar, NamedKey::ArrowLeft, ModifiersState::empty());
+        }
+        assert_eq!(calendar.current, date(3, 15));
+        assert!(press(&mut calendar, NamedKey::Enter, ModifiersState::empty()));
+        assert!(!calendar.take_committed());
+        press(&mut calendar, NamedKey::ArrowRight, ModifiersState::empty());
+        assert!(!press(&mut calendar, NamedKey::Enter, ModifiersState::empty()));
+        assert!(calendar.take_committed());
+        assert_eq!(*changes.borrow(), vec![Some(date(3, 16))]);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::glyph_label::GlyphLabel;
+use crate::components::press_state::{PressResult, PressState};
+use crate::data::{ComponentId, Point, Rectangle, Size};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::TextStyle;
+use crate::tool_tip::ToolTip;
+use crate::visuals::{self, PathCommand};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Neither checked nor unchecked, eg. if only some of the items a check box stands for
+    /// are checked.
+    Indeterminate,
+}
+
+pub struct CheckBox {
+    /// The text displayed next to the box.
+    // #[property]
+    pub text: String,
+    // #[property]
+    pub text_style: TextStyle,
+    // #[property]
+    pub state: CheckState,
+    /// Whether clicking cycles through [`CheckState::Indeterminate`] as well.
+    // #[property]
+    pub three_state: bool,
+    // #[property]
+    pub enabled: bool,
+    /// The width and height of the box.
+    // #[property]
+    pub box_size: f32,
+    /// The space between the box and the text.
+    // #[property]
+    pub spacing: f32,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub hover_background: Color,
+    // #[property]
+    pub pressed_background: Color,
+    /// The background of the box while it is checked or indeterminate.
+    // #[property]
+    pub checked_background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    /// The color of the check mark.
+    // #[property]
+    pub mark: Color,
+    // #[property]
+    pub disabled_foreground: Color,
+    /// Called with the new state whenever the user changed the state.
+    // #[event]
+    pub on_state_changed: Option<Box<dyn FnMut(CheckState)>>,
+    /// The tool tip shown while the pointer hovers the check box.
+    // #[property]
+    pub tool_tip: Option<ToolTip>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    layout: GlyphLabel,
+    render_style: TextStyle,
+    press: PressState,
+}
+
+impl Component for CheckBox {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, _available: Size<f32>) -> Size<f32> {
+        GlyphLabel::measure(self.glyph_size(), self.spacing, &self.text, &self.text_style)
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.layout = GlyphLabel::arrange(self.glyph_size(), self.spacing, &self.text_style, given);
+        self.render_style = self.text_style.clone();
+        if !self.enabled {
+            self.render_style.color = self.disabled_foreground;
+        }
+        self.size = given;
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let glyph = self.layout.glyph;
+        let checked = self.state != CheckState::Unchecked;
+        let background = if !self.enabled {
+            self.background
+        } else if self.press.is_pressed() {
+            self.pressed_background
+        } else if checked {
+            self.checked_background
+        } else if self.press.hovered {
+            self.hover_background
+        } else {
+            self.background
+        };
+        visuals::RoundedRectangle {
+            x: glyph.x,
+            y: glyph.y,
+            width: glyph.width,
+            height: glyph.height,
+            radius: glyph.width / 8f32,
+            fill: background,
+            stroke: self.border_color(),
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        let mark = if self.enabled { self.mark } else { self.disabled_foreground };
+        match self.state {
+            CheckState::Unchecked => {}
+            CheckState::Checked => {
+                let point = |x: f32, y: f32| Point {
+                    x: glyph.x + glyph.width * x,
+                    y: glyph.y + glyph.height * y,
+                };
+                visuals::Path {
+                    commands: &[
+                        PathCommand::MoveTo(point(0.25f32, 0.5f32)),
+                        PathCommand::LineTo(point(0.42f32, 0.68f32)),
+                        PathCommand::LineTo(point(0.75f32, 0.32f32)),
+                    ],
+                    fill: Color::TRANSPARENT,
+                    stroke: mark,
+                    thickness: f32::max(1f32, glyph.width / 8f32),
+                }
+                .draw(renderer);
+            }
+            CheckState::Indeterminate => {
+                visuals::Rectangle {
+                    x: glyph.x + glyph.width / 4f32,
+                    y: glyph.y + glyph.height * 7f32 / 16f32,
+                    width: glyph.width / 2f32,
+                    height: glyph.height / 8f32,
+                    fill: mark,
+                }
+                .draw(renderer);
+            }
+        }
+
+        if !self.text.is_empty() {
+            visuals::Text {
+                x: self.layout.text_origin.x,
+                y: self.layout.text_origin.y,
+                text: &self.text,
+                style: &self.render_style,
+            }
+            .draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        let bounds = Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height };
+        match self.press.handle_event(event, context, bounds, self.enabled) {
+            PressResult::Unhandled => false,
+            PressResult::Handled => true,
+            PressResult::Clicked => {
+                self.toggle();
+                true
+            }
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+
+    fn tool_tip(&self) -> Option<&ToolTip> {
+        self.tool_tip.as_ref()
+    }
+}
+
+impl CheckBox {
+    pub fn new() -> CheckBox {
+        CheckBox {
+            id: ComponentId::new(),
+            text: String::new(),
+            text_style: TextStyle::new(),
+            state: CheckState::Unchecked,
+            three_state: false,
+            enabled: true,
+            box_size: 16f32,
+            spacing: 6f32,
+            background: Color::WHITE,
+            hover_background: Color::GAINSBORO,
+            pressed_background: Color::LIGHT_GRAY,
+            checked_background: Color::CORNFLOWER_BLUE,
+            border: Color::GRAY,
+            focused_border: Color::MIDNIGHT_BLUE,
+            mark: Color::WHITE,
+            disabled_foreground: Color::GRAY,
+            on_state_changed: None,
+            tool_tip: None,
+            size: Size { width: 0f32, height: 0f32 },
+            layout: GlyphLabel::new(),
+            render_style: TextStyle::new(),
+            press: PressState::new(),
+        }
+    }
+
+    /// # Description
+    /// Advances the state as if the user clicked the check box: from unchecked to checked,
+    /// then to indeterminate (if *three_state* is set) and back to unchecked.
+    pub fn toggle(&mut self) {
+        self.state = match self.state {
+            CheckState::Unchecked => CheckState::Checked,
+            CheckState::Checked if self.three_state => CheckState::Indeterminate,
+            CheckState::Checked | CheckState::Indeterminate => CheckState::Unchecked,
+        };
+        if let Some(callback) = self.on_state_changed.as_mut() {
+            callback(self.state);
+        }
+    }
+
+    fn glyph_size(&self) -> Size<f32> {
+        Size { width: self.box_size, height: self.box_size }
+    }
+
+    fn border_color(&self) -> Color {
+        if !self.enabled {
+            self.disabled_foreground
+        } else if self.press.focused {
+            self.focused_border
+        } else {
+            self.border
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use std::time::Instant;
+    use winit::event::MouseButton;
+    use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+    fn send(check_box: &mut CheckBox, event: InputEvent) -> bool {
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+        check_box.handle_event(&event, &mut context)
+    }
+
+    fn click(check_box: &mut CheckBox) {
+        let position = Point { x: 8f32, y: 8f32 };
+        send(check_box, InputEvent::PointerPressed { position, button: MouseButton::Left });
+        send(check_box, InputEvent::PointerReleased { position, button: MouseButton::Left });
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut check_box = CheckBox::new();
+        check_box.arrange(Size { width: 100f32, height: 20f32 });
+        click(&mut check_box);
+        assert_eq!(check_box.state, CheckState::Checked);
+        click(&mut check_box);
+        assert_eq!(check_box.state, CheckState::Unchecked);
+
+        check_box.three_state = true;
+        let key = Key::Named(NamedKey::Space);
+        let modifiers = ModifiersState::empty();
+        for expected in [CheckState::Checked, CheckState::Indeterminate, CheckState::Unchecked].iter() {
+            send(&mut check_box, InputEvent::KeyPressed { key: key.clone(), text: None, modifiers, repeat: false });
+            send(&mut check_box, InputEvent::KeyReleased { key: key.clone(), modifiers });
+            assert_eq!(check_box.state, *expected);
+        }
+
+        check_box.enabled = false;
+        click(&mut check_box);
+        assert_eq!(check_box.state, CheckState::Unchecked);
+    }
+}
@@ -0,0 +1,608 @@
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::{NumericUpDown, TextBox};
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size};
+use crate::input::{EventContext, InputEvent};
+use crate::visuals;
+
+/// The space between the parts of a color picker.
+const SPACING: f32 = 6f32;
+/// The thickness of the hue and the alpha slider.
+const BAR_THICKNESS: f32 = 16f32;
+/// The size of a palette or recent color swatch.
+const SWATCH_SIZE: f32 = 16f32;
+/// The distance between the swatches of the palette.
+const SWATCH_SPACING: f32 = 2f32;
+/// The maximum size of the saturation and value square.
+const MAX_SQUARE_SIZE: f32 = 160f32;
+/// The width a color picker measures with if its available width is unbounded.
+const DEFAULT_WIDTH: f32 = 320f32;
+/// The size of the cells of the checkerboard displayed behind translucent colors.
+const CHECKER_SIZE: f32 = 4f32;
+
+/// The colors the hue slider runs through, from top to bottom.
+const HUES: &[Color] = &[
+    Color::RED,
+    Color::YELLOW,
+    Color::LIME,
+    Color::CYAN,
+    Color::BLUE,
+    Color::MAGENTA,
+    Color::RED,
+];
+
+/// The named colors offered by a color picker by default.
+const DEFAULT_PALETTE: &[Color] = &[
+    Color::BLACK,
+    Color::WHITE,
+    Color::RED,
+    Color::GREEN,
+    Color::BLUE,
+    Color::YELLOW,
+    Color::CYAN,
+    Color::MAGENTA,
+    Color::ORANGE,
+    Color::PURPLE,
+    Color::GRAY,
+    Color::LIGHT_GRAY,
+    Color::DARK_GRAY,
+    Color::DIM_GRAY,
+    Color::GAINSBORO,
+    Color::LIGHT_SLATE_GRAY,
+    Color::BROWN,
+    Color::PINK,
+    Color::LIME,
+    Color::OLIVE,
+    Color::MAROON,
+    Color::NAVY,
+    Color::TEAL,
+    Color::SILVER,
+    Color::GOLD,
+    Color::BEIGE,
+    Color::IVORY,
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ColorPickerDrag {
+    Square,
+    Hue,
+    Alpha,
+}
+
+/// The bounds of the parts of a color picker.
+struct ColorPickerLayout {
+    square: Rectangle<f32>,
+    hue_bar: Rectangle<f32>,
+    alpha_bar: Rectangle<f32>,
+    preview: Rectangle<f32>,
+    hex: Rectangle<f32>,
+    channels: [Rectangle<f32>; 4],
+    palette: Vec<Rectangle<f32>>,
+    recent: Vec<Rectangle<f32>>,
+    height: f32,
+}
+
+/// # Description
+/// Lets the user choose a color using a saturation and value square, a hue and an alpha
+/// slider, hex and RGBA input fields, a palette of named colors and the colors chosen recently.
+///
+/// # Remarks
+/// A color is added to the recent colors once the user finished choosing it by releasing a
+/// slider, clicking a swatch or entering its hex code.
+pub struct ColorPicker {
+    // #[property]
+    pub color: Color,
+    // #[property]
+    pub palette: Vec<Color>,
+    /// The colors chosen recently, most recent first. Can be set to restore them.
+    // #[property]
+    pub recent_colors: Vec<Color>,
+    // #[property]
+    pub max_recent_colors: usize,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub border: Color,
+    /// The border of the swatch of the current color.
+    // #[property]
+    pub selected_border: Color,
+    /// Called with the new color whenever the user changed the color.
+    // #[event]
+    pub on_color_changed: Option<Box<dyn FnMut(Color)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    layout: ColorPickerLayout,
+    /// The hue, saturation and value, kept separately as grays and black have no hue.
+    hsv: (f32, f32, f32),
+    /// The color *hsv* was last synchronized with.
+    hsv_color: Option<Color>,
+    hex: TextBox,
+    hex_focused: bool,
+    channels: [NumericUpDown; 4],
+    /// The color the input fields were last synchronized with.
+    displayed: Option<Color>,
+    drag: Option<ColorPickerDrag>,
+}
+
+impl Component for ColorPicker {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let width = if available.width.is_finite() { available.width } else { DEFAULT_WIDTH };
+        Size { width, height: self.compute_layout(width).height }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        self.layout = self.compute_layout(given.width);
+        self.arrange_fields();
+
+        // Apply the values entered into the fields
+        let focused = self.hex.is_focused();
+        if self.hex_focused && !focused {
+            self.commit_hex();
+        }
+        self.hex_focused = focused;
+        if let Some(displayed) = self.displayed {
+            let channel = |d: &NumericUpDown| d.range.value.round() as u8;
+            let [red, green, blue, alpha] = &self.channels;
+            let entered = Color::from_rgba(channel(red), channel(green), channel(blue), channel(alpha));
+            if entered != displayed {
+                self.set_color(entered);
+            }
+        }
+
+        // Synchronize the fields with a color changed otherwise
+        if self.displayed != Some(self.color) {
+            let values = [self.color.red(), self.color.green(), self.color.blue(), self.color.alpha()];
+            for (channel, value) in self.channels.iter_mut().zip(values.iter()) {
+                channel.range.value = *value as f32;
+            }
+            if !focused {
+                self.hex.text = self.color.to_hex();
+            }
+            self.displayed = Some(self.color);
+            self.arrange_fields();
+        }
+        if self.hsv_color != Some(self.color) {
+            let (hue, saturation, value) = self.color.to_hsv();
+            // Grays keep the hue and black additionally keeps the saturation
+            let hue = if saturation > 0f32 && value > 0f32 { hue } else { self.hsv.0 };
+            let saturation = if value > 0f32 { saturation } else { self.hsv.1 };
+            self.hsv = (hue, saturation, value);
+            self.hsv_color = Some(self.color);
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let layout = &self.layout;
+        let (hue, saturation, value) = self.hsv;
+
+        // The saturation increases to the right and the value decreases to the bottom
+        let square = layout.square;
+        visuals::LinearGradient {
+            x: square.x,
+            y: square.y,
+            width: square.width,
+            height: square.height,
+            orientation: Orientation::Horizontal,
+            colors: &[Color::WHITE, Color::from_hsv(hue, 1f32, 1f32)],
+        }
+        .draw(renderer);
+        visuals::LinearGradient {
+            x: square.x,
+            y: square.y,
+            width: square.width,
+            height: square.height,
+            orientation: Orientation::Vertical,
+            colors: &[Color::TRANSPARENT, Color::BLACK],
+        }
+        .draw(renderer);
+        self.render_border(renderer, square);
+        let marker = Point {
+            x: square.x + saturation * square.width,
+            y: square.y + (1f32 - value) * square.height,
+        };
+        visuals::RoundedRectangle {
+            x: marker.x - 4f32,
+            y: marker.y - 4f32,
+            width: 8f32,
+            height: 8f32,
+            radius: 4f32,
+            fill: Color::TRANSPARENT,
+            stroke: if value > 0.5f32 { Color::BLACK } else { Color::WHITE },
+            thickness: 1.5f32,
+        }
+        .draw(renderer);
+
+        let bar = layout.hue_bar;
+        visuals::LinearGradient {
+            x: bar.x,
+            y: bar.y,
+            width: bar.width,
+            height: bar.height,
+            orientation: Orientation::Vertical,
+            colors: HUES,
+        }
+        .draw(renderer);
+        self.render_border(renderer, bar);
+        self.render_bar_marker(renderer, bar, hue / 360f32);
+
+        let bar = layout.alpha_bar;
+        render_checkerboard(renderer, bar);
+        visuals::LinearGradient {
+            x: bar.x,
+            y: bar.y,
+            width: bar.width,
+            height: bar.height,
+            orientation: Orientation::Vertical,
+            colors: &[self.color.with_alpha(0xFF), self.color.with_alpha(0)],
+        }
+        .draw(renderer);
+        self.render_border(renderer, bar);
+        self.render_bar_marker(renderer, bar, 1f32 - self.color.alpha() as f32 / 255f32);
+
+        render_checkerboard(renderer, layout.preview);
+        self.render_swatch(renderer, layout.preview, self.color, false);
+        for (bounds, component) in self.fields() {
+            renderer.translate(bounds.x, bounds.y);
+            component.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+        for (bounds, color) in layout.palette.iter().zip(self.palette.iter()) {
+            self.render_swatch(renderer, *bounds, *color, *color == self.color);
+        }
+        for (bounds, color) in layout.recent.iter().zip(self.recent_colors.iter()) {
+            render_checkerboard(renderer, *bounds);
+            self.render_swatch(renderer, *bounds, *color, *color == self.color);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerPressed { position, button: MouseButton::Left } if self.enabled => {
+                let drag = if self.layout.square.contains(*position) {
+                    ColorPickerDrag::Square
+                } else if self.layout.hue_bar.contains(*position) {
+                    ColorPickerDrag::Hue
+                } else if self.layout.alpha_bar.contains(*position) {
+                    ColorPickerDrag::Alpha
+                } else {
+                    let palette = self.layout.palette.iter().zip(self.palette.iter());
+                    let recent = self.layout.recent.iter().zip(self.recent_colors.iter());
+                    return match palette.chain(recent).find(|d| d.0.contains(*position)) {
+                        None => false,
+                        Some((_, color)) => {
+                            self.set_color(*color);
+                            self.add_recent_color();
+                            true
+                        }
+                    };
+                };
+                self.drag = Some(drag);
+                self.apply_drag(drag, *position);
+                context.capture_pointer();
+                true
+            }
+            InputEvent::PointerMoved { position } => match self.drag {
+                None => false,
+                Some(drag) => {
+                    self.apply_drag(drag, *position);
+                    true
+                }
+            },
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                if self.drag.take().is_none() {
+                    return false;
+                }
+                context.release_pointer_capture();
+                self.add_recent_color();
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.drag = None;
+                true
+            }
+            // Enter and Escape bubble from the hex field
+            InputEvent::KeyPressed { key: Key::Named(NamedKey::Enter), .. } if self.hex.is_focused() => {
+                self.commit_hex();
+                true
+            }
+            InputEvent::KeyPressed { key: Key::Named(NamedKey::Escape), .. } if self.hex.is_focused() => {
+                self.hex.text = self.color.to_hex();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        for (bounds, component) in self.fields() {
+            visitor(bounds, component);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        visitor(self.layout.hex, &mut self.hex);
+        for (bounds, channel) in self.layout.channels.iter().zip(self.channels.iter_mut()) {
+            visitor(*bounds, channel);
+        }
+    }
+}
+
+impl ColorPicker {
+    pub fn new() -> ColorPicker {
+        let channels = [
+            ColorPicker::channel(),
+            ColorPicker::channel(),
+            ColorPicker::channel(),
+            ColorPicker::channel(),
+        ];
+        ColorPicker {
+            id: ComponentId::new(),
+            color: Color::WHITE,
+            palette: DEFAULT_PALETTE.to_vec(),
+            recent_colors: vec![],
+            max_recent_colors: 12,
+            enabled: true,
+            border: Color::GRAY,
+            selected_border: Color::CORNFLOWER_BLUE,
+            on_color_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            layout: ColorPickerLayout {
+                square: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+                hue_bar: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+                alpha_bar: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+                preview: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+                hex: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+                channels: [Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 }; 4],
+                palette: vec![],
+                recent: vec![],
+                height: 0f32,
+            },
+            hsv: (0f32, 0f32, 1f32),
+            hsv_color: None,
+            hex: TextBox::new(),
+            hex_focused: false,
+            channels,
+            displayed: None,
+            drag: None,
+        }
+    }
+
+    /// An input field for a channel of the color.
+    fn channel() -> NumericUpDown {
+        let mut channel = NumericUpDown::new();
+        channel.range.max_value = 255f32;
+        channel.large_change = 16f32;
+        channel
+    }
+
+    fn set_color(&mut self, color: Color) {
+        if self.color == color {
+            return;
+        }
+        self.color = color;
+        if let Some(callback) = self.on_color_changed.as_mut() {
+            callback(color);
+        }
+    }
+
+    fn add_recent_color(&mut self) {
+        push_recent_color(&mut self.recent_colors, self.color, self.max_recent_colors);
+    }
+
+    /// Applies the color entered into the hex field, which is reverted if it is not valid.
+    fn commit_hex(&mut self) {
+        if let Ok(color) = Color::parse_hex(&self.hex.text) {
+            self.set_color(color);
+            self.add_recent_color();
+        }
+        self.hex.text = self.color.to_hex();
+    }
+
+    fn apply_drag(&mut self, drag: ColorPickerDrag, position: Point<f32>) {
+        let ratio = |d: f32, start: f32, length: f32| {
+            if length > 0f32 { f32::max(0f32, f32::min((d - start) / length, 1f32)) } else { 0f32 }
+        };
+        let (mut hue, mut saturation, mut value) = self.hsv;
+        let mut alpha = self.color.alpha();
+        match drag {
+            ColorPickerDrag::Square => {
+                let square = self.layout.square;
+                saturation = ratio(position.x, square.x, square.width);
+                value = 1f32 - ratio(position.y, square.y, square.height);
+            }
+            ColorPickerDrag::Hue => {
+                let bar = self.layout.hue_bar;
+                // The bottom of the bar is a hue of 360, which equals 0
+                hue = f32::min(ratio(position.y, bar.y, bar.height) * 360f32, 359.99f32);
+            }
+            ColorPickerDrag::Alpha => {
+                let bar = self.layout.alpha_bar;
+                alpha = ((1f32 - ratio(position.y, bar.y, bar.height)) * 255f32).round() as u8;
+            }
+        }
+        self.hsv = (hue, saturation, value);
+        let color = Color::from_hsv(hue, saturation, value).with_alpha(alpha);
+        self.set_color(color);
+        self.hsv_color = Some(color);
+    }
+
+    fn compute_layout(&self, width: f32) -> ColorPickerLayout {
+        let side = f32::max(0f32, f32::min(MAX_SQUARE_SIZE, width - 2f32 * (SPACING + BAR_THICKNESS)));
+        let square = Rectangle { x: 0f32, y: 0f32, width: side, height: side };
+        let hue_bar = Rectangle { x: side + SPACING, y: 0f32, width: BAR_THICKNESS, height: side };
+        let alpha_bar = Rectangle { x: hue_bar.x + BAR_THICKNESS + SPACING, ..hue_bar };
+
+        // The preview and the hex field share a row with the channel fields
+        let field_height = self.channels[0].measure(Size { width, height: f32::INFINITY }).height;
+        let y = side + SPACING;
+        let preview = Rectangle { x: 0f32, y, width: field_height, height: field_height };
+        let field_width = f32::max(0f32, (width - field_height - 5f32 * SPACING) / 6f32);
+        let hex = Rectangle {
+            x: field_height + SPACING,
+            y,
+            width: 2f32 * field_width,
+            height: field_height,
+        };
+        let mut channels = [hex; 4];
+        for (index, channel) in channels.iter_mut().enumerate() {
+            channel.x = hex.x + hex.width + SPACING + index as f32 * (field_width + SPACING);
+            channel.width = field_width;
+        }
+
+        let step = SWATCH_SIZE + SWATCH_SPACING;
+        let columns = usize::max(1, ((width + SWATCH_SPACING) / step) as usize);
+        let swatches = |count: usize, top: f32| -> Vec<Rectangle<f32>> {
+            (0..count)
+                .map(|d| Rectangle {
+                    x: (d % columns) as f32 * step,
+                    y: top + (d / columns) as f32 * step,
+                    width: SWATCH_SIZE,
+                    height: SWATCH_SIZE,
+                })
+                .collect()
+        };
+        let rows = |count: usize| ((count + columns - 1) / columns) as f32;
+        let palette_top = y + field_height + SPACING;
+        let palette = swatches(self.palette.len(), palette_top);
+        let recent_top = palette_top + rows(self.palette.len()) * step + SPACING;
+        let recent_count = usize::min(self.recent_colors.len(), self.max_recent_colors);
+        let recent = swatches(recent_count, recent_top);
+        let height = recent_top + f32::max(1f32, rows(recent_count)) * step;
+        ColorPickerLayout { square, hue_bar, alpha_bar, preview, hex, channels, palette, recent, height }
+    }
+
+    fn arrange_fields(&mut self) {
+        let size = |d: Rectangle<f32>| Size { width: d.width, height: d.height };
+        self.hex.measure(size(self.layout.hex));
+        self.hex.arrange(size(self.layout.hex));
+        for (bounds, channel) in self.layout.channels.iter().zip(self.channels.iter_mut()) {
+            channel.measure(size(*bounds));
+            channel.arrange(size(*bounds));
+        }
+    }
+
+    fn fields(&self) -> Vec<(Rectangle<f32>, &dyn Component)> {
+        let mut fields: Vec<(Rectangle<f32>, &dyn Component)> = vec![(self.layout.hex, &self.hex)];
+        for (bounds, channel) in self.layout.channels.iter().zip(self.channels.iter()) {
+            fields.push((*bounds, channel));
+        }
+        fields
+    }
+
+    fn render_border(&self, renderer: &mut dyn Renderer, bounds: Rectangle<f32>) {
+        visuals::Outline {
+            x: bounds.x + 0.5f32,
+            y: bounds.y + 0.5f32,
+            width: bounds.width - 1f32,
+            height: bounds.height - 1f32,
+            stroke: self.border,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+
+    fn render_swatch(
+        &self,
+        renderer: &mut dyn Renderer,
+        bounds: Rectangle<f32>,
+        color: Color,
+        selected: bool,
+    ) {
+        visuals::Rectangle {
+            x: bounds.x,
+            y: bounds.y,
+            width: bounds.width,
+            height: bounds.height,
+            fill: color,
+        }
+        .draw(renderer);
+        let thickness = if selected { 2f32 } else { 1f32 };
+        visuals::Outline {
+            x: bounds.x + thickness / 2f32,
+            y: bounds.y + thickness / 2f32,
+            width: bounds.width - thickness,
+            height: bounds.height - thickness,
+            stroke: if selected { self.selected_border } else { self.border },
+            thickness,
+        }
+        .draw(renderer);
+    }
+
+    /// Draws a marker across *bar* at *ratio* of its height.
+    fn render_bar_marker(&self, renderer: &mut dyn Renderer, bar: Rectangle<f32>, ratio: f32) {
+        let y = bar.y + ratio * bar.height;
+        visuals::Outline {
+            x: bar.x - 1.5f32,
+            y: y - 2f32,
+            width: bar.width + 3f32,
+            height: 4f32,
+            stroke: Color::BLACK,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+}
+
+/// Draws a checkerboard into *bounds*, displayed behind translucent colors.
+fn render_checkerboard(renderer: &mut dyn Renderer, bounds: Rectangle<f32>) {
+    visuals::Rectangle {
+        x: bounds.x,
+        y: bounds.y,
+        width: bounds.width,
+        height: bounds.height,
+        fill: Color::WHITE,
+    }
+    .draw(renderer);
+    let columns = (bounds.width / CHECKER_SIZE).ceil() as usize;
+    let rows = (bounds.height / CHECKER_SIZE).ceil() as usize;
+    for row in 0..rows {
+        for column in (row % 2..columns).step_by(2) {
+            let x = column as f32 * CHECKER_SIZE;
+            let y = row as f32 * CHECKER_SIZE;
+            visuals::Rectangle {
+                x: bounds.x + x,
+                y: bounds.y + y,
+                width: f32::min(CHECKER_SIZE, bounds.width - x),
+                height: f32::min(CHECKER_SIZE, bounds.height - y),
+                fill: Color::GAINSBORO,
+            }
+            .draw(renderer);
+        }
+    }
+}
+
+/// Moves *color* to the front of *recent*, keeping at most *max* colors.
+fn push_recent_color(recent: &mut Vec<Color>, color: Color, max: usize) {
+    recent.retain(|d| *d != color);
+    recent.insert(0, color);
+    recent.truncate(max);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_recent_color() {
+        let mut recent = vec![];
+        push_recent_color(&mut recent, Color::RED, 3);
+        push_recent_color(&mut recent, Color::LIME, 3);
+        push_recent_color(&mut recent, Color::BLUE, 3);
+        push_recent_color(&mut recent, Color::LIME, 3);
+        assert_eq!(recent, vec![Color::LIME, Color::BLUE, Color::RED]);
+        push_recent_color(&mut recent, Color::WHITE, 3);
+        assert_eq!(recent, vec![Color::WHITE, Color::LIME, Color::BLUE]);
+    }
+}
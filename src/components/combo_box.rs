@@ -0,0 +1,885 @@
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::{ScrollBar, TextBox};
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::overlay::{Placement, PopupPlacement};
+use crate::text::{self, TextStyle};
+use crate::visuals::{self, PathCommand};
+
+/// The maximum time between two key presses searching for the same item.
+const SEARCH_TIMEOUT: Duration = Duration::from_millis(1000);
+const SCROLL_BAR_THICKNESS: f32 = 14f32;
+
+/// # Description
+/// The list of items a [`ComboBox`] shows while open.
+///
+/// # Remarks
+/// Only the items currently scrolled into view are rendered, so long lists stay cheap.
+struct DropDown {
+    id: ComponentId,
+    items: Vec<String>,
+    text_style: TextStyle,
+    padding: Thickness,
+    background: Color,
+    border: Color,
+    highlight_background: Color,
+    /// The width of the combo box, which the drop-down matches.
+    width: f32,
+    max_items: usize,
+    /// The item under the pointer or selected using the keyboard.
+    highlighted: Option<usize>,
+    /// The item the pointer was released on, to be selected by the combo box.
+    committed: Option<usize>,
+    scroll_to_highlighted: bool,
+    size: Size<f32>,
+    item_height: f32,
+    baseline: f32,
+    scroll_bar: ScrollBar,
+    scroll_bar_visible: bool,
+}
+
+impl Component for DropDown {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, _available: Size<f32>) -> Size<f32> {
+        let font = text::text_metrics().font_metrics(&self.text_style);
+        let item_height = font.line_height + self.padding.vertical();
+        let count = usize::max(1, usize::min(self.items.len(), self.max_items));
+        Size {
+            width: self.width,
+            height: count as f32 * item_height + 2f32,
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        let font = text::text_metrics().font_metrics(&self.text_style);
+        self.item_height = font.line_height + self.padding.vertical();
+        self.baseline = self.padding.top + font.ascent;
+        self.size = given;
+
+        let viewport = self.viewport();
+        let content_height = self.items.len() as f32 * self.item_height;
+        self.scroll_bar_visible = content_height > viewport.height;
+        self.scroll_bar.range.max_value = f32::max(0f32, content_height - viewport.height);
+        self.scroll_bar.bar_value = viewport.height;
+        self.scroll_bar.range.small_change = self.item_height;
+        if self.scroll_to_highlighted {
+            self.scroll_to_highlighted = false;
+            if let Some(index) = self.highlighted {
+                let top = index as f32 * self.item_height;
+                let scroll = self.scroll_bar.range.value;
+                if top < scroll {
+                    self.scroll_bar.range.value = top;
+                } else if top + self.item_height > scroll + viewport.height {
+                    self.scroll_bar.range.value = top + self.item_height - viewport.height;
+                }
+            }
+        }
+        self.scroll_bar.set_value(self.scroll_bar.range.value);
+        let bounds = self.scroll_bar_bounds();
+        self.scroll_bar.arrange(Size { width: bounds.width, height: bounds.height });
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            fill: self.background,
+        }
+        .draw(renderer);
+        visuals::Outline {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            stroke: self.border,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        let viewport = self.viewport();
+        if self.item_height > 0f32 {
+            renderer.push_clip(viewport.x, viewport.y, viewport.width, viewport.height);
+            let scroll = self.scroll_bar.range.value;
+            let first = (scroll / self.item_height).floor().max(0f32) as usize;
+            let visible = (viewport.height / self.item_height).ceil() as usize + 1;
+            for index in first..usize::min(first + visible, self.items.len()) {
+                let y = viewport.y + index as f32 * self.item_height - scroll;
+                if self.highlighted == Some(index) {
+                    visuals::Rectangle {
+                        x: viewport.x,
+                        y,
+                        width: viewport.width,
+                        height: self.item_height,
+                        fill: self.highlight_background,
+                    }
+                    .draw(renderer);
+                }
+                visuals::Text {
+                    x: viewport.x + self.padding.left,
+                    y: y + self.baseline,
+                    text: &self.items[index],
+                    style: &self.text_style,
+                }
+                .draw(renderer);
+            }
+            renderer.pop_clip();
+        }
+
+        if self.scroll_bar_visible {
+            let bounds = self.scroll_bar_bounds();
+            renderer.translate(bounds.x, bounds.y);
+            self.scroll_bar.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, _context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerPressed { button: MouseButton::Left, .. } => true,
+            InputEvent::PointerReleased { position, button: MouseButton::Left } => {
+                // The combo box selects the item, as the event bubbles up to it
+                self.committed = self.item_at(*position);
+                false
+            }
+            InputEvent::PointerMoved { position } => {
+                if let Some(index) = self.item_at(*position) {
+                    self.highlighted = Some(index);
+                }
+                false
+            }
+            InputEvent::PointerWheel { delta, .. } => {
+                let value = self.scroll_bar.range.value - delta.y * self.scroll_bar.range.small_change;
+                self.scroll_bar.set_value(value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if self.scroll_bar_visible {
+            visitor(self.scroll_bar_bounds(), &self.scroll_bar);
+        }
+    }
+
+    fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component)) {
+        if self.scroll_bar_visible {
+            visitor(self.scroll_bar_bounds(), &mut self.scroll_bar);
+        }
+    }
+}
+
+impl DropDown {
+    fn new() -> DropDown {
+        let mut scroll_bar = ScrollBar::new();
+        scroll_bar.mode = Orientation::Vertical;
+        scroll_bar.thickness = SCROLL_BAR_THICKNESS;
+        DropDown {
+            id: ComponentId::new(),
+            items: vec![],
+            text_style: TextStyle::new(),
+            padding: Thickness::uniform(4f32),
+            background: Color::WHITE,
+            border: Color::GRAY,
+            highlight_background: Color::LIGHT_GRAY,
+            width: 0f32,
+            max_items: 0,
+            highlighted: None,
+            committed: None,
+            scroll_to_highlighted: false,
+            size: Size { width: 0f32, height: 0f32 },
+            item_height: 0f32,
+            baseline: 0f32,
+            scroll_bar,
+            scroll_bar_visible: false,
+        }
+    }
+
+    /// The area the items are displayed in, inside the border.
+    fn viewport(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 1f32,
+            y: 1f32,
+            width: f32::max(
+                0f32,
+                self.size.width - 2f32 - if self.scroll_bar_visible { SCROLL_BAR_THICKNESS } else { 0f32 },
+            ),
+            height: f32::max(0f32, self.size.height - 2f32),
+        }
+    }
+
+    fn scroll_bar_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: f32::max(0f32, self.size.width - 1f32 - SCROLL_BAR_THICKNESS),
+            y: 1f32,
+            width: SCROLL_BAR_THICKNESS,
+            height: f32::max(0f32, self.size.height - 2f32),
+        }
+    }
+
+    /// The number of items fitting into the drop-down at once.
+    fn page_size(&self) -> usize {
+        if self.item_height > 0f32 {
+            usize::max(1, (self.viewport().height / self.item_height).floor() as usize)
+        } else {
+            1
+        }
+    }
+
+    /// The item at *position*, relative to the drop-down.
+    fn item_at(&self, position: Point<f32>) -> Option<usize> {
+        let viewport = self.viewport();
+        if !viewport.contains(position) || self.item_height <= 0f32 {
+            return None;
+        }
+        let index = ((position.y - viewport.y + self.scroll_bar.range.value) / self.item_height) as usize;
+        if index < self.items.len() {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    fn highlight(&mut self, index: usize) {
+        self.highlighted = Some(index);
+        self.scroll_to_highlighted = true;
+    }
+}
+
+/// # Description
+/// Selects one of a list of items, which is shown in a drop-down while the combo box is open.
+///
+/// # Remarks
+/// Editable combo boxes allow entering any text, highlighting the first item starting with it.
+/// Typing while a non-editable combo box has the focus selects the first item starting with
+/// the typed text.
+pub struct ComboBox {
+    // #[property]
+    pub items: Vec<String>,
+    // #[property]
+    pub selected_index: Option<usize>,
+    /// Whether the user can enter text not matching any item.
+    // #[property]
+    pub is_editable: bool,
+    /// The text displayed while no item is selected.
+    // #[property]
+    pub placeholder: String,
+    // #[property]
+    pub text_style: TextStyle,
+    /// The maximum number of items visible in the drop-down without scrolling.
+    // #[property]
+    pub max_drop_down_items: usize,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub corner_radius: f32,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub hover_background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    /// The color of the arrow indicating the drop-down.
+    // #[property]
+    pub arrow: Color,
+    // #[property]
+    pub placeholder_color: Color,
+    // #[property]
+    pub disabled_foreground: Color,
+    /// The background of the highlighted item in the drop-down.
+    // #[property]
+    pub highlight_background: Color,
+    /// Called with the new selected index whenever the user changed the selection.
+    // #[event]
+    pub on_selection_changed: Option<Box<dyn FnMut(Option<usize>)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    baseline: f32,
+    hovered: bool,
+    focused: bool,
+    is_open: bool,
+    drop_down: DropDown,
+    text_box: TextBox,
+    /// The text of the text box as of the last layout pass, to notice edits.
+    last_text: String,
+    search: String,
+    last_search: Option<Instant>,
+    render_style: TextStyle,
+}
+
+impl Component for ComboBox {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let metrics = text::text_metrics();
+        let line_height = metrics.font_metrics(&self.text_style).line_height;
+        let height = line_height + self.padding.vertical();
+        let width = if available.width.is_finite() {
+            available.width
+        } else {
+            let text = self
+                .items
+                .iter()
+                .map(|d| metrics.measure_text(d, &self.text_style))
+                .fold(metrics.measure_text(&self.placeholder, &self.text_style), f32::max);
+            text + self.padding.horizontal() + height
+        };
+        Size { width, height }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let font = text::text_metrics().font_metrics(&self.text_style);
+        self.baseline = (given.height - font.line_height) / 2f32 + font.ascent;
+        self.render_style = self.text_style.clone();
+        if !self.enabled {
+            self.render_style.color = self.disabled_foreground;
+        }
+        if let Some(index) = self.selected_index {
+            if index >= self.items.len() {
+                self.selected_index = None;
+            }
+        }
+
+        if self.is_editable {
+            self.text_box.text_style = self.text_style.clone();
+            self.text_box.padding = self.padding;
+            self.text_box.placeholder = self.placeholder.clone();
+            self.text_box.read_only = !self.enabled;
+            let bounds = self.text_box_bounds();
+            self.text_box.arrange(Size { width: bounds.width, height: bounds.height });
+            if self.text_box.text != self.last_text {
+                self.last_text = self.text_box.text.clone();
+                self.text_edited();
+            }
+        }
+
+        if self.is_open {
+            self.drop_down.text_style = self.text_style.clone();
+            self.drop_down.background = self.background;
+            self.drop_down.border = self.border;
+            self.drop_down.highlight_background = self.highlight_background;
+            self.drop_down.width = given.width;
+            self.drop_down.max_items = self.max_drop_down_items;
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let arrow = self.arrow_bounds();
+        if !self.is_editable {
+            visuals::RoundedRectangle {
+                x: 0f32,
+                y: 0f32,
+                width: self.size.width,
+                height: self.size.height,
+                radius: self.corner_radius,
+                fill: if self.enabled && (self.hovered || self.is_open) {
+                    self.hover_background
+                } else {
+                    self.background
+                },
+                stroke: if self.focused { self.focused_border } else { self.border },
+                thickness: 1f32,
+            }
+            .draw(renderer);
+
+            let (text, style) = match self.selected_index {
+                Some(index) => (self.items[index].as_str(), self.render_style.clone()),
+                None => {
+                    let mut style = self.render_style.clone();
+                    style.color = self.placeholder_color;
+                    (self.placeholder.as_str(), style)
+                }
+            };
+            renderer.push_clip(0f32, 0f32, f32::max(0f32, arrow.x), self.size.height);
+            visuals::Text {
+                x: self.padding.left,
+                y: self.baseline,
+                text,
+                style: &style,
+            }
+            .draw(renderer);
+            renderer.pop_clip();
+        } else {
+            let bounds = self.text_box_bounds();
+            renderer.translate(bounds.x, bounds.y);
+            self.text_box.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+            visuals::RoundedRectangle {
+                x: arrow.x,
+                y: arrow.y,
+                width: arrow.width,
+                height: arrow.height,
+                radius: self.corner_radius,
+                fill: if self.enabled && (self.hovered || self.is_open) {
+                    self.hover_background
+                } else {
+                    self.background
+                },
+                stroke: self.border,
+                thickness: 1f32,
+            }
+            .draw(renderer);
+        }
+
+        // A chevron pointing down, or up while open
+        let center = Point {
+            x: arrow.x + arrow.width / 2f32,
+            y: arrow.y + arrow.height / 2f32,
+        };
+        let extent = arrow.height / 8f32;
+        let direction = if self.is_open { -1f32 } else { 1f32 };
+        visuals::Path {
+            commands: &[
+                PathCommand::MoveTo(Point { x: center.x - extent * 2f32, y: center.y - extent * direction }),
+                PathCommand::LineTo(Point { x: center.x, y: center.y + extent * direction }),
+                PathCommand::LineTo(Point { x: center.x + extent * 2f32, y: center.y - extent * direction }),
+            ],
+            fill: Color::TRANSPARENT,
+            stroke: if self.enabled { self.arrow } else { self.disabled_foreground },
+            thickness: 1.5f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerEntered => {
+                self.hovered = true;
+                true
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = false;
+                true
+            }
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                self.close();
+                true
+            }
+            InputEvent::LightDismiss => {
+                self.close();
+                true
+            }
+            _ if !self.enabled => false,
+            InputEvent::PointerPressed { button: MouseButton::Left, .. } => {
+                if self.is_open {
+                    self.close();
+                } else {
+                    self.open();
+                }
+                true
+            }
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                match self.drop_down.committed.take() {
+                    Some(index) if self.is_open => {
+                        self.select(Some(index));
+                        self.close();
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            InputEvent::KeyPressed { key, text, modifiers, .. } => {
+                let searching = self
+                    .last_search
+                    .map_or(false, |d| context.now().duration_since(d) <= SEARCH_TIMEOUT);
+                let handled = if self.is_open {
+                    self.handle_open_key(key)
+                } else if modifiers.alt_key() && *key == Key::Named(NamedKey::ArrowDown)
+                    || *key == Key::Named(NamedKey::F4)
+                    || !self.is_editable && !searching && *key == Key::Named(NamedKey::Space)
+                {
+                    self.open();
+                    true
+                } else {
+                    self.handle_closed_key(key)
+                };
+                match text {
+                    _ if handled => true,
+                    Some(text)
+                        if !self.is_editable
+                            && !modifiers.control_key()
+                            && !modifiers.alt_key()
+                            && !text.chars().any(char::is_control) =>
+                    {
+                        self.search(text, context.now());
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if self.is_editable {
+            visitor(self.text_box_bounds(), &self.text_box);
+        }
+    }
+
+    fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component)) {
+        if self.is_editable {
+            visitor(self.text_box_bounds(), &mut self.text_box);
+        }
+    }
+
+    fn visit_popups(&self, visitor: &mut dyn FnMut(PopupPlacement, &dyn Component)) {
+        if self.is_open {
+            visitor(self.drop_down_placement(), &self.drop_down);
+        }
+    }
+
+    fn visit_popups_mut(&mut self, visitor: &mut dyn FnMut(PopupPlacement, &mut dyn Component)) {
+        if self.is_open {
+            visitor(self.drop_down_placement(), &mut self.drop_down);
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        // Editable combo boxes are focused through their text box
+        if self.enabled && !self.is_editable {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+}
+
+impl ComboBox {
+    pub fn new() -> ComboBox {
+        ComboBox {
+            id: ComponentId::new(),
+            items: vec![],
+            selected_index: None,
+            is_editable: false,
+            placeholder: String::new(),
+            text_style: TextStyle::new(),
+            max_drop_down_items: 8,
+            enabled: true,
+            padding: Thickness::uniform(4f32),
+            corner_radius: 2f32,
+            background: Color::WHITE,
+            hover_background: Color::GAINSBORO,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            arrow: Color::DIM_GRAY,
+            placeholder_color: Color::GRAY,
+            disabled_foreground: Color::GRAY,
+            highlight_background: Color::LIGHT_GRAY,
+            on_selection_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            baseline: 0f32,
+            hovered: false,
+            focused: false,
+            is_open: false,
+            drop_down: DropDown::new(),
+            text_box: TextBox::new(),
+            last_text: String::new(),
+            search: String::new(),
+            last_search: None,
+            render_style: TextStyle::new(),
+        }
+    }
+
+    /// # Description
+    /// The text of the combo box: the entered text if editable, otherwise the selected item.
+    pub fn text(&self) -> &str {
+        if self.is_editable {
+            &self.text_box.text
+        } else {
+            match self.selected_index.and_then(|d| self.items.get(d)) {
+                None => "",
+                Some(d) => d,
+            }
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// # Description
+    /// Shows the drop-down, highlighting the selected item.
+    pub fn open(&mut self) {
+        if self.is_open {
+            return;
+        }
+        self.is_open = true;
+        self.drop_down.items = self.items.clone();
+        self.drop_down.highlighted = None;
+        if let Some(index) = self.selected_index {
+            self.drop_down.highlight(index);
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.drop_down.items.clear();
+    }
+
+    /// # Description
+    /// Selects the item at *index*, raising *on_selection_changed* if the selection changed.
+    ///
+    /// # Remarks
+    /// Editable combo boxes replace their text with the selected item.
+    pub fn select(&mut self, index: Option<usize>) {
+        let index = index.filter(|d| *d < self.items.len());
+        if let (true, Some(index)) = (self.is_editable, index) {
+            self.text_box.text = self.items[index].clone();
+            self.text_box.select_all();
+            self.last_text = self.text_box.text.clone();
+        }
+        if index == self.selected_index {
+            return;
+        }
+        self.selected_index = index;
+        if let Some(callback) = self.on_selection_changed.as_mut() {
+            callback(index);
+        }
+    }
+
+    /// Handles a key press while the drop-down is open.
+    fn handle_open_key(&mut self, key: &Key) -> bool {
+        let last = match self.items.len() {
+            0 => return false,
+            d => d - 1,
+        };
+        let highlighted = self.drop_down.highlighted;
+        let page = self.drop_down.page_size();
+        let target = match key {
+            Key::Named(NamedKey::Escape) => {
+                self.close();
+                return true;
+            }
+            Key::Named(NamedKey::Enter) | Key::Named(NamedKey::F4) => {
+                if let Some(index) = highlighted {
+                    self.select(Some(index));
+                }
+                self.close();
+                return true;
+            }
+            Key::Named(NamedKey::Tab) => {
+                self.close();
+                return false;
+            }
+            Key::Named(NamedKey::ArrowDown) => highlighted.map_or(0, |d| usize::min(d + 1, last)),
+            Key::Named(NamedKey::ArrowUp) => highlighted.map_or(0, |d| d.saturating_sub(1)),
+            Key::Named(NamedKey::PageDown) => highlighted.map_or(0, |d| usize::min(d + page, last)),
+            Key::Named(NamedKey::PageUp) => highlighted.map_or(0, |d| d.saturating_sub(page)),
+            Key::Named(NamedKey::Home) if !self.is_editable => 0,
+            Key::Named(NamedKey::End) if !self.is_editable => last,
+            _ => return false,
+        };
+        self.drop_down.highlight(target);
+        true
+    }
+
+    /// Handles a key press while the drop-down is closed, changing the selection directly.
+    fn handle_closed_key(&mut self, key: &Key) -> bool {
+        let last = match self.items.len() {
+            0 => return false,
+            d => d - 1,
+        };
+        let selected = self.selected_index;
+        let target = match key {
+            Key::Named(NamedKey::ArrowDown) => selected.map_or(0, |d| usize::min(d + 1, last)),
+            Key::Named(NamedKey::ArrowUp) => selected.map_or(0, |d| d.saturating_sub(1)),
+            Key::Named(NamedKey::Home) if !self.is_editable => 0,
+            Key::Named(NamedKey::End) if !self.is_editable => last,
+            _ => return false,
+        };
+        self.select(Some(target));
+        true
+    }
+
+    /// Selects (or highlights, while open) the next item starting with the text typed so far.
+    fn search(&mut self, text: &str, now: Instant) {
+        if self.last_search.map_or(true, |d| now.duration_since(d) > SEARCH_TIMEOUT) {
+            self.search.clear();
+        }
+        self.last_search = Some(now);
+        self.search.push_str(&text.to_lowercase());
+
+        let current = if self.is_open { self.drop_down.highlighted } else { self.selected_index };
+        // Repeating a single character cycles through the items starting with it
+        let mut characters = self.search.chars();
+        let first = characters.next();
+        let (prefix, start) = match first {
+            Some(d) if characters.all(|c| c == d) => (d.to_string(), current.map_or(0, |d| d + 1)),
+            _ => (self.search.clone(), current.unwrap_or(0)),
+        };
+        let count = self.items.len();
+        let found = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|d| self.items[*d].to_lowercase().starts_with(&prefix));
+        if let Some(index) = found {
+            if self.is_open {
+                self.drop_down.highlight(index);
+            } else {
+                self.select(Some(index));
+            }
+        }
+    }
+
+    /// Updates the selection and highlighted item after the user edited the text.
+    fn text_edited(&mut self) {
+        let text = self.text_box.text.to_lowercase();
+        let exact = self.items.iter().position(|d| d.to_lowercase() == text);
+        if exact != self.selected_index {
+            self.selected_index = exact;
+            if let Some(callback) = self.on_selection_changed.as_mut() {
+                callback(exact);
+            }
+        }
+        if self.is_open && !text.is_empty() {
+            if let Some(index) = self.items.iter().position(|d| d.to_lowercase().starts_with(&text)) {
+                self.drop_down.highlight(index);
+            }
+        }
+    }
+
+    /// The drop-down opens below the combo box, or above it if there is not enough space.
+    fn drop_down_placement(&self) -> PopupPlacement {
+        let anchor = Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height };
+        PopupPlacement::new(anchor, Placement::Bottom)
+    }
+
+    /// The area of the arrow indicating the drop-down, a square at the right edge.
+    fn arrow_bounds(&self) -> Rectangle<f32> {
+        let width = f32::min(self.size.height, self.size.width);
+        Rectangle {
+            x: self.size.width - width,
+            y: 0f32,
+            width,
+            height: self.size.height,
+        }
+    }
+
+    fn text_box_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.arrow_bounds().x,
+            height: self.size.height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use winit::keyboard::ModifiersState;
+
+    fn combo_box() -> ComboBox {
+        let mut combo_box = ComboBox::new();
+        combo_box.items = ["Apple", "Banana", "Blueberry", "Cherry"].iter().map(|d| d.to_string()).collect();
+        combo_box.arrange(Size { width: 120f32, height: 24f32 });
+        combo_box
+    }
+
+    fn press(combo_box: &mut ComboBox, key: Key, modifiers: ModifiersState, now: Instant) -> bool {
+        let text = match &key {
+            Key::Character(d) => Some(d.clone()),
+            Key::Named(NamedKey::Space) => Some(" ".into()),
+            _ => None,
+        };
+        let mut context = EventContext::new(now, &PointerState::new(), None, modifiers);
+        let event = InputEvent::KeyPressed { key, text, modifiers, repeat: false };
+        combo_box.handle_event(&event, &mut context)
+    }
+
+    fn named(combo_box: &mut ComboBox, key: NamedKey) -> bool {
+        press(combo_box, Key::Named(key), ModifiersState::empty(), Instant::now())
+    }
+
+    #[test]
+    fn test_closed_keys() {
+        let mut combo_box = combo_box();
+        named(&mut combo_box, NamedKey::ArrowDown);
+        assert_eq!(combo_box.selected_index, Some(0));
+        named(&mut combo_box, NamedKey::ArrowDown);
+        assert_eq!(combo_box.text(), "Banana");
+        named(&mut combo_box, NamedKey::End);
+        assert_eq!(combo_box.selected_index, Some(3));
+        named(&mut combo_box, NamedKey::ArrowDown);
+        assert_eq!(combo_box.selected_index, Some(3));
+        named(&mut combo_box, NamedKey::Home);
+        assert_eq!(combo_box.selected_index, Some(0));
+        assert!(!combo_box.is_open());
+        assert!(!named(&mut combo_box, NamedKey::Enter));
+    }
+
+    #[test]
+    fn test_open_keys() {
+        let mut combo_box = combo_box();
+        combo_box.select(Some(1));
+        press(&mut combo_box, Key::Named(NamedKey::ArrowDown), ModifiersState::ALT, Instant::now());
+        assert!(combo_box.is_open());
+        assert_eq!(combo_box.drop_down.highlighted, Some(1));
+        // Moving the highlight leaves the selection until it is committed
+        named(&mut combo_box, NamedKey::ArrowDown);
+        named(&mut combo_box, NamedKey::ArrowDown);
+        assert_eq!(combo_box.drop_down.highlighted, Some(3));
+        assert_eq!(combo_box.selected_index, Some(1));
+        named(&mut combo_box, NamedKey::Escape);
+        assert!(!combo_box.is_open());
+        assert_eq!(combo_box.selected_index, Some(1));
+
+        named(&mut combo_box, NamedKey::F4);
+        assert!(combo_box.is_open());
+        named(&mut combo_box, NamedKey::ArrowUp);
+        named(&mut combo_box, NamedKey::Enter);
+        assert!(!combo_box.is_open());
+        assert_eq!(combo_box.selected_index, Some(0));
+    }
+
+    #[test]
+    fn test_search() {
+        let mut combo_box = combo_box();
+        let now = Instant::now();
+        press(&mut combo_box, Key::Character("b".into()), ModifiersState::empty(), now);
+        assert_eq!(combo_box.text(), "Banana");
+        // Repeating the character cycles through the items starting with it
+        press(&mut combo_box, Key::Character("b".into()), ModifiersState::empty(), now);
+        assert_eq!(combo_box.text(), "Blueberry");
+        press(&mut combo_box, Key::Character("b".into()), ModifiersState::empty(), now);
+        assert_eq!(combo_box.text(), "Banana");
+
+        // A pause starts a new search, matching the whole typed text
+        let later = now + SEARCH_TIMEOUT * 2;
+        press(&mut combo_box, Key::Character("b".into()), ModifiersState::empty(), later);
+        press(&mut combo_box, Key::Character("l".into()), ModifiersState::empty(), later);
+        assert_eq!(combo_box.text(), "Blueberry");
+        // Space continues a search instead of opening the drop-down
+        press(&mut combo_box, Key::Named(NamedKey::Space), ModifiersState::empty(), later);
+        assert!(!combo_box.is_open());
+        press(&mut combo_box, Key::Named(NamedKey::Space), ModifiersState::empty(), later + SEARCH_TIMEOUT * 2);
+        assert!(combo_box.is_open());
+    }
+}
@@ -0,0 +1,115 @@
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer};
+use crate::components::menu::{Menu, MenuItem, MenuKey, MenuStyle};
+use crate::data::{ComponentId, Rectangle, Size};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+
+/// # Description
+/// A menu opened by right-clicking a component, or by pressing the context menu key or
+/// Shift+F10 while it has the keyboard focus.
+///
+/// # Remarks
+/// Context menus are created by [`Component::context_menu`] every time they are opened and
+/// shown by the window as an overlay next to the pointer, which receives the keyboard focus
+/// until an item was clicked or the menu was dismissed.
+pub struct ContextMenu {
+    id: ComponentId,
+    size: Size<f32>,
+    menu: Menu,
+}
+
+impl Component for ContextMenu {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        self.menu.measure(available)
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = self.menu.arrange(given);
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        self.menu.render(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::KeyPressed { key, .. } => match self.menu.key_pressed(key) {
+                MenuKey::Handled => true,
+                MenuKey::Activated => {
+                    context.close_overlay(self.id);
+                    true
+                }
+                MenuKey::Unhandled => match key {
+                    Key::Named(NamedKey::Escape) | Key::Named(NamedKey::Alt) => {
+                        context.close_overlay(self.id);
+                        true
+                    }
+                    _ => false,
+                },
+            },
+            InputEvent::PointerReleased { .. } => {
+                let activated = self.menu.take_activated();
+                if activated {
+                    context.close_overlay(self.id);
+                }
+                activated
+            }
+            InputEvent::FocusLost => {
+                context.close_overlay(self.id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.bounds(), &self.menu);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        visitor(self.bounds(), &mut self.menu);
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        let mut focusable = Focusable::new();
+        focusable.tab_stop = false;
+        focusable.focus_visual = false;
+        Some(focusable)
+    }
+}
+
+impl ContextMenu {
+    pub fn new(items: Vec<MenuItem>) -> ContextMenu {
+        let mut menu = Menu::new();
+        menu.items = items;
+        ContextMenu {
+            id: ComponentId::new(),
+            size: Size { width: 0f32, height: 0f32 },
+            menu,
+        }
+    }
+
+    pub fn with_style(mut self, style: MenuStyle) -> Self {
+        self.menu.style = style;
+        self
+    }
+
+    /// # Description
+    /// Highlights the first item, used if the menu was opened using the keyboard.
+    pub(crate) fn highlight_first(&mut self) {
+        self.menu.move_highlight(true);
+    }
+
+    fn bounds(&self) -> Rectangle<f32> {
+        Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height }
+    }
+}
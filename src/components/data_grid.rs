@@ -0,0 +1,1249 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::rc::Rc;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::{ScrollBar, SelectionMode, SelectionModel, TextBox};
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent, PIXELS_PER_WHEEL_LINE};
+use crate::text::{self, TextStyle};
+use crate::visuals::{self, PathCommand};
+
+const SCROLL_BAR_THICKNESS: f32 = 14f32;
+/// The distance from the right edge of a column header within which dragging resizes the column.
+const RESIZE_GRIP: f32 = 4f32;
+/// The distance a column header has to be dragged before it is moved instead of clicked.
+const DRAG_THRESHOLD: f32 = 4f32;
+
+/// # Description
+/// Provides the rows displayed by a [`DataGrid`], each having a text for every field.
+///
+/// # Remarks
+/// Sources changing their rows have to be refreshed using [`DataGrid::refresh`].
+pub trait DataGridSource {
+    /// # Description
+    /// The number of rows.
+    fn row_count(&self) -> usize;
+
+    /// # Description
+    /// The text of the *field* of the row at *row*.
+    fn cell_text(&self, row: usize, field: usize) -> String;
+
+    /// # Description
+    /// Stores the text entered by editing a cell.
+    ///
+    /// # Returns
+    /// Whether the text was accepted.
+    ///
+    /// # Remarks
+    /// The default implementation rejects every edit.
+    fn set_cell_text(&self, _row: usize, _field: usize, _text: &str) -> bool {
+        false
+    }
+}
+
+impl DataGridSource for RefCell<Vec<Vec<String>>> {
+    fn row_count(&self) -> usize {
+        self.borrow().len()
+    }
+
+    fn cell_text(&self, row: usize, field: usize) -> String {
+        self.borrow()[row].get(field).cloned().unwrap_or_default()
+    }
+
+    fn set_cell_text(&self, row: usize, field: usize, text: &str) -> bool {
+        match self.borrow_mut()[row].get_mut(field) {
+            None => false,
+            Some(d) => {
+                *d = text.to_string();
+                true
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// # Description
+/// Controls whether a [`DataGrid`] selects whole rows or single cells.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DataGridSelectionUnit {
+    Row,
+    /// Cells are selected, Shift selecting the rectangle from the last clicked cell in extended
+    /// selection mode.
+    Cell,
+}
+
+/// # Description
+/// A column of a [`DataGrid`], displaying one field of its source.
+pub struct DataGridColumn {
+    // #[property]
+    pub header: String,
+    /// The field of the source displayed in the column.
+    // #[property]
+    pub field: usize,
+    // #[property]
+    pub width: f32,
+    /// The width the column can not be resized below.
+    // #[property]
+    pub min_width: f32,
+    // #[property]
+    pub can_resize: bool,
+    // #[property]
+    pub can_sort: bool,
+    // #[property]
+    pub can_reorder: bool,
+    // #[property]
+    pub is_read_only: bool,
+    /// The direction the rows are sorted in by this column, if any.
+    /// Set by clicking the header of the column.
+    // #[property]
+    pub sort_direction: Option<SortDirection>,
+}
+
+impl DataGridColumn {
+    pub fn new(header: &str, field: usize) -> DataGridColumn {
+        DataGridColumn {
+            header: header.to_string(),
+            field,
+            width: 100f32,
+            min_width: 20f32,
+            can_resize: true,
+            can_sort: true,
+            can_reorder: true,
+            is_read_only: false,
+            sort_direction: None,
+        }
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum HeaderDrag {
+    /// The right edge of the header of *column* is dragged.
+    Resize { column: usize, origin: f32, origin_width: f32 },
+    /// The header of *column* is pressed, and moved once dragged beyond the threshold.
+    Move { column: usize, origin: f32, position: f32, moved: bool },
+}
+
+/// The text box editing a cell in place.
+struct CellEditor {
+    row: usize,
+    column: usize,
+    text_box: TextBox,
+    /// Whether the text box received the keyboard focus, committing the edit once it lost it.
+    focused: bool,
+}
+
+/// # Description
+/// A table of rows and columns, whose columns can be resized, reordered and sorted by.
+///
+/// # Remarks
+/// Only the visible rows and columns are rendered, allowing sources with any number of rows.
+/// The first *frozen_columns* columns do not scroll horizontally.
+/// Dragging the right edge of a column header resizes the column, dragging the header moves
+/// the column and clicking it sorts the rows by the column.
+/// Double-clicking a cell, pressing F2 or typing starts editing the current cell in place,
+/// Enter commits the edit and Escape cancels it.
+pub struct DataGrid {
+    /// The rows of the data grid. Call *refresh* after they changed.
+    // #[property]
+    pub source: Rc<dyn DataGridSource>,
+    // #[property]
+    pub columns: Vec<DataGridColumn>,
+    /// The number of leading columns not scrolling horizontally.
+    // #[property]
+    pub frozen_columns: usize,
+    // #[property]
+    pub selection_unit: DataGridSelectionUnit,
+    // #[property]
+    pub selection_mode: SelectionMode,
+    // #[property]
+    pub is_read_only: bool,
+    // #[property]
+    pub enabled: bool,
+    /// The padding of every cell around its text.
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub text_style: TextStyle,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub header_background: Color,
+    // #[property]
+    pub grid_lines: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    /// The background of selected cells while the data grid has the keyboard focus.
+    // #[property]
+    pub selection_background: Color,
+    /// The background of selected cells while the data grid does not have the keyboard focus.
+    // #[property]
+    pub inactive_selection_background: Color,
+    /// Called whenever the user changed the selection, see *selected_rows* and *selected_cells*.
+    // #[event]
+    pub on_selection_changed: Option<Box<dyn FnMut()>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    focused: bool,
+    row_height: f32,
+    baseline: f32,
+    horizontal_bar: ScrollBar,
+    vertical_bar: ScrollBar,
+    horizontal_bar_visible: bool,
+    vertical_bar_visible: bool,
+    /// The source row of every displayed row, in display order.
+    order: Vec<usize>,
+    /// The selected displayed rows, and the selected rows of cells in cell selection unit.
+    selection: SelectionModel,
+    anchor_column: Option<usize>,
+    current_column: Option<usize>,
+    scroll_to_current: bool,
+    drag: Option<HeaderDrag>,
+    editor: Option<CellEditor>,
+    /// The cell pressed last and the click count of that press.
+    last_click: Option<((usize, usize), usize)>,
+    /// The displayed rows and the columns visible as of the last layout pass.
+    visible_rows: Range<usize>,
+    visible_columns: Vec<usize>,
+    /// The texts of the visible cells, row by row.
+    cells: Vec<String>,
+}
+
+impl Component for DataGrid {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        available
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let font = text::text_metrics().font_metrics(&self.text_style);
+        self.row_height = font.line_height + self.padding.vertical();
+        self.baseline = self.padding.top + font.ascent;
+        let count = self.source.row_count();
+        if self.order.len() != count {
+            self.sort_rows();
+        }
+        self.selection.mode = self.selection_mode;
+        self.selection.coerce(count);
+        let columns = self.columns.len();
+        self.anchor_column = self.anchor_column.filter(|d| *d < columns);
+        self.current_column = self.current_column.filter(|d| *d < columns);
+
+        // Showing one scroll bar shrinks the viewport, which may require the other one
+        let inner = self.inner_bounds();
+        let total_width: f32 = self.columns.iter().map(|d| d.width).sum();
+        let total_height = count as f32 * self.row_height;
+        let (mut horizontal, mut vertical) = (false, false);
+        for _ in 0..2 {
+            let width = inner.width - if vertical { SCROLL_BAR_THICKNESS } else { 0f32 };
+            let height = inner.height - self.row_height - if horizontal { SCROLL_BAR_THICKNESS } else { 0f32 };
+            horizontal = total_width > width;
+            vertical = total_height > height;
+        }
+        self.horizontal_bar_visible = horizontal;
+        self.vertical_bar_visible = vertical;
+
+        let viewport = self.viewport();
+        let frozen_width = self.frozen_width();
+        self.vertical_bar.range.max_value = f32::max(0f32, total_height - viewport.height);
+        self.vertical_bar.bar_value = viewport.height;
+        self.vertical_bar.range.small_change = self.row_height;
+        self.horizontal_bar.range.max_value = f32::max(0f32, total_width - viewport.width);
+        self.horizontal_bar.bar_value = f32::max(0f32, viewport.width - frozen_width);
+        self.horizontal_bar.range.small_change = PIXELS_PER_WHEEL_LINE;
+        self.vertical_bar.set_value(self.vertical_bar.range.value);
+        self.horizontal_bar.set_value(self.horizontal_bar.range.value);
+        if self.scroll_to_current {
+            self.scroll_to_current = false;
+            self.scroll_current_into_view();
+        }
+        self.vertical_bar.arrange(Size { width: SCROLL_BAR_THICKNESS, height: viewport.height });
+        self.horizontal_bar.arrange(Size { width: viewport.width, height: SCROLL_BAR_THICKNESS });
+
+        // Only the texts of the visible cells are fetched from the source
+        let first = (self.vertical_bar.range.value / self.row_height) as usize;
+        let last = ((self.vertical_bar.range.value + viewport.height) / self.row_height).ceil() as usize;
+        self.visible_rows = usize::min(first, count)..usize::min(last, count);
+        let frozen_right = inner.x + frozen_width;
+        let right = viewport.x + viewport.width;
+        self.visible_columns = (0..columns)
+            .filter(|d| {
+                let x = self.column_x(*d);
+                *d < self.frozen_count() || x + self.columns[*d].width > frozen_right && x < right
+            })
+            .collect();
+        self.cells.clear();
+        for row in self.visible_rows.clone() {
+            for column in self.visible_columns.iter() {
+                let text = self.source.cell_text(self.order[row], self.columns[*column].field);
+                self.cells.push(text);
+            }
+        }
+
+        let mut commit = false;
+        if let Some(editor) = self.editor.as_mut() {
+            if editor.text_box.is_focused() {
+                editor.focused = true;
+            } else if editor.focused {
+                commit = true;
+            }
+        }
+        if commit {
+            self.commit_edit();
+        }
+        if let Some((row, column)) = self.editor.as_ref().map(|d| (d.row, d.column)) {
+            let bounds = self.cell_bounds(row, column);
+            let size = Size { width: bounds.width, height: bounds.height };
+            let editor = self.editor.as_mut().unwrap();
+            editor.text_box.measure(size);
+            editor.text_box.arrange(size);
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            fill: self.background,
+        }
+        .draw(renderer);
+        let inner = self.inner_bounds();
+        let viewport = self.viewport();
+        let frozen_width = f32::min(self.frozen_width(), viewport.width);
+        let frozen_right = viewport.x + frozen_width;
+        let selection = if self.focused { self.selection_background } else { self.inactive_selection_background };
+
+        // Scrolled columns first, covered by the frozen columns
+        for frozen in [false, true].iter() {
+            let columns: Vec<(usize, usize)> = self
+                .visible_columns
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|(_, d)| (*d < self.frozen_count()) == *frozen)
+                .collect();
+            let (left, width) = if *frozen {
+                (viewport.x, frozen_width)
+            } else {
+                (frozen_right, f32::max(0f32, viewport.width - frozen_width))
+            };
+
+            renderer.push_clip(left, viewport.y, width, viewport.height);
+            for (offset, row) in self.visible_rows.clone().enumerate() {
+                for (index, column) in columns.iter() {
+                    let bounds = self.cell_bounds(row, *column);
+                    if self.is_cell_selected(row, *column) {
+                        visuals::Rectangle {
+                            x: bounds.x,
+                            y: bounds.y,
+                            width: bounds.width,
+                            height: bounds.height,
+                            fill: selection,
+                        }
+                        .draw(renderer);
+                    }
+                    let text = &self.cells[offset * self.visible_columns.len() + index];
+                    self.render_text(renderer, bounds, text);
+                    self.render_grid_lines(renderer, bounds);
+                }
+            }
+            renderer.pop_clip();
+
+            renderer.push_clip(left, inner.y, width, self.row_height);
+            for (_, column) in columns.iter() {
+                self.render_header(renderer, *column);
+            }
+            renderer.pop_clip();
+        }
+
+        if let (true, Some(row), Some(column)) = (self.focused, self.selection.current(), self.current_column) {
+            let bounds = match self.selection_unit {
+                DataGridSelectionUnit::Row => Rectangle {
+                    x: viewport.x,
+                    y: self.cell_bounds(row, column).y,
+                    width: viewport.width,
+                    height: self.row_height,
+                },
+                DataGridSelectionUnit::Cell => self.cell_bounds(row, column),
+            };
+            renderer.push_clip(viewport.x, viewport.y, viewport.width, viewport.height);
+            visuals::Outline {
+                x: bounds.x + 0.5f32,
+                y: bounds.y + 0.5f32,
+                width: bounds.width - 1f32,
+                height: bounds.height - 1f32,
+                stroke: self.focused_border,
+                thickness: 1f32,
+            }
+            .draw(renderer);
+            renderer.pop_clip();
+        }
+
+        if let Some(editor) = self.editor.as_ref() {
+            let bounds = self.cell_bounds(editor.row, editor.column);
+            renderer.push_clip(viewport.x, viewport.y, viewport.width, viewport.height);
+            renderer.translate(bounds.x, bounds.y);
+            editor.text_box.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+            renderer.pop_clip();
+        }
+
+        if let Some(HeaderDrag::Move { position, moved: true, .. }) = self.drag {
+            let target = self.drop_position(position);
+            let x = if target < self.columns.len() {
+                self.column_x(target)
+            } else {
+                self.column_x(target - 1) + self.columns[target - 1].width
+            };
+            visuals::Rectangle {
+                x: x - 1f32,
+                y: inner.y,
+                width: 2f32,
+                height: self.row_height + viewport.height,
+                fill: self.focused_border,
+            }
+            .draw(renderer);
+        }
+
+        if self.vertical_bar_visible {
+            let bounds = self.vertical_bar_bounds();
+            renderer.translate(bounds.x, bounds.y);
+            self.vertical_bar.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+        if self.horizontal_bar_visible {
+            let bounds = self.horizontal_bar_bounds();
+            renderer.translate(bounds.x, bounds.y);
+            self.horizontal_bar.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+
+        visuals::Outline {
+            x: 0.5f32,
+            y: 0.5f32,
+            width: self.size.width - 1f32,
+            height: self.size.height - 1f32,
+            stroke: if self.focused { self.focused_border } else { self.border },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        self.selection.mode = self.selection_mode;
+        match event {
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                true
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left } if self.enabled => {
+                self.commit_edit();
+                let inner = self.inner_bounds();
+                if position.y >= inner.y && position.y < inner.y + self.row_height {
+                    self.press_header(*position, context);
+                    return true;
+                }
+                let (row, column) = match (self.row_at(position.y), self.column_at(position.x)) {
+                    (Some(row), Some(column)) => (row, column),
+                    _ => return true,
+                };
+                // Only a second click on the same cell starts editing it
+                let count = context.click_count();
+                let double_click = self
+                    .last_click
+                    .map_or(false, |(cell, last_count)| cell == (row, column) && last_count + 1 == count);
+                self.last_click = if double_click { None } else { Some(((row, column), count)) };
+                let modifiers = context.modifiers();
+                self.select_cell(row, column, modifiers.control_key(), modifiers.shift_key(), false);
+                if double_click {
+                    self.begin_edit(None, context);
+                }
+                true
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Right } if self.enabled => {
+                // Right-clicking an unselected cell selects it for its context menu
+                if let (Some(row), Some(column)) = (self.row_at(position.y), self.column_at(position.x)) {
+                    if !self.is_cell_selected(row, column) {
+                        self.commit_edit();
+                        self.select_cell(row, column, false, false, false);
+                    }
+                }
+                true
+            }
+            InputEvent::PointerMoved { position } => match self.drag {
+                Some(HeaderDrag::Resize { column, origin, origin_width }) => {
+                    let width = origin_width + position.x - origin;
+                    self.columns[column].width = f32::max(self.columns[column].min_width, width);
+                    true
+                }
+                Some(HeaderDrag::Move { column, origin, moved, .. }) => {
+                    let moved = moved
+                        || self.columns[column].can_reorder && (position.x - origin).abs() > DRAG_THRESHOLD;
+                    self.drag = Some(HeaderDrag::Move { column, origin, position: position.x, moved });
+                    true
+                }
+                None => false,
+            },
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                let drag = match self.drag.take() {
+                    None => return false,
+                    Some(d) => d,
+                };
+                context.release_pointer_capture();
+                match drag {
+                    HeaderDrag::Move { column, moved: false, .. } if self.columns[column].can_sort => {
+                        self.sort_by(column);
+                    }
+                    HeaderDrag::Move { column, position, moved: true, .. } => {
+                        let target = self.drop_position(position);
+                        self.move_column(column, target);
+                    }
+                    _ => {}
+                }
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.drag = None;
+                true
+            }
+            InputEvent::PointerWheel { delta, .. } => {
+                if delta.y != 0f32 {
+                    let value = self.vertical_bar.range.value - delta.y * self.vertical_bar.range.small_change;
+                    self.vertical_bar.set_value(value);
+                }
+                if delta.x != 0f32 {
+                    let value =
+                        self.horizontal_bar.range.value - delta.x * self.horizontal_bar.range.small_change;
+                    self.horizontal_bar.set_value(value);
+                }
+                true
+            }
+            InputEvent::KeyPressed { key, text, modifiers, .. } if self.enabled => {
+                self.handle_key(key, text.as_deref(), *modifiers, context)
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if let Some(editor) = self.editor.as_ref() {
+            visitor(self.cell_bounds(editor.row, editor.column), &editor.text_box);
+        }
+        if self.vertical_bar_visible {
+            visitor(self.vertical_bar_bounds(), &self.vertical_bar);
+        }
+        if self.horizontal_bar_visible {
+            visitor(self.horizontal_bar_bounds(), &self.horizontal_bar);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        if let Some((row, column)) = self.editor.as_ref().map(|d| (d.row, d.column)) {
+            let bounds = self.cell_bounds(row, column);
+            visitor(bounds, &mut self.editor.as_mut().unwrap().text_box);
+        }
+        if self.vertical_bar_visible {
+            let bounds = self.vertical_bar_bounds();
+            visitor(bounds, &mut self.vertical_bar);
+        }
+        if self.horizontal_bar_visible {
+            let bounds = self.horizontal_bar_bounds();
+            visitor(bounds, &mut self.horizontal_bar);
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+}
+
+impl DataGrid {
+    pub fn new() -> DataGrid {
+        let mut horizontal_bar = ScrollBar::new();
+        horizontal_bar.mode = Orientation::Horizontal;
+        horizontal_bar.thickness = SCROLL_BAR_THICKNESS;
+        let mut vertical_bar = ScrollBar::new();
+        vertical_bar.mode = Orientation::Vertical;
+        vertical_bar.thickness = SCROLL_BAR_THICKNESS;
+        DataGrid {
+            id: ComponentId::new(),
+            source: Rc::new(RefCell::new(Vec::<Vec<String>>::new())),
+            columns: vec![],
+            frozen_columns: 0,
+            selection_unit: DataGridSelectionUnit::Row,
+            selection_mode: SelectionMode::Extended,
+            is_read_only: false,
+            enabled: true,
+            padding: Thickness::uniform(4f32),
+            text_style: TextStyle::new(),
+            background: Color::WHITE,
+            header_background: Color::GAINSBORO,
+            grid_lines: Color::LIGHT_GRAY,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            selection_background: Color::LIGHT_SKY_BLUE,
+            inactive_selection_background: Color::LIGHT_GRAY,
+            on_selection_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            focused: false,
+            row_height: 0f32,
+            baseline: 0f32,
+            horizontal_bar,
+            vertical_bar,
+            horizontal_bar_visible: false,
+            vertical_bar_visible: false,
+            order: vec![],
+            selection: SelectionModel::new(SelectionMode::Extended),
+            anchor_column: None,
+            current_column: None,
+            scroll_to_current: false,
+            drag: None,
+            editor: None,
+            last_click: None,
+            visible_rows: 0..0,
+            visible_columns: vec![],
+            cells: vec![],
+        }
+    }
+
+    /// # Description
+    /// The source rows of the selected rows, or of the rows having selected cells, in display
+    /// order.
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.selection.selected().into_iter().map(|d| self.order[d]).collect()
+    }
+
+    /// # Description
+    /// The source rows and the columns of the selected cells, in display order.
+    ///
+    /// # Remarks
+    /// In row selection unit, every cell of the selected rows is selected.
+    pub fn selected_cells(&self) -> Vec<(usize, usize)> {
+        let columns: Vec<usize> = (0..self.columns.len())
+            .filter(|d| self.is_column_selected(*d))
+            .collect();
+        self.selection
+            .selected()
+            .into_iter()
+            .flat_map(|row| columns.iter().map(move |column| (row, *column)))
+            .map(|(row, column)| (self.order[row], column))
+            .collect()
+    }
+
+    /// # Description
+    /// Sorts the rows by the column at *column*, ascending unless already sorted ascending by it.
+    pub fn sort_by(&mut self, column: usize) {
+        let direction = match self.columns[column].sort_direction {
+            Some(SortDirection::Ascending) => SortDirection::Descending,
+            _ => SortDirection::Ascending,
+        };
+        for column in self.columns.iter_mut() {
+            column.sort_direction = None;
+        }
+        self.columns[column].sort_direction = Some(direction);
+        self.commit_edit();
+        self.sort_rows();
+    }
+
+    /// # Description
+    /// Fetches and sorts the rows again, after the source changed.
+    pub fn refresh(&mut self) {
+        self.editor = None;
+        self.sort_rows();
+    }
+
+    fn handle_key(
+        &mut self,
+        key: &Key,
+        text: Option<&str>,
+        modifiers: ModifiersState,
+        context: &mut EventContext,
+    ) -> bool {
+        if self.editor.is_some() {
+            match key {
+                Key::Named(NamedKey::Enter) => {
+                    self.commit_edit();
+                    context.request_focus(self.id);
+                    return true;
+                }
+                Key::Named(NamedKey::Escape) => {
+                    self.editor = None;
+                    context.request_focus(self.id);
+                    return true;
+                }
+                // Keys the text box does not handle move to another cell
+                Key::Named(NamedKey::ArrowUp) | Key::Named(NamedKey::ArrowDown) => {
+                    self.commit_edit();
+                    context.request_focus(self.id);
+                }
+                _ => return false,
+            }
+        }
+        let count = self.order.len();
+        let columns = self.columns.len();
+        if count == 0 || columns == 0 {
+            return false;
+        }
+        let row = self.selection.current();
+        let column = self.current_column.unwrap_or(0);
+        let page = usize::max(1, (self.viewport().height / self.row_height) as usize);
+        let control = modifiers.control_key();
+        let (row, column) = match key {
+            Key::Named(NamedKey::ArrowUp) => (row.map_or(0, |d| d.saturating_sub(1)), column),
+            Key::Named(NamedKey::ArrowDown) => (row.map_or(0, |d| usize::min(d + 1, count - 1)), column),
+            Key::Named(NamedKey::PageUp) => (row.map_or(0, |d| d.saturating_sub(page)), column),
+            Key::Named(NamedKey::PageDown) => (row.map_or(0, |d| usize::min(d + page, count - 1)), column),
+            Key::Named(NamedKey::ArrowLeft) => (row.unwrap_or(0), column.saturating_sub(1)),
+            Key::Named(NamedKey::ArrowRight) => (row.unwrap_or(0), usize::min(column + 1, columns - 1)),
+            Key::Named(NamedKey::Home) if control => (0, column),
+            Key::Named(NamedKey::End) if control => (count - 1, column),
+            Key::Named(NamedKey::Home) => (row.unwrap_or(0), 0),
+            Key::Named(NamedKey::End) => (row.unwrap_or(0), columns - 1),
+            Key::Named(NamedKey::Space) if self.selection_unit == DataGridSelectionUnit::Row => {
+                if self.selection.toggle_current() {
+                    self.selection_changed();
+                }
+                return row.is_some();
+            }
+            Key::Character(text) if control && text.eq_ignore_ascii_case("a") => {
+                let mut changed = self.selection.select_all(count);
+                if self.selection_unit == DataGridSelectionUnit::Cell && self.selection_mode != SelectionMode::Single {
+                    changed |= (self.anchor_column, self.current_column) != (Some(0), Some(columns - 1));
+                    self.anchor_column = Some(0);
+                    self.current_column = Some(columns - 1);
+                }
+                if changed {
+                    self.selection_changed();
+                }
+                return true;
+            }
+            Key::Named(NamedKey::F2) => {
+                self.begin_edit(None, context);
+                return true;
+            }
+            _ => match text {
+                Some(text) if !control && !modifiers.alt_key() && !text.chars().any(char::is_control) => {
+                    self.begin_edit(Some(text), context);
+                    return true;
+                }
+                _ => return false,
+            },
+        };
+        self.select_cell(row, column, control, modifiers.shift_key(), true);
+        true
+    }
+
+    /// Applies a click on or a keyboard navigation to the cell at *row* and *column*.
+    fn select_cell(&mut self, row: usize, column: usize, toggle: bool, extend: bool, keyboard: bool) {
+        let cells = self.selection_unit == DataGridSelectionUnit::Cell;
+        // Cells are selected in rectangles, which can not be toggled
+        let toggle = toggle && !cells;
+        let extend = extend && self.selection_mode == SelectionMode::Extended;
+        let columns = (self.anchor_column, self.current_column);
+        let mut changed = if keyboard {
+            self.selection.navigate(row, toggle, extend)
+        } else {
+            self.selection.click(row, toggle, extend)
+        };
+        if !extend || self.anchor_column.is_none() {
+            self.anchor_column = Some(column);
+        }
+        self.current_column = Some(column);
+        changed |= cells && (self.anchor_column, self.current_column) != columns;
+        self.scroll_to_current = true;
+        if changed {
+            self.selection_changed();
+        }
+    }
+
+    fn selection_changed(&mut self) {
+        if let Some(callback) = self.on_selection_changed.as_mut() {
+            callback();
+        }
+    }
+
+    fn is_column_selected(&self, column: usize) -> bool {
+        match (self.selection_unit, self.anchor_column, self.current_column) {
+            (DataGridSelectionUnit::Row, _, _) => true,
+            (DataGridSelectionUnit::Cell, Some(anchor), Some(current)) => {
+                column >= usize::min(anchor, current) && column <= usize::max(anchor, current)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_cell_selected(&self, row: usize, column: usize) -> bool {
+        self.selection.is_selected(row) && self.is_column_selected(column)
+    }
+
+    /// Starts editing the current cell, replacing its text with *text* if given.
+    fn begin_edit(&mut self, text: Option<&str>, context: &mut EventContext) {
+        let (row, column) = match (self.selection.current(), self.current_column) {
+            (Some(row), Some(column)) => (row, column),
+            _ => return,
+        };
+        if self.is_read_only || self.columns[column].is_read_only {
+            return;
+        }
+        let mut text_box = TextBox::new();
+        text_box.text_style = self.text_style.clone();
+        text_box.padding = self.padding;
+        text_box.corner_radius = 0f32;
+        match text {
+            Some(text) => {
+                text_box.text = text.to_string();
+                text_box.select(text.len(), text.len());
+            }
+            None => {
+                text_box.text = self.source.cell_text(self.order[row], self.columns[column].field);
+                text_box.select_all();
+            }
+        }
+        context.request_focus(text_box.id());
+        self.editor = Some(CellEditor { row, column, text_box, focused: false });
+        self.scroll_to_current = true;
+    }
+
+    /// Stores the text of the cell being edited in the source, if any.
+    fn commit_edit(&mut self) {
+        if let Some(editor) = self.editor.take() {
+            let row = self.order[editor.row];
+            let field = self.columns[editor.column].field;
+            self.source.set_cell_text(row, field, &editor.text_box.text);
+        }
+    }
+
+    /// Sorts the rows by the column having a sort direction, keeping the selected rows.
+    fn sort_rows(&mut self) {
+        let count = self.source.row_count();
+        let mut order: Vec<usize> = (0..count).collect();
+        if let Some(column) = self.columns.iter().find(|d| d.sort_direction.is_some()) {
+            let keys: Vec<SortKey> = (0..count)
+                .map(|d| SortKey::new(&self.source.cell_text(d, column.field)))
+                .collect();
+            let descending = column.sort_direction == Some(SortDirection::Descending);
+            order.sort_by(|a, b| {
+                let ordering = keys[*a].cmp(&keys[*b]);
+                if descending { ordering.reverse() } else { ordering }
+            });
+        }
+        if self.order.len() == count {
+            let mut display = vec![0; count];
+            for (index, row) in order.iter().enumerate() {
+                display[*row] = index;
+            }
+            let previous = &self.order;
+            self.selection.remap(|d| display[previous[d]]);
+        }
+        self.order = order;
+    }
+
+    /// Moves the column at *column* before the column at *target*.
+    fn move_column(&mut self, column: usize, target: usize) {
+        let target = if target > column { target - 1 } else { target };
+        if target == column {
+            return;
+        }
+        self.commit_edit();
+        let moved = self.columns.remove(column);
+        self.columns.insert(target, moved);
+        self.anchor_column = self.anchor_column.map(|d| moved_index(d, column, target));
+        self.current_column = self.current_column.map(|d| moved_index(d, column, target));
+    }
+
+    fn press_header(&mut self, position: Point<f32>, context: &mut EventContext) {
+        let resize = (0..self.columns.len()).rev().find(|d| {
+            let right = self.column_x(*d) + self.columns[*d].width;
+            self.columns[*d].can_resize && (position.x - right).abs() <= RESIZE_GRIP
+        });
+        self.drag = match (resize, self.column_at(position.x)) {
+            (Some(column), _) => Some(HeaderDrag::Resize {
+                column,
+                origin: position.x,
+                origin_width: self.columns[column].width,
+            }),
+            (None, Some(column)) => Some(HeaderDrag::Move {
+                column,
+                origin: position.x,
+                position: position.x,
+                moved: false,
+            }),
+            (None, None) => None,
+        };
+        if self.drag.is_some() {
+            context.capture_pointer();
+        }
+    }
+
+    /// The position a column dragged to the horizontal position *x* is moved to.
+    fn drop_position(&self, x: f32) -> usize {
+        (0..self.columns.len())
+            .find(|d| x < self.column_x(*d) + self.columns[*d].width / 2f32)
+            .unwrap_or(self.columns.len())
+    }
+
+    /// Scrolls the least distance making the current cell visible.
+    fn scroll_current_into_view(&mut self) {
+        let viewport = self.viewport();
+        if let Some(row) = self.selection.current() {
+            let top = row as f32 * self.row_height;
+            let offset = self.vertical_bar.range.value;
+            if top < offset {
+                self.vertical_bar.set_value(top);
+            } else if top + self.row_height > offset + viewport.height {
+                self.vertical_bar.set_value(top + self.row_height - viewport.height);
+            }
+        }
+        match self.current_column {
+            Some(column) if column >= self.frozen_count() => {
+                let frozen_width = self.frozen_width();
+                let left: f32 = self.columns[..column].iter().map(|d| d.width).sum::<f32>() - frozen_width;
+                let width = self.columns[column].width;
+                let visible = f32::max(0f32, viewport.width - frozen_width);
+                let offset = self.horizontal_bar.range.value;
+                if left < offset || width > visible {
+                    self.horizontal_bar.set_value(left);
+                } else if left + width > offset + visible {
+                    self.horizontal_bar.set_value(left + width - visible);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render_header(&self, renderer: &mut dyn Renderer, column: usize) {
+        let inner = self.inner_bounds();
+        let bounds = Rectangle {
+            x: self.column_x(column),
+            y: inner.y,
+            width: self.columns[column].width,
+            height: self.row_height,
+        };
+        visuals::Rectangle {
+            x: bounds.x,
+            y: bounds.y,
+            width: bounds.width,
+            height: bounds.height,
+            fill: self.header_background,
+        }
+        .draw(renderer);
+        self.render_text(renderer, bounds, &self.columns[column].header);
+        if let Some(direction) = self.columns[column].sort_direction {
+            // A triangle pointing up while ascending, or down while descending
+            let x = bounds.x + bounds.width - self.padding.right - 4f32;
+            let y = bounds.y + bounds.height / 2f32;
+            let extent = if direction == SortDirection::Ascending { -2f32 } else { 2f32 };
+            visuals::Path {
+                commands: &[
+                    PathCommand::MoveTo(Point { x: x - 4f32, y: y - extent }),
+                    PathCommand::LineTo(Point { x: x + 4f32, y: y - extent }),
+                    PathCommand::LineTo(Point { x, y: y + extent }),
+                    PathCommand::Close,
+                ],
+                fill: self.text_style.color,
+                stroke: Color::TRANSPARENT,
+                thickness: 0f32,
+            }
+            .draw(renderer);
+        }
+        self.render_grid_lines(renderer, bounds);
+    }
+
+    fn render_text(&self, renderer: &mut dyn Renderer, bounds: Rectangle<f32>, text: &str) {
+        renderer.push_clip(
+            bounds.x + self.padding.left,
+            bounds.y,
+            f32::max(0f32, bounds.width - self.padding.horizontal()),
+            bounds.height,
+        );
+        visuals::Text {
+            x: bounds.x + self.padding.left,
+            y: bounds.y + self.baseline,
+            text,
+            style: &self.text_style,
+        }
+        .draw(renderer);
+        renderer.pop_clip();
+    }
+
+    fn render_grid_lines(&self, renderer: &mut dyn Renderer, bounds: Rectangle<f32>) {
+        visuals::Rectangle {
+            x: bounds.x + bounds.width - 1f32,
+            y: bounds.y,
+            width: 1f32,
+            height: bounds.height,
+            fill: self.grid_lines,
+        }
+        .draw(renderer);
+        visuals::Rectangle {
+            x: bounds.x,
+            y: bounds.y + bounds.height - 1f32,
+            width: bounds.width,
+            height: 1f32,
+            fill: self.grid_lines,
+        }
+        .draw(renderer);
+    }
+
+    fn frozen_count(&self) -> usize {
+        usize::min(self.frozen_columns, self.columns.len())
+    }
+
+    fn frozen_width(&self) -> f32 {
+        self.columns[..self.frozen_count()].iter().map(|d| d.width).sum()
+    }
+
+    /// The left edge of the column at *column*, relative to the data grid.
+    fn column_x(&self, column: usize) -> f32 {
+        let x: f32 = self.columns[..column].iter().map(|d| d.width).sum();
+        if column < self.frozen_count() {
+            self.inner_bounds().x + x
+        } else {
+            self.inner_bounds().x + x - self.horizontal_bar.range.value
+        }
+    }
+
+    /// The column at the horizontal position *x*, relative to the data grid.
+    fn column_at(&self, x: f32) -> Option<usize> {
+        let viewport = self.viewport();
+        if x < viewport.x || x >= viewport.x + viewport.width {
+            return None;
+        }
+        // Frozen columns cover the columns scrolled below them
+        let frozen_right = viewport.x + self.frozen_width();
+        (0..self.columns.len()).find(|d| {
+            let left = self.column_x(*d);
+            (*d < self.frozen_count() || x >= frozen_right) && x >= left && x < left + self.columns[*d].width
+        })
+    }
+
+    /// The displayed row at the vertical position *y*, relative to the data grid.
+    fn row_at(&self, y: f32) -> Option<usize> {
+        let viewport = self.viewport();
+        if y < viewport.y || y >= viewport.y + viewport.height || self.row_height <= 0f32 {
+            return None;
+        }
+        let row = ((y - viewport.y + self.vertical_bar.range.value) / self.row_height) as usize;
+        if row < self.order.len() {
+            Some(row)
+        } else {
+            None
+        }
+    }
+
+    fn cell_bounds(&self, row: usize, column: usize) -> Rectangle<f32> {
+        Rectangle {
+            x: self.column_x(column),
+            y: self.viewport().y + row as f32 * self.row_height - self.vertical_bar.range.value,
+            width: self.columns[column].width,
+            height: self.row_height,
+        }
+    }
+
+    fn inner_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 1f32,
+            y: 1f32,
+            width: f32::max(0f32, self.size.width - 2f32),
+            height: f32::max(0f32, self.size.height - 2f32),
+        }
+    }
+
+    /// The area of the cells below the headers, excluding the scroll bars.
+    fn viewport(&self) -> Rectangle<f32> {
+        let inner = self.inner_bounds();
+        let vertical_bar = if self.vertical_bar_visible { SCROLL_BAR_THICKNESS } else { 0f32 };
+        let horizontal_bar = if self.horizontal_bar_visible { SCROLL_BAR_THICKNESS } else { 0f32 };
+        Rectangle {
+            x: inner.x,
+            y: inner.y + self.row_height,
+            width: f32::max(0f32, inner.width - vertical_bar),
+            height: f32::max(0f32, inner.height - self.row_height - horizontal_bar),
+        }
+    }
+
+    fn vertical_bar_bounds(&self) -> Rectangle<f32> {
+        let viewport = self.viewport();
+        Rectangle {
+            x: viewport.x + viewport.width,
+            y: viewport.y,
+            width: SCROLL_BAR_THICKNESS,
+            height: viewport.height,
+        }
+    }
+
+    fn horizontal_bar_bounds(&self) -> Rectangle<f32> {
+        let viewport = self.viewport();
+        Rectangle {
+            x: viewport.x,
+            y: viewport.y + viewport.height,
+            width: viewport.width,
+            height: SCROLL_BAR_THICKNESS,
+        }
+    }
+}
+
+/// # Description
+/// The text of a cell prepared for sorting.
+///
+/// # Remarks
+/// Numbers sort numerically and before all texts, which sort case-insensitively.
+#[derive(Debug)]
+enum SortKey {
+    Number(f64),
+    Text(String),
+}
+
+impl SortKey {
+    fn new(text: &str) -> SortKey {
+        match text.trim().parse::<f64>() {
+            Ok(d) => SortKey::Number(d),
+            Err(_) => SortKey::Text(text.to_lowercase()),
+        }
+    }
+}
+
+impl PartialEq for SortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortKey {}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortKey::Number(a), SortKey::Number(b)) => a.total_cmp(b),
+            (SortKey::Number(_), SortKey::Text(_)) => Ordering::Less,
+            (SortKey::Text(_), SortKey::Number(_)) => Ordering::Greater,
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+        }
+    }
+}
+
+/// The position of the item at *index* after moving the item at *from* to *to*.
+fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_key() {
+        let compare = |a: &str, b: &str| SortKey::new(a).cmp(&SortKey::new(b));
+        assert_eq!(compare("9", "10"), Ordering::Less);
+        assert_eq!(compare("2.5", " 2.5"), Ordering::Equal);
+        assert_eq!(compare("apple", "Banana"), Ordering::Less);
+        assert_eq!(compare("10", "apple"), Ordering::Less);
+        // Mixing numbers and texts still is a total order
+        assert_eq!(compare("2", "10"), Ordering::Less);
+        assert_eq!(compare("10", "1a"), Ordering::Less);
+        assert_eq!(compare("2", "1a"), Ordering::Less);
+        assert_eq!(compare("NaN", "1"), Ordering::Greater);
+        let mut keys: Vec<SortKey> = ["1a", "NaN", "2", "b", "10", "-1", "A"]
+            .iter()
+            .map(|d| SortKey::new(d))
+            .collect();
+        keys.sort();
+        let expected = ["-1", "2", "10", "NaN", "1a", "a", "b"];
+        let sorted: Vec<SortKey> = expected.iter().map(|d| SortKey::new(d)).collect();
+        assert_eq!(format!("{:?}", keys), format!("{:?}", sorted));
+    }
+
+    #[test]
+    fn test_moved_index() {
+        let mut items = vec!['a', 'b', 'c', 'd'];
+        let item = items.remove(1);
+        items.insert(3, item);
+        assert_eq!(items, vec!['a', 'c', 'd', 'b']);
+        for (index, item) in ['a', 'b', 'c', 'd'].iter().enumerate() {
+            assert_eq!(items[moved_index(index, 1, 3)], *item);
+        }
+        assert_eq!(moved_index(0, 3, 0), 1);
+        assert_eq!(moved_index(3, 3, 0), 0);
+    }
+
+    #[test]
+    fn test_double_click() {
+        use crate::pointer::PointerState;
+        use std::time::Instant;
+
+        let mut data_grid = DataGrid::new();
+        data_grid.source = Rc::new(RefCell::new(vec![
+            vec!["a".to_string()],
+            vec!["b".to_string()],
+        ]));
+        data_grid.columns = vec![DataGridColumn::new("Name", 0)];
+        data_grid.arrange(Size { width: 300f32, height: 300f32 });
+        let click = |data_grid: &mut DataGrid, row: usize, count: usize| {
+            let mut context =
+                EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+            context.click_count = count;
+            let bounds = data_grid.cell_bounds(row, 0);
+            let position = Point { x: bounds.x + 10f32, y: bounds.y + bounds.height / 2f32 };
+            data_grid.handle_event(&InputEvent::PointerPressed { position, button: MouseButton::Left }, &mut context);
+        };
+
+        // Two quick clicks on different rows only select
+        click(&mut data_grid, 0, 1);
+        click(&mut data_grid, 1, 2);
+        assert!(data_grid.editor.is_none());
+        assert_eq!(data_grid.selection.current(), Some(1));
+
+        click(&mut data_grid, 1, 1);
+        click(&mut data_grid, 1, 2);
+        assert_eq!(data_grid.editor.as_ref().map(|d| d.row), Some(1));
+    }
+}
@@ -0,0 +1,501 @@
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::{Calendar, TextBox};
+use crate::data::{ComponentId, Point, Rectangle, Size};
+use crate::date::{Date, DateFormat, Weekday};
+use crate::input::{EventContext, InputEvent};
+use crate::overlay::{Placement, PopupPlacement};
+use crate::visuals::{self, PathCommand};
+
+/// # Description
+/// A text box for entering a date, with a button showing a [`Calendar`] to pick it from.
+///
+/// # Remarks
+/// The text is written and parsed according to *format*, which defaults to the format of
+/// the system locale. It is applied when pressing Enter or leaving the text box and reverted
+/// when pressing Escape. While the text is neither empty nor a date that can be selected,
+/// the border turns *invalid_border*.
+/// Alt+Down and F4 open the calendar, which receives the keyboard focus. Picking a day closes
+/// it again, as do Escape and clicking elsewhere.
+pub struct DatePicker {
+    // #[property]
+    pub selected_date: Option<Date>,
+    /// The earliest date that can be selected.
+    // #[property]
+    pub min_date: Option<Date>,
+    /// The latest date that can be selected.
+    // #[property]
+    pub max_date: Option<Date>,
+    /// Dates that cannot be selected, although they are within the limits.
+    // #[property]
+    pub blackout_dates: Vec<Date>,
+    /// The day each row of the calendar starts with. Defaults to the one of the system locale.
+    // #[property]
+    pub first_day_of_week: Weekday,
+    // #[property]
+    pub format: DateFormat,
+    // #[property]
+    pub placeholder: String,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub button_width: f32,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub invalid_border: Color,
+    // #[property]
+    pub button_background: Color,
+    // #[property]
+    pub hover_button_background: Color,
+    // #[property]
+    pub glyph: Color,
+    /// Called with the new selected date whenever the user changed the selection.
+    // #[event]
+    pub on_selected_date_changed: Option<Box<dyn FnMut(Option<Date>)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    text_box: TextBox,
+    text_box_focused: bool,
+    calendar: Calendar,
+    calendar_focused: bool,
+    is_open: bool,
+    /// The date the text was formatted from, if it was not edited since.
+    displayed_date: Option<Option<Date>>,
+    is_valid: bool,
+    hovered: bool,
+}
+
+impl Component for DatePicker {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let desired = self.text_box.measure(Size {
+            width: f32::max(0f32, available.width - self.button_width),
+            height: available.height,
+        });
+        Size { width: desired.width + self.button_width, height: desired.height }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let focused = self.text_box.is_focused();
+        if self.text_box_focused && !focused {
+            self.commit();
+        }
+        self.text_box_focused = focused;
+        // Moving the focus out of the calendar, eg. using Tab, closes it
+        let calendar_focused = self.calendar.is_focused();
+        if self.calendar_focused && !calendar_focused {
+            self.close();
+        }
+        self.calendar_focused = calendar_focused;
+        if !focused && self.displayed_date != Some(self.selected_date) {
+            self.update_text();
+        }
+        self.is_valid = self.parse().is_ok();
+        self.text_box.read_only = !self.enabled;
+        self.text_box.placeholder = self.placeholder.clone();
+        if self.is_valid {
+            self.text_box.border = self.border;
+            self.text_box.focused_border = self.focused_border;
+        } else {
+            self.text_box.border = self.invalid_border;
+            self.text_box.focused_border = self.invalid_border;
+        }
+        let bounds = self.text_box_bounds();
+        let size = Size { width: bounds.width, height: bounds.height };
+        self.text_box.measure(size);
+        self.text_box.arrange(size);
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        self.text_box.render(renderer);
+        let bounds = self.button_bounds();
+        let fill = if self.enabled && (self.hovered || self.is_open) {
+            self.hover_button_background
+        } else {
+            self.button_background
+        };
+        visuals::Rectangle { x: bounds.x, y: bounds.y, width: bounds.width, height: bounds.height, fill }
+            .draw(renderer);
+        visuals::Outline {
+            x: bounds.x + 0.5f32,
+            y: bounds.y + 0.5f32,
+            width: bounds.width - 1f32,
+            height: bounds.height - 1f32,
+            stroke: self.border,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        // A sheet of a calendar, with a bar at its top
+        let center = Point { x: bounds.x + bounds.width / 2f32, y: bounds.y + bounds.height / 2f32 };
+        let extent = f32::min(5f32, bounds.height / 4f32);
+        let point = |x: f32, y: f32| Point { x: center.x + x * extent, y: center.y + y * extent };
+        visuals::Path {
+            commands: &[
+                PathCommand::MoveTo(point(-1f32, -1f32)),
+                PathCommand::LineTo(point(1f32, -1f32)),
+                PathCommand::LineTo(point(1f32, 1f32)),
+                PathCommand::LineTo(point(-1f32, 1f32)),
+                PathCommand::Close,
+                PathCommand::MoveTo(point(-1f32, -0.5f32)),
+                PathCommand::LineTo(point(1f32, -0.5f32)),
+            ],
+            fill: Color::TRANSPARENT,
+            stroke: self.glyph,
+            thickness: 1.5f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerMoved { position } => {
+                self.hovered = self.button_bounds().contains(*position);
+                false
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = false;
+                false
+            }
+            InputEvent::LightDismiss => {
+                self.close();
+                true
+            }
+            _ if !self.enabled => false,
+            InputEvent::PointerPressed { position, button: MouseButton::Left }
+                if self.button_bounds().contains(*position) =>
+            {
+                if self.is_open {
+                    self.close();
+                    context.request_focus(self.text_box.id());
+                } else {
+                    self.open();
+                    context.request_focus(self.calendar.id());
+                }
+                true
+            }
+            // Picking a day in the calendar bubbles up from it
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                if !self.calendar.take_committed() || !self.is_open {
+                    return false;
+                }
+                self.pick(context);
+                true
+            }
+            InputEvent::KeyPressed { key, modifiers, .. } if self.is_open => {
+                match key {
+                    Key::Named(NamedKey::Enter) if self.calendar.take_committed() => self.pick(context),
+                    Key::Named(NamedKey::Escape) | Key::Named(NamedKey::F4) => {
+                        self.close();
+                        context.request_focus(self.text_box.id());
+                    }
+                    Key::Named(NamedKey::ArrowUp) if modifiers.alt_key() => {
+                        self.close();
+                        context.request_focus(self.text_box.id());
+                    }
+                    _ => return false,
+                }
+                true
+            }
+            InputEvent::KeyPressed { key, modifiers, .. } => {
+                match key {
+                    Key::Named(NamedKey::ArrowDown) if modifiers.alt_key() => {
+                        self.open();
+                        context.request_focus(self.calendar.id());
+                    }
+                    Key::Named(NamedKey::F4) => {
+                        self.open();
+                        context.request_focus(self.calendar.id());
+                    }
+                    Key::Named(NamedKey::Enter) => self.commit(),
+                    Key::Named(NamedKey::Escape) => self.update_text(),
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.text_box_bounds(), &self.text_box);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        visitor(self.text_box_bounds(), &mut self.text_box);
+    }
+
+    fn visit_popups(&self, visitor: &mut dyn FnMut(PopupPlacement, &dyn Component)) {
+        if self.is_open {
+            visitor(self.calendar_placement(), &self.calendar);
+        }
+    }
+
+    fn visit_popups_mut(&mut self, visitor: &mut dyn FnMut(PopupPlacement, &mut dyn Component)) {
+        if self.is_open {
+            visitor(self.calendar_placement(), &mut self.calendar);
+        }
+    }
+}
+
+impl DatePicker {
+    pub fn new() -> DatePicker {
+        let format = DateFormat::system();
+        DatePicker {
+            id: ComponentId::new(),
+            selected_date: None,
+            min_date: None,
+            max_date: None,
+            blackout_dates: vec![],
+            first_day_of_week: format.first_day_of_week,
+            format,
+            placeholder: String::new(),
+            enabled: true,
+            button_width: 24f32,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            invalid_border: Color::CRIMSON,
+            button_background: Color::WHITE,
+            hover_button_background: Color::GAINSBORO,
+            glyph: Color::DIM_GRAY,
+            on_selected_date_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            text_box: TextBox::new(),
+            text_box_focused: false,
+            calendar: Calendar::new(),
+            calendar_focused: false,
+            is_open: false,
+            displayed_date: None,
+            is_valid: true,
+            hovered: false,
+        }
+    }
+
+    /// # Description
+    /// Whether the text entered is empty or a date that can be selected.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// # Description
+    /// Shows the calendar, with the date entered or else the selected date selected in it.
+    ///
+    /// # Remarks
+    /// The calendar takes over the limits, the blackout dates and the format when opened.
+    pub fn open(&mut self) {
+        if self.is_open {
+            return;
+        }
+        self.is_open = true;
+        self.calendar.min_date = self.min_date;
+        self.calendar.max_date = self.max_date;
+        self.calendar.blackout_dates = self.blackout_dates.clone();
+        self.calendar.first_day_of_week = self.first_day_of_week;
+        self.calendar.format = self.format.clone();
+        self.calendar.text_style = self.text_box.text_style.clone();
+        let date = self.parse().unwrap_or(self.selected_date);
+        self.calendar.selected_date = date;
+        self.calendar.move_to(date.unwrap_or_else(Date::today));
+        self.calendar.take_committed();
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.calendar_focused = false;
+    }
+
+    /// # Description
+    /// Selects *date*, raising *on_selected_date_changed* if the selection changed.
+    ///
+    /// # Remarks
+    /// Dates that cannot be selected are ignored.
+    pub fn select(&mut self, date: Option<Date>) {
+        if let Some(date) = date {
+            if !self.is_selectable(date) {
+                return;
+            }
+        }
+        self.update_text_to(date);
+        if date == self.selected_date {
+            return;
+        }
+        self.selected_date = date;
+        if let Some(callback) = self.on_selected_date_changed.as_mut() {
+            callback(date);
+        }
+    }
+
+    fn is_selectable(&self, date: Date) -> bool {
+        self.min_date.map_or(true, |d| date >= d)
+            && self.max_date.map_or(true, |d| date <= d)
+            && !self.blackout_dates.contains(&date)
+    }
+
+    /// Parses the text entered, which is empty for no date.
+    fn parse(&self) -> Result<Option<Date>, ()> {
+        if self.text_box.text.trim().is_empty() {
+            return Ok(None);
+        }
+        match self.format.parse(&self.text_box.text) {
+            Ok(d) if self.is_selectable(d) => Ok(Some(d)),
+            _ => Err(()),
+        }
+    }
+
+    /// Selects the day picked in the calendar and closes it.
+    fn pick(&mut self, context: &mut EventContext) {
+        self.select(self.calendar.selected_date);
+        self.close();
+        context.request_focus(self.text_box.id());
+    }
+
+    /// Applies the text entered, which is reverted if it is not a date that can be selected.
+    fn commit(&mut self) {
+        match self.parse() {
+            Ok(date) => self.select(date),
+            Err(_) => self.update_text(),
+        }
+    }
+
+    fn update_text(&mut self) {
+        self.update_text_to(self.selected_date);
+    }
+
+    fn update_text_to(&mut self, date: Option<Date>) {
+        self.text_box.text = date.map_or_else(String::new, |d| self.format.format(d));
+        let end = self.text_box.text.len();
+        self.text_box.select(end, end);
+        self.displayed_date = Some(date);
+    }
+
+    fn calendar_placement(&self) -> PopupPlacement {
+        let anchor = Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height };
+        PopupPlacement::new(anchor, Placement::Bottom)
+    }
+
+    fn text_box_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: f32::max(0f32, self.size.width - self.button_width),
+            height: self.size.height,
+        }
+    }
+
+    fn button_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: f32::max(0f32, self.size.width - self.button_width),
+            y: 0f32,
+            width: f32::min(self.button_width, self.size.width),
+            height: self.size.height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use std::time::Instant;
+    use winit::keyboard::ModifiersState;
+
+    fn date(month: u32, day: u32) -> Date {
+        Date::new(2024, month, day).unwrap()
+    }
+
+    /// A date picker limited to the 10th of March to the 20th of April 2024 without the 15th
+    /// of March.
+    fn date_picker() -> DatePicker {
+        let mut date_picker = DatePicker::new();
+        date_picker.format = DateFormat::iso();
+        date_picker.min_date = Some(date(3, 10));
+        date_picker.max_date = Some(date(4, 20));
+        date_picker.blackout_dates = vec![date(3, 15)];
+        date_picker.arrange(Size { width: 200f32, height: 24f32 });
+        date_picker
+    }
+
+    fn key(key: NamedKey) -> InputEvent {
+        InputEvent::KeyPressed { key: Key::Named(key), text: None, modifiers: ModifiersState::empty(), repeat: false }
+    }
+
+    fn send(component: &mut dyn Component, event: InputEvent) -> bool {
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+        component.handle_event(&event, &mut context)
+    }
+
+    /// Enters *text* and presses Enter, returning whether the text was valid before.
+    fn enter(date_picker: &mut DatePicker, text: &str) -> bool {
+        date_picker.text_box.text = text.to_string();
+        date_picker.arrange(Size { width: 200f32, height: 24f32 });
+        let is_valid = date_picker.is_valid();
+        send(date_picker, key(NamedKey::Enter));
+        is_valid
+    }
+
+    #[test]
+    fn test_enter() {
+        let mut date_picker = date_picker();
+        assert!(enter(&mut date_picker, "2024-03-12"));
+        assert_eq!(date_picker.selected_date, Some(date(3, 12)));
+
+        // Dates outside of the limits or blacked out are reverted
+        for text in ["2024-03-09", "2024-03-15", "2024-04-21", "2024-03-1x5"] {
+            assert!(!enter(&mut date_picker, text));
+            assert_eq!(date_picker.selected_date, Some(date(3, 12)));
+            assert_eq!(date_picker.text_box.text, "2024-03-12");
+        }
+        date_picker.select(Some(date(3, 15)));
+        assert_eq!(date_picker.selected_date, Some(date(3, 12)));
+        assert!(enter(&mut date_picker, ""));
+        assert_eq!(date_picker.selected_date, None);
+    }
+
+    #[test]
+    fn test_calendar() {
+        let mut date_picker = date_picker();
+        date_picker.select(Some(date(3, 12)));
+        send(&mut date_picker, key(NamedKey::F4));
+        assert!(date_picker.is_open());
+        assert_eq!(date_picker.calendar.min_date, Some(date(3, 10)));
+        assert_eq!(date_picker.calendar.max_date, Some(date(4, 20)));
+        assert_eq!(date_picker.calendar.blackout_dates, vec![date(3, 15)]);
+        let size = date_picker.calendar.measure(Size { width: 300f32, height: 300f32 });
+        date_picker.calendar.arrange(size);
+
+        // Enter on a blacked out day is handled by the calendar without picking it
+        send(&mut date_picker.calendar, InputEvent::FocusGained);
+        send(&mut date_picker.calendar, key(NamedKey::ArrowRight));
+        send(&mut date_picker.calendar, key(NamedKey::ArrowRight));
+        send(&mut date_picker.calendar, key(NamedKey::ArrowRight));
+        assert!(send(&mut date_picker.calendar, key(NamedKey::Enter)));
+        assert!(date_picker.is_open());
+        assert_eq!(date_picker.selected_date, Some(date(3, 12)));
+
+        // Otherwise Enter bubbles up to the date picker, which picks the day
+        send(&mut date_picker.calendar, key(NamedKey::ArrowRight));
+        assert!(!send(&mut date_picker.calendar, key(NamedKey::Enter)));
+        send(&mut date_picker, key(NamedKey::Enter));
+        assert!(!date_picker.is_open());
+        assert_eq!(date_picker.selected_date, Some(date(3, 16)));
+        assert_eq!(date_picker.text_box.text, "2024-03-16");
+    }
+}
@@ -0,0 +1,445 @@
+use std::time::{Duration, Instant};
+use crate::abstraction::{Component, ContainerComponent, Renderer, Visual};
+use crate::color::Color;
+use crate::components::press_state::{PressResult, PressState};
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::{self, TextStyle};
+use crate::visuals::{self, PathCommand};
+
+/// The time expanding or collapsing the content takes, if animated.
+const ANIMATION_DURATION: Duration = Duration::from_millis(150);
+/// The width of the chevron in front of the header text.
+const CHEVRON_WIDTH: f32 = 16f32;
+
+/// # Description
+/// Displays a header, which the user clicks to show or hide the single child below it.
+///
+/// # Remarks
+/// Pressing Space while the expander has the keyboard focus toggles it as well.
+/// Adding a child using [`ContainerComponent::add_child`] replaces the previous child.
+pub struct Expander {
+    // #[property]
+    pub header: String,
+    // #[property]
+    pub child: Option<Box<dyn Component>>,
+    // #[property]
+    pub is_expanded: bool,
+    /// Whether the content slides in and out instead of appearing at once.
+    // #[property]
+    pub is_animated: bool,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub text_style: TextStyle,
+    /// The padding of the header around its chevron and text.
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub hover_background: Color,
+    // #[property]
+    pub focused_border: Color,
+    /// The color of the chevron.
+    // #[property]
+    pub glyph: Color,
+    /// Called with the new expansion whenever the user expanded or collapsed the expander.
+    // #[event]
+    pub on_expanded_changed: Option<Box<dyn FnMut(bool)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    press: PressState,
+    header_hovered: bool,
+    header_height: f32,
+    baseline: f32,
+    content_height: f32,
+    /// How far the content is expanded, from `0` to `1`.
+    progress: f32,
+    last_tick: Option<Instant>,
+}
+
+impl Component for Expander {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let metrics = text::text_metrics();
+        let header_height = self.compute_header_height();
+        let header_width = CHEVRON_WIDTH
+            + metrics.measure_text(&self.header, &self.text_style)
+            + self.padding.horizontal();
+        let progress = self.current_progress();
+        let desired = match &self.child {
+            Some(child) if progress > 0f32 => child.measure(Size {
+                width: available.width,
+                height: f32::max(0f32, available.height - header_height),
+            }),
+            _ => Size { width: 0f32, height: 0f32 },
+        };
+        Size {
+            width: f32::max(header_width, desired.width),
+            height: header_height + desired.height * progress,
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let font = text::text_metrics().font_metrics(&self.text_style);
+        self.header_height = self.compute_header_height();
+        self.baseline = (self.header_height - font.line_height) / 2f32 + font.ascent;
+        if !self.is_animated {
+            self.progress = self.target();
+        }
+        let remaining = f32::max(0f32, given.height - self.header_height);
+        if let Some(child) = self.child.as_mut() {
+            // While animating, the content keeps its size and is revealed gradually
+            let desired = child.measure(Size { width: given.width, height: remaining });
+            self.content_height = if self.progress >= 1f32 { remaining } else { desired.height };
+            child.arrange(Size { width: given.width, height: self.content_height });
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let header = self.header_bounds();
+        if self.enabled && self.header_hovered {
+            visuals::Rectangle {
+                x: header.x,
+                y: header.y,
+                width: header.width,
+                height: header.height,
+                fill: self.hover_background,
+            }
+            .draw(renderer);
+        }
+        if self.press.focused {
+            visuals::Outline {
+                x: 0.5f32,
+                y: 0.5f32,
+                width: header.width - 1f32,
+                height: header.height - 1f32,
+                stroke: self.focused_border,
+                thickness: 1f32,
+            }
+            .draw(renderer);
+        }
+
+        // The chevron points right while collapsed and turns down while expanding
+        let center = Point {
+            x: self.padding.left + CHEVRON_WIDTH / 2f32,
+            y: header.height / 2f32,
+        };
+        let angle = self.progress * std::f32::consts::FRAC_PI_2;
+        let (sin, cos) = angle.sin_cos();
+        let point = |x: f32, y: f32| Point {
+            x: center.x + x * cos - y * sin,
+            y: center.y + x * sin + y * cos,
+        };
+        visuals::Path {
+            commands: &[
+                PathCommand::MoveTo(point(-2f32, -4f32)),
+                PathCommand::LineTo(point(2f32, 0f32)),
+                PathCommand::LineTo(point(-2f32, 4f32)),
+            ],
+            fill: Color::TRANSPARENT,
+            stroke: self.glyph,
+            thickness: 1.5f32,
+        }
+        .draw(renderer);
+        visuals::Text {
+            x: self.padding.left + CHEVRON_WIDTH,
+            y: self.baseline,
+            text: &self.header,
+            style: &self.text_style,
+        }
+        .draw(renderer);
+
+        if let Some(child) = self.visible_child() {
+            let bounds = self.child_bounds();
+            let visible = f32::max(0f32, self.size.height - self.header_height);
+            renderer.push_clip(bounds.x, bounds.y, bounds.width, visible);
+            renderer.translate(bounds.x, bounds.y);
+            child.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+            renderer.pop_clip();
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::Tick if self.progress != self.target() => {
+                let now = context.now();
+                if let Some(last_tick) = self.last_tick {
+                    let elapsed = now.duration_since(last_tick).as_secs_f32();
+                    let step = elapsed / ANIMATION_DURATION.as_secs_f32();
+                    self.progress = if self.is_expanded {
+                        f32::min(1f32, self.progress + step)
+                    } else {
+                        f32::max(0f32, self.progress - step)
+                    };
+                }
+                self.last_tick = Some(now);
+                return true;
+            }
+            InputEvent::Tick => {
+                self.last_tick = None;
+                return false;
+            }
+            InputEvent::PointerMoved { position } => {
+                self.header_hovered = self.header_bounds().contains(*position);
+            }
+            InputEvent::PointerLeft => self.header_hovered = false,
+            // Events bubbling from the content only toggle the expander if they concern the header
+            InputEvent::PointerPressed { position, .. }
+                if !self.header_bounds().contains(*position) =>
+            {
+                return false;
+            }
+            InputEvent::KeyPressed { .. } | InputEvent::KeyReleased { .. } if !self.press.focused => {
+                return false;
+            }
+            _ => {}
+        }
+        match self.press.handle_event(event, context, self.header_bounds(), self.enabled) {
+            PressResult::Unhandled => false,
+            PressResult::Handled => true,
+            PressResult::Clicked => {
+                self.toggle();
+                true
+            }
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if let Some(child) = self.visible_child() {
+            visitor(self.child_bounds(), child);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.child_bounds();
+        if self.progress <= 0f32 {
+            return;
+        }
+        if let Some(child) = self.child.as_mut() {
+            visitor(bounds, &mut **child);
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        self.is_animated && self.progress != self.target()
+    }
+}
+
+impl ContainerComponent for Expander {
+    fn add_child(&mut self, child: Box<dyn Component>) {
+        self.child = Some(child);
+    }
+}
+
+impl Expander {
+    pub fn new() -> Expander {
+        Expander {
+            id: ComponentId::new(),
+            header: String::new(),
+            child: None,
+            is_expanded: false,
+            is_animated: true,
+            enabled: true,
+            text_style: TextStyle::new(),
+            padding: Thickness::uniform(4f32),
+            hover_background: Color::GAINSBORO,
+            focused_border: Color::MIDNIGHT_BLUE,
+            glyph: Color::DIM_GRAY,
+            on_expanded_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            press: PressState::new(),
+            header_hovered: false,
+            header_height: 0f32,
+            baseline: 0f32,
+            content_height: 0f32,
+            progress: 0f32,
+            last_tick: None,
+        }
+    }
+
+    pub fn with_child(mut self, child: Box<dyn Component>) -> Self {
+        self.child = Some(child);
+        self
+    }
+
+    /// # Description
+    /// Expands the expander if collapsed and collapses it otherwise, as if the user clicked
+    /// its header.
+    pub fn toggle(&mut self) {
+        self.is_expanded = !self.is_expanded;
+        if let Some(callback) = self.on_expanded_changed.as_mut() {
+            callback(self.is_expanded);
+        }
+    }
+
+    fn target(&self) -> f32 {
+        if self.is_expanded { 1f32 } else { 0f32 }
+    }
+
+    /// The progress to lay out with, which follows the expansion at once if not animated.
+    fn current_progress(&self) -> f32 {
+        if self.is_animated { self.progress } else { self.target() }
+    }
+
+    fn compute_header_height(&self) -> f32 {
+        let line_height = text::text_metrics().font_metrics(&self.text_style).line_height;
+        f32::max(line_height, CHEVRON_WIDTH) + self.padding.vertical()
+    }
+
+    fn visible_child(&self) -> Option<&dyn Component> {
+        if self.progress <= 0f32 {
+            return None;
+        }
+        self.child.as_deref()
+    }
+
+    fn header_bounds(&self) -> Rectangle<f32> {
+        Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.header_height }
+    }
+
+    fn child_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: self.header_height,
+            width: self.size.width,
+            height: self.content_height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::TextBlock;
+    use crate::pointer::PointerState;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use winit::event::MouseButton;
+    use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+    /// An expander recording its expansion changes in the returned vector.
+    fn expander(is_animated: bool) -> (Expander, Rc<RefCell<Vec<bool>>>) {
+        let changes = Rc::new(RefCell::new(vec![]));
+        let mut child = TextBlock::new();
+        child.text = "Content".to_string();
+        let mut expander = Expander::new().with_child(Box::new(child));
+        expander.header = "Header".to_string();
+        expander.is_animated = is_animated;
+        let recorder = changes.clone();
+        expander.on_expanded_changed = Some(Box::new(move |d| recorder.borrow_mut().push(d)));
+        expander.arrange(Size { width: 200f32, height: 200f32 });
+        (expander, changes)
+    }
+
+    fn send_at(expander: &mut Expander, event: InputEvent, now: Instant) -> bool {
+        let mut context = EventContext::new(now, &PointerState::new(), None, ModifiersState::empty());
+        expander.handle_event(&event, &mut context)
+    }
+
+    fn send(expander: &mut Expander, event: InputEvent) -> bool {
+        send_at(expander, event, Instant::now())
+    }
+
+    fn click(expander: &mut Expander, position: Point<f32>) {
+        send(expander, InputEvent::PointerPressed { position, button: MouseButton::Left });
+        send(expander, InputEvent::PointerReleased { position, button: MouseButton::Left });
+    }
+
+    fn count_children(expander: &mut Expander) -> usize {
+        let mut count = 0;
+        expander.visit_children_mut(&mut |_, _| count += 1);
+        count
+    }
+
+    #[test]
+    fn test_toggle() {
+        let (mut expander, changes) = expander(false);
+        let header = Point { x: 100f32, y: expander.header_height / 2f32 };
+        assert_eq!(count_children(&mut expander), 0);
+        click(&mut expander, header);
+        assert!(expander.is_expanded);
+        expander.arrange(Size { width: 200f32, height: 200f32 });
+        assert_eq!(count_children(&mut expander), 1);
+
+        // Presses on the content do not collapse the expander
+        let content = Point { x: 100f32, y: expander.header_height + 4f32 };
+        click(&mut expander, content);
+        assert!(expander.is_expanded);
+
+        // Space only toggles while focused
+        let space = InputEvent::KeyPressed {
+            key: Key::Named(NamedKey::Space),
+            text: None,
+            modifiers: ModifiersState::empty(),
+            repeat: false,
+        };
+        let release = InputEvent::KeyReleased { key: Key::Named(NamedKey::Space), modifiers: ModifiersState::empty() };
+        assert!(!send(&mut expander, space.clone()));
+        send(&mut expander, InputEvent::FocusGained);
+        send(&mut expander, space);
+        send(&mut expander, release);
+        assert!(!expander.is_expanded);
+        expander.arrange(Size { width: 200f32, height: 200f32 });
+        assert_eq!(count_children(&mut expander), 0);
+        assert_eq!(*changes.borrow(), vec![true, false]);
+        assert!(!expander.is_animating());
+    }
+
+    #[test]
+    fn test_animation() {
+        let (mut expander, _) = expander(true);
+        let available = Size { width: 200f32, height: 200f32 };
+        let collapsed = expander.measure(available).height;
+        expander.toggle();
+        assert!(expander.is_animating());
+        assert_eq!(expander.measure(available).height, collapsed);
+
+        // The content slides in over the animation duration
+        let start = Instant::now();
+        assert!(send_at(&mut expander, InputEvent::Tick, start));
+        assert!(send_at(&mut expander, InputEvent::Tick, start + ANIMATION_DURATION / 2));
+        assert!((expander.progress - 0.5f32).abs() < 0.01f32);
+        let halfway = expander.measure(available).height;
+        assert!(halfway > collapsed);
+        expander.arrange(available);
+        assert_eq!(count_children(&mut expander), 1);
+        send_at(&mut expander, InputEvent::Tick, start + ANIMATION_DURATION);
+        assert_eq!(expander.progress, 1f32);
+        assert!(!expander.is_animating());
+        let expanded = expander.measure(available).height;
+        assert!(expanded > halfway);
+        assert!(!send_at(&mut expander, InputEvent::Tick, start + ANIMATION_DURATION * 2));
+
+        // Collapsing keeps the content visible until the animation ends
+        expander.toggle();
+        let start = start + ANIMATION_DURATION * 3;
+        send_at(&mut expander, InputEvent::Tick, start);
+        send_at(&mut expander, InputEvent::Tick, start + ANIMATION_DURATION / 2);
+        expander.arrange(available);
+        assert_eq!(count_children(&mut expander), 1);
+        send_at(&mut expander, InputEvent::Tick, start + ANIMATION_DURATION);
+        expander.arrange(available);
+        assert_eq!(count_children(&mut expander), 0);
+        assert_eq!(expander.measure(available).height, collapsed);
+        assert!(!expander.is_animating());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::data::{Point, Rectangle, Size};
+use crate::text::{self, TextStyle};
+
+/// # Description
+/// The layout of a glyph (eg. the box of a check box) followed by a single line of text,
+/// both centered vertically.
+pub(crate) struct GlyphLabel {
+    pub(crate) glyph: Rectangle<f32>,
+    /// The start of the baseline of the text.
+    pub(crate) text_origin: Point<f32>,
+}
+
+impl GlyphLabel {
+    pub(crate) fn new() -> GlyphLabel {
+        GlyphLabel {
+            glyph: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+            text_origin: Point { x: 0f32, y: 0f32 },
+        }
+    }
+
+    pub(crate) fn measure(glyph: Size<f32>, spacing: f32, text: &str, style: &TextStyle) -> Size<f32> {
+        if text.is_empty() {
+            return glyph;
+        }
+        let metrics = text::text_metrics();
+        let line_height = metrics.font_metrics(style).line_height;
+        Size {
+            width: glyph.width + spacing + metrics.measure_text(text, style),
+            height: f32::max(glyph.height, line_height),
+        }
+    }
+
+    pub(crate) fn arrange(glyph: Size<f32>, spacing: f32, style: &TextStyle, given: Size<f32>) -> GlyphLabel {
+        let font = text::text_metrics().font_metrics(style);
+        GlyphLabel {
+            glyph: Rectangle {
+                x: 0f32,
+                y: (given.height - glyph.height) / 2f32,
+                width: glyph.width,
+                height: glyph.height,
+            },
+            text_origin: Point {
+                x: glyph.width + spacing,
+                y: (given.height - font.line_height) / 2f32 + font.ascent,
+            },
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::abstraction::{Component, ContainerComponent, Renderer, Visual};
+use crate::color::Color;
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::text::{self, TextStyle};
+use crate::visuals::{self, PathCommand};
+
+/// The distance of the header from the left edge of the frame.
+const HEADER_INSET: f32 = 8f32;
+/// The space the frame leaves around the header.
+const HEADER_GAP: f32 = 3f32;
+
+/// # Description
+/// Draws a frame titled by a header around a single child.
+///
+/// # Remarks
+/// Adding a child using [`ContainerComponent::add_child`] replaces the previous child.
+pub struct GroupBox {
+    // #[property]
+    pub header: String,
+    // #[property]
+    pub child: Option<Box<dyn Component>>,
+    // #[property]
+    pub text_style: TextStyle,
+    // #[property]
+    pub border_brush: Color,
+    /// The space between the frame and the child.
+    // #[property]
+    pub padding: Thickness,
+
+    id: ComponentId,
+    size: Size<f32>,
+    header_height: f32,
+    header_width: f32,
+    baseline: f32,
+}
+
+impl Component for GroupBox {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let metrics = text::text_metrics();
+        let header_height = metrics.font_metrics(&self.text_style).line_height;
+        let header_width = metrics.measure_text(&self.header, &self.text_style);
+        let horizontal = self.padding.horizontal() + 2f32;
+        let vertical = self.padding.vertical() + header_height + 1f32;
+        let desired = match &self.child {
+            None => Size { width: 0f32, height: 0f32 },
+            Some(child) => child.measure(Size {
+                width: f32::max(0f32, available.width - horizontal),
+                height: f32::max(0f32, available.height - vertical),
+            }),
+        };
+        let header_width = header_width + 2f32 * (HEADER_INSET + HEADER_GAP);
+        Size {
+            width: f32::max(desired.width + horizontal, header_width),
+            height: desired.height + vertical,
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let metrics = text::text_metrics();
+        let font = metrics.font_metrics(&self.text_style);
+        self.header_height = font.line_height;
+        self.baseline = font.ascent;
+        self.header_width = metrics.measure_text(&self.header, &self.text_style);
+        let bounds = self.child_bounds();
+        if let Some(child) = self.child.as_mut() {
+            let size = Size { width: bounds.width, height: bounds.height };
+            child.measure(size);
+            child.arrange(size);
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let top = (self.header_height / 2f32).round() + 0.5f32;
+        let (left, right, bottom) = (0.5f32, self.size.width - 0.5f32, self.size.height - 0.5f32);
+        // The frame is open where the header is drawn
+        let gap_start = f32::min(right, HEADER_INSET);
+        let gap_end = if self.header.is_empty() {
+            gap_start
+        } else {
+            f32::min(right, HEADER_INSET + 2f32 * HEADER_GAP + self.header_width)
+        };
+        visuals::Path {
+            commands: &[
+                PathCommand::MoveTo(Point { x: gap_start, y: top }),
+                PathCommand::LineTo(Point { x: left, y: top }),
+                PathCommand::LineTo(Point { x: left, y: bottom }),
+                PathCommand::LineTo(Point { x: right, y: bottom }),
+                PathCommand::LineTo(Point { x: right, y: top }),
+                PathCommand::LineTo(Point { x: gap_end, y: top }),
+            ],
+            fill: Color::TRANSPARENT,
+            stroke: self.border_brush,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+        let header_x = HEADER_INSET + HEADER_GAP;
+        let header_width = f32::max(0f32, gap_end - HEADER_GAP - header_x);
+        renderer.push_clip(header_x, 0f32, header_width, self.header_height);
+        visuals::Text { x: header_x, y: self.baseline, text: &self.header, style: &self.text_style }
+            .draw(renderer);
+        renderer.pop_clip();
+
+        if let Some(child) = &self.child {
+            let bounds = self.child_bounds();
+            renderer.push_clip(bounds.x, bounds.y, bounds.width, bounds.height);
+            renderer.translate(bounds.x, bounds.y);
+            child.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+            renderer.pop_clip();
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if let Some(child) = &self.child {
+            visitor(self.child_bounds(), &**child);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.child_bounds();
+        if let Some(child) = self.child.as_mut() {
+            visitor(bounds, &mut **child);
+        }
+    }
+}
+
+impl ContainerComponent for GroupBox {
+    fn add_child(&mut self, child: Box<dyn Component>) {
+        self.child = Some(child);
+    }
+}
+
+impl GroupBox {
+    pub fn new() -> GroupBox {
+        GroupBox {
+            id: ComponentId::new(),
+            header: String::new(),
+            child: None,
+            text_style: TextStyle::new(),
+            border_brush: Color::GRAY,
+            padding: Thickness::uniform(6f32),
+            size: Size { width: 0f32, height: 0f32 },
+            header_height: 0f32,
+            header_width: 0f32,
+            baseline: 0f32,
+        }
+    }
+
+    pub fn with_child(mut self, child: Box<dyn Component>) -> Self {
+        self.child = Some(child);
+        self
+    }
+
+    fn child_bounds(&self) -> Rectangle<f32> {
+        let top = self.header_height + self.padding.top;
+        Rectangle {
+            x: 1f32 + self.padding.left,
+            y: top,
+            width: f32::max(0f32, self.size.width - 2f32 - self.padding.horizontal()),
+            height: f32::max(0f32, self.size.height - top - 1f32 - self.padding.bottom),
+        }
+    }
+}
@@ -0,0 +1,387 @@
+use winit::event::MouseButton;
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::ScrollBar;
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size, Transform};
+use crate::image::{Image, ImageInterpolation};
+use crate::input::{EventContext, InputEvent, PIXELS_PER_WHEEL_LINE};
+use crate::visuals;
+
+const SCROLL_BAR_THICKNESS: f32 = 14f32;
+/// The factor a single line of the mouse wheel zooms in or out by.
+const WHEEL_ZOOM_FACTOR: f32 = 1.25f32;
+
+/// # Description
+/// Controls how large an [`ImageView`] shows its image.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZoomMode {
+    /// The whole image is shown, as large as fits into the view.
+    Fit,
+    /// The image covers the whole view, as small as possible.
+    Fill,
+    /// Every pixel of the image takes a single pixel of the view.
+    ActualSize,
+    /// The image keeps the zoom set using the mouse wheel or [`ImageView::zoom_to`].
+    Manual,
+}
+
+/// # Description
+/// Shows an image, which the user zooms using the mouse wheel and pans by dragging it.
+///
+/// # Remarks
+/// The mouse wheel zooms around the pointer, keeping the pixel under it in place, and switches
+/// to [`ZoomMode::Manual`]. The image is dragged using the left or the middle mouse button.
+/// Scroll bars are shown while the image is larger than the view, images smaller than the view
+/// are centered in it.
+pub struct ImageView {
+    // #[property]
+    pub image: Option<Image>,
+    // #[property]
+    pub zoom_mode: ZoomMode,
+    /// The smallest zoom the user can zoom out to.
+    // #[property]
+    pub min_zoom: f32,
+    /// The largest zoom the user can zoom in to.
+    // #[property]
+    pub max_zoom: f32,
+    // #[property]
+    pub interpolation: ImageInterpolation,
+    // #[property]
+    pub background: Color,
+    /// Called with the new zoom whenever it changed, eg. by the user zooming or by the view
+    /// being resized while fitting the image.
+    // #[event]
+    pub on_zoom_changed: Option<Box<dyn FnMut(f32)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    zoom: f32,
+    viewport: Size<f32>,
+    extent: Size<f32>,
+    horizontal_bar: ScrollBar,
+    vertical_bar: ScrollBar,
+    horizontal_bar_visible: bool,
+    vertical_bar_visible: bool,
+    /// The scroll offset to apply during the next layout pass, once the extent is known.
+    pending_scroll: Option<Point<f32>>,
+    /// The last pointer position while the image is dragged.
+    panning: Option<Point<f32>>,
+}
+
+impl Component for ImageView {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let image = self.image_size();
+        Size {
+            width: if available.width.is_finite() { available.width } else { image.width },
+            height: if available.height.is_finite() { available.height } else { image.height },
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let image = self.image_size();
+        let (mut horizontal, mut vertical) = (false, false);
+        let mut viewport = self.viewport_for(horizontal, vertical);
+        let mut zoom = self.zoom_for(viewport);
+        // Showing a scroll bar shrinks the viewport, which may require the other one
+        for _ in 0..3 {
+            // Less than half a pixel is not worth scrolling
+            let next_horizontal = image.width * zoom - viewport.width > 0.5f32;
+            let next_vertical = image.height * zoom - viewport.height > 0.5f32;
+            if next_horizontal == horizontal && next_vertical == vertical {
+                break;
+            }
+            horizontal = next_horizontal;
+            vertical = next_vertical;
+            viewport = self.viewport_for(horizontal, vertical);
+            zoom = self.zoom_for(viewport);
+        }
+        self.horizontal_bar_visible = horizontal;
+        self.vertical_bar_visible = vertical;
+        self.viewport = viewport;
+        self.extent = Size { width: image.width * zoom, height: image.height * zoom };
+        if zoom != self.zoom {
+            self.zoom = zoom;
+            if let Some(callback) = self.on_zoom_changed.as_mut() {
+                callback(zoom);
+            }
+        }
+
+        self.horizontal_bar.range.max_value = f32::max(0f32, self.extent.width - viewport.width);
+        self.horizontal_bar.bar_value = viewport.width;
+        self.vertical_bar.range.max_value = f32::max(0f32, self.extent.height - viewport.height);
+        self.vertical_bar.bar_value = viewport.height;
+        let scroll = self.pending_scroll.take().unwrap_or(Point {
+            x: self.horizontal_bar.range.value,
+            y: self.vertical_bar.range.value,
+        });
+        self.horizontal_bar.set_value(scroll.x);
+        self.vertical_bar.set_value(scroll.y);
+        self.horizontal_bar.arrange(Size { width: viewport.width, height: SCROLL_BAR_THICKNESS });
+        self.vertical_bar.arrange(Size { width: SCROLL_BAR_THICKNESS, height: viewport.height });
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.viewport.width,
+            height: self.viewport.height,
+            fill: self.background,
+        }
+        .draw(renderer);
+        if let Some(image) = &self.image {
+            let origin = self.origin();
+            renderer.push_clip(0f32, 0f32, self.viewport.width, self.viewport.height);
+            let transform = Transform::scaling(self.zoom, self.zoom)
+                .then(&Transform::translation(origin.x, origin.y));
+            renderer.push_transform(transform);
+            visuals::Image {
+                x: 0f32,
+                y: 0f32,
+                width: image.width() as f32,
+                height: image.height() as f32,
+                image,
+                interpolation: self.interpolation,
+            }
+            .draw(renderer);
+            renderer.pop_transform();
+            renderer.pop_clip();
+        }
+        if self.horizontal_bar_visible {
+            renderer.translate(0f32, self.viewport.height);
+            self.horizontal_bar.render(renderer);
+            renderer.translate(0f32, -self.viewport.height);
+        }
+        if self.vertical_bar_visible {
+            renderer.translate(self.viewport.width, 0f32);
+            self.vertical_bar.render(renderer);
+            renderer.translate(-self.viewport.width, 0f32);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerWheel { position, delta } if self.image.is_some() && delta.y != 0f32 => {
+                let zoom = self.zoom * WHEEL_ZOOM_FACTOR.powf(delta.y);
+                self.zoom_around(*position, zoom);
+                true
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left | MouseButton::Middle }
+                if self.image.is_some() =>
+            {
+                self.panning = Some(*position);
+                context.capture_pointer();
+                true
+            }
+            InputEvent::PointerMoved { position } => {
+                let last = match self.panning {
+                    None => return false,
+                    Some(d) => d,
+                };
+                let value = self.horizontal_bar.range.value - (position.x - last.x);
+                self.horizontal_bar.set_value(value);
+                let value = self.vertical_bar.range.value - (position.y - last.y);
+                self.vertical_bar.set_value(value);
+                self.panning = Some(*position);
+                true
+            }
+            InputEvent::PointerReleased { button: MouseButton::Left | MouseButton::Middle, .. } => {
+                if self.panning.take().is_none() {
+                    return false;
+                }
+                context.release_pointer_capture();
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.panning = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if self.horizontal_bar_visible {
+            visitor(self.horizontal_bar_bounds(), &self.horizontal_bar);
+        }
+        if self.vertical_bar_visible {
+            visitor(self.vertical_bar_bounds(), &self.vertical_bar);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        if self.horizontal_bar_visible {
+            visitor(self.horizontal_bar_bounds(), &mut self.horizontal_bar);
+        }
+        if self.vertical_bar_visible {
+            visitor(self.vertical_bar_bounds(), &mut self.vertical_bar);
+        }
+    }
+}
+
+impl ImageView {
+    pub fn new() -> ImageView {
+        let mut horizontal_bar = ScrollBar::new();
+        horizontal_bar.mode = Orientation::Horizontal;
+        horizontal_bar.thickness = SCROLL_BAR_THICKNESS;
+        horizontal_bar.range.small_change = PIXELS_PER_WHEEL_LINE;
+        let mut vertical_bar = ScrollBar::new();
+        vertical_bar.mode = Orientation::Vertical;
+        vertical_bar.thickness = SCROLL_BAR_THICKNESS;
+        vertical_bar.range.small_change = PIXELS_PER_WHEEL_LINE;
+        ImageView {
+            id: ComponentId::new(),
+            image: None,
+            zoom_mode: ZoomMode::Fit,
+            min_zoom: 0.05f32,
+            max_zoom: 32f32,
+            interpolation: ImageInterpolation::Linear,
+            background: Color::TRANSPARENT,
+            on_zoom_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            zoom: 1f32,
+            viewport: Size { width: 0f32, height: 0f32 },
+            extent: Size { width: 0f32, height: 0f32 },
+            horizontal_bar,
+            vertical_bar,
+            horizontal_bar_visible: false,
+            vertical_bar_visible: false,
+            pending_scroll: None,
+            panning: None,
+        }
+    }
+
+    pub fn with_image(mut self, image: Image) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    /// # Description
+    /// The factor the image is scaled by, as of the last layout pass.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// # Description
+    /// Zooms to *zoom*, clamped to *min_zoom* and *max_zoom*, keeping the center of the view in
+    /// place.
+    ///
+    /// # Remarks
+    /// Switches to [`ZoomMode::Manual`].
+    pub fn zoom_to(&mut self, zoom: f32) {
+        let center = Point { x: self.viewport.width / 2f32, y: self.viewport.height / 2f32 };
+        self.zoom_around(center, zoom);
+    }
+
+    /// Zooms to *zoom*, keeping the pixel at *position* in place.
+    fn zoom_around(&mut self, position: Point<f32>, zoom: f32) {
+        let zoom = zoom.max(self.min_zoom).min(self.max_zoom);
+        let origin = self.origin();
+        self.pending_scroll = Some(Point {
+            x: scroll_for_zoom(position.x, origin.x, self.zoom, zoom),
+            y: scroll_for_zoom(position.y, origin.y, self.zoom, zoom),
+        });
+        self.zoom_mode = ZoomMode::Manual;
+        if zoom != self.zoom {
+            self.zoom = zoom;
+            if let Some(callback) = self.on_zoom_changed.as_mut() {
+                callback(zoom);
+            }
+        }
+    }
+
+    fn image_size(&self) -> Size<f32> {
+        match &self.image {
+            None => Size { width: 0f32, height: 0f32 },
+            Some(d) => Size { width: d.width() as f32, height: d.height() as f32 },
+        }
+    }
+
+    /// The zoom of the current mode for *viewport*.
+    fn zoom_for(&self, viewport: Size<f32>) -> f32 {
+        let image = self.image_size();
+        if image.width <= 0f32 || image.height <= 0f32 {
+            return self.zoom;
+        }
+        let (horizontal, vertical) = (viewport.width / image.width, viewport.height / image.height);
+        match self.zoom_mode {
+            ZoomMode::Fit => f32::min(horizontal, vertical),
+            ZoomMode::Fill => f32::max(horizontal, vertical),
+            ZoomMode::ActualSize => 1f32,
+            ZoomMode::Manual => self.zoom,
+        }
+    }
+
+    /// The position of the top left corner of the image in the view.
+    fn origin(&self) -> Point<f32> {
+        let origin = |extent: f32, viewport: f32, scroll: f32| {
+            if extent <= viewport { (viewport - extent) / 2f32 } else { -scroll }
+        };
+        Point {
+            x: origin(self.extent.width, self.viewport.width, self.horizontal_bar.range.value),
+            y: origin(self.extent.height, self.viewport.height, self.vertical_bar.range.value),
+        }
+    }
+
+    fn viewport_for(&self, horizontal_bar: bool, vertical_bar: bool) -> Size<f32> {
+        Size {
+            width: f32::max(0f32, self.size.width - if vertical_bar { SCROLL_BAR_THICKNESS } else { 0f32 }),
+            height: f32::max(0f32, self.size.height - if horizontal_bar { SCROLL_BAR_THICKNESS } else { 0f32 }),
+        }
+    }
+
+    fn horizontal_bar_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: self.viewport.height,
+            width: self.viewport.width,
+            height: SCROLL_BAR_THICKNESS,
+        }
+    }
+
+    fn vertical_bar_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: self.viewport.width,
+            y: 0f32,
+            width: SCROLL_BAR_THICKNESS,
+            height: self.viewport.height,
+        }
+    }
+}
+
+/// # Description
+/// The scroll offset along one axis keeping the pixel at *position* in place while zooming.
+///
+/// # Parameters
+/// - **position**: The position in the view to zoom around.
+/// - **origin**: The position of the image in the view before zooming.
+/// - **zoom**: The zoom before zooming.
+/// - **new_zoom**: The zoom after zooming.
+fn scroll_for_zoom(position: f32, origin: f32, zoom: f32, new_zoom: f32) -> f32 {
+    (position - origin) / zoom * new_zoom - position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_for_zoom() {
+        // The image is scrolled by 100 pixels, the pointer is over pixel 75 of it
+        let scroll = scroll_for_zoom(50f32, -100f32, 2f32, 4f32);
+        assert_eq!(scroll, 250f32);
+        assert_eq!((50f32 + scroll) / 4f32, 75f32);
+        // A centered image starts scrolling where the pointer is
+        assert_eq!(scroll_for_zoom(60f32, 10f32, 1f32, 2f32), 40f32);
+        assert_eq!(scroll_for_zoom(0f32, 0f32, 1f32, 0.5f32), 0f32);
+    }
+}
@@ -0,0 +1,635 @@
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::{
+    ScrollBarVisibility, ScrollViewer, SelectionMode, SelectionModel, TextBlock, VirtualizingPanel,
+};
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::{TextStyle, TextTrimming};
+use crate::visuals;
+
+/// The maximum time between two key presses searching for the same item.
+const SEARCH_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// # Description
+/// Provides the items displayed by a [`ListView`].
+///
+/// # Remarks
+/// Sources changing their items have to be refreshed using [`ListView::refresh`].
+pub trait ItemSource {
+    /// # Description
+    /// The number of items.
+    fn count(&self) -> usize;
+
+    /// # Description
+    /// The text of the item at *index*.
+    ///
+    /// # Remarks
+    /// Displayed by the default item template and used to search items by typing their text.
+    fn text(&self, index: usize) -> String;
+}
+
+impl ItemSource for Vec<String> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn text(&self, index: usize) -> String {
+        self[index].clone()
+    }
+}
+
+/// # Description
+/// Creates the component displaying the item at the given index of an [`ItemSource`].
+pub type ItemTemplate = Rc<dyn Fn(&dyn ItemSource, usize) -> Box<dyn Component>>;
+
+/// # Description
+/// The container of an item within a [`ListView`] or [`super::TreeView`], drawing its selection and hover state
+/// behind the component created by the item template.
+pub(crate) struct ListViewItem {
+    id: ComponentId,
+    content: Box<dyn Component>,
+    padding: Thickness,
+    size: Size<f32>,
+    hovered: bool,
+    /// The background of the item while selected.
+    pub(crate) selection: Option<Color>,
+    pub(crate) hover_background: Color,
+    /// The outline marking the current item while the list view has the keyboard focus.
+    pub(crate) current: Option<Color>,
+}
+
+impl Component for ListViewItem {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        available
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let content = self.content_bounds();
+        self.content.measure(Size { width: content.width, height: content.height });
+        self.content.arrange(Size { width: content.width, height: content.height });
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let fill = match self.selection {
+            Some(d) => d,
+            None if self.hovered => self.hover_background,
+            None => Color::TRANSPARENT,
+        };
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            fill,
+        }
+        .draw(renderer);
+        let content = self.content_bounds();
+        renderer.push_clip(content.x, content.y, content.width, content.height);
+        renderer.translate(content.x, content.y);
+        self.content.render(renderer);
+        renderer.translate(-content.x, -content.y);
+        renderer.pop_clip();
+        if let Some(stroke) = self.current {
+            visuals::Outline {
+                x: 0.5f32,
+                y: 0.5f32,
+                width: self.size.width - 1f32,
+                height: self.size.height - 1f32,
+                stroke,
+                thickness: 1f32,
+            }
+            .draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, _context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerEntered => {
+                self.hovered = true;
+                true
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.content_bounds(), &*self.content);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.content_bounds();
+        visitor(bounds, &mut *self.content);
+    }
+}
+
+impl ListViewItem {
+    pub(crate) fn new(content: Box<dyn Component>, padding: Thickness) -> ListViewItem {
+        ListViewItem {
+            id: ComponentId::new(),
+            content,
+            padding,
+            size: Size { width: 0f32, height: 0f32 },
+            hovered: false,
+            selection: None,
+            hover_background: Color::TRANSPARENT,
+            current: None,
+        }
+    }
+
+    fn content_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: self.padding.left,
+            y: self.padding.top,
+            width: f32::max(0f32, self.size.width - self.padding.horizontal()),
+            height: f32::max(0f32, self.size.height - self.padding.vertical()),
+        }
+    }
+}
+
+/// # Description
+/// A scrollable list of items, of which one or more can be selected depending on its
+/// *selection_mode*.
+///
+/// # Remarks
+/// Only the items visible are created, using the *item_template*, allowing lists of any length.
+/// All items have the same *item_height*.
+/// Besides clicking, the arrow keys, Page Up, Page Down, Home and End move the selection,
+/// Space toggles the selection of the current item and Ctrl+A selects all items.
+/// Typing the text of an item selects it.
+pub struct ListView {
+    /// The items of the list. Call *refresh* after they changed.
+    // #[property]
+    pub items: Rc<dyn ItemSource>,
+    /// Creates the components displaying the items, or `None` to display their text.
+    // #[property]
+    pub item_template: Option<ItemTemplate>,
+    // #[property]
+    pub item_height: f32,
+    // #[property]
+    pub selection_mode: SelectionMode,
+    // #[property]
+    pub enabled: bool,
+    /// The padding of every item around the component created by the item template.
+    // #[property]
+    pub padding: Thickness,
+    /// The text style of the default item template.
+    // #[property]
+    pub text_style: TextStyle,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub hover_background: Color,
+    /// The background of selected items while the list view has the keyboard focus.
+    // #[property]
+    pub selection_background: Color,
+    /// The background of selected items while the list view does not have the keyboard focus.
+    // #[property]
+    pub inactive_selection_background: Color,
+    /// Called with the indices of the selected items whenever the user changed the selection.
+    // #[event]
+    pub on_selection_changed: Option<Box<dyn FnMut(&[usize])>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    focused: bool,
+    viewer: ScrollViewer<VirtualizingPanel<ListViewItem>>,
+    selection: SelectionModel,
+    search: String,
+    last_search: Option<Instant>,
+}
+
+impl Component for ListView {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let height = self.items.count() as f32 * self.item_height + 2f32;
+        Size {
+            width: available.width,
+            height: f32::min(height, available.height),
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let items = self.items.clone();
+        let count = items.count();
+        let template = self.item_template.clone().unwrap_or_else(|| text_template(self.text_style.clone()));
+        let padding = self.padding;
+        let panel = &mut self.viewer.content;
+        panel.item_count = count;
+        panel.item_height = self.item_height;
+        panel.create_item = Box::new(move |index| ListViewItem::new(template(&*items, index), padding));
+        self.selection.mode = self.selection_mode;
+        self.selection.coerce(count);
+
+        let inner = self.inner_bounds();
+        self.viewer.arrange(Size { width: inner.width, height: inner.height });
+        let selection = if self.focused { self.selection_background } else { self.inactive_selection_background };
+        let current = self.selection.current().filter(|_| self.focused);
+        let model = &self.selection;
+        for (index, item) in self.viewer.content.realized_mut() {
+            item.selection = Some(selection).filter(|_| model.is_selected(index));
+            item.hover_background = if self.enabled { self.hover_background } else { Color::TRANSPARENT };
+            item.current = if current == Some(index) { Some(self.focused_border) } else { None };
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            fill: self.background,
+        }
+        .draw(renderer);
+        let inner = self.inner_bounds();
+        renderer.translate(inner.x, inner.y);
+        self.viewer.render(renderer);
+        renderer.translate(-inner.x, -inner.y);
+        visuals::Outline {
+            x: 0.5f32,
+            y: 0.5f32,
+            width: self.size.width - 1f32,
+            height: self.size.height - 1f32,
+            stroke: if self.focused { self.focused_border } else { self.border },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        self.selection.mode = self.selection_mode;
+        match event {
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                true
+            }
+            InputEvent::PointerPressed { position, button } if self.enabled => {
+                let index = match self.index_at(*position) {
+                    None => return *button == MouseButton::Left,
+                    Some(d) => d,
+                };
+                let modifiers = context.modifiers();
+                let changed = match button {
+                    MouseButton::Left => {
+                        self.selection.click(index, modifiers.control_key(), modifiers.shift_key())
+                    }
+                    // Right-clicking an unselected item selects it for its context menu
+                    MouseButton::Right if !self.selection.is_selected(index) => {
+                        self.selection.click(index, false, false)
+                    }
+                    MouseButton::Right => false,
+                    _ => return false,
+                };
+                self.selection_changed(changed);
+                true
+            }
+            InputEvent::KeyPressed { key, text, modifiers, .. } if self.enabled => {
+                let searching = self
+                    .last_search
+                    .map_or(false, |d| context.now().duration_since(d) <= SEARCH_TIMEOUT);
+                if !(searching && *key == Key::Named(NamedKey::Space)) && self.handle_key(key, *modifiers) {
+                    return true;
+                }
+                match text {
+                    Some(text)
+                        if !modifiers.control_key()
+                            && !modifiers.alt_key()
+                            && !text.chars().any(char::is_control) =>
+                    {
+                        self.search(text, context.now());
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.inner_bounds(), &self.viewer);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.inner_bounds();
+        visitor(bounds, &mut self.viewer);
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+}
+
+impl ListView {
+    pub fn new() -> ListView {
+        let mut viewer = ScrollViewer::new(VirtualizingPanel::new(Box::new(|_| {
+            ListViewItem::new(Box::new(TextBlock::new()), Thickness::uniform(0f32))
+        })));
+        viewer.horizontal_scroll_bar_visibility = ScrollBarVisibility::Disabled;
+        ListView {
+            id: ComponentId::new(),
+            items: Rc::new(Vec::<String>::new()),
+            item_template: None,
+            item_height: 24f32,
+            selection_mode: SelectionMode::Single,
+            enabled: true,
+            padding: Thickness::uniform(4f32),
+            text_style: TextStyle::new(),
+            background: Color::WHITE,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            hover_background: Color::GAINSBORO,
+            selection_background: Color::LIGHT_SKY_BLUE,
+            inactive_selection_background: Color::LIGHT_GRAY,
+            on_selection_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            focused: false,
+            viewer,
+            selection: SelectionModel::new(SelectionMode::Single),
+            search: String::new(),
+            last_search: None,
+        }
+    }
+
+    /// # Description
+    /// The indices of the selected items, in ascending order.
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.selection.selected()
+    }
+
+    /// # Description
+    /// The index of the first selected item, if any.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selection.selected().first().copied()
+    }
+
+    /// # Description
+    /// Selects the item at *index* only, or clears the selection if `None`, and scrolls it
+    /// into view.
+    ///
+    /// # Remarks
+    /// Does not call *on_selection_changed*, which is reserved for changes made by the user.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selection.select(index);
+        if let Some(index) = index {
+            self.scroll_into_view(index);
+        }
+    }
+
+    /// # Description
+    /// Scrolls the list by the least distance making the item at *index* visible.
+    pub fn scroll_into_view(&mut self, index: usize) {
+        let bounds = self.viewer.content.item_bounds(index);
+        self.viewer.scroll_into_view(bounds);
+    }
+
+    /// # Description
+    /// Creates the components of the visible items again, after the items changed.
+    ///
+    /// # Remarks
+    /// Selected items beyond the new number of items are deselected.
+    pub fn refresh(&mut self) {
+        self.viewer.content.refresh();
+        self.selection.coerce(self.items.count());
+    }
+
+    fn handle_key(&mut self, key: &Key, modifiers: ModifiersState) -> bool {
+        let count = self.items.count();
+        if count == 0 {
+            return false;
+        }
+        let current = self.selection.current();
+        let page = self.viewer.content.page_size();
+        let target = match key {
+            Key::Named(NamedKey::ArrowUp) => current.map_or(0, |d| d.saturating_sub(1)),
+            Key::Named(NamedKey::ArrowDown) => current.map_or(0, |d| usize::min(d + 1, count - 1)),
+            Key::Named(NamedKey::PageUp) => current.map_or(0, |d| d.saturating_sub(page)),
+            Key::Named(NamedKey::PageDown) => current.map_or(0, |d| usize::min(d + page, count - 1)),
+            Key::Named(NamedKey::Home) => 0,
+            Key::Named(NamedKey::End) => count - 1,
+            Key::Named(NamedKey::Space) => {
+                let changed = self.selection.toggle_current();
+                self.selection_changed(changed);
+                return current.is_some();
+            }
+            Key::Character(text) if modifiers.control_key() && text.eq_ignore_ascii_case("a") => {
+                let changed = self.selection.select_all(count);
+                self.selection_changed(changed);
+                return true;
+            }
+            _ => return false,
+        };
+        let changed = self.selection.navigate(target, modifiers.control_key(), modifiers.shift_key());
+        self.selection_changed(changed);
+        true
+    }
+
+    /// Selects the next item starting with the text typed so far.
+    fn search(&mut self, text: &str, now: Instant) {
+        if self.last_search.map_or(true, |d| now.duration_since(d) > SEARCH_TIMEOUT) {
+            self.search.clear();
+        }
+        self.last_search = Some(now);
+        self.search.push_str(&text.to_lowercase());
+
+        let current = self.selection.current();
+        // Repeating a single character cycles through the items starting with it
+        let mut characters = self.search.chars();
+        let first = characters.next();
+        let (prefix, start) = match first {
+            Some(d) if characters.all(|c| c == d) => (d.to_string(), current.map_or(0, |d| d + 1)),
+            _ => (self.search.clone(), current.unwrap_or(0)),
+        };
+        let count = self.items.count();
+        let found = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|d| self.items.text(*d).to_lowercase().starts_with(&prefix));
+        if let Some(index) = found {
+            let changed = self.selection.navigate(index, false, false);
+            self.selection_changed(changed);
+        }
+    }
+
+    /// Scrolls the current item into view and notifies about a changed selection.
+    fn selection_changed(&mut self, changed: bool) {
+        if let Some(current) = self.selection.current() {
+            self.scroll_into_view(current);
+        }
+        if !changed {
+            return;
+        }
+        let selected = self.selection.selected();
+        if let Some(callback) = self.on_selection_changed.as_mut() {
+            callback(&selected);
+        }
+    }
+
+    fn index_at(&self, position: Point<f32>) -> Option<usize> {
+        let inner = self.inner_bounds();
+        let viewport = self.viewer.viewport();
+        let x = position.x - inner.x;
+        let y = position.y - inner.y;
+        if x < 0f32 || y < 0f32 || x >= viewport.width || y >= viewport.height {
+            return None;
+        }
+        self.viewer.content.index_at(y + self.viewer.offset().y)
+    }
+
+    fn inner_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 1f32,
+            y: 1f32,
+            width: f32::max(0f32, self.size.width - 2f32),
+            height: f32::max(0f32, self.size.height - 2f32),
+        }
+    }
+}
+
+/// The default item template, displaying the text of the items.
+fn text_template(text_style: TextStyle) -> ItemTemplate {
+    Rc::new(move |items, index| {
+        let mut text_block = TextBlock::new();
+        text_block.text = items.text(index);
+        text_block.text_style = text_style.clone();
+        text_block.trimming = TextTrimming::Ellipsis;
+        Box::new(text_block)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use std::cell::RefCell;
+
+    fn list_view(mode: SelectionMode) -> (ListView, Rc<RefCell<Vec<usize>>>) {
+        let changed = Rc::new(RefCell::new(vec![]));
+        let mut list_view = ListView::new();
+        list_view.items = Rc::new((0..10).map(|d| format!("Item {}", d)).collect::<Vec<_>>());
+        list_view.selection_mode = mode;
+        let callback = changed.clone();
+        list_view.on_selection_changed = Some(Box::new(move |d| *callback.borrow_mut() = d.to_vec()));
+        list_view.arrange(Size { width: 200f32, height: 300f32 });
+        (list_view, changed)
+    }
+
+    fn send(list_view: &mut ListView, event: InputEvent, modifiers: ModifiersState) -> bool {
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, modifiers);
+        list_view.handle_event(&event, &mut context)
+    }
+
+    fn click(list_view: &mut ListView, index: usize, modifiers: ModifiersState) {
+        let inner = list_view.inner_bounds();
+        let bounds = list_view.viewer.content.item_bounds(index);
+        let position = Point { x: inner.x + 10f32, y: inner.y + bounds.y + bounds.height / 2f32 };
+        send(list_view, InputEvent::PointerPressed { position, button: MouseButton::Left }, modifiers);
+    }
+
+    fn press(list_view: &mut ListView, key: NamedKey, modifiers: ModifiersState) -> bool {
+        let event = InputEvent::KeyPressed { key: Key::Named(key), text: None, modifiers, repeat: false };
+        send(list_view, event, modifiers)
+    }
+
+    #[test]
+    fn test_single_selection() {
+        let (mut list_view, changed) = list_view(SelectionMode::Single);
+        click(&mut list_view, 2, ModifiersState::empty());
+        click(&mut list_view, 4, ModifiersState::SHIFT);
+        assert_eq!(list_view.selected_indices(), vec![4]);
+        assert_eq!(*changed.borrow(), vec![4]);
+        // Ctrl-clicking the selected item deselects it
+        click(&mut list_view, 4, ModifiersState::CONTROL);
+        assert_eq!(list_view.selected_indices(), vec![]);
+        press(&mut list_view, NamedKey::ArrowDown, ModifiersState::empty());
+        assert_eq!(list_view.selected_indices(), vec![5]);
+        press(&mut list_view, NamedKey::End, ModifiersState::SHIFT);
+        assert_eq!(list_view.selected_indices(), vec![9]);
+        let all = InputEvent::KeyPressed {
+            key: Key::Character("a".into()),
+            text: None,
+            modifiers: ModifiersState::CONTROL,
+            repeat: false,
+        };
+        send(&mut list_view, all, ModifiersState::CONTROL);
+        assert_eq!(list_view.selected_indices(), vec![9]);
+    }
+
+    #[test]
+    fn test_multiple_selection() {
+        let (mut list_view, changed) = list_view(SelectionMode::Multiple);
+        click(&mut list_view, 1, ModifiersState::empty());
+        click(&mut list_view, 3, ModifiersState::empty());
+        assert_eq!(list_view.selected_indices(), vec![1, 3]);
+        click(&mut list_view, 1, ModifiersState::empty());
+        assert_eq!(*changed.borrow(), vec![3]);
+        // The arrow keys only move the current item, Space toggles it
+        press(&mut list_view, NamedKey::ArrowDown, ModifiersState::empty());
+        assert_eq!(list_view.selected_indices(), vec![3]);
+        press(&mut list_view, NamedKey::Space, ModifiersState::empty());
+        assert_eq!(list_view.selected_indices(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_extended_selection() {
+        let (mut list_view, changed) = list_view(SelectionMode::Extended);
+        click(&mut list_view, 2, ModifiersState::empty());
+        click(&mut list_view, 5, ModifiersState::SHIFT);
+        assert_eq!(list_view.selected_indices(), vec![2, 3, 4, 5]);
+        click(&mut list_view, 8, ModifiersState::CONTROL);
+        assert_eq!(*changed.borrow(), vec![2, 3, 4, 5, 8]);
+        click(&mut list_view, 0, ModifiersState::empty());
+        assert_eq!(list_view.selected_indices(), vec![0]);
+        press(&mut list_view, NamedKey::ArrowDown, ModifiersState::SHIFT);
+        press(&mut list_view, NamedKey::ArrowDown, ModifiersState::SHIFT);
+        assert_eq!(list_view.selected_indices(), vec![0, 1, 2]);
+        press(&mut list_view, NamedKey::ArrowDown, ModifiersState::empty());
+        assert_eq!(list_view.selected_indices(), vec![3]);
+        let all = InputEvent::KeyPressed {
+            key: Key::Character("a".into()),
+            text: None,
+            modifiers: ModifiersState::CONTROL,
+            repeat: false,
+        };
+        send(&mut list_view, all, ModifiersState::CONTROL);
+        assert_eq!(list_view.selected_indices().len(), 10);
+    }
+}
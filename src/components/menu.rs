@@ -0,0 +1,877 @@
+use std::rc::Rc;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::commands::{Command, KeyGesture};
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::input::{EventContext, InputEvent};
+use crate::overlay::{Placement, PopupPlacement};
+use crate::text::{self, TextStyle};
+use crate::visuals::{self, PathCommand};
+
+/// The width of the column displaying the icon or check mark of menu items.
+const ICON_COLUMN: f32 = 24f32;
+const ICON_SIZE: f32 = 16f32;
+/// The width of the column displaying the arrow of menu items opening a submenu.
+const ARROW_COLUMN: f32 = 20f32;
+/// The space between the text and the key gesture of menu items.
+const GESTURE_SPACING: f32 = 24f32;
+const SEPARATOR_HEIGHT: f32 = 7f32;
+
+/// # Description
+/// The appearance of a menu, shared by its items and submenus.
+#[derive(Clone, Debug)]
+pub struct MenuStyle {
+    pub text_style: TextStyle,
+    /// The space around the text of an item.
+    pub padding: Thickness,
+    pub background: Color,
+    pub border: Color,
+    /// The background of the highlighted item.
+    pub highlight_background: Color,
+    pub disabled_foreground: Color,
+    pub separator: Color,
+}
+
+impl MenuStyle {
+    pub fn new() -> MenuStyle {
+        MenuStyle {
+            text_style: TextStyle::new(),
+            padding: Thickness { left: 8f32, top: 4f32, right: 8f32, bottom: 4f32 },
+            background: Color::WHITE,
+            border: Color::GRAY,
+            highlight_background: Color::GAINSBORO,
+            disabled_foreground: Color::GRAY,
+            separator: Color::LIGHT_GRAY,
+        }
+    }
+}
+
+/// # Description
+/// What a menu item does when clicked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MenuItemKind {
+    /// An item invoking its action or opening its submenu.
+    Normal,
+    /// An item toggling whether it is checked.
+    Check,
+    /// An item checking itself and unchecking the other items of the same *group* in its menu.
+    Radio { group: String },
+    /// A line separating groups of items.
+    Separator,
+}
+
+/// The result of a key press handled by a menu.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum MenuKey {
+    Handled,
+    Unhandled,
+    /// An item was activated, the menu should be closed.
+    Activated,
+}
+
+/// # Description
+/// Splits the text of a menu item into the displayed text and its mnemonic.
+///
+/// # Returns
+/// The text without mnemonic markers and the byte offset and lowercase character of the
+/// mnemonic within it, if any.
+///
+/// # Remarks
+/// An underscore marks the following character as the mnemonic, two underscores display a
+/// single one.
+pub(crate) fn parse_mnemonic(text: &str) -> (String, Option<(usize, char)>) {
+    let mut display = String::with_capacity(text.len());
+    let mut mnemonic = None;
+    let mut chars = text.chars();
+    while let Some(d) = chars.next() {
+        if d != '_' {
+            display.push(d);
+            continue;
+        }
+        match chars.next() {
+            None => display.push('_'),
+            Some('_') => display.push('_'),
+            Some(d) => {
+                if mnemonic.is_none() {
+                    mnemonic = Some((display.len(), d.to_lowercase().next().unwrap_or(d)));
+                }
+                display.push(d);
+            }
+        }
+    }
+    (display, mnemonic)
+}
+
+/// # Description
+/// An entry of a [`Menu`] or a [`crate::components::MenuBar`], optionally opening a submenu.
+pub struct MenuItem {
+    /// The text of the item.
+    /// An underscore marks the following character as the mnemonic of the item (eg. `_File`),
+    /// two underscores display a single one.
+    // #[property]
+    pub text: String,
+    // #[property]
+    pub kind: MenuItemKind,
+    /// Whether a check or radio item is checked.
+    // #[property]
+    pub is_checked: bool,
+    /// An optional icon, displayed in front of the text.
+    // #[property]
+    pub icon: Option<Box<dyn Component>>,
+    /// The key gesture displayed next to the text, eg. the gesture bound to *command*.
+    // #[property]
+    pub gesture: Option<KeyGesture>,
+    /// Whether the item can be interacted with.
+    // #[property]
+    pub enabled: bool,
+    /// The command executed when the item is clicked.
+    /// The item is disabled while the command cannot be executed.
+    // #[property]
+    pub command: Option<Rc<dyn Command>>,
+    /// The submenu opened by the item, if it has any items.
+    // #[property]
+    pub submenu: Menu,
+    /// Called when the item was clicked, after a check or radio item changed *is_checked*.
+    // #[event]
+    pub on_click: Option<Box<dyn FnMut()>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    /// The style of the menu displaying the item.
+    pub(crate) style: MenuStyle,
+    /// Whether the item is displayed by a menu bar rather than a menu.
+    pub(crate) top_level: bool,
+    pub(crate) highlighted: bool,
+    pub(crate) is_open: bool,
+    /// The widths of the text and the gesture column of the menu displaying the item.
+    pub(crate) columns: (f32, f32),
+}
+
+impl Component for MenuItem {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, _available: Size<f32>) -> Size<f32> {
+        if self.top_level {
+            Size { width: self.header_width(&self.style), height: self.row_height(&self.style) }
+        } else {
+            Size {
+                width: row_width(self.columns.0, self.columns.1),
+                height: self.row_height(&self.style),
+            }
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        if let Some(icon) = self.icon.as_mut() {
+            icon.arrange(Size { width: ICON_SIZE, height: ICON_SIZE });
+        }
+        self.submenu.style = self.style.clone();
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        if self.kind == MenuItemKind::Separator {
+            visuals::Rectangle {
+                x: ICON_COLUMN,
+                y: (self.size.height / 2f32).floor(),
+                width: f32::max(0f32, self.size.width - ICON_COLUMN - 4f32),
+                height: 1f32,
+                fill: self.style.separator,
+            }
+            .draw(renderer);
+            return;
+        }
+        let enabled = self.is_enabled();
+        if enabled && (self.highlighted || self.is_open) {
+            visuals::Rectangle {
+                x: 0f32,
+                y: 0f32,
+                width: self.size.width,
+                height: self.size.height,
+                fill: self.style.highlight_background,
+            }
+            .draw(renderer);
+        }
+
+        let mut style = self.style.text_style.clone();
+        if !enabled {
+            style.color = self.style.disabled_foreground;
+        }
+        let metrics = text::text_metrics();
+        let font = metrics.font_metrics(&style);
+        let baseline = (self.size.height - font.line_height) / 2f32 + font.ascent;
+        let text_x = if self.top_level { self.style.padding.left } else { ICON_COLUMN };
+        let (text, mnemonic) = parse_mnemonic(&self.text);
+        visuals::Text { x: text_x, y: baseline, text: &text, style: &style }.draw(renderer);
+        if let Some((offset, _)) = mnemonic {
+            let character = text[offset..].chars().next().map_or(0, |d| d.len_utf8());
+            visuals::Rectangle {
+                x: text_x + metrics.measure_text(&text[..offset], &style),
+                y: baseline + 1f32,
+                width: metrics.measure_text(&text[offset..offset + character], &style),
+                height: 1f32,
+                fill: style.color,
+            }
+            .draw(renderer);
+        }
+        if self.top_level {
+            return;
+        }
+
+        let center = Point { x: ICON_COLUMN / 2f32, y: self.size.height / 2f32 };
+        match (&self.icon, &self.kind) {
+            (Some(icon), _) => {
+                let bounds = self.icon_bounds();
+                renderer.translate(bounds.x, bounds.y);
+                icon.render(renderer);
+                renderer.translate(-bounds.x, -bounds.y);
+            }
+            (None, MenuItemKind::Check) if self.is_checked => {
+                let point = |x: f32, y: f32| Point { x: center.x + x, y: center.y + y };
+                visuals::Path {
+                    commands: &[
+                        PathCommand::MoveTo(point(-4f32, 0f32)),
+                        PathCommand::LineTo(point(-1.5f32, 3f32)),
+                        PathCommand::LineTo(point(4f32, -3.5f32)),
+                    ],
+                    fill: Color::TRANSPARENT,
+                    stroke: style.color,
+                    thickness: 1.5f32,
+                }
+                .draw(renderer);
+            }
+            (None, MenuItemKind::Radio { .. }) if self.is_checked => {
+                visuals::RoundedRectangle {
+                    x: center.x - 3f32,
+                    y: center.y - 3f32,
+                    width: 6f32,
+                    height: 6f32,
+                    radius: 3f32,
+                    fill: style.color,
+                    stroke: Color::TRANSPARENT,
+                    thickness: 0f32,
+                }
+                .draw(renderer);
+            }
+            _ => {}
+        }
+        if let Some(gesture) = &self.gesture {
+            visuals::Text {
+                x: ICON_COLUMN + self.columns.0 + GESTURE_SPACING,
+                y: baseline,
+                text: &gesture.to_string(),
+                style: &style,
+            }
+            .draw(renderer);
+        }
+        if self.has_submenu() {
+            let x = self.size.width - ARROW_COLUMN / 2f32;
+            visuals::Path {
+                commands: &[
+                    PathCommand::MoveTo(Point { x: x - 2f32, y: center.y - 4f32 }),
+                    PathCommand::LineTo(Point { x: x + 2f32, y: center.y }),
+                    PathCommand::LineTo(Point { x: x - 2f32, y: center.y + 4f32 }),
+                ],
+                fill: style.color,
+                stroke: Color::TRANSPARENT,
+                thickness: 0f32,
+            }
+            .draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, _context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::LightDismiss => {
+                self.close();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if let Some(icon) = self.icon.as_ref() {
+            visitor(self.icon_bounds(), &**icon);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.icon_bounds();
+        if let Some(icon) = self.icon.as_mut() {
+            visitor(bounds, &mut **icon);
+        }
+    }
+
+    fn visit_popups(&self, visitor: &mut dyn FnMut(PopupPlacement, &dyn Component)) {
+        if self.is_open && self.has_submenu() {
+            visitor(self.submenu_placement(), &self.submenu);
+        }
+    }
+
+    fn visit_popups_mut(&mut self, visitor: &mut dyn FnMut(PopupPlacement, &mut dyn Component)) {
+        if self.is_open && self.has_submenu() {
+            visitor(self.submenu_placement(), &mut self.submenu);
+        }
+    }
+}
+
+impl MenuItem {
+    pub fn new() -> MenuItem {
+        MenuItem {
+            id: ComponentId::new(),
+            text: String::new(),
+            kind: MenuItemKind::Normal,
+            is_checked: false,
+            icon: None,
+            gesture: None,
+            enabled: true,
+            command: None,
+            submenu: Menu::new(),
+            on_click: None,
+            size: Size { width: 0f32, height: 0f32 },
+            style: MenuStyle::new(),
+            top_level: false,
+            highlighted: false,
+            is_open: false,
+            columns: (0f32, 0f32),
+        }
+    }
+
+    /// # Description
+    /// Creates a line separating groups of items.
+    pub fn separator() -> MenuItem {
+        let mut item = MenuItem::new();
+        item.kind = MenuItemKind::Separator;
+        item
+    }
+
+    pub fn has_submenu(&self) -> bool {
+        !self.submenu.items.is_empty()
+    }
+
+    /// # Description
+    /// Whether the item can be highlighted and clicked.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+            && self.kind != MenuItemKind::Separator
+            && self.command.as_ref().map_or(true, |command| command.can_execute())
+    }
+
+    pub(crate) fn mnemonic(&self) -> Option<char> {
+        parse_mnemonic(&self.text).1.map(|(_, d)| d)
+    }
+
+    /// # Description
+    /// Opens the submenu of the item.
+    ///
+    /// # Parameters
+    /// - **keyboard**: Whether the submenu was opened using the keyboard, highlighting its
+    ///                 first item.
+    pub(crate) fn open(&mut self, keyboard: bool) {
+        if !self.has_submenu() {
+            return;
+        }
+        self.is_open = true;
+        self.submenu.set_highlighted(None);
+        if keyboard {
+            self.submenu.move_highlight(true);
+        }
+    }
+
+    /// # Description
+    /// Closes the submenu of the item and all submenus opened from it.
+    pub(crate) fn close(&mut self) {
+        self.is_open = false;
+        self.submenu.close();
+    }
+
+    /// # Description
+    /// Invokes the action of the item.
+    pub(crate) fn click(&mut self) {
+        if let Some(on_click) = self.on_click.as_mut() {
+            on_click();
+        }
+        if let Some(command) = self.command.as_ref() {
+            if command.can_execute() {
+                command.execute();
+            }
+        }
+    }
+
+    pub(crate) fn row_height(&self, style: &MenuStyle) -> f32 {
+        if self.kind == MenuItemKind::Separator {
+            return SEPARATOR_HEIGHT;
+        }
+        let line_height = text::text_metrics().font_metrics(&style.text_style).line_height;
+        f32::max(line_height, ICON_SIZE) + style.padding.vertical()
+    }
+
+    /// # Description
+    /// The width of the item when displayed by a menu bar.
+    pub(crate) fn header_width(&self, style: &MenuStyle) -> f32 {
+        self.text_width(style) + style.padding.horizontal()
+    }
+
+    pub(crate) fn text_width(&self, style: &MenuStyle) -> f32 {
+        match self.kind {
+            MenuItemKind::Separator => 0f32,
+            _ => text::text_metrics().measure_text(&parse_mnemonic(&self.text).0, &style.text_style),
+        }
+    }
+
+    pub(crate) fn gesture_width(&self, style: &MenuStyle) -> f32 {
+        match &self.gesture {
+            None => 0f32,
+            Some(gesture) => text::text_metrics().measure_text(&gesture.to_string(), &style.text_style),
+        }
+    }
+
+    fn icon_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: (ICON_COLUMN - ICON_SIZE) / 2f32,
+            y: (self.size.height - ICON_SIZE) / 2f32,
+            width: ICON_SIZE,
+            height: ICON_SIZE,
+        }
+    }
+
+    /// Submenus of a menu bar open below the item, others next to it.
+    fn submenu_placement(&self) -> PopupPlacement {
+        let anchor = Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height };
+        if self.top_level {
+            PopupPlacement::new(anchor, Placement::Bottom)
+        } else {
+            // Align the first item of the submenu with the item, compensating for the border
+            PopupPlacement::new(Rectangle { y: -1f32, ..anchor }, Placement::Right)
+        }
+    }
+}
+
+/// The width of an item of a menu having text and gesture columns of the given widths.
+fn row_width(text: f32, gesture: f32) -> f32 {
+    let gesture = if gesture > 0f32 { GESTURE_SPACING + gesture } else { 0f32 };
+    ICON_COLUMN + text + gesture + ARROW_COLUMN
+}
+
+/// # Description
+/// A list of menu items, shown as a popup by menu items, menu bars and context menus.
+///
+/// # Remarks
+/// The menu is styled by the item, menu bar or context menu showing it.
+pub struct Menu {
+    // #[property]
+    pub items: Vec<MenuItem>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    pub(crate) style: MenuStyle,
+    bounds: Vec<Rectangle<f32>>,
+    highlighted: Option<usize>,
+    /// Whether an item was clicked using the pointer and the menu should be closed.
+    activated: bool,
+}
+
+impl Component for Menu {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, _available: Size<f32>) -> Size<f32> {
+        let (text, gesture) = self.columns();
+        Size {
+            width: row_width(text, gesture) + 2f32,
+            height: self.items.iter().map(|d| d.row_height(&self.style)).sum::<f32>() + 2f32,
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let columns = self.columns();
+        let width = f32::max(0f32, given.width - 2f32);
+        let mut y = 1f32;
+        self.bounds.clear();
+        for (index, item) in self.items.iter_mut().enumerate() {
+            let height = item.row_height(&self.style);
+            item.style = self.style.clone();
+            item.top_level = false;
+            item.columns = columns;
+            item.highlighted = self.highlighted == Some(index);
+            item.arrange(Size { width, height });
+            self.bounds.push(Rectangle { x: 1f32, y, width, height });
+            y += height;
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            fill: self.style.background,
+        }
+        .draw(renderer);
+        for (item, bounds) in self.items.iter().zip(self.bounds.iter()) {
+            renderer.translate(bounds.x, bounds.y);
+            item.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+        visuals::Outline {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            stroke: self.style.border,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, _context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerMoved { position } if self.contains(*position) => {
+                if let Some(index) = self.item_at(*position) {
+                    self.set_highlighted(Some(index));
+                    if self.open_index() != Some(index) {
+                        self.close_submenus();
+                        self.items[index].open(false);
+                    }
+                }
+                true
+            }
+            InputEvent::PointerPressed { position, .. } => self.contains(*position),
+            InputEvent::PointerReleased { position, button: MouseButton::Left | MouseButton::Right }
+                if self.contains(*position) =>
+            {
+                // A click in an overlapping submenu was handled already
+                if self.is_activated() {
+                    return false;
+                }
+                match self.item_at(*position) {
+                    Some(index) if !self.items[index].has_submenu() => {
+                        self.activate(index);
+                        // The component showing the menu closes it, as the event bubbles up
+                        self.activated = true;
+                        false
+                    }
+                    _ => true,
+                }
+            }
+            InputEvent::PointerLeft => {
+                if self.open_index().is_none() {
+                    self.set_highlighted(None);
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        for (item, bounds) in self.items.iter().zip(self.bounds.iter()) {
+            visitor(*bounds, item);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        for (item, bounds) in self.items.iter_mut().zip(self.bounds.iter()) {
+            visitor(*bounds, item);
+        }
+    }
+}
+
+impl Menu {
+    pub fn new() -> Menu {
+        Menu {
+            id: ComponentId::new(),
+            items: vec![],
+            size: Size { width: 0f32, height: 0f32 },
+            style: MenuStyle::new(),
+            bounds: vec![],
+            highlighted: None,
+            activated: false,
+        }
+    }
+
+    /// # Description
+    /// Handles a key press while the menu is open, forwarding it to the open submenu first.
+    pub(crate) fn key_pressed(&mut self, key: &Key) -> MenuKey {
+        if let Some(index) = self.open_index() {
+            return match self.items[index].submenu.key_pressed(key) {
+                MenuKey::Unhandled => match key {
+                    Key::Named(NamedKey::ArrowLeft) | Key::Named(NamedKey::Escape) => {
+                        self.items[index].close();
+                        MenuKey::Handled
+                    }
+                    _ => MenuKey::Unhandled,
+                },
+                result => result,
+            };
+        }
+        match key {
+            Key::Named(NamedKey::ArrowDown) => self.move_highlight(true),
+            Key::Named(NamedKey::ArrowUp) => self.move_highlight(false),
+            Key::Named(NamedKey::Home) => {
+                self.set_highlighted(None);
+                self.move_highlight(true);
+            }
+            Key::Named(NamedKey::End) => {
+                self.set_highlighted(None);
+                self.move_highlight(false);
+            }
+            Key::Named(NamedKey::ArrowRight) => match self.highlighted {
+                Some(index) if self.items[index].has_submenu() => self.items[index].open(true),
+                _ => return MenuKey::Unhandled,
+            },
+            Key::Named(NamedKey::Enter) | Key::Named(NamedKey::Space) => match self.highlighted {
+                Some(index) => return self.invoke(index),
+                None => {}
+            },
+            Key::Character(text) => {
+                let mnemonic = text.chars().next().and_then(|d| d.to_lowercase().next());
+                let index = self
+                    .items
+                    .iter()
+                    .position(|d| d.is_enabled() && mnemonic.is_some() && d.mnemonic() == mnemonic);
+                match index {
+                    Some(index) => {
+                        self.set_highlighted(Some(index));
+                        return self.invoke(index);
+                    }
+                    None => return MenuKey::Unhandled,
+                }
+            }
+            _ => return MenuKey::Unhandled,
+        }
+        MenuKey::Handled
+    }
+
+    /// # Description
+    /// Whether an item of the menu or one of its submenus was clicked using the pointer,
+    /// resetting the state.
+    pub(crate) fn take_activated(&mut self) -> bool {
+        let mut activated = std::mem::take(&mut self.activated);
+        for item in self.items.iter_mut() {
+            activated |= item.submenu.take_activated();
+        }
+        activated
+    }
+
+    /// # Description
+    /// Closes all submenus and removes the highlight.
+    pub(crate) fn close(&mut self) {
+        self.close_submenus();
+        self.set_highlighted(None);
+    }
+
+    /// # Description
+    /// Highlights the next (or previous if not *forward*) enabled item, wrapping around.
+    pub(crate) fn move_highlight(&mut self, forward: bool) {
+        let count = self.items.len();
+        let mut index = self.highlighted;
+        for _ in 0..count {
+            let next = match (index, forward) {
+                (None, true) => 0,
+                (None, false) => count - 1,
+                (Some(d), true) => (d + 1) % count,
+                (Some(d), false) => (d + count - 1) % count,
+            };
+            if self.items[next].is_enabled() {
+                self.set_highlighted(Some(next));
+                return;
+            }
+            index = Some(next);
+        }
+    }
+
+    pub(crate) fn set_highlighted(&mut self, index: Option<usize>) {
+        self.highlighted = index;
+        for (i, item) in self.items.iter_mut().enumerate() {
+            item.highlighted = index == Some(i);
+        }
+    }
+
+    fn open_index(&self) -> Option<usize> {
+        self.items.iter().position(|d| d.is_open)
+    }
+
+    fn close_submenus(&mut self) {
+        for item in self.items.iter_mut() {
+            item.close();
+        }
+    }
+
+    /// Opens the submenu of the item at *index* or activates it.
+    fn invoke(&mut self, index: usize) -> MenuKey {
+        if !self.items[index].is_enabled() {
+            return MenuKey::Handled;
+        }
+        if self.items[index].has_submenu() {
+            self.items[index].open(true);
+            MenuKey::Handled
+        } else {
+            self.activate(index);
+            MenuKey::Activated
+        }
+    }
+
+    fn activate(&mut self, index: usize) {
+        match self.items[index].kind.clone() {
+            MenuItemKind::Check => self.items[index].is_checked = !self.items[index].is_checked,
+            MenuItemKind::Radio { group } => {
+                for (i, item) in self.items.iter_mut().enumerate() {
+                    if item.kind == (MenuItemKind::Radio { group: group.clone() }) {
+                        item.is_checked = i == index;
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.items[index].click();
+    }
+
+    fn is_activated(&self) -> bool {
+        self.activated || self.items.iter().any(|d| d.submenu.is_activated())
+    }
+
+    fn columns(&self) -> (f32, f32) {
+        self.items.iter().fold((0f32, 0f32), |(text, gesture), d| {
+            (
+                f32::max(text, d.text_width(&self.style)),
+                f32::max(gesture, d.gesture_width(&self.style)),
+            )
+        })
+    }
+
+    fn contains(&self, position: Point<f32>) -> bool {
+        Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height }.contains(position)
+    }
+
+    fn item_at(&self, position: Point<f32>) -> Option<usize> {
+        self.bounds
+            .iter()
+            .position(|d| d.contains(position))
+            .filter(|d| self.items[*d].is_enabled())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, kind: MenuItemKind) -> MenuItem {
+        let mut item = MenuItem::new();
+        item.text = text.to_string();
+        item.kind = kind;
+        item
+    }
+
+    #[test]
+    fn test_parse_mnemonic() {
+        assert_eq!(parse_mnemonic("_File"), ("File".to_string(), Some((0, 'f'))));
+        assert_eq!(parse_mnemonic("Save _As"), ("Save As".to_string(), Some((5, 'a'))));
+        assert_eq!(parse_mnemonic("a__b_c"), ("a_bc".to_string(), Some((3, 'c'))));
+        assert_eq!(parse_mnemonic("Exit_"), ("Exit_".to_string(), None));
+    }
+
+    #[test]
+    fn test_menu_navigation() {
+        let mut menu = Menu::new();
+        menu.items.push(item("_One", MenuItemKind::Normal));
+        menu.items.push(MenuItem::separator());
+        let mut disabled = item("_Two", MenuItemKind::Normal);
+        disabled.enabled = false;
+        menu.items.push(disabled);
+        menu.items.push(item("T_hree", MenuItemKind::Check));
+
+        let down = Key::Named(NamedKey::ArrowDown);
+        assert_eq!(menu.key_pressed(&down), MenuKey::Handled);
+        assert_eq!(menu.highlighted, Some(0));
+        // The separator and the disabled item are skipped
+        menu.key_pressed(&down);
+        assert_eq!(menu.highlighted, Some(3));
+        menu.key_pressed(&down);
+        assert_eq!(menu.highlighted, Some(0));
+        menu.key_pressed(&Key::Named(NamedKey::ArrowUp));
+        assert_eq!(menu.highlighted, Some(3));
+
+        assert_eq!(menu.key_pressed(&Key::Named(NamedKey::Enter)), MenuKey::Activated);
+        assert!(menu.items[3].is_checked);
+        assert_eq!(menu.key_pressed(&Key::Character("t".into())), MenuKey::Unhandled);
+        assert_eq!(menu.key_pressed(&Key::Character("H".into())), MenuKey::Activated);
+        assert!(!menu.items[3].is_checked);
+    }
+
+    #[test]
+    fn test_menu_radio_and_submenu() {
+        let group = || MenuItemKind::Radio { group: "size".to_string() };
+        let mut menu = Menu::new();
+        menu.items.push(item("_Small", group()));
+        menu.items.push(item("_Large", group()));
+        let mut more = item("_More", MenuItemKind::Normal);
+        more.submenu.items.push(item("_Other", group()));
+        menu.items.push(more);
+
+        menu.key_pressed(&Key::Character("s".into()));
+        menu.key_pressed(&Key::Character("l".into()));
+        assert!(!menu.items[0].is_checked);
+        assert!(menu.items[1].is_checked);
+
+        assert_eq!(menu.key_pressed(&Key::Character("m".into())), MenuKey::Handled);
+        assert!(menu.items[2].is_open);
+        assert_eq!(menu.items[2].submenu.highlighted, Some(0));
+        // Radio groups are scoped to their menu
+        assert_eq!(menu.key_pressed(&Key::Named(NamedKey::Enter)), MenuKey::Activated);
+        assert!(menu.items[1].is_checked);
+        assert!(menu.items[2].submenu.items[0].is_checked);
+        assert_eq!(menu.key_pressed(&Key::Named(NamedKey::ArrowLeft)), MenuKey::Handled);
+        assert!(!menu.items[2].is_open);
+    }
+
+    #[test]
+    fn test_submenu_hover() {
+        use crate::pointer::PointerState;
+        use std::time::Instant;
+        use winit::keyboard::ModifiersState;
+
+        let mut menu = Menu::new();
+        let mut more = item("_More", MenuItemKind::Normal);
+        more.submenu.items.push(item("_Other", MenuItemKind::Normal));
+        menu.items.push(more);
+        menu.items.push(item("_Close", MenuItemKind::Normal));
+        let size = menu.measure(Size { width: 500f32, height: 500f32 });
+        menu.arrange(size);
+        let hover = |menu: &mut Menu, index: usize| {
+            let bounds = menu.bounds[index];
+            let position = Point { x: bounds.x + bounds.width / 2f32, y: bounds.y + bounds.height / 2f32 };
+            let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+            menu.handle_event(&InputEvent::PointerMoved { position }, &mut context);
+        };
+
+        hover(&mut menu, 0);
+        assert!(menu.items[0].is_open);
+        // Moving the pointer to another item closes the submenu
+        hover(&mut menu, 1);
+        assert!(!menu.items[0].is_open);
+        assert_eq!(menu.highlighted, Some(1));
+
+        // Pressing outside of the submenu dismisses it
+        hover(&mut menu, 0);
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+        menu.items[0].handle_event(&InputEvent::LightDismiss, &mut context);
+        assert!(!menu.items[0].is_open);
+    }
+}
@@ -0,0 +1,319 @@
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::menu::{MenuItem, MenuKey, MenuStyle};
+use crate::data::{ComponentId, Point, Rectangle, Size};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::visuals;
+
+/// # Description
+/// A horizontal bar of menu items, usually at the top of a window, opening their submenus
+/// below them.
+///
+/// # Remarks
+/// Pressing and releasing Alt moves the keyboard focus to the menu bar, Alt together with the
+/// mnemonic of an item opens its submenu. While the menu bar has the focus, the arrow keys
+/// navigate the menus and Escape or Alt return the focus to the previously focused component.
+pub struct MenuBar {
+    // #[property]
+    pub items: Vec<MenuItem>,
+    /// The style of the items and their submenus.
+    // #[property]
+    pub style: MenuStyle,
+    // #[property]
+    pub background: Color,
+
+    id: ComponentId,
+    size: Size<f32>,
+    bounds: Vec<Rectangle<f32>>,
+    highlighted: Option<usize>,
+    /// Whether the menu bar has the keyboard focus for navigating its menus.
+    active: bool,
+    /// The component focused before the menu bar was activated.
+    restore_focus: Option<ComponentId>,
+}
+
+impl Component for MenuBar {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let height = self
+            .items
+            .iter()
+            .map(|d| d.row_height(&self.style))
+            .fold(0f32, f32::max);
+        Size { width: available.width, height }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        self.bounds.clear();
+        let mut x = 0f32;
+        for (index, item) in self.items.iter_mut().enumerate() {
+            let width = item.header_width(&self.style);
+            item.style = self.style.clone();
+            item.top_level = true;
+            item.highlighted = self.highlighted == Some(index);
+            item.arrange(Size { width, height: given.height });
+            self.bounds.push(Rectangle { x, y: 0f32, width, height: given.height });
+            x += width;
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            fill: self.background,
+        }
+        .draw(renderer);
+        for (item, bounds) in self.items.iter().zip(self.bounds.iter()) {
+            renderer.translate(bounds.x, bounds.y);
+            item.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::AccessKey { key: None } => {
+                if self.active {
+                    self.deactivate(context);
+                } else {
+                    self.activate(context);
+                    self.move_highlight(true);
+                }
+                true
+            }
+            InputEvent::AccessKey { key: Some(mnemonic) } => match self.mnemonic_index(*mnemonic) {
+                Some(index) => {
+                    self.activate(context);
+                    self.open(index, true, context);
+                    true
+                }
+                None => false,
+            },
+            InputEvent::FocusLost => {
+                self.active = false;
+                self.restore_focus = None;
+                self.close();
+                true
+            }
+            InputEvent::PointerMoved { position } => match self.item_at(*position) {
+                Some(index) => {
+                    match self.open_index() {
+                        Some(open) if open != index => self.open(index, false, context),
+                        Some(_) => {}
+                        None => self.set_highlighted(Some(index)),
+                    }
+                    true
+                }
+                None => false,
+            },
+            InputEvent::PointerLeft => {
+                if !self.active {
+                    self.set_highlighted(None);
+                }
+                false
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left } => {
+                match self.item_at(*position) {
+                    Some(index) if self.open_index() == Some(index) => self.deactivate(context),
+                    Some(index) => {
+                        self.activate(context);
+                        self.open(index, false, context);
+                    }
+                    None => return false,
+                }
+                true
+            }
+            InputEvent::PointerReleased { .. } => {
+                let activated = self
+                    .items
+                    .iter_mut()
+                    .fold(false, |activated, d| d.submenu.take_activated() || activated);
+                if activated {
+                    self.deactivate(context);
+                }
+                activated
+            }
+            InputEvent::KeyPressed { key, .. } if self.active => self.key_pressed(key, context),
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        for (item, bounds) in self.items.iter().zip(self.bounds.iter()) {
+            visitor(*bounds, item);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        for (item, bounds) in self.items.iter_mut().zip(self.bounds.iter()) {
+            visitor(*bounds, item);
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        // Only focusable while navigating the menus, see *activate*
+        if self.active {
+            let mut focusable = Focusable::new();
+            focusable.tab_stop = false;
+            focusable.focus_visual = false;
+            Some(focusable)
+        } else {
+            None
+        }
+    }
+}
+
+impl MenuBar {
+    pub fn new() -> MenuBar {
+        MenuBar {
+            id: ComponentId::new(),
+            items: vec![],
+            style: MenuStyle::new(),
+            background: Color::WHITE,
+            size: Size { width: 0f32, height: 0f32 },
+            bounds: vec![],
+            highlighted: None,
+            active: false,
+            restore_focus: None,
+        }
+    }
+
+    fn key_pressed(&mut self, key: &Key, context: &mut EventContext) -> bool {
+        let count = self.items.len();
+        if let Some(index) = self.open_index() {
+            match self.items[index].submenu.key_pressed(key) {
+                MenuKey::Handled => return true,
+                MenuKey::Activated => {
+                    self.deactivate(context);
+                    return true;
+                }
+                MenuKey::Unhandled => {}
+            }
+            match key {
+                Key::Named(NamedKey::ArrowLeft) => self.open((index + count - 1) % count, true, context),
+                Key::Named(NamedKey::ArrowRight) => self.open((index + 1) % count, true, context),
+                Key::Named(NamedKey::Escape) => self.items[index].close(),
+                Key::Named(NamedKey::Alt) => self.deactivate(context),
+                _ => return false,
+            }
+            return true;
+        }
+        match key {
+            Key::Named(NamedKey::ArrowLeft) => self.move_highlight(false),
+            Key::Named(NamedKey::ArrowRight) => self.move_highlight(true),
+            Key::Named(NamedKey::ArrowDown) | Key::Named(NamedKey::Enter) | Key::Named(NamedKey::Space) => {
+                if let Some(index) = self.highlighted {
+                    self.open(index, true, context);
+                }
+            }
+            Key::Named(NamedKey::Escape) | Key::Named(NamedKey::Alt) => self.deactivate(context),
+            Key::Character(text) => {
+                let index = text
+                    .chars()
+                    .next()
+                    .and_then(|d| d.to_lowercase().next())
+                    .and_then(|d| self.mnemonic_index(d));
+                match index {
+                    Some(index) => self.open(index, true, context),
+                    None => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Moves the keyboard focus to the menu bar, remembering the focused component.
+    fn activate(&mut self, context: &mut EventContext) {
+        if self.active {
+            return;
+        }
+        self.active = true;
+        self.restore_focus = context.focused().filter(|d| *d != self.id);
+        context.request_focus(self.id);
+    }
+
+    /// Closes the menus and returns the keyboard focus to the previously focused component.
+    fn deactivate(&mut self, context: &mut EventContext) {
+        self.close();
+        self.active = false;
+        if let Some(id) = self.restore_focus.take() {
+            context.request_focus(id);
+        }
+    }
+
+    /// Opens the submenu of the item at *index*, or clicks it if it has no submenu.
+    fn open(&mut self, index: usize, keyboard: bool, context: &mut EventContext) {
+        if !self.items[index].is_enabled() {
+            return;
+        }
+        self.close();
+        self.set_highlighted(Some(index));
+        if self.items[index].has_submenu() {
+            self.items[index].open(keyboard);
+        } else {
+            self.items[index].click();
+            self.deactivate(context);
+        }
+    }
+
+    fn close(&mut self) {
+        for item in self.items.iter_mut() {
+            item.close();
+        }
+        self.set_highlighted(None);
+    }
+
+    fn move_highlight(&mut self, forward: bool) {
+        let count = self.items.len();
+        let mut index = self.highlighted;
+        for _ in 0..count {
+            let next = match (index, forward) {
+                (None, true) => 0,
+                (None, false) => count - 1,
+                (Some(d), true) => (d + 1) % count,
+                (Some(d), false) => (d + count - 1) % count,
+            };
+            if self.items[next].is_enabled() {
+                self.set_highlighted(Some(next));
+                return;
+            }
+            index = Some(next);
+        }
+    }
+
+    fn set_highlighted(&mut self, index: Option<usize>) {
+        self.highlighted = index;
+        for (i, item) in self.items.iter_mut().enumerate() {
+            item.highlighted = index == Some(i);
+        }
+    }
+
+    fn open_index(&self) -> Option<usize> {
+        self.items.iter().position(|d| d.is_open)
+    }
+
+    fn mnemonic_index(&self, mnemonic: char) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|d| d.is_enabled() && d.mnemonic() == Some(mnemonic))
+    }
+
+    fn item_at(&self, position: Point<f32>) -> Option<usize> {
+        self.bounds.iter().position(|d| d.contains(position))
+    }
+}
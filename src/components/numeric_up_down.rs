@@ -0,0 +1,474 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::{RangeValue, TextBox};
+use crate::data::{ComponentId, Point, Rectangle, Size};
+use crate::input::{EventContext, InputEvent};
+use crate::visuals::{self, PathCommand};
+
+/// The time a button has to be held before it starts repeating.
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+/// The time between two repetitions of a held button.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug)]
+pub enum NumberFormatErrors {
+    Empty,
+    InvalidNumber(String),
+}
+
+impl Display for NumberFormatErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumberFormatErrors::Empty => write!(f, "Number is empty"),
+            NumberFormatErrors::InvalidNumber(d) => write!(f, "Invalid number ({})", d),
+        }
+    }
+}
+
+impl Error for NumberFormatErrors {}
+
+/// # Description
+/// Describes how a [`NumericUpDown`] displays its value and parses the text entered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberFormat {
+    /// The number of digits after the decimal separator.
+    pub decimals: usize,
+    pub decimal_separator: char,
+    /// The separator inserted between every group of three integer digits, if any.
+    pub thousands_separator: Option<char>,
+    /// The text displayed before the number, eg. a currency symbol.
+    pub prefix: String,
+    /// The text displayed after the number, eg. a unit like ` px`.
+    pub suffix: String,
+}
+
+impl NumberFormat {
+    pub fn new() -> NumberFormat {
+        NumberFormat {
+            decimals: 0,
+            decimal_separator: '.',
+            thousands_separator: None,
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+
+    pub fn format(&self, value: f32) -> String {
+        let digits = format!("{:.*}", self.decimals, (value as f64).abs());
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+        let mut text = self.prefix.clone();
+        // Rounding may turn small negative values into zero, which has no sign
+        if value < 0f32 && digits.chars().any(|d| d != '0' && d != '.') {
+            text.push('-');
+        }
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                text.extend(self.thousands_separator);
+            }
+            text.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            text.push(self.decimal_separator);
+            text.push_str(fraction);
+        }
+        text.push_str(&self.suffix);
+        text
+    }
+
+    /// # Description
+    /// Parses a number entered by the user.
+    ///
+    /// # Remarks
+    /// The prefix, the suffix and thousands separators are optional, surrounding whitespace
+    /// is ignored. The number is not rounded to *decimals*.
+    pub fn parse(&self, text: &str) -> Result<f32, NumberFormatErrors> {
+        let mut number = text.trim();
+        number = number.strip_prefix(self.prefix.trim()).unwrap_or(number);
+        number = number.strip_suffix(self.suffix.trim()).unwrap_or(number).trim();
+        if number.is_empty() {
+            return Err(NumberFormatErrors::Empty);
+        }
+        let number: String = number
+            .chars()
+            .filter(|d| Some(*d) != self.thousands_separator)
+            .map(|d| if d == self.decimal_separator { '.' } else { d })
+            .collect();
+        match number.parse::<f32>() {
+            Ok(d) if d.is_finite() => Ok(d),
+            _ => Err(NumberFormatErrors::InvalidNumber(text.to_string())),
+        }
+    }
+
+    /// # Description
+    /// Rounds *value* to *decimals* digits after the decimal separator.
+    pub fn round(&self, value: f32) -> f32 {
+        let factor = 10f64.powi(self.decimals as i32);
+        ((value as f64 * factor).round() / factor) as f32
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ButtonPress {
+    /// `1` for the increase button, `-1` for the decrease button.
+    direction: f32,
+    started: Instant,
+    next_repeat: Instant,
+}
+
+/// # Description
+/// A text box for entering a number, with buttons increasing and decreasing it.
+///
+/// # Remarks
+/// The arrow keys and Page Up and Page Down step the value, as does the mouse wheel while the
+/// text box has the keyboard focus. Holding a button repeats it, taking larger steps the longer
+/// it is held.
+/// The text entered is applied when pressing Enter or leaving the text box and reverted when
+/// pressing Escape. While the text is not a number within the range, the border turns
+/// *invalid_border*.
+pub struct NumericUpDown {
+    /// The value, its limits and the step the buttons and arrow keys change it by.
+    // #[property]
+    pub range: RangeValue,
+    /// The step Page Up and Page Down change the value by.
+    // #[property]
+    pub large_change: f32,
+    // #[property]
+    pub format: NumberFormat,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub button_width: f32,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub invalid_border: Color,
+    // #[property]
+    pub button_background: Color,
+    // #[property]
+    pub hover_button_background: Color,
+    // #[property]
+    pub pressed_button_background: Color,
+    // #[property]
+    pub glyph: Color,
+
+    id: ComponentId,
+    size: Size<f32>,
+    text_box: TextBox,
+    text_box_focused: bool,
+    /// The value the text was formatted from, if it was not edited since.
+    displayed_value: Option<f32>,
+    is_valid: bool,
+    hovered: Option<f32>,
+    pressed: Option<ButtonPress>,
+}
+
+impl Component for NumericUpDown {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let desired = self.text_box.measure(Size {
+            width: f32::max(0f32, available.width - self.button_width),
+            height: available.height,
+        });
+        Size { width: desired.width + self.button_width, height: desired.height }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let focused = self.text_box.is_focused();
+        if self.text_box_focused && !focused {
+            self.commit();
+        }
+        self.text_box_focused = focused;
+        if !focused && self.displayed_value != Some(self.range.value) {
+            self.update_text();
+        }
+        self.is_valid = match self.format.parse(&self.text_box.text) {
+            Ok(d) => d >= self.range.min_value && d <= self.range.max_value,
+            Err(_) => false,
+        };
+        self.text_box.read_only = !self.enabled;
+        if self.is_valid {
+            self.text_box.border = self.border;
+            self.text_box.focused_border = self.focused_border;
+        } else {
+            self.text_box.border = self.invalid_border;
+            self.text_box.focused_border = self.invalid_border;
+        }
+        let bounds = self.text_box_bounds();
+        let size = Size { width: bounds.width, height: bounds.height };
+        self.text_box.measure(size);
+        self.text_box.arrange(size);
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        self.text_box.render(renderer);
+        for direction in [1f32, -1f32] {
+            let bounds = self.button_bounds(direction);
+            let fill = match self.pressed {
+                Some(press) if press.direction == direction => self.pressed_button_background,
+                _ if self.enabled && self.hovered == Some(direction) => self.hover_button_background,
+                _ => self.button_background,
+            };
+            visuals::Rectangle { x: bounds.x, y: bounds.y, width: bounds.width, height: bounds.height, fill }
+                .draw(renderer);
+            visuals::Outline {
+                x: bounds.x + 0.5f32,
+                y: bounds.y + 0.5f32,
+                width: bounds.width - 1f32,
+                height: bounds.height - 1f32,
+                stroke: self.border,
+                thickness: 1f32,
+            }
+            .draw(renderer);
+            let center = Point { x: bounds.x + bounds.width / 2f32, y: bounds.y + bounds.height / 2f32 };
+            let extent = f32::min(4f32, bounds.height / 4f32);
+            // A chevron pointing up for the increase button and down for the decrease button
+            let point = |x: f32, y: f32| Point { x: center.x + x * extent, y: center.y - y * extent * direction };
+            visuals::Path {
+                commands: &[
+                    PathCommand::MoveTo(point(-1f32, -0.5f32)),
+                    PathCommand::LineTo(point(0f32, 0.5f32)),
+                    PathCommand::LineTo(point(1f32, -0.5f32)),
+                ],
+                fill: Color::TRANSPARENT,
+                stroke: self.glyph,
+                thickness: 1.5f32,
+            }
+            .draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerMoved { position } => {
+                self.hovered = self.button_at(*position);
+                self.pressed.is_some()
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = None;
+                false
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left } if self.enabled => {
+                let direction = match self.button_at(*position) {
+                    None => return false,
+                    Some(d) => d,
+                };
+                let now = context.now();
+                let press = ButtonPress { direction, started: now, next_repeat: now + REPEAT_DELAY };
+                self.pressed = Some(press);
+                self.step(direction);
+                context.request_wake_up(press.next_repeat);
+                context.capture_pointer();
+                true
+            }
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                if self.pressed.take().is_none() {
+                    return false;
+                }
+                context.release_pointer_capture();
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.pressed = None;
+                true
+            }
+            InputEvent::Tick => {
+                let mut press = match self.pressed {
+                    None => return false,
+                    Some(d) => d,
+                };
+                let now = context.now();
+                if now >= press.next_repeat {
+                    self.step(press.direction * acceleration(now - press.started));
+                    press.next_repeat = now + REPEAT_INTERVAL;
+                    self.pressed = Some(press);
+                }
+                context.request_wake_up(press.next_repeat);
+                true
+            }
+            InputEvent::PointerWheel { delta, .. }
+                if self.enabled && self.text_box.is_focused() && delta.y != 0f32 =>
+            {
+                self.step(delta.y.signum());
+                true
+            }
+            InputEvent::KeyPressed { key, .. } if self.enabled => {
+                let large = self.large_change / self.range.small_change;
+                match key {
+                    Key::Named(NamedKey::ArrowUp) => self.step(1f32),
+                    Key::Named(NamedKey::ArrowDown) => self.step(-1f32),
+                    Key::Named(NamedKey::PageUp) => self.step(large),
+                    Key::Named(NamedKey::PageDown) => self.step(-large),
+                    Key::Named(NamedKey::Enter) => self.commit(),
+                    Key::Named(NamedKey::Escape) => self.update_text(),
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.text_box_bounds(), &self.text_box);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        visitor(self.text_box_bounds(), &mut self.text_box);
+    }
+}
+
+impl NumericUpDown {
+    pub fn new() -> NumericUpDown {
+        let mut range = RangeValue::new();
+        range.max_value = 100f32;
+        NumericUpDown {
+            id: ComponentId::new(),
+            range,
+            large_change: 10f32,
+            format: NumberFormat::new(),
+            enabled: true,
+            button_width: 18f32,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            invalid_border: Color::CRIMSON,
+            button_background: Color::WHITE,
+            hover_button_background: Color::GAINSBORO,
+            pressed_button_background: Color::LIGHT_GRAY,
+            glyph: Color::DIM_GRAY,
+            size: Size { width: 0f32, height: 0f32 },
+            text_box: TextBox::new(),
+            text_box_focused: false,
+            displayed_value: None,
+            is_valid: true,
+            hovered: None,
+            pressed: None,
+        }
+    }
+
+    /// # Description
+    /// Whether the text entered is a number within the range.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    /// Changes the value by *steps* times *small_change*, starting from the text entered if it
+    /// is a number.
+    fn step(&mut self, steps: f32) {
+        let value = self.format.parse(&self.text_box.text).unwrap_or(self.range.value);
+        let value = self.format.round(self.range.clamp(value + steps * self.range.small_change));
+        self.range.set_value(value);
+        self.update_text();
+    }
+
+    /// Applies the text entered, which is reverted if it is not a number.
+    fn commit(&mut self) {
+        if let Ok(value) = self.format.parse(&self.text_box.text) {
+            let value = self.format.round(self.range.clamp(value));
+            self.range.set_value(value);
+        }
+        self.update_text();
+    }
+
+    fn update_text(&mut self) {
+        self.text_box.text = self.format.format(self.range.value);
+        let end = self.text_box.text.len();
+        self.text_box.select(end, end);
+        self.displayed_value = Some(self.range.value);
+    }
+
+    fn text_box_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: f32::max(0f32, self.size.width - self.button_width),
+            height: self.size.height,
+        }
+    }
+
+    /// The bounds of the increase button for a *direction* of `1` and of the decrease button
+    /// for `-1`.
+    fn button_bounds(&self, direction: f32) -> Rectangle<f32> {
+        let height = self.size.height / 2f32;
+        Rectangle {
+            x: f32::max(0f32, self.size.width - self.button_width),
+            y: if direction > 0f32 { 0f32 } else { height },
+            width: f32::min(self.button_width, self.size.width),
+            height,
+        }
+    }
+
+    fn button_at(&self, position: Point<f32>) -> Option<f32> {
+        [1f32, -1f32].iter().copied().find(|d| self.button_bounds(*d).contains(position))
+    }
+}
+
+/// The number of steps a repetition of a button held for *held* takes.
+fn acceleration(held: Duration) -> f32 {
+    if held >= Duration::from_secs(4) {
+        100f32
+    } else if held >= Duration::from_secs(2) {
+        10f32
+    } else {
+        1f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acceleration() {
+        assert_eq!(acceleration(Duration::from_millis(500)), 1f32);
+        assert_eq!(acceleration(Duration::from_millis(2500)), 10f32);
+        assert_eq!(acceleration(Duration::from_secs(5)), 100f32);
+    }
+
+    #[test]
+    fn test_format() {
+        let mut format = NumberFormat::new();
+        format.decimals = 2;
+        format.thousands_separator = Some(',');
+        format.suffix = " px".to_string();
+        assert_eq!(format.format(1234567.891f32), "1,234,567.88 px");
+        assert_eq!(format.format(-999.5f32), "-999.50 px");
+        assert_eq!(format.format(-0.001f32), "0.00 px");
+        format.decimals = 0;
+        format.prefix = "$".to_string();
+        format.suffix = String::new();
+        assert_eq!(format.format(-1000f32), "$-1,000");
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut format = NumberFormat::new();
+        format.decimal_separator = ',';
+        format.thousands_separator = Some('.');
+        format.suffix = " kg".to_string();
+        assert_eq!(format.parse(" 1.234,5 kg ").unwrap(), 1234.5f32);
+        assert_eq!(format.parse("12kg").unwrap(), 12f32);
+        assert_eq!(format.parse("-3").unwrap(), -3f32);
+        assert!(matches!(format.parse(" kg"), Err(NumberFormatErrors::Empty)));
+        assert!(matches!(format.parse("12 g"), Err(NumberFormatErrors::InvalidNumber(_))));
+        format.decimals = 1;
+        assert_eq!(format.round(2.26f32), 2.3f32);
+    }
+}
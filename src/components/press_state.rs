@@ -0,0 +1,108 @@
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::data::Rectangle;
+use crate::input::{EventContext, InputEvent};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PressResult {
+    Unhandled,
+    Handled,
+    /// The event completed a click.
+    Clicked,
+}
+
+/// # Description
+/// Tracks the interaction with a clickable component (eg. a button or a check box), which
+/// is clicked by releasing the left mouse button over it or by releasing the space key.
+pub(crate) struct PressState {
+    pub(crate) hovered: bool,
+    pub(crate) focused: bool,
+    pointer_pressed: bool,
+    key_pressed: bool,
+}
+
+impl PressState {
+    pub(crate) fn new() -> PressState {
+        PressState {
+            hovered: false,
+            focused: false,
+            pointer_pressed: false,
+            key_pressed: false,
+        }
+    }
+
+    /// # Description
+    /// Whether the component is to be displayed pressed.
+    pub(crate) fn is_pressed(&self) -> bool {
+        (self.pointer_pressed && self.hovered) || self.key_pressed
+    }
+
+    /// # Parameters
+    /// - **bounds**: The bounds of the component, relative to itself.
+    /// - **enabled**: Whether the component can be clicked. Disabled components still track
+    ///                hover and focus.
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &InputEvent,
+        context: &mut EventContext,
+        bounds: Rectangle<f32>,
+        enabled: bool,
+    ) -> PressResult {
+        match event {
+            InputEvent::PointerEntered => {
+                self.hovered = true;
+                PressResult::Handled
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = false;
+                PressResult::Handled
+            }
+            InputEvent::FocusGained => {
+                self.focused = true;
+                PressResult::Handled
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                self.key_pressed = false;
+                PressResult::Handled
+            }
+            InputEvent::PointerCaptureLost => {
+                self.pointer_pressed = false;
+                PressResult::Handled
+            }
+            // Releases end a press even if the component was disabled meanwhile, so the pointer
+            // capture is not kept
+            InputEvent::PointerReleased { position, button: MouseButton::Left } => {
+                if !self.pointer_pressed {
+                    return PressResult::Unhandled;
+                }
+                self.pointer_pressed = false;
+                context.release_pointer_capture();
+                // Clicks only happen if the button is released on the component
+                if enabled && bounds.contains(*position) {
+                    PressResult::Clicked
+                } else {
+                    PressResult::Handled
+                }
+            }
+            InputEvent::KeyReleased { key: Key::Named(NamedKey::Space), .. } => {
+                if !self.key_pressed {
+                    return PressResult::Unhandled;
+                }
+                self.key_pressed = false;
+                if enabled { PressResult::Clicked } else { PressResult::Handled }
+            }
+            _ if !enabled => PressResult::Unhandled,
+            InputEvent::PointerPressed { button: MouseButton::Left, .. } => {
+                self.pointer_pressed = true;
+                context.capture_pointer();
+                PressResult::Handled
+            }
+            InputEvent::KeyPressed { key: Key::Named(NamedKey::Space), .. } => {
+                self.key_pressed = true;
+                PressResult::Handled
+            }
+            _ => PressResult::Unhandled,
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::RangeValue;
+use crate::data::{ComponentId, Orientation, Size};
+use crate::input::{EventContext, InputEvent};
+use crate::visuals;
+
+/// The time the indicator of an indeterminate progress bar takes to cross the bar once.
+const INDETERMINATE_PERIOD: Duration = Duration::from_millis(1500);
+/// The length of the indicator of an indeterminate progress bar, relative to the bar.
+const INDETERMINATE_LENGTH: f32 = 0.3f32;
+
+/// # Description
+/// Displays the progress of an operation.
+///
+/// # Remarks
+/// Vertical progress bars fill from the bottom.
+/// An indeterminate progress bar only animates while it was arranged with an area, so a bar
+/// collapsed to nothing does not keep its window rendering.
+pub struct ProgressBar {
+    /// The progress, displayed as the filled part of the bar.
+    // #[property]
+    pub range: RangeValue,
+    /// The orientation of the progress bar.
+    // #[property]
+    pub mode: Orientation,
+    /// Whether the progress is unknown, displaying an indicator moving across the bar
+    /// instead of the value.
+    // #[property]
+    pub is_indeterminate: bool,
+    /// The thickness of the progress bar.
+    // #[property]
+    pub thickness: f32,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub foreground: Color,
+
+    id: ComponentId,
+    size: Size<f32>,
+    /// The position of the indeterminate indicator, from `0` to `1`.
+    phase: f32,
+    last_tick: Option<Instant>,
+}
+
+impl Component for ProgressBar {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        match self.mode {
+            Orientation::Horizontal => Size { width: available.width, height: self.thickness },
+            Orientation::Vertical => Size { width: self.thickness, height: available.height },
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = match self.mode {
+            Orientation::Horizontal => Size {
+                width: given.width,
+                height: f32::min(self.thickness, given.height),
+            },
+            Orientation::Vertical => Size {
+                width: f32::min(self.thickness, given.width),
+                height: given.height,
+            },
+        };
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let radius = match self.mode {
+            Orientation::Horizontal => self.size.height / 2f32,
+            Orientation::Vertical => self.size.width / 2f32,
+        };
+        visuals::RoundedRectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            radius,
+            fill: self.background,
+            stroke: Color::TRANSPARENT,
+            thickness: 0f32,
+        }
+        .draw(renderer);
+
+        // The indicator as start and end along the bar, relative to its length
+        let (start, end) = if self.is_indeterminate {
+            let start = self.phase * (1f32 + INDETERMINATE_LENGTH) - INDETERMINATE_LENGTH;
+            (f32::max(0f32, start), f32::min(1f32, start + INDETERMINATE_LENGTH))
+        } else {
+            (0f32, self.range.ratio())
+        };
+        if end <= start {
+            return;
+        }
+        let (x, y, width, height) = match self.mode {
+            Orientation::Horizontal => (
+                start * self.size.width,
+                0f32,
+                (end - start) * self.size.width,
+                self.size.height,
+            ),
+            Orientation::Vertical => (
+                0f32,
+                (1f32 - end) * self.size.height,
+                self.size.width,
+                (end - start) * self.size.height,
+            ),
+        };
+        visuals::RoundedRectangle {
+            x,
+            y,
+            width,
+            height,
+            radius,
+            fill: self.foreground,
+            stroke: Color::TRANSPARENT,
+            thickness: 0f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::Tick if self.is_animating() => {
+                let now = context.now();
+                if let Some(last_tick) = self.last_tick {
+                    let elapsed = now.duration_since(last_tick).as_secs_f32();
+                    self.phase = (self.phase + elapsed / INDETERMINATE_PERIOD.as_secs_f32()).fract();
+                }
+                self.last_tick = Some(now);
+                true
+            }
+            InputEvent::Tick => {
+                self.last_tick = None;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        self.is_indeterminate && self.size.width > 0f32 && self.size.height > 0f32
+    }
+}
+
+impl ProgressBar {
+    pub fn new() -> ProgressBar {
+        let mut range = RangeValue::new();
+        range.max_value = 100f32;
+        ProgressBar {
+            id: ComponentId::new(),
+            range,
+            mode: Orientation::Horizontal,
+            is_indeterminate: false,
+            thickness: 6f32,
+            background: Color::LIGHT_GRAY,
+            foreground: Color::CORNFLOWER_BLUE,
+            size: Size { width: 0f32, height: 0f32 },
+            phase: 0f32,
+            last_tick: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use winit::keyboard::ModifiersState;
+
+    fn tick(progress_bar: &mut ProgressBar, now: Instant) -> bool {
+        let mut context = EventContext::new(now, &PointerState::new(), None, ModifiersState::empty());
+        progress_bar.handle_event(&InputEvent::Tick, &mut context)
+    }
+
+    #[test]
+    fn test_indeterminate() {
+        let mut progress_bar = ProgressBar::new();
+        progress_bar.arrange(Size { width: 100f32, height: 20f32 });
+        assert!(!progress_bar.is_animating());
+        assert!(!tick(&mut progress_bar, Instant::now()));
+
+        progress_bar.is_indeterminate = true;
+        assert!(progress_bar.is_animating());
+        let now = Instant::now();
+        assert!(tick(&mut progress_bar, now));
+        assert_eq!(progress_bar.phase, 0f32);
+        tick(&mut progress_bar, now + INDETERMINATE_PERIOD / 2);
+        assert!((progress_bar.phase - 0.5f32).abs() < 0.001f32);
+        // The indicator starts over once it crossed the bar
+        tick(&mut progress_bar, now + INDETERMINATE_PERIOD * 5 / 4);
+        assert!((progress_bar.phase - 0.25f32).abs() < 0.001f32);
+    }
+
+    #[test]
+    fn test_collapsed() {
+        let mut progress_bar = ProgressBar::new();
+        progress_bar.is_indeterminate = true;
+        progress_bar.arrange(Size { width: 0f32, height: 20f32 });
+        assert!(!progress_bar.is_animating());
+        let now = Instant::now();
+        assert!(!tick(&mut progress_bar, now));
+
+        // The time spent collapsed does not advance the indicator
+        progress_bar.arrange(Size { width: 100f32, height: 20f32 });
+        assert!(progress_bar.is_animating());
+        tick(&mut progress_bar, now + INDETERMINATE_PERIOD);
+        tick(&mut progress_bar, now + INDETERMINATE_PERIOD * 5 / 4);
+        assert!((progress_bar.phase - 0.25f32).abs() < 0.001f32);
+    }
+}
@@ -0,0 +1,327 @@
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::glyph_label::GlyphLabel;
+use crate::components::press_state::{PressResult, PressState};
+use crate::data::{ComponentId, Rectangle, Size};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::TextStyle;
+use crate::tool_tip::ToolTip;
+use crate::visuals;
+
+/// # Description
+/// A choice of which only one per *group* can be checked.
+///
+/// # Remarks
+/// Checking a radio button unchecks the other radio buttons of its group. Radio buttons
+/// without a *group* name are grouped with their siblings, while named groups span the whole
+/// window regardless of where in the component tree their members are.
+/// The arrow keys check and focus the next (Down, Right) or previous (Up, Left) enabled
+/// radio button of the group.
+pub struct RadioButton {
+    /// The text displayed next to the circle.
+    // #[property]
+    pub text: String,
+    // #[property]
+    pub text_style: TextStyle,
+    // #[property]
+    pub checked: bool,
+    /// The name of the mutually exclusive group this radio button belongs to, or empty to
+    /// form a group with the sibling radio buttons.
+    // #[property]
+    pub group: String,
+    // #[property]
+    pub enabled: bool,
+    /// The diameter of the circle.
+    // #[property]
+    pub circle_size: f32,
+    /// The space between the circle and the text.
+    // #[property]
+    pub spacing: f32,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub hover_background: Color,
+    // #[property]
+    pub pressed_background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    /// The color of the dot marking the checked radio button.
+    // #[property]
+    pub mark: Color,
+    // #[property]
+    pub disabled_foreground: Color,
+    /// Called with the new value of *checked* whenever it was changed by the user or by
+    /// another radio button of the group.
+    // #[event]
+    pub on_checked_changed: Option<Box<dyn FnMut(bool)>>,
+    /// The tool tip shown while the pointer hovers the radio button.
+    // #[property]
+    pub tool_tip: Option<ToolTip>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    layout: GlyphLabel,
+    render_style: TextStyle,
+    press: PressState,
+}
+
+impl Component for RadioButton {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, _available: Size<f32>) -> Size<f32> {
+        GlyphLabel::measure(self.glyph_size(), self.spacing, &self.text, &self.text_style)
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.layout = GlyphLabel::arrange(self.glyph_size(), self.spacing, &self.text_style, given);
+        self.render_style = self.text_style.clone();
+        if !self.enabled {
+            self.render_style.color = self.disabled_foreground;
+        }
+        self.size = given;
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let glyph = self.layout.glyph;
+        let background = if !self.enabled {
+            self.background
+        } else if self.press.is_pressed() {
+            self.pressed_background
+        } else if self.press.hovered {
+            self.hover_background
+        } else {
+            self.background
+        };
+        visuals::RoundedRectangle {
+            x: glyph.x,
+            y: glyph.y,
+            width: glyph.width,
+            height: glyph.height,
+            radius: glyph.width / 2f32,
+            fill: background,
+            stroke: if !self.enabled {
+                self.disabled_foreground
+            } else if self.press.focused {
+                self.focused_border
+            } else {
+                self.border
+            },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        if self.checked {
+            let dot = glyph.width / 2f32;
+            visuals::RoundedRectangle {
+                x: glyph.x + (glyph.width - dot) / 2f32,
+                y: glyph.y + (glyph.height - dot) / 2f32,
+                width: dot,
+                height: dot,
+                radius: dot / 2f32,
+                fill: if self.enabled { self.mark } else { self.disabled_foreground },
+                stroke: Color::TRANSPARENT,
+                thickness: 0f32,
+            }
+            .draw(renderer);
+        }
+
+        if !self.text.is_empty() {
+            visuals::Text {
+                x: self.layout.text_origin.x,
+                y: self.layout.text_origin.y,
+                text: &self.text,
+                style: &self.render_style,
+            }
+            .draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::GroupChecked { group, id } => {
+                if *id != self.id && *group == self.group && self.checked {
+                    self.set_checked(false);
+                }
+                return false;
+            }
+            InputEvent::GroupStepped { group, id, .. } => {
+                if *id == self.id || *group != self.group || !self.enabled {
+                    return false;
+                }
+                self.check(context);
+                context.request_focus(self.id);
+                return true;
+            }
+            InputEvent::KeyPressed { key: Key::Named(key), modifiers, .. }
+                if self.enabled && modifiers.is_empty() =>
+            {
+                let forward = match key {
+                    NamedKey::ArrowDown | NamedKey::ArrowRight => Some(true),
+                    NamedKey::ArrowUp | NamedKey::ArrowLeft => Some(false),
+                    _ => None,
+                };
+                if let Some(forward) = forward {
+                    context.broadcast(InputEvent::GroupStepped {
+                        group: self.group.clone(),
+                        id: self.id,
+                        forward,
+                    });
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        let bounds = Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height };
+        match self.press.handle_event(event, context, bounds, self.enabled) {
+            PressResult::Unhandled => false,
+            PressResult::Handled => true,
+            PressResult::Clicked => {
+                self.check(context);
+                true
+            }
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+
+    fn tool_tip(&self) -> Option<&ToolTip> {
+        self.tool_tip.as_ref()
+    }
+}
+
+impl RadioButton {
+    pub fn new() -> RadioButton {
+        RadioButton {
+            id: ComponentId::new(),
+            text: String::new(),
+            text_style: TextStyle::new(),
+            checked: false,
+            group: String::new(),
+            enabled: true,
+            circle_size: 16f32,
+            spacing: 6f32,
+            background: Color::WHITE,
+            hover_background: Color::GAINSBORO,
+            pressed_background: Color::LIGHT_GRAY,
+            border: Color::GRAY,
+            focused_border: Color::MIDNIGHT_BLUE,
+            mark: Color::CORNFLOWER_BLUE,
+            disabled_foreground: Color::GRAY,
+            on_checked_changed: None,
+            tool_tip: None,
+            size: Size { width: 0f32, height: 0f32 },
+            layout: GlyphLabel::new(),
+            render_style: TextStyle::new(),
+            press: PressState::new(),
+        }
+    }
+
+    /// Checks the radio button, unchecking the other members of its group.
+    fn check(&mut self, context: &mut EventContext) {
+        if self.checked {
+            return;
+        }
+        self.set_checked(true);
+        context.broadcast(InputEvent::GroupChecked {
+            group: self.group.clone(),
+            id: self.id,
+        });
+    }
+
+    fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+        if let Some(callback) = self.on_checked_changed.as_mut() {
+            callback(checked);
+        }
+    }
+
+    fn glyph_size(&self) -> Size<f32> {
+        Size { width: self.circle_size, height: self.circle_size }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Point;
+    use crate::pointer::PointerState;
+    use std::time::Instant;
+    use winit::event::MouseButton;
+    use winit::keyboard::ModifiersState;
+
+    fn context() -> EventContext {
+        EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty())
+    }
+
+    #[test]
+    fn test_check() {
+        let mut radio_button = RadioButton::new();
+        radio_button.group = "size".to_string();
+        radio_button.arrange(Size { width: 100f32, height: 20f32 });
+        let mut context = context();
+        let position = Point { x: 8f32, y: 8f32 };
+        radio_button.handle_event(&InputEvent::PointerPressed { position, button: MouseButton::Left }, &mut context);
+        radio_button.handle_event(&InputEvent::PointerReleased { position, button: MouseButton::Left }, &mut context);
+        assert!(radio_button.checked);
+        assert!(matches!(
+            context.broadcasts.as_slice(),
+            [InputEvent::GroupChecked { group, id }] if group == "size" && *id == radio_button.id
+        ));
+
+        // Clicking a checked radio button does not uncheck it
+        let mut context = self::context();
+        radio_button.handle_event(&InputEvent::PointerPressed { position, button: MouseButton::Left }, &mut context);
+        radio_button.handle_event(&InputEvent::PointerReleased { position, button: MouseButton::Left }, &mut context);
+        assert!(radio_button.checked);
+        assert!(context.broadcasts.is_empty());
+
+        let other = InputEvent::GroupChecked { group: "color".to_string(), id: ComponentId::new() };
+        radio_button.handle_event(&other, &mut self::context());
+        assert!(radio_button.checked);
+        let same = InputEvent::GroupChecked { group: "size".to_string(), id: ComponentId::new() };
+        radio_button.handle_event(&same, &mut self::context());
+        assert!(!radio_button.checked);
+    }
+
+    #[test]
+    fn test_arrow_keys() {
+        let mut radio_button = RadioButton::new();
+        let mut context = context();
+        let key = InputEvent::KeyPressed {
+            key: Key::Named(NamedKey::ArrowUp),
+            text: None,
+            modifiers: ModifiersState::empty(),
+            repeat: false,
+        };
+        assert!(radio_button.handle_event(&key, &mut context));
+        assert!(matches!(
+            context.broadcasts.as_slice(),
+            [InputEvent::GroupStepped { id, forward: false, .. }] if *id == radio_button.id
+        ));
+
+        // A step from another member checks and focuses the radio button
+        let mut context = self::context();
+        let step = InputEvent::GroupStepped { group: String::new(), id: ComponentId::new(), forward: true };
+        assert!(radio_button.handle_event(&step, &mut context));
+        assert!(radio_button.checked);
+        assert_eq!(context.focus_request, Some(radio_button.id));
+
+        let mut disabled = RadioButton::new();
+        disabled.enabled = false;
+        assert!(!disabled.handle_event(&step, &mut self::context()));
+        assert!(!disabled.checked);
+    }
+}
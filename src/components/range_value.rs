@@ -0,0 +1,135 @@
+/// # Description
+/// A value between a minimum and a maximum, shared by components like [`super::ScrollBar`],
+/// [`super::Slider`] and [`super::ProgressBar`].
+pub struct RangeValue {
+    /// The minimum value
+    // #[property]
+    pub min_value: f32,
+    /// The actual value
+    // #[property]
+    pub value: f32,
+    /// The maximum value
+    // #[property]
+    pub max_value: f32,
+    /// The value a single step (eg. an arrow key or a mouse wheel line) changes the value by.
+    // #[property]
+    pub small_change: f32,
+    /// Called with the new value whenever the value changed.
+    // #[event]
+    pub on_value_changed: Option<Box<dyn FnMut(f32)>>,
+}
+
+impl RangeValue {
+    pub fn new() -> RangeValue {
+        RangeValue {
+            min_value: 0f32,
+            value: 0f32,
+            max_value: 0f32,
+            small_change: 1f32,
+            on_value_changed: None,
+        }
+    }
+
+    /// # Description
+    /// The distance between *min_value* and *max_value*, or `0` if the range is empty.
+    pub fn range(&self) -> f32 {
+        f32::max(0f32, self.max_value - self.min_value)
+    }
+
+    /// # Description
+    /// Clamps *value* between *min_value* and *max_value*.
+    pub fn clamp(&self, value: f32) -> f32 {
+        f32::max(self.min_value, f32::min(value, self.max_value))
+    }
+
+    /// # Description
+    /// The position of the value within the range.
+    ///
+    /// # Returns
+    /// `0` for *min_value*, `1` for *max_value*, or `0` if the range is empty.
+    pub fn ratio(&self) -> f32 {
+        let range = self.range();
+        if range > 0f32 {
+            (self.clamp(self.value) - self.min_value) / range
+        } else {
+            0f32
+        }
+    }
+
+    /// # Description
+    /// The value at *ratio* within the range, the reverse of [`RangeValue::ratio`].
+    pub fn value_at(&self, ratio: f32) -> f32 {
+        self.clamp(self.min_value + ratio * self.range())
+    }
+
+    /// # Description
+    /// Rounds *value* to the closest multiple of *step*, counted from *min_value*.
+    ///
+    /// # Remarks
+    /// The result is clamped to the range, *value* is returned unchanged if *step* is not
+    /// positive.
+    pub fn snap(&self, value: f32, step: f32) -> f32 {
+        if step <= 0f32 {
+            return value;
+        }
+        self.clamp(self.min_value + ((value - self.min_value) / step).round() * step)
+    }
+
+    /// # Description
+    /// Sets the value, clamped between *min_value* and *max_value*,
+    /// raising *on_value_changed* if the value actually changed.
+    ///
+    /// # Returns
+    /// Whether the value changed.
+    pub fn set_value(&mut self, value: f32) -> bool {
+        let value = self.clamp(value);
+        if value == self.value {
+            return false;
+        }
+        self.value = value;
+        if let Some(callback) = self.on_value_changed.as_mut() {
+            callback(value);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(min_value: f32, max_value: f32) -> RangeValue {
+        let mut range = RangeValue::new();
+        range.min_value = min_value;
+        range.max_value = max_value;
+        range
+    }
+
+    #[test]
+    fn test_ratio() {
+        let mut range = range(10f32, 30f32);
+        range.value = 15f32;
+        assert_eq!(range.ratio(), 0.25f32);
+        assert_eq!(range.value_at(0.25f32), 15f32);
+        assert_eq!(range.value_at(2f32), 30f32);
+        assert_eq!(self::range(5f32, 5f32).ratio(), 0f32);
+    }
+
+    #[test]
+    fn test_snap() {
+        let range = range(1f32, 10f32);
+        assert_eq!(range.snap(3.9f32, 2f32), 3f32);
+        assert_eq!(range.snap(4.1f32, 2f32), 5f32);
+        assert_eq!(range.snap(9.9f32, 2f32), 9f32);
+        assert_eq!(range.snap(12f32, 4f32), 10f32);
+        assert_eq!(range.snap(4.1f32, 0f32), 4.1f32);
+    }
+
+    #[test]
+    fn test_set_value() {
+        let mut range = range(0f32, 10f32);
+        assert!(range.set_value(12f32));
+        assert_eq!(range.value, 10f32);
+        assert!(!range.set_value(10f32));
+    }
+}
@@ -0,0 +1,355 @@
+use crate::abstraction::{Component, Renderer};
+use crate::components::ScrollBar;
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size};
+use crate::input::{EventContext, InputEvent, PIXELS_PER_WHEEL_LINE};
+
+const SCROLL_BAR_THICKNESS: f32 = 14f32;
+
+/// # Description
+/// Controls when a [`ScrollViewer`] shows a scroll bar.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScrollBarVisibility {
+    /// The scroll bar is shown if the content does not fit.
+    Auto,
+    /// The scroll bar is always shown.
+    Visible,
+    /// The content can be scrolled, eg. using the mouse wheel, but no scroll bar is shown.
+    Hidden,
+    /// The content can not be scrolled and is constrained to the viewport in this direction.
+    Disabled,
+}
+
+/// # Description
+/// Displays a part of its content, which can be scrolled using scroll bars or the mouse wheel.
+///
+/// # Remarks
+/// The content is measured with an infinite size in every direction it can be scrolled in and
+/// informed about the visible part through [`Component::set_viewport`] before being arranged.
+pub struct ScrollViewer<C: Component> {
+    // #[property]
+    pub content: C,
+    // #[property]
+    pub horizontal_scroll_bar_visibility: ScrollBarVisibility,
+    // #[property]
+    pub vertical_scroll_bar_visibility: ScrollBarVisibility,
+
+    id: ComponentId,
+    size: Size<f32>,
+    viewport: Size<f32>,
+    extent: Size<f32>,
+    content_size: Size<f32>,
+    horizontal_bar: ScrollBar,
+    vertical_bar: ScrollBar,
+    horizontal_bar_visible: bool,
+    vertical_bar_visible: bool,
+    /// The area of the content to scroll into view during the next layout pass.
+    pending_scroll: Option<Rectangle<f32>>,
+}
+
+impl<C: Component> Component for ScrollViewer<C> {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let desired = self.content.measure(self.content_constraint(available));
+        let vertical_bar = match self.vertical_scroll_bar_visibility {
+            ScrollBarVisibility::Visible => SCROLL_BAR_THICKNESS,
+            _ => 0f32,
+        };
+        let horizontal_bar = match self.horizontal_scroll_bar_visibility {
+            ScrollBarVisibility::Visible => SCROLL_BAR_THICKNESS,
+            _ => 0f32,
+        };
+        Size {
+            width: f32::min(desired.width + vertical_bar, available.width),
+            height: f32::min(desired.height + horizontal_bar, available.height),
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let mut horizontal = self.horizontal_scroll_bar_visibility == ScrollBarVisibility::Visible;
+        let mut vertical = self.vertical_scroll_bar_visibility == ScrollBarVisibility::Visible;
+        let mut viewport = self.viewport_for(horizontal, vertical);
+        let mut extent = self.content.measure(self.content_constraint(viewport));
+        // Showing an automatic scroll bar shrinks the viewport, which may require the other one
+        for _ in 0..2 {
+            let next_horizontal = match self.horizontal_scroll_bar_visibility {
+                ScrollBarVisibility::Auto => extent.width > viewport.width,
+                _ => horizontal,
+            };
+            let next_vertical = match self.vertical_scroll_bar_visibility {
+                ScrollBarVisibility::Auto => extent.height > viewport.height,
+                _ => vertical,
+            };
+            if next_horizontal == horizontal && next_vertical == vertical {
+                break;
+            }
+            horizontal = next_horizontal;
+            vertical = next_vertical;
+            viewport = self.viewport_for(horizontal, vertical);
+            extent = self.content.measure(self.content_constraint(viewport));
+        }
+        self.horizontal_bar_visible = horizontal;
+        self.vertical_bar_visible = vertical;
+        self.viewport = viewport;
+        self.extent = extent;
+
+        self.horizontal_bar.range.max_value = f32::max(0f32, extent.width - viewport.width);
+        self.horizontal_bar.bar_value = viewport.width;
+        self.vertical_bar.range.max_value = f32::max(0f32, extent.height - viewport.height);
+        self.vertical_bar.bar_value = viewport.height;
+        if let Some(area) = self.pending_scroll.take() {
+            let x = scroll_target(self.horizontal_bar.range.value, viewport.width, area.x, area.width);
+            let y = scroll_target(self.vertical_bar.range.value, viewport.height, area.y, area.height);
+            self.horizontal_bar.set_value(x);
+            self.vertical_bar.set_value(y);
+        } else {
+            self.horizontal_bar.set_value(self.horizontal_bar.range.value);
+            self.vertical_bar.set_value(self.vertical_bar.range.value);
+        }
+
+        let offset = self.offset();
+        self.content.set_viewport(Rectangle {
+            x: offset.x,
+            y: offset.y,
+            width: viewport.width,
+            height: viewport.height,
+        });
+        self.content_size = self.content.arrange(Size {
+            width: match self.horizontal_scroll_bar_visibility {
+                ScrollBarVisibility::Disabled => viewport.width,
+                _ => f32::max(extent.width, viewport.width),
+            },
+            height: match self.vertical_scroll_bar_visibility {
+                ScrollBarVisibility::Disabled => viewport.height,
+                _ => f32::max(extent.height, viewport.height),
+            },
+        });
+        self.horizontal_bar.arrange(Size { width: viewport.width, height: SCROLL_BAR_THICKNESS });
+        self.vertical_bar.arrange(Size { width: SCROLL_BAR_THICKNESS, height: viewport.height });
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let offset = self.offset();
+        renderer.push_clip(0f32, 0f32, self.viewport.width, self.viewport.height);
+        renderer.translate(-offset.x, -offset.y);
+        self.content.render(renderer);
+        renderer.translate(offset.x, offset.y);
+        renderer.pop_clip();
+        if self.horizontal_bar_visible {
+            renderer.translate(0f32, self.viewport.height);
+            self.horizontal_bar.render(renderer);
+            renderer.translate(0f32, -self.viewport.height);
+        }
+        if self.vertical_bar_visible {
+            renderer.translate(self.viewport.width, 0f32);
+            self.vertical_bar.render(renderer);
+            renderer.translate(-self.viewport.width, 0f32);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, _context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerWheel { delta, .. } => {
+                let offset = self.offset();
+                if self.vertical_scroll_bar_visibility != ScrollBarVisibility::Disabled && delta.y != 0f32 {
+                    self.vertical_bar.set_value(offset.y - delta.y * PIXELS_PER_WHEEL_LINE);
+                }
+                if self.horizontal_scroll_bar_visibility != ScrollBarVisibility::Disabled && delta.x != 0f32 {
+                    self.horizontal_bar.set_value(offset.x - delta.x * PIXELS_PER_WHEEL_LINE);
+                }
+                // Let an outer scroll area scroll once this one reached its end
+                self.offset() != offset
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.content_bounds(), &self.content);
+        if self.horizontal_bar_visible {
+            visitor(self.horizontal_bar_bounds(), &self.horizontal_bar);
+        }
+        if self.vertical_bar_visible {
+            visitor(self.vertical_bar_bounds(), &self.vertical_bar);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        visitor(self.content_bounds(), &mut self.content);
+        if self.horizontal_bar_visible {
+            visitor(self.horizontal_bar_bounds(), &mut self.horizontal_bar);
+        }
+        if self.vertical_bar_visible {
+            visitor(self.vertical_bar_bounds(), &mut self.vertical_bar);
+        }
+    }
+}
+
+impl<C: Component> ScrollViewer<C> {
+    pub fn new(content: C) -> ScrollViewer<C> {
+        let mut horizontal_bar = ScrollBar::new();
+        horizontal_bar.mode = Orientation::Horizontal;
+        horizontal_bar.thickness = SCROLL_BAR_THICKNESS;
+        horizontal_bar.range.small_change = PIXELS_PER_WHEEL_LINE;
+        let mut vertical_bar = ScrollBar::new();
+        vertical_bar.mode = Orientation::Vertical;
+        vertical_bar.thickness = SCROLL_BAR_THICKNESS;
+        vertical_bar.range.small_change = PIXELS_PER_WHEEL_LINE;
+        ScrollViewer {
+            id: ComponentId::new(),
+            content,
+            horizontal_scroll_bar_visibility: ScrollBarVisibility::Auto,
+            vertical_scroll_bar_visibility: ScrollBarVisibility::Auto,
+            size: Size { width: 0f32, height: 0f32 },
+            viewport: Size { width: 0f32, height: 0f32 },
+            extent: Size { width: 0f32, height: 0f32 },
+            content_size: Size { width: 0f32, height: 0f32 },
+            horizontal_bar,
+            vertical_bar,
+            horizontal_bar_visible: false,
+            vertical_bar_visible: false,
+            pending_scroll: None,
+        }
+    }
+
+    /// # Description
+    /// The position of the content visible in the top left corner of the viewport.
+    pub fn offset(&self) -> Point<f32> {
+        Point {
+            x: match self.horizontal_scroll_bar_visibility {
+                ScrollBarVisibility::Disabled => 0f32,
+                _ => self.horizontal_bar.range.value,
+            },
+            y: match self.vertical_scroll_bar_visibility {
+                ScrollBarVisibility::Disabled => 0f32,
+                _ => self.vertical_bar.range.value,
+            },
+        }
+    }
+
+    /// # Description
+    /// The size of the visible area, as of the last layout pass.
+    pub fn viewport(&self) -> Size<f32> {
+        self.viewport
+    }
+
+    /// # Description
+    /// The desired size of the content, as of the last layout pass.
+    pub fn extent(&self) -> Size<f32> {
+        self.extent
+    }
+
+    /// # Description
+    /// Scrolls the content to *offset*, clamped to the scrollable range.
+    ///
+    /// # Remarks
+    /// The content is scrolled during the next layout pass, once its size is known.
+    pub fn scroll_to(&mut self, offset: Point<f32>) {
+        // An area larger than the viewport is scrolled to its top left corner
+        self.pending_scroll = Some(Rectangle {
+            x: offset.x,
+            y: offset.y,
+            width: f32::INFINITY,
+            height: f32::INFINITY,
+        });
+    }
+
+    /// # Description
+    /// Scrolls the content by the least distance making *area* visible.
+    ///
+    /// # Parameters
+    /// - **area**: The area to make visible, relative to the content.
+    ///
+    /// # Remarks
+    /// The content is scrolled during the next layout pass, once its size is known.
+    /// If *area* is larger than the viewport, its top left corner is made visible.
+    pub fn scroll_into_view(&mut self, area: Rectangle<f32>) {
+        self.pending_scroll = Some(area);
+    }
+
+    fn viewport_for(&self, horizontal_bar: bool, vertical_bar: bool) -> Size<f32> {
+        Size {
+            width: f32::max(0f32, self.size.width - if vertical_bar { SCROLL_BAR_THICKNESS } else { 0f32 }),
+            height: f32::max(0f32, self.size.height - if horizontal_bar { SCROLL_BAR_THICKNESS } else { 0f32 }),
+        }
+    }
+
+    fn content_constraint(&self, viewport: Size<f32>) -> Size<f32> {
+        Size {
+            width: match self.horizontal_scroll_bar_visibility {
+                ScrollBarVisibility::Disabled => viewport.width,
+                _ => f32::INFINITY,
+            },
+            height: match self.vertical_scroll_bar_visibility {
+                ScrollBarVisibility::Disabled => viewport.height,
+                _ => f32::INFINITY,
+            },
+        }
+    }
+
+    fn content_bounds(&self) -> Rectangle<f32> {
+        let offset = self.offset();
+        Rectangle {
+            x: -offset.x,
+            y: -offset.y,
+            width: self.content_size.width,
+            height: self.content_size.height,
+        }
+    }
+
+    fn horizontal_bar_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: self.viewport.height,
+            width: self.viewport.width,
+            height: SCROLL_BAR_THICKNESS,
+        }
+    }
+
+    fn vertical_bar_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: self.viewport.width,
+            y: 0f32,
+            width: SCROLL_BAR_THICKNESS,
+            height: self.viewport.height,
+        }
+    }
+}
+
+/// # Description
+/// The offset along one axis scrolling the least distance to make a span visible.
+///
+/// # Parameters
+/// - **offset**: The current offset.
+/// - **viewport**: The length of the viewport.
+/// - **start**: The start of the span to make visible.
+/// - **length**: The length of the span to make visible.
+fn scroll_target(offset: f32, viewport: f32, start: f32, length: f32) -> f32 {
+    if start < offset || length > viewport {
+        start
+    } else if start + length > offset + viewport {
+        start + length - viewport
+    } else {
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_target() {
+        assert_eq!(scroll_target(100f32, 50f32, 120f32, 10f32), 100f32);
+        assert_eq!(scroll_target(100f32, 50f32, 80f32, 10f32), 80f32);
+        assert_eq!(scroll_target(100f32, 50f32, 145f32, 10f32), 105f32);
+        assert_eq!(scroll_target(100f32, 50f32, 200f32, 80f32), 200f32);
+    }
+}
@@ -1,9 +1,38 @@
-use std::cmp::min;
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
 use crate::abstraction::{Component, Renderer, Visual};
 use crate::color::Color;
-use crate::data::{Orientation, Size};
+use crate::components::RangeValue;
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size};
+use crate::input::{EventContext, InputEvent};
 use crate::visuals;
 
+/// The time an arrow button or the track has to be held before it starts repeating.
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+/// The time between two repetitions of a held arrow button or track.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ScrollBarPart {
+    DecreaseButton,
+    DecreaseTrack,
+    Bar,
+    IncreaseTrack,
+    IncreaseButton,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ScrollBarPress {
+    part: ScrollBarPart,
+    /// Pointer position along the scroll axis when the press started.
+    origin: f32,
+    /// Value of the scroll bar when the press started.
+    origin_value: f32,
+    /// Last known pointer position along the scroll axis.
+    position: f32,
+    next_repeat: Instant,
+}
+
 pub struct ScrollBar {
     /// The orientation of the scroll bar.
     // #[property]
@@ -11,25 +40,29 @@ pub struct ScrollBar {
     /// The thickness of the scroll bar.
     // #[property]
     pub thickness: f32,
-    /// The minimum value of the scroll bar
-    // #[property]
-    pub min_value: f32,
-    /// The actual value of the scroll bar
+    /// The value of the scroll bar.
+    /// An arrow button click or a single mouse wheel line changes the value by its
+    /// *small_change*.
     // #[property]
-    pub value: f32,
-    /// The maximum value of the scroll bar
-    // #[property]
-    pub max_value: f32,
+    pub range: RangeValue,
     /// The value the bar represents.
+    /// Clicking the track pages the value by this amount.
     // #[property]
     pub bar_value: f32,
 
+    id: ComponentId,
     render_thickness: f32,
     size: Size<f32>,
+    bar_total: f32,
     bar_length: f32,
     bar_offset: f32,
+    pressed: Option<ScrollBarPress>,
 }
 impl Component for ScrollBar {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
     fn measure(&self, available: Size<f32>) -> Size<f32> {
         match self.mode {
             Orientation::Horizontal => {
@@ -48,14 +81,10 @@ impl Component for ScrollBar {
     }
 
     fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
-        let p_value = (self.value - self.min_value) / (self.max_value - self.min_value);
-        let p_bar = f32::min(self.bar_value / (self.max_value - self.min_value), 1.0);
         self.size = match self.mode {
             Orientation::Horizontal => {
                 self.render_thickness = f32::min(self.thickness, given.height);
-                let bar_total = given.width - self.render_thickness - self.render_thickness;
-                self.bar_length = bar_total * p_bar;
-                self.bar_offset = (bar_total - self.bar_length) * p_value;
+                self.bar_total = given.width - self.render_thickness - self.render_thickness;
                 Size::<f32> {
                     height: self.render_thickness,
                     width: given.width,
@@ -63,15 +92,14 @@ impl Component for ScrollBar {
             }
             Orientation::Vertical => {
                 self.render_thickness = f32::min(self.thickness, given.width);
-                let bar_total = given.height - self.render_thickness - self.render_thickness;
-                self.bar_length = bar_total * p_bar;
-                self.bar_offset = (bar_total - (self.bar_length)) * p_value;
+                self.bar_total = given.height - self.render_thickness - self.render_thickness;
                 Size::<f32> {
                     height: given.height,
                     width: self.render_thickness,
                 }
             }
         };
+        self.update_bar();
         self.size
     }
 
@@ -109,25 +137,250 @@ impl Component for ScrollBar {
                 &visuals::Rectangle { x: 0f32, y: thickness, width: thickness, height: length - thickness - thickness, fill: Color::GRAY }.draw(renderer);
 
                 // Scroll Bar
-                &visuals::Rectangle { x: 0f32, y: bar_offset, width: thickness, height: bar_length, fill: Color::GOLD }.draw(renderer);
+                &visuals::Rectangle { x: 0f32, y: bar_offset + thickness, width: thickness, height: bar_length, fill: Color::GOLD }.draw(renderer);
             }
         }
     }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerPressed { position, button: MouseButton::Left } => {
+                if !self.bounds().contains(*position) {
+                    return false;
+                }
+                let along = self.along(*position);
+                let part = self.hit_test(along);
+                let press = ScrollBarPress {
+                    part,
+                    origin: along,
+                    origin_value: self.range.value,
+                    position: along,
+                    next_repeat: context.now() + REPEAT_DELAY,
+                };
+                self.pressed = Some(press);
+                if part != ScrollBarPart::Bar {
+                    self.apply(part, along);
+                    context.request_wake_up(press.next_repeat);
+                }
+                context.capture_pointer();
+                true
+            }
+            InputEvent::PointerMoved { position } => {
+                let mut press = match self.pressed {
+                    None => return false,
+                    Some(d) => d,
+                };
+                press.position = self.along(*position);
+                self.pressed = Some(press);
+                if press.part == ScrollBarPart::Bar {
+                    let movable = self.bar_total - self.bar_length;
+                    if movable > 0f32 {
+                        let delta = (press.position - press.origin) / movable
+                            * self.range.range();
+                        self.set_value(press.origin_value + delta);
+                    }
+                }
+                true
+            }
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                if self.pressed.is_none() {
+                    return false;
+                }
+                self.pressed = None;
+                context.release_pointer_capture();
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.pressed = None;
+                true
+            }
+            InputEvent::PointerWheel { position, delta } => {
+                if !self.bounds().contains(*position) {
+                    return false;
+                }
+                let lines = match self.mode {
+                    Orientation::Horizontal if delta.x != 0f32 => delta.x,
+                    _ => delta.y,
+                };
+                self.set_value(self.range.value - lines * self.range.small_change);
+                true
+            }
+            InputEvent::Tick => {
+                let mut press = match self.pressed {
+                    Some(d) if d.part != ScrollBarPart::Bar => d,
+                    _ => return false,
+                };
+                if context.now() >= press.next_repeat {
+                    self.apply(press.part, press.position);
+                    press.next_repeat = context.now() + REPEAT_INTERVAL;
+                    self.pressed = Some(press);
+                }
+                context.request_wake_up(press.next_repeat);
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl ScrollBar {
     pub fn new() -> ScrollBar {
         ScrollBar {
+            id: ComponentId::new(),
             bar_offset: 0f32,
+            range: RangeValue::new(),
             bar_value: 0f32,
-            max_value: 0f32,
-            value: 0f32,
+            bar_total: 0f32,
             bar_length: 0f32,
             size: Size { width: 0f32, height: 0f32 },
             mode: Orientation::Horizontal,
             thickness: 0f32,
             render_thickness: 0f32,
-            min_value: 0f32,
+            pressed: None,
+        }
+    }
+
+    /// # Description
+    /// Sets the value of the scroll bar, see [`RangeValue::set_value`].
+    pub fn set_value(&mut self, value: f32) {
+        if self.range.set_value(value) {
+            self.update_bar();
+        }
+    }
+
+    fn update_bar(&mut self) {
+        let range = self.range.range();
+        let (p_value, p_bar) = if range > 0f32 {
+            (self.range.ratio(), f32::min(self.bar_value / range, 1.0))
+        } else {
+            (0f32, 1f32)
+        };
+        self.bar_length = self.bar_total * p_bar;
+        self.bar_offset = (self.bar_total - self.bar_length) * p_value;
+    }
+
+    fn bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+        }
+    }
+
+    /// Projects *position* onto the scroll axis.
+    fn along(&self, position: Point<f32>) -> f32 {
+        match self.mode {
+            Orientation::Horizontal => position.x,
+            Orientation::Vertical => position.y,
+        }
+    }
+
+    fn hit_test(&self, along: f32) -> ScrollBarPart {
+        let thickness = self.render_thickness;
+        let length = self.bar_total + thickness + thickness;
+        if along < thickness {
+            ScrollBarPart::DecreaseButton
+        } else if along >= length - thickness {
+            ScrollBarPart::IncreaseButton
+        } else if along < thickness + self.bar_offset {
+            ScrollBarPart::DecreaseTrack
+        } else if along < thickness + self.bar_offset + self.bar_length {
+            ScrollBarPart::Bar
+        } else {
+            ScrollBarPart::IncreaseTrack
+        }
+    }
+
+    /// Applies a click on *part*, with the pointer being at *along* on the scroll axis.
+    fn apply(&mut self, part: ScrollBarPart, along: f32) {
+        let bar_start = self.render_thickness + self.bar_offset;
+        match part {
+            ScrollBarPart::DecreaseButton => self.set_value(self.range.value - self.range.small_change),
+            ScrollBarPart::IncreaseButton => self.set_value(self.range.value + self.range.small_change),
+            // Paging stops once the bar reached the pointer
+            ScrollBarPart::DecreaseTrack if along < bar_start => {
+                self.set_value(self.range.value - self.bar_value)
+            }
+            ScrollBarPart::IncreaseTrack if along >= bar_start + self.bar_length => {
+                self.set_value(self.range.value + self.bar_value)
+            }
+            _ => {}
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use winit::keyboard::ModifiersState;
+
+    /// A horizontal scroll bar over 0 to 100 with a track of 100 and a bar of 25 pixels.
+    fn scroll_bar() -> ScrollBar {
+        let mut scroll_bar = ScrollBar::new();
+        scroll_bar.thickness = 10f32;
+        scroll_bar.range.max_value = 100f32;
+        scroll_bar.bar_value = 25f32;
+        scroll_bar.arrange(Size { width: 120f32, height: 10f32 });
+        scroll_bar
+    }
+
+    fn send(scroll_bar: &mut ScrollBar, event: InputEvent, now: Instant) {
+        let mut context = EventContext::new(now, &PointerState::new(), None, ModifiersState::empty());
+        scroll_bar.handle_event(&event, &mut context);
+    }
+
+    fn at(x: f32) -> Point<f32> {
+        Point { x, y: 5f32 }
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut scroll_bar = scroll_bar();
+        let now = Instant::now();
+        send(&mut scroll_bar, InputEvent::PointerPressed { position: at(20f32), button: MouseButton::Left }, now);
+        assert_eq!(scroll_bar.range.value, 0f32);
+        // The bar can move 75 pixels, so half of that is half of the range
+        send(&mut scroll_bar, InputEvent::PointerMoved { position: at(57.5f32) }, now);
+        assert_eq!(scroll_bar.range.value, 50f32);
+        send(&mut scroll_bar, InputEvent::PointerMoved { position: at(500f32) }, now);
+        assert_eq!(scroll_bar.range.value, 100f32);
+        send(&mut scroll_bar, InputEvent::PointerReleased { position: at(500f32), button: MouseButton::Left }, now);
+        send(&mut scroll_bar, InputEvent::PointerMoved { position: at(20f32) }, now);
+        assert_eq!(scroll_bar.range.value, 100f32);
+    }
+
+    #[test]
+    fn test_paging() {
+        let mut scroll_bar = scroll_bar();
+        let now = Instant::now();
+        send(&mut scroll_bar, InputEvent::PointerPressed { position: at(60f32), button: MouseButton::Left }, now);
+        assert_eq!(scroll_bar.range.value, 25f32);
+        send(&mut scroll_bar, InputEvent::Tick, now + REPEAT_DELAY / 2);
+        assert_eq!(scroll_bar.range.value, 25f32);
+        send(&mut scroll_bar, InputEvent::Tick, now + REPEAT_DELAY);
+        assert_eq!(scroll_bar.range.value, 50f32);
+        // The bar covers the pointer now, so holding the track stops paging
+        send(&mut scroll_bar, InputEvent::Tick, now + REPEAT_DELAY + REPEAT_INTERVAL);
+        assert_eq!(scroll_bar.range.value, 50f32);
+        send(&mut scroll_bar, InputEvent::PointerReleased { position: at(60f32), button: MouseButton::Left }, now);
+
+        send(&mut scroll_bar, InputEvent::PointerPressed { position: at(5f32), button: MouseButton::Left }, now);
+        assert_eq!(scroll_bar.range.value, 49f32);
+    }
+
+    #[test]
+    fn test_wheel() {
+        let mut scroll_bar = scroll_bar();
+        scroll_bar.set_value(50f32);
+        let now = Instant::now();
+        send(&mut scroll_bar, InputEvent::PointerWheel { position: at(60f32), delta: Point { x: 0f32, y: 2f32 } }, now);
+        assert_eq!(scroll_bar.range.value, 48f32);
+        send(&mut scroll_bar, InputEvent::PointerWheel { position: at(60f32), delta: Point { x: -3f32, y: 2f32 } }, now);
+        assert_eq!(scroll_bar.range.value, 51f32);
+        // Wheeling outside of the scroll bar leaves it to the parent
+        send(&mut scroll_bar, InputEvent::PointerWheel { position: at(200f32), delta: Point { x: 0f32, y: 2f32 } }, now);
+        assert_eq!(scroll_bar.range.value, 51f32);
+    }
+}
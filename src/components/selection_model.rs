@@ -0,0 +1,262 @@
+use std::collections::BTreeSet;
+
+/// # Description
+/// Controls how many items of a component like [`super::ListView`] can be selected and how the
+/// user selects them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SelectionMode {
+    /// At most one item is selected.
+    Single,
+    /// Clicking an item or pressing Space toggles its selection, without affecting other items.
+    Multiple,
+    /// Clicking an item selects only it, Ctrl toggles single items and Shift selects the range
+    /// from the last clicked item.
+    Extended,
+}
+
+/// # Description
+/// Tracks the selected items of a list, by their index.
+///
+/// # Remarks
+/// Besides the selected items, the model tracks the *current* item, which the keyboard
+/// navigates, and the *anchor* item, which ranges selected using Shift start from.
+/// Every method changing the selection returns whether it changed.
+pub struct SelectionModel {
+    // #[property]
+    pub mode: SelectionMode,
+
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+    current: Option<usize>,
+}
+
+impl SelectionModel {
+    pub fn new(mode: SelectionMode) -> SelectionModel {
+        SelectionModel {
+            mode,
+            selected: BTreeSet::new(),
+            anchor: None,
+            current: None,
+        }
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// # Description
+    /// The indices of the selected items, in ascending order.
+    pub fn selected(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    /// # Description
+    /// The item navigated to using the keyboard or clicked last.
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// # Description
+    /// Selects the item at *index* only, or clears the selection if `None`.
+    pub fn select(&mut self, index: Option<usize>) -> bool {
+        let previous = self.selected.clone();
+        self.selected.clear();
+        self.selected.extend(index);
+        self.anchor = index;
+        self.current = index;
+        self.selected != previous
+    }
+
+    /// # Description
+    /// Applies a click on the item at *index*.
+    ///
+    /// # Parameters
+    /// - **toggle**: Whether Ctrl was held.
+    /// - **extend**: Whether Shift was held.
+    pub fn click(&mut self, index: usize, toggle: bool, extend: bool) -> bool {
+        let previous = self.selected.clone();
+        match self.mode {
+            SelectionMode::Single => {
+                let deselect = toggle && self.is_selected(index);
+                self.selected.clear();
+                if !deselect {
+                    self.selected.insert(index);
+                }
+                self.anchor = Some(index);
+            }
+            SelectionMode::Multiple => {
+                self.toggle(index);
+                self.anchor = Some(index);
+            }
+            SelectionMode::Extended if extend => self.select_range(index, toggle),
+            SelectionMode::Extended if toggle => {
+                self.toggle(index);
+                self.anchor = Some(index);
+            }
+            SelectionMode::Extended => {
+                self.selected.clear();
+                self.selected.insert(index);
+                self.anchor = Some(index);
+            }
+        }
+        self.current = Some(index);
+        self.selected != previous
+    }
+
+    /// # Description
+    /// Moves the current item to *index* using the keyboard.
+    ///
+    /// # Parameters
+    /// - **toggle**: Whether Ctrl was held, moving only the current item in extended mode.
+    /// - **extend**: Whether Shift was held, selecting the range from the anchor in extended mode.
+    ///
+    /// # Remarks
+    /// In multiple mode, only the current item moves and Space toggles its selection, see
+    /// [`SelectionModel::toggle_current`].
+    pub fn navigate(&mut self, index: usize, toggle: bool, extend: bool) -> bool {
+        match self.mode {
+            SelectionMode::Single => self.select(Some(index)),
+            SelectionMode::Multiple => {
+                self.current = Some(index);
+                false
+            }
+            SelectionMode::Extended if extend => {
+                let previous = self.selected.clone();
+                self.select_range(index, toggle);
+                self.current = Some(index);
+                self.selected != previous
+            }
+            SelectionMode::Extended if toggle => {
+                self.current = Some(index);
+                false
+            }
+            SelectionMode::Extended => self.select(Some(index)),
+        }
+    }
+
+    /// # Description
+    /// Toggles the selection of the current item, as done by pressing Space.
+    ///
+    /// # Remarks
+    /// In single mode, the current item is selected instead.
+    pub fn toggle_current(&mut self) -> bool {
+        let index = match self.current {
+            None => return false,
+            Some(d) => d,
+        };
+        match self.mode {
+            SelectionMode::Single => self.select(Some(index)),
+            SelectionMode::Multiple | SelectionMode::Extended => {
+                self.toggle(index);
+                self.anchor = Some(index);
+                true
+            }
+        }
+    }
+
+    /// # Description
+    /// Selects every one of *count* items, unless in single mode.
+    pub fn select_all(&mut self, count: usize) -> bool {
+        if self.mode == SelectionMode::Single || self.selected.len() == count {
+            return false;
+        }
+        self.selected.extend(0..count);
+        true
+    }
+
+    /// # Description
+    /// Removes the items beyond *count* after items were removed.
+    pub fn coerce(&mut self, count: usize) -> bool {
+        self.anchor = self.anchor.filter(|d| *d < count);
+        self.current = self.current.filter(|d| *d < count);
+        let removed = self.selected.split_off(&count);
+        !removed.is_empty()
+    }
+
+    /// # Description
+    /// Moves the selection along with the items after they were reordered.
+    ///
+    /// # Parameters
+    /// - **map**: Maps the previous index of an item to its new index.
+    pub fn remap(&mut self, map: impl Fn(usize) -> usize) {
+        self.selected = self.selected.iter().map(|d| map(*d)).collect();
+        self.anchor = self.anchor.map(&map);
+        self.current = self.current.map(&map);
+    }
+
+    fn toggle(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+    }
+
+    /// Selects the items from the anchor to *index*, keeping the other selected items if *add*.
+    fn select_range(&mut self, index: usize, add: bool) {
+        let anchor = *self.anchor.get_or_insert(index);
+        if !add {
+            self.selected.clear();
+        }
+        self.selected.extend(usize::min(anchor, index)..=usize::max(anchor, index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_selection() {
+        let mut model = SelectionModel::new(SelectionMode::Extended);
+        assert!(model.click(2, false, false));
+        assert!(model.click(5, false, true));
+        assert_eq!(model.selected(), vec![2, 3, 4, 5]);
+        assert!(model.click(3, true, false));
+        assert_eq!(model.selected(), vec![2, 4, 5]);
+        // The anchor moved to the ctrl-clicked item
+        assert!(model.click(1, false, true));
+        assert_eq!(model.selected(), vec![1, 2, 3]);
+        assert!(model.navigate(4, true, true));
+        assert_eq!(model.selected(), vec![1, 2, 3, 4]);
+        assert!(!model.navigate(0, true, false));
+        assert_eq!(model.current(), Some(0));
+        assert!(model.toggle_current());
+        assert_eq!(model.selected(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_multiple_selection() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        assert!(model.click(1, false, false));
+        assert!(model.click(3, false, false));
+        assert!(!model.navigate(2, false, false));
+        assert_eq!(model.selected(), vec![1, 3]);
+        assert!(model.toggle_current());
+        assert!(model.click(1, false, false));
+        assert_eq!(model.selected(), vec![2, 3]);
+        assert!(model.coerce(3));
+        assert_eq!(model.selected(), vec![2]);
+    }
+
+    #[test]
+    fn test_remap() {
+        let mut model = SelectionModel::new(SelectionMode::Extended);
+        model.click(0, false, false);
+        model.click(1, false, true);
+        model.remap(|d| 3 - d);
+        assert_eq!(model.selected(), vec![2, 3]);
+        assert_eq!(model.current(), Some(2));
+        assert!(model.click(0, false, true));
+        assert_eq!(model.selected(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_single_selection() {
+        let mut model = SelectionModel::new(SelectionMode::Single);
+        assert!(model.click(1, false, true));
+        assert!(model.navigate(2, false, true));
+        assert_eq!(model.selected(), vec![2]);
+        assert!(!model.select_all(5));
+        assert!(model.click(2, true, false));
+        assert_eq!(model.selected(), vec![]);
+    }
+}
@@ -0,0 +1,416 @@
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::RangeValue;
+use crate::data::{ComponentId, Orientation, Point, Size};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::tool_tip::ToolTip;
+use crate::visuals;
+
+/// # Description
+/// Selects a value from a range by dragging a thumb along a track.
+///
+/// # Remarks
+/// Vertical sliders have their minimum at the bottom.
+pub struct Slider {
+    /// The value of the slider.
+    /// The arrow keys and a single mouse wheel line change the value by its *small_change*.
+    // #[property]
+    pub range: RangeValue,
+    /// The orientation of the slider.
+    // #[property]
+    pub mode: Orientation,
+    /// The value page up and page down change the value by.
+    // #[property]
+    pub large_change: f32,
+    /// The distance between two ticks, or `0` to show no ticks.
+    // #[property]
+    pub tick_frequency: f32,
+    /// Whether the value is rounded to the closest tick.
+    // #[property]
+    pub snap_to_ticks: bool,
+    // #[property]
+    pub enabled: bool,
+    /// The diameter of the thumb.
+    // #[property]
+    pub thumb_size: f32,
+    /// The thickness of the track.
+    // #[property]
+    pub track_thickness: f32,
+    /// The length of a tick mark.
+    // #[property]
+    pub tick_length: f32,
+    // #[property]
+    pub track: Color,
+    /// The color of the part of the track between the minimum and the value.
+    // #[property]
+    pub fill: Color,
+    // #[property]
+    pub thumb: Color,
+    // #[property]
+    pub thumb_border: Color,
+    // #[property]
+    pub hover_border: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub tick: Color,
+    // #[property]
+    pub disabled_foreground: Color,
+    /// The tool tip shown while the pointer hovers the slider.
+    // #[property]
+    pub tool_tip: Option<ToolTip>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    hovered: bool,
+    focused: bool,
+    dragging: bool,
+}
+
+impl Component for Slider {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let thickness = self.thickness();
+        match self.mode {
+            Orientation::Horizontal => Size { width: available.width, height: thickness },
+            Orientation::Vertical => Size { width: thickness, height: available.height },
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        let thickness = f32::min(self.thickness(), match self.mode {
+            Orientation::Horizontal => given.height,
+            Orientation::Vertical => given.width,
+        });
+        self.size = match self.mode {
+            Orientation::Horizontal => Size { width: given.width, height: thickness },
+            Orientation::Vertical => Size { width: thickness, height: given.height },
+        };
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let thumb_radius = self.thumb_size / 2f32;
+        let track_offset = thumb_radius - self.track_thickness / 2f32;
+        let value = self.position(self.range.ratio());
+        let (start, end) = (self.position(0f32), self.position(1f32));
+        let fill = if self.enabled { self.fill } else { self.disabled_foreground };
+        let track = |from: f32, to: f32, color: Color, renderer: &mut dyn Renderer| {
+            let (from, to) = (f32::min(from, to), f32::max(from, to));
+            let (x, y, width, height) = match self.mode {
+                Orientation::Horizontal => (from, track_offset, to - from, self.track_thickness),
+                Orientation::Vertical => (track_offset, from, self.track_thickness, to - from),
+            };
+            visuals::RoundedRectangle {
+                x,
+                y,
+                width,
+                height,
+                radius: self.track_thickness / 2f32,
+                fill: color,
+                stroke: Color::TRANSPARENT,
+                thickness: 0f32,
+            }
+            .draw(renderer);
+        };
+        track(start, end, self.track, renderer);
+        track(start, value, fill, renderer);
+
+        if self.tick_frequency > 0f32 && self.range.range() > 0f32 {
+            let count = (self.range.range() / self.tick_frequency).floor() as usize;
+            let offset = self.thumb_size + 1f32;
+            for index in 0..=count {
+                let ratio = f32::min(1f32, index as f32 * self.tick_frequency / self.range.range());
+                let along = self.position(ratio);
+                let (x, y, width, height) = match self.mode {
+                    Orientation::Horizontal => (along - 0.5f32, offset, 1f32, self.tick_length),
+                    Orientation::Vertical => (offset, along - 0.5f32, self.tick_length, 1f32),
+                };
+                visuals::Rectangle { x, y, width, height, fill: self.tick }.draw(renderer);
+            }
+        }
+
+        let (x, y) = match self.mode {
+            Orientation::Horizontal => (value - thumb_radius, 0f32),
+            Orientation::Vertical => (0f32, value - thumb_radius),
+        };
+        visuals::RoundedRectangle {
+            x,
+            y,
+            width: self.thumb_size,
+            height: self.thumb_size,
+            radius: thumb_radius,
+            fill: if self.enabled { self.thumb } else { self.disabled_foreground },
+            stroke: if !self.enabled {
+                self.disabled_foreground
+            } else if self.focused {
+                self.focused_border
+            } else if self.hovered || self.dragging {
+                self.hover_border
+            } else {
+                self.thumb_border
+            },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerEntered => {
+                self.hovered = true;
+                true
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = false;
+                true
+            }
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.dragging = false;
+                true
+            }
+            _ if !self.enabled => false,
+            InputEvent::PointerPressed { position, button: MouseButton::Left } => {
+                self.dragging = true;
+                context.capture_pointer();
+                self.drag_to(*position);
+                true
+            }
+            InputEvent::PointerMoved { position } if self.dragging => {
+                self.drag_to(*position);
+                true
+            }
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } if self.dragging => {
+                self.dragging = false;
+                context.release_pointer_capture();
+                true
+            }
+            InputEvent::PointerWheel { delta, .. } => {
+                let lines = match self.mode {
+                    Orientation::Horizontal if delta.x != 0f32 => delta.x,
+                    _ => delta.y,
+                };
+                self.step(lines * self.range.small_change);
+                true
+            }
+            InputEvent::KeyPressed { key: Key::Named(key), .. } => {
+                let small = self.range.small_change;
+                match key {
+                    NamedKey::ArrowLeft | NamedKey::ArrowDown => self.step(-small),
+                    NamedKey::ArrowRight | NamedKey::ArrowUp => self.step(small),
+                    NamedKey::PageDown => self.step(-self.large_change),
+                    NamedKey::PageUp => self.step(self.large_change),
+                    NamedKey::Home => {
+                        self.range.set_value(self.range.min_value);
+                    }
+                    NamedKey::End => {
+                        self.range.set_value(self.range.max_value);
+                    }
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+
+    fn tool_tip(&self) -> Option<&ToolTip> {
+        self.tool_tip.as_ref()
+    }
+}
+
+impl Slider {
+    pub fn new() -> Slider {
+        let mut range = RangeValue::new();
+        range.max_value = 100f32;
+        Slider {
+            id: ComponentId::new(),
+            range,
+            mode: Orientation::Horizontal,
+            large_change: 10f32,
+            tick_frequency: 0f32,
+            snap_to_ticks: false,
+            enabled: true,
+            thumb_size: 16f32,
+            track_thickness: 4f32,
+            tick_length: 4f32,
+            track: Color::LIGHT_GRAY,
+            fill: Color::CORNFLOWER_BLUE,
+            thumb: Color::WHITE,
+            thumb_border: Color::GRAY,
+            hover_border: Color::DIM_GRAY,
+            focused_border: Color::MIDNIGHT_BLUE,
+            tick: Color::GRAY,
+            disabled_foreground: Color::GRAY,
+            tool_tip: None,
+            size: Size { width: 0f32, height: 0f32 },
+            hovered: false,
+            focused: false,
+            dragging: false,
+        }
+    }
+
+    /// # Description
+    /// Sets the value of the slider, rounded to the closest tick if *snap_to_ticks* is set.
+    pub fn set_value(&mut self, value: f32) {
+        let value = self.coerce(value);
+        self.range.set_value(value);
+    }
+
+    fn coerce(&self, value: f32) -> f32 {
+        if self.snap_to_ticks {
+            self.range.snap(value, self.tick_frequency)
+        } else {
+            self.range.clamp(value)
+        }
+    }
+
+    /// Changes the value by *delta*, moving at least one tick if snapping to ticks.
+    fn step(&mut self, delta: f32) {
+        let delta = if self.snap_to_ticks && self.tick_frequency > 0f32 {
+            f32::max(delta.abs(), self.tick_frequency).copysign(delta)
+        } else {
+            delta
+        };
+        self.set_value(self.range.value + delta);
+    }
+
+    fn drag_to(&mut self, position: Point<f32>) {
+        let length = self.length() - self.thumb_size;
+        if length <= 0f32 {
+            return;
+        }
+        let ratio = match self.mode {
+            Orientation::Horizontal => (position.x - self.thumb_size / 2f32) / length,
+            Orientation::Vertical => 1f32 - (position.y - self.thumb_size / 2f32) / length,
+        };
+        self.set_value(self.range.value_at(f32::max(0f32, f32::min(ratio, 1f32))));
+    }
+
+    /// The thickness of the slider, including the ticks.
+    fn thickness(&self) -> f32 {
+        if self.tick_frequency > 0f32 {
+            self.thumb_size + 1f32 + self.tick_length
+        } else {
+            self.thumb_size
+        }
+    }
+
+    fn length(&self) -> f32 {
+        match self.mode {
+            Orientation::Horizontal => self.size.width,
+            Orientation::Vertical => self.size.height,
+        }
+    }
+
+    /// The position of the center of the thumb along the track at *ratio* of the range.
+    fn position(&self, ratio: f32) -> f32 {
+        let length = f32::max(0f32, self.length() - self.thumb_size);
+        match self.mode {
+            Orientation::Horizontal => self.thumb_size / 2f32 + ratio * length,
+            Orientation::Vertical => self.thumb_size / 2f32 + (1f32 - ratio) * length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::PointerState;
+    use std::time::Instant;
+    use winit::keyboard::ModifiersState;
+
+    /// A horizontal slider over 0 to 100, its thumb moving 100 pixels.
+    fn slider() -> Slider {
+        let mut slider = Slider::new();
+        slider.arrange(Size { width: 116f32, height: 16f32 });
+        slider
+    }
+
+    fn send(slider: &mut Slider, event: InputEvent) -> bool {
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+        slider.handle_event(&event, &mut context)
+    }
+
+    fn press(slider: &mut Slider, key: NamedKey) -> bool {
+        let event = InputEvent::KeyPressed {
+            key: Key::Named(key),
+            text: None,
+            modifiers: ModifiersState::empty(),
+            repeat: false,
+        };
+        send(slider, event)
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut slider = slider();
+        send(&mut slider, InputEvent::PointerPressed { position: Point { x: 58f32, y: 8f32 }, button: MouseButton::Left });
+        assert_eq!(slider.range.value, 50f32);
+        send(&mut slider, InputEvent::PointerMoved { position: Point { x: 33f32, y: 8f32 } });
+        assert_eq!(slider.range.value, 25f32);
+        send(&mut slider, InputEvent::PointerMoved { position: Point { x: 500f32, y: 8f32 } });
+        assert_eq!(slider.range.value, 100f32);
+        send(&mut slider, InputEvent::PointerReleased { position: Point { x: 500f32, y: 8f32 }, button: MouseButton::Left });
+        send(&mut slider, InputEvent::PointerMoved { position: Point { x: 8f32, y: 8f32 } });
+        assert_eq!(slider.range.value, 100f32);
+
+        slider.enabled = false;
+        assert!(!send(&mut slider, InputEvent::PointerPressed { position: Point { x: 8f32, y: 8f32 }, button: MouseButton::Left }));
+        assert_eq!(slider.range.value, 100f32);
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let mut slider = slider();
+        press(&mut slider, NamedKey::ArrowRight);
+        assert_eq!(slider.range.value, 1f32);
+        press(&mut slider, NamedKey::PageUp);
+        assert_eq!(slider.range.value, 11f32);
+        press(&mut slider, NamedKey::ArrowDown);
+        assert_eq!(slider.range.value, 10f32);
+        press(&mut slider, NamedKey::End);
+        assert_eq!(slider.range.value, 100f32);
+        press(&mut slider, NamedKey::Home);
+        assert_eq!(slider.range.value, 0f32);
+        assert!(!press(&mut slider, NamedKey::Enter));
+        send(&mut slider, InputEvent::PointerWheel { position: Point { x: 8f32, y: 8f32 }, delta: Point { x: 0f32, y: 3f32 } });
+        assert_eq!(slider.range.value, 3f32);
+    }
+
+    #[test]
+    fn test_snap_to_ticks() {
+        let mut slider = slider();
+        slider.tick_frequency = 10f32;
+        slider.snap_to_ticks = true;
+        send(&mut slider, InputEvent::PointerPressed { position: Point { x: 62f32, y: 8f32 }, button: MouseButton::Left });
+        assert_eq!(slider.range.value, 50f32);
+        // A step smaller than a tick still moves to the next tick
+        press(&mut slider, NamedKey::ArrowRight);
+        assert_eq!(slider.range.value, 60f32);
+        slider.set_value(74f32);
+        assert_eq!(slider.range.value, 70f32);
+    }
+}
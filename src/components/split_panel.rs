@@ -0,0 +1,646 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use winit::event::MouseButton;
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size};
+use crate::input::{EventContext, InputEvent};
+use crate::visuals;
+
+
+/// # Description
+/// A pane of a [`SplitPanel`].
+pub struct SplitPane {
+    // #[property]
+    pub content: Box<dyn Component>,
+    // #[property]
+    pub min_size: f32,
+    // #[property]
+    pub max_size: f32,
+    /// The share of the available space the pane initially gets, relative to the other panes.
+    // #[property]
+    pub weight: f32,
+
+    is_collapsed: bool,
+    size: f32,
+    /// The size of the pane before it was collapsed.
+    restore_size: f32,
+}
+
+impl SplitPane {
+    pub fn new(content: Box<dyn Component>) -> SplitPane {
+        SplitPane {
+            content,
+            min_size: 0f32,
+            max_size: f32::INFINITY,
+            weight: 1f32,
+            is_collapsed: false,
+            size: 0f32,
+            restore_size: 0f32,
+        }
+    }
+
+    pub fn with_min_size(mut self, min_size: f32) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: f32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.is_collapsed
+    }
+
+    /// # Description
+    /// The size of the pane along the orientation of its panel, as of the last layout pass.
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    fn limits(&self) -> (f32, f32) {
+        if self.is_collapsed {
+            (0f32, 0f32)
+        } else {
+            (self.min_size, f32::max(self.min_size, self.max_size))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SplitPanelLayoutErrors {
+    Empty,
+    InvalidRatio(String),
+}
+
+impl Display for SplitPanelLayoutErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SplitPanelLayoutErrors::Empty => write!(f, "Split panel layout is empty"),
+            SplitPanelLayoutErrors::InvalidRatio(d) => write!(f, "Invalid pane ratio ({})", d),
+        }
+    }
+}
+
+impl Error for SplitPanelLayoutErrors {}
+
+/// # Description
+/// The splitter positions of a [`SplitPanel`], eg. to persist them between sessions.
+///
+/// # Remarks
+/// The layout displays and parses as the comma separated ratios of the panes, with collapsed
+/// panes in parentheses, eg. `0.25, (0.5), 0.25`. The ratio of a collapsed pane is the one it
+/// is restored to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitPanelLayout {
+    /// The share of every pane of the space available to the panes.
+    pub ratios: Vec<f32>,
+    pub collapsed: Vec<bool>,
+}
+
+impl SplitPanelLayout {
+    pub fn parse(text: &str) -> Result<SplitPanelLayout, SplitPanelLayoutErrors> {
+        if text.trim().is_empty() {
+            return Err(SplitPanelLayoutErrors::Empty);
+        }
+        let mut layout = SplitPanelLayout { ratios: vec![], collapsed: vec![] };
+        for part in text.split(',').map(|d| d.trim()) {
+            let (ratio, collapsed) = match part.strip_prefix('(').and_then(|d| d.strip_suffix(')')) {
+                Some(d) => (d.trim(), true),
+                None => (part, false),
+            };
+            let ratio = match ratio.parse::<f32>() {
+                Ok(d) if d.is_finite() && d >= 0f32 => d,
+                _ => return Err(SplitPanelLayoutErrors::InvalidRatio(part.to_string())),
+            };
+            layout.ratios.push(ratio);
+            layout.collapsed.push(collapsed);
+        }
+        Ok(layout)
+    }
+}
+
+impl Display for SplitPanelLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, (ratio, collapsed)) in self.ratios.iter().zip(self.collapsed.iter()).enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            if *collapsed {
+                write!(f, "({})", ratio)?;
+            } else {
+                write!(f, "{}", ratio)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// # Description
+/// Arranges its panes next to each other, separated by splitters the user drags to resize them.
+///
+/// # Remarks
+/// The panes are placed side by side for [`Orientation::Horizontal`] and stacked for
+/// [`Orientation::Vertical`]. When the panel is resized, its panes keep their proportions
+/// within their minimum and maximum sizes.
+/// Double-clicking a splitter collapses the pane before it, or restores a collapsed pane next
+/// to it.
+pub struct SplitPanel {
+    // #[property]
+    pub panes: Vec<SplitPane>,
+    // #[property]
+    pub orientation: Orientation,
+    // #[property]
+    pub splitter_thickness: f32,
+    // #[property]
+    pub splitter: Color,
+    // #[property]
+    pub hover_splitter: Color,
+    /// Called after the user moved a splitter or collapsed or restored a pane.
+    // #[event]
+    pub on_layout_changed: Option<Box<dyn FnMut(&SplitPanelLayout)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    /// The space available to the panes in the last layout pass, excluding the splitters.
+    length: f32,
+    /// The number of panes in the last layout pass.
+    pane_count: usize,
+    pending_layout: Option<SplitPanelLayout>,
+    hovered: Option<usize>,
+    /// The dragged splitter, the pointer position and the pane sizes when the drag started.
+    drag: Option<(usize, f32, Vec<f32>)>,
+    /// The splitter pressed last and the click count of that press.
+    last_click: Option<(usize, usize)>,
+}
+
+impl Component for SplitPanel {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        available
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let splitters = self.panes.len().saturating_sub(1) as f32 * self.splitter_thickness;
+        let length = f32::max(0f32, self.along(given) - splitters);
+        if self.panes.len() != self.pane_count {
+            // Panes were added or removed, the space is shared anew
+            self.pane_count = self.panes.len();
+            self.length = 0f32;
+        }
+        if let Some(layout) = self.pending_layout.take() {
+            if layout.ratios.len() == self.panes.len() {
+                let entries = layout.ratios.iter().zip(layout.collapsed);
+                for (pane, (ratio, collapsed)) in self.panes.iter_mut().zip(entries) {
+                    pane.is_collapsed = collapsed;
+                    pane.restore_size = ratio * length;
+                    pane.size = if collapsed { 0f32 } else { ratio * length };
+                }
+                self.length = length;
+            }
+        }
+        if self.length <= 0f32 {
+            // The panes share the space by their weights
+            let total: f32 = self.panes.iter().filter(|d| !d.is_collapsed).map(|d| d.weight).sum();
+            for pane in self.panes.iter_mut().filter(|d| !d.is_collapsed) {
+                pane.size = if total > 0f32 { length * pane.weight / total } else { 0f32 };
+            }
+        } else if length != self.length {
+            let factor = length / self.length;
+            for pane in self.panes.iter_mut() {
+                pane.size *= factor;
+                pane.restore_size *= factor;
+            }
+        }
+        self.length = length;
+        self.fit();
+
+        let mut position = 0f32;
+        for index in 0..self.panes.len() {
+            let bounds = self.pane_bounds(index, position);
+            let pane = &mut self.panes[index];
+            let size = Size { width: bounds.width, height: bounds.height };
+            pane.content.measure(size);
+            pane.content.arrange(size);
+            position += pane.size + self.splitter_thickness;
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let mut position = 0f32;
+        for (index, pane) in self.panes.iter().enumerate() {
+            let bounds = self.pane_bounds(index, position);
+            if !pane.is_collapsed {
+                renderer.push_clip(bounds.x, bounds.y, bounds.width, bounds.height);
+                renderer.translate(bounds.x, bounds.y);
+                pane.content.render(renderer);
+                renderer.translate(-bounds.x, -bounds.y);
+                renderer.pop_clip();
+            }
+            position += pane.size;
+            if index + 1 < self.panes.len() {
+                let splitter = self.splitter_bounds(position);
+                let fill = if self.hovered == Some(index) || self.drag.as_ref().map(|d| d.0) == Some(index) {
+                    self.hover_splitter
+                } else {
+                    self.splitter
+                };
+                visuals::Rectangle {
+                    x: splitter.x,
+                    y: splitter.y,
+                    width: splitter.width,
+                    height: splitter.height,
+                    fill,
+                }
+                .draw(renderer);
+            }
+            position += self.splitter_thickness;
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::PointerMoved { position } => {
+                let (index, origin, sizes) = match &self.drag {
+                    None => {
+                        self.hovered = self.splitter_at(*position);
+                        return false;
+                    }
+                    Some(d) => d.clone(),
+                };
+                let limits: Vec<(f32, f32)> = self.panes.iter().map(|d| d.limits()).collect();
+                let mut sizes = sizes;
+                resize(&mut sizes, &limits, index, self.along_point(*position) - origin);
+                for (pane, size) in self.panes.iter_mut().zip(sizes) {
+                    pane.size = size;
+                }
+                true
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = None;
+                false
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left } => {
+                let index = match self.splitter_at(*position) {
+                    None => return false,
+                    Some(d) => d,
+                };
+                // Pressing two different splitters in quick succession is no double-click
+                let count = context.click_count();
+                let double_click = self
+                    .last_click
+                    .map_or(false, |(last, last_count)| last == index && last_count + 1 == count);
+                self.last_click = if double_click { None } else { Some((index, count)) };
+                if double_click {
+                    if self.panes[index].is_collapsed {
+                        self.expand(index);
+                    } else if self.panes[index + 1].is_collapsed {
+                        self.expand(index + 1);
+                    } else {
+                        self.collapse(index);
+                    }
+                    self.notify_layout_changed();
+                } else {
+                    let sizes = self.panes.iter().map(|d| d.size).collect();
+                    self.drag = Some((index, self.along_point(*position), sizes));
+                    context.capture_pointer();
+                }
+                true
+            }
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                if self.drag.take().is_none() {
+                    return false;
+                }
+                context.release_pointer_capture();
+                self.notify_layout_changed();
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.drag = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        let mut position = 0f32;
+        for (index, pane) in self.panes.iter().enumerate() {
+            if !pane.is_collapsed {
+                visitor(self.pane_bounds(index, position), &*pane.content);
+            }
+            position += pane.size + self.splitter_thickness;
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let mut position = 0f32;
+        for index in 0..self.panes.len() {
+            let bounds = self.pane_bounds(index, position);
+            let pane = &mut self.panes[index];
+            if !pane.is_collapsed {
+                visitor(bounds, &mut *pane.content);
+            }
+            position += pane.size + self.splitter_thickness;
+        }
+    }
+}
+
+impl SplitPanel {
+    pub fn new() -> SplitPanel {
+        SplitPanel {
+            id: ComponentId::new(),
+            panes: vec![],
+            orientation: Orientation::Horizontal,
+            splitter_thickness: 5f32,
+            splitter: Color::GAINSBORO,
+            hover_splitter: Color::CORNFLOWER_BLUE,
+            on_layout_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            length: 0f32,
+            pane_count: 0,
+            pending_layout: None,
+            hovered: None,
+            drag: None,
+            last_click: None,
+        }
+    }
+
+    pub fn with_pane(mut self, pane: SplitPane) -> Self {
+        self.panes.push(pane);
+        self
+    }
+
+    /// # Description
+    /// The current splitter positions, eg. to restore them using [`SplitPanel::set_layout`].
+    pub fn layout(&self) -> SplitPanelLayout {
+        let ratio = |d: f32| if self.length > 0f32 { d / self.length } else { 0f32 };
+        SplitPanelLayout {
+            ratios: self
+                .panes
+                .iter()
+                .map(|d| ratio(if d.is_collapsed { d.restore_size } else { d.size }))
+                .collect(),
+            collapsed: self.panes.iter().map(|d| d.is_collapsed).collect(),
+        }
+    }
+
+    /// # Description
+    /// Restores splitter positions previously returned by [`SplitPanel::layout`].
+    ///
+    /// # Remarks
+    /// The layout is applied during the next layout pass and ignored if its number of panes
+    /// does not match.
+    pub fn set_layout(&mut self, layout: SplitPanelLayout) {
+        self.pending_layout = Some(layout);
+    }
+
+    /// # Description
+    /// Collapses the pane at *index*, giving its space to a neighbouring pane.
+    pub fn collapse(&mut self, index: usize) {
+        if self.panes[index].is_collapsed {
+            return;
+        }
+        let neighbour = self.neighbour(index);
+        let pane = &mut self.panes[index];
+        let size = pane.size;
+        pane.is_collapsed = true;
+        pane.restore_size = size;
+        pane.size = 0f32;
+        if let Some(neighbour) = neighbour {
+            self.panes[neighbour].size += size;
+        }
+        self.fit();
+    }
+
+    /// # Description
+    /// Restores the pane at *index* to the size it had before it was collapsed, taking the space
+    /// from a neighbouring pane.
+    pub fn expand(&mut self, index: usize) {
+        if !self.panes[index].is_collapsed {
+            return;
+        }
+        let neighbour = self.neighbour(index);
+        let pane = &mut self.panes[index];
+        let size = pane.restore_size;
+        pane.is_collapsed = false;
+        pane.size = size;
+        if let Some(neighbour) = neighbour {
+            self.panes[neighbour].size -= size;
+        }
+        self.fit();
+    }
+
+    /// The pane next to the pane at *index* that is not collapsed, preferring the following one.
+    fn neighbour(&self, index: usize) -> Option<usize> {
+        (index + 1..self.panes.len())
+            .chain((0..index).rev())
+            .find(|d| !self.panes[*d].is_collapsed)
+    }
+
+    /// Adjusts the pane sizes to fill the available space, within their limits.
+    fn fit(&mut self) {
+        if self.length <= 0f32 {
+            return;
+        }
+        let limits: Vec<(f32, f32)> = self.panes.iter().map(|d| d.limits()).collect();
+        let mut sizes: Vec<f32> = self.panes.iter().map(|d| d.size).collect();
+        fit(&mut sizes, &limits, self.length);
+        for (pane, size) in self.panes.iter_mut().zip(sizes) {
+            pane.size = size;
+        }
+    }
+
+    fn notify_layout_changed(&mut self) {
+        if self.on_layout_changed.is_none() {
+            return;
+        }
+        let layout = self.layout();
+        if let Some(callback) = self.on_layout_changed.as_mut() {
+            callback(&layout);
+        }
+    }
+
+    fn along(&self, size: Size<f32>) -> f32 {
+        match self.orientation {
+            Orientation::Horizontal => size.width,
+            Orientation::Vertical => size.height,
+        }
+    }
+
+    fn along_point(&self, point: Point<f32>) -> f32 {
+        match self.orientation {
+            Orientation::Horizontal => point.x,
+            Orientation::Vertical => point.y,
+        }
+    }
+
+    /// The bounds of the pane at *index*, starting at *position* along the orientation.
+    fn pane_bounds(&self, index: usize, position: f32) -> Rectangle<f32> {
+        let size = self.panes[index].size;
+        match self.orientation {
+            Orientation::Horizontal => Rectangle { x: position, y: 0f32, width: size, height: self.size.height },
+            Orientation::Vertical => Rectangle { x: 0f32, y: position, width: self.size.width, height: size },
+        }
+    }
+
+    /// The bounds of the splitter starting at *position* along the orientation.
+    fn splitter_bounds(&self, position: f32) -> Rectangle<f32> {
+        match self.orientation {
+            Orientation::Horizontal => Rectangle {
+                x: position,
+                y: 0f32,
+                width: self.splitter_thickness,
+                height: self.size.height,
+            },
+            Orientation::Vertical => Rectangle {
+                x: 0f32,
+                y: position,
+                width: self.size.width,
+                height: self.splitter_thickness,
+            },
+        }
+    }
+
+    fn splitter_at(&self, point: Point<f32>) -> Option<usize> {
+        let mut position = 0f32;
+        for (index, pane) in self.panes.iter().enumerate().take(self.panes.len().saturating_sub(1)) {
+            position += pane.size;
+            if self.splitter_bounds(position).contains(point) {
+                return Some(index);
+            }
+            position += self.splitter_thickness;
+        }
+        None
+    }
+}
+
+/// Moves the splitter after the pane at *index* by *delta*, resizing the panes next to it
+/// within their *limits*.
+fn resize(sizes: &mut [f32], limits: &[(f32, f32)], index: usize, delta: f32) {
+    let (before, after) = (index, index + 1);
+    let lower = f32::max(limits[before].0 - sizes[before], sizes[after] - limits[after].1);
+    let upper = f32::min(limits[before].1 - sizes[before], sizes[after] - limits[after].0);
+    if lower > upper {
+        return;
+    }
+    let delta = f32::max(lower, f32::min(upper, delta));
+    sizes[before] += delta;
+    sizes[after] -= delta;
+}
+
+/// Adjusts *sizes* to add up to *length* within their *limits*, distributing the difference
+/// among the panes proportional to their sizes.
+fn fit(sizes: &mut [f32], limits: &[(f32, f32)], length: f32) {
+    // Every pass either fits the sizes or pins at least one pane to a limit
+    for _ in 0..=sizes.len() {
+        let difference = length - sizes.iter().sum::<f32>();
+        if difference.abs() < 0.01f32 {
+            return;
+        }
+        let adjustable: Vec<usize> = (0..sizes.len())
+            .filter(|d| if difference > 0f32 { sizes[*d] < limits[*d].1 } else { sizes[*d] > limits[*d].0 })
+            .collect();
+        if adjustable.is_empty() {
+            return;
+        }
+        let total: f32 = adjustable.iter().map(|d| sizes[*d]).sum();
+        for index in adjustable.iter().copied() {
+            let share = if total > 0f32 {
+                difference * sizes[index] / total
+            } else {
+                difference / adjustable.len() as f32
+            };
+            sizes[index] = f32::max(limits[index].0, f32::min(limits[index].1, sizes[index] + share));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let limits = [(0f32, f32::INFINITY), (50f32, 100f32), (0f32, f32::INFINITY)];
+        let mut sizes = [100f32, 100f32, 200f32];
+        fit(&mut sizes, &limits, 200f32);
+        assert_eq!(sizes, [50f32, 50f32, 100f32]);
+        fit(&mut sizes, &limits, 800f32);
+        assert_eq!(sizes[1], 100f32);
+        assert!((sizes[0] - 700f32 / 3f32).abs() < 0.01f32);
+        assert!((sizes[2] - 1400f32 / 3f32).abs() < 0.01f32);
+    }
+
+    #[test]
+    fn test_layout_parse() {
+        let layout = SplitPanelLayout::parse("0.25, (0.5),0.25").unwrap();
+        assert_eq!(layout.ratios, vec![0.25f32, 0.5f32, 0.25f32]);
+        assert_eq!(layout.collapsed, vec![false, true, false]);
+        assert_eq!(layout.to_string(), "0.25, (0.5), 0.25");
+        assert!(SplitPanelLayout::parse("").is_err());
+        assert!(SplitPanelLayout::parse("0.5, half").is_err());
+    }
+
+    #[test]
+    fn test_resize() {
+        let limits = [(20f32, 150f32), (0f32, 0f32), (30f32, f32::INFINITY)];
+        let mut sizes = [100f32, 0f32, 100f32];
+        resize(&mut sizes, &limits, 0, -200f32);
+        assert_eq!(sizes, [100f32, 0f32, 100f32]);
+        let limits = [(20f32, 150f32), (30f32, f32::INFINITY)];
+        let mut sizes = [100f32, 100f32];
+        resize(&mut sizes, &limits, 0, -200f32);
+        assert_eq!(sizes, [20f32, 180f32]);
+        resize(&mut sizes, &limits, 0, 200f32);
+        assert_eq!(sizes, [150f32, 50f32]);
+    }
+
+    #[test]
+    fn test_double_click() {
+        use crate::components::TextBlock;
+        use crate::pointer::PointerState;
+        use std::time::Instant;
+        use winit::keyboard::ModifiersState;
+
+        let mut split_panel = SplitPanel::new()
+            .with_pane(SplitPane::new(Box::new(TextBlock::new())))
+            .with_pane(SplitPane::new(Box::new(TextBlock::new())))
+            .with_pane(SplitPane::new(Box::new(TextBlock::new())));
+        let thickness = split_panel.splitter_thickness;
+        split_panel.arrange(Size { width: 300f32 + 2f32 * thickness, height: 100f32 });
+        let click = |split_panel: &mut SplitPanel, splitter: usize, count: usize| {
+            let mut context =
+                EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+            context.click_count = count;
+            let x = (0..=splitter).map(|d| split_panel.panes[d].size).sum::<f32>()
+                + (splitter as f32 + 0.5f32) * thickness;
+            let position = Point { x, y: 50f32 };
+            split_panel.handle_event(&InputEvent::PointerPressed { position, button: MouseButton::Left }, &mut context);
+            split_panel.handle_event(&InputEvent::PointerReleased { position, button: MouseButton::Left }, &mut context);
+        };
+
+        click(&mut split_panel, 0, 1);
+        click(&mut split_panel, 1, 2);
+        assert!(split_panel.panes.iter().all(|d| !d.is_collapsed()));
+
+        click(&mut split_panel, 1, 1);
+        click(&mut split_panel, 1, 2);
+        assert!(split_panel.panes[1].is_collapsed());
+    }
+}
@@ -0,0 +1,653 @@
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent, PIXELS_PER_WHEEL_LINE};
+use crate::text::{self, TextStyle};
+use crate::visuals::{self, PathCommand};
+
+/// The size of the close button of a closable tab.
+const CLOSE_SIZE: f32 = 14f32;
+/// The distance between the header of a closable tab and its close button.
+const CLOSE_SPACING: f32 = 6f32;
+/// The size of the buttons scrolling the tab strip if the tabs do not fit.
+const SCROLL_BUTTON_SIZE: f32 = 16f32;
+
+/// # Description
+/// The side of a [`TabControl`] its tab strip is placed at.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TabStripPlacement {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// # Description
+/// A tab of a [`TabControl`].
+///
+/// # Remarks
+/// The content of a tab is created the first time the tab is selected and kept, including its
+/// state, while other tabs are selected.
+pub struct TabItem {
+    // #[property]
+    pub header: String,
+    /// Whether the tab shows a button closing it.
+    // #[property]
+    pub is_closable: bool,
+
+    create_content: Option<Box<dyn FnOnce() -> Box<dyn Component>>>,
+    content: Option<Box<dyn Component>>,
+}
+
+impl TabItem {
+    pub fn new(header: &str, create_content: impl FnOnce() -> Box<dyn Component> + 'static) -> TabItem {
+        TabItem {
+            header: header.to_string(),
+            is_closable: false,
+            create_content: Some(Box::new(create_content)),
+            content: None,
+        }
+    }
+
+    pub fn with_closable(mut self, is_closable: bool) -> Self {
+        self.is_closable = is_closable;
+        self
+    }
+
+    /// # Description
+    /// The content of the tab, if it was created yet.
+    pub fn content(&self) -> Option<&dyn Component> {
+        self.content.as_deref()
+    }
+
+    /// The content of the tab, creating it if not done yet.
+    fn realize(&mut self) -> &mut Box<dyn Component> {
+        let create_content = &mut self.create_content;
+        self.content.get_or_insert_with(|| (create_content.take().unwrap())())
+    }
+}
+
+/// # Description
+/// Displays the content of one of its tabs, selected using a strip of tab headers.
+///
+/// # Remarks
+/// If the tabs do not fit into the tab strip, it can be scrolled using the mouse wheel or the
+/// buttons at its end. Ctrl+Tab and Ctrl+Page Down select the next tab, Ctrl+Shift+Tab and
+/// Ctrl+Page Up the previous one, and the arrow keys switch tabs while the tab strip has the
+/// keyboard focus. Closable tabs are closed by their close button or the middle mouse button.
+pub struct TabControl {
+    // #[property]
+    pub tabs: Vec<TabItem>,
+    // #[property]
+    pub selected_index: Option<usize>,
+    // #[property]
+    pub placement: TabStripPlacement,
+    // #[property]
+    pub text_style: TextStyle,
+    /// The padding of every tab header around its text.
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub strip_background: Color,
+    // #[property]
+    pub tab_background: Color,
+    // #[property]
+    pub hover_tab_background: Color,
+    // #[property]
+    pub selected_tab_background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    /// The color of the close buttons and the scroll buttons.
+    // #[property]
+    pub glyph: Color,
+    /// Called with the new selected index whenever the user changed the selected tab.
+    // #[event]
+    pub on_selection_changed: Option<Box<dyn FnMut(Option<usize>)>>,
+    /// Called with the index a tab had after the user closed it.
+    // #[event]
+    pub on_tab_closed: Option<Box<dyn FnMut(usize)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    focused: bool,
+    hovered: Option<usize>,
+    baseline: f32,
+    strip: Rectangle<f32>,
+    /// The part of the strip showing tabs, excluding the scroll buttons.
+    tabs_area: Rectangle<f32>,
+    tab_bounds: Vec<Rectangle<f32>>,
+    scroll_buttons: Option<(Rectangle<f32>, Rectangle<f32>)>,
+    scroll_offset: f32,
+    scroll_to_selected: bool,
+    content_bounds: Rectangle<f32>,
+}
+
+impl Component for TabControl {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        available
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        self.selected_index = self.selected_index.filter(|d| *d < self.tabs.len());
+        let metrics = text::text_metrics();
+        let font = metrics.font_metrics(&self.text_style);
+        let tab_height = font.line_height + self.padding.vertical();
+        self.baseline = self.padding.top + font.ascent;
+        let widths: Vec<f32> = self
+            .tabs
+            .iter()
+            .map(|d| {
+                let close = if d.is_closable { CLOSE_SPACING + CLOSE_SIZE } else { 0f32 };
+                metrics.measure_text(&d.header, &self.text_style) + self.padding.horizontal() + close
+            })
+            .collect();
+
+        let horizontal = self.is_horizontal();
+        let thickness = if horizontal {
+            f32::min(tab_height, given.height)
+        } else {
+            f32::min(widths.iter().copied().fold(0f32, f32::max), given.width)
+        };
+        self.strip = match self.placement {
+            TabStripPlacement::Top => Rectangle { x: 0f32, y: 0f32, width: given.width, height: thickness },
+            TabStripPlacement::Bottom => Rectangle {
+                x: 0f32,
+                y: given.height - thickness,
+                width: given.width,
+                height: thickness,
+            },
+            TabStripPlacement::Left => Rectangle { x: 0f32, y: 0f32, width: thickness, height: given.height },
+            TabStripPlacement::Right => Rectangle {
+                x: given.width - thickness,
+                y: 0f32,
+                width: thickness,
+                height: given.height,
+            },
+        };
+        self.content_bounds = match self.placement {
+            TabStripPlacement::Top => Rectangle {
+                x: 0f32,
+                y: thickness,
+                width: given.width,
+                height: f32::max(0f32, given.height - thickness),
+            },
+            TabStripPlacement::Bottom => Rectangle {
+                x: 0f32,
+                y: 0f32,
+                width: given.width,
+                height: f32::max(0f32, given.height - thickness),
+            },
+            TabStripPlacement::Left => Rectangle {
+                x: thickness,
+                y: 0f32,
+                width: f32::max(0f32, given.width - thickness),
+                height: given.height,
+            },
+            TabStripPlacement::Right => Rectangle {
+                x: 0f32,
+                y: 0f32,
+                width: f32::max(0f32, given.width - thickness),
+                height: given.height,
+            },
+        };
+
+        // Tabs are laid out along the strip, scrolled if they do not fit
+        let lengths: Vec<f32> = if horizontal { widths } else { vec![tab_height; self.tabs.len()] };
+        let total: f32 = lengths.iter().sum();
+        let length = if horizontal { self.strip.width } else { self.strip.height };
+        let overflow = total > length;
+        let available = if overflow { f32::max(0f32, length - 2f32 * SCROLL_BUTTON_SIZE) } else { length };
+        self.tabs_area = if horizontal {
+            Rectangle { width: available, ..self.strip }
+        } else {
+            Rectangle { height: available, ..self.strip }
+        };
+        self.scroll_buttons = if !overflow {
+            None
+        } else if horizontal {
+            let x = self.strip.x + available;
+            Some((
+                Rectangle { x, width: SCROLL_BUTTON_SIZE, ..self.strip },
+                Rectangle { x: x + SCROLL_BUTTON_SIZE, width: SCROLL_BUTTON_SIZE, ..self.strip },
+            ))
+        } else {
+            let y = self.strip.y + available;
+            Some((
+                Rectangle { y, height: SCROLL_BUTTON_SIZE, ..self.strip },
+                Rectangle { y: y + SCROLL_BUTTON_SIZE, height: SCROLL_BUTTON_SIZE, ..self.strip },
+            ))
+        };
+        if self.scroll_to_selected {
+            self.scroll_to_selected = false;
+            if let Some(index) = self.selected_index {
+                let start: f32 = lengths[..index].iter().sum();
+                let end = start + lengths[index];
+                if start < self.scroll_offset {
+                    self.scroll_offset = start;
+                } else if end > self.scroll_offset + available {
+                    self.scroll_offset = end - available;
+                }
+            }
+        }
+        self.scroll_offset = f32::max(0f32, f32::min(self.scroll_offset, total - available));
+        self.tab_bounds.clear();
+        let mut position = -self.scroll_offset;
+        for length in lengths {
+            self.tab_bounds.push(if horizontal {
+                Rectangle { x: self.strip.x + position, width: length, ..self.strip }
+            } else {
+                Rectangle { y: self.strip.y + position, height: length, ..self.strip }
+            });
+            position += length;
+        }
+
+        let inner = self.content_inner_bounds();
+        if let Some(index) = self.selected_index {
+            let content = self.tabs[index].realize();
+            let size = Size { width: inner.width, height: inner.height };
+            content.measure(size);
+            content.arrange(size);
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: self.content_bounds.x,
+            y: self.content_bounds.y,
+            width: self.content_bounds.width,
+            height: self.content_bounds.height,
+            fill: self.background,
+        }
+        .draw(renderer);
+        visuals::Rectangle {
+            x: self.strip.x,
+            y: self.strip.y,
+            width: self.strip.width,
+            height: self.strip.height,
+            fill: self.strip_background,
+        }
+        .draw(renderer);
+
+        let area = self.tabs_area;
+        renderer.push_clip(area.x, area.y, area.width, area.height);
+        for (index, (tab, bounds)) in self.tabs.iter().zip(self.tab_bounds.iter()).enumerate() {
+            let fill = if self.selected_index == Some(index) {
+                self.selected_tab_background
+            } else if self.enabled && self.hovered == Some(index) {
+                self.hover_tab_background
+            } else {
+                self.tab_background
+            };
+            visuals::Rectangle { x: bounds.x, y: bounds.y, width: bounds.width, height: bounds.height, fill }
+                .draw(renderer);
+            let close = if tab.is_closable { CLOSE_SPACING + CLOSE_SIZE } else { 0f32 };
+            renderer.push_clip(
+                bounds.x + self.padding.left,
+                bounds.y,
+                f32::max(0f32, bounds.width - self.padding.horizontal() - close),
+                bounds.height,
+            );
+            visuals::Text {
+                x: bounds.x + self.padding.left,
+                y: bounds.y + self.baseline,
+                text: &tab.header,
+                style: &self.text_style,
+            }
+            .draw(renderer);
+            renderer.pop_clip();
+            if tab.is_closable {
+                let close = self.close_bounds(*bounds);
+                let extent = close.width / 4f32;
+                let center = Point { x: close.x + close.width / 2f32, y: close.y + close.height / 2f32 };
+                visuals::Path {
+                    commands: &[
+                        PathCommand::MoveTo(Point { x: center.x - extent, y: center.y - extent }),
+                        PathCommand::LineTo(Point { x: center.x + extent, y: center.y + extent }),
+                        PathCommand::MoveTo(Point { x: center.x + extent, y: center.y - extent }),
+                        PathCommand::LineTo(Point { x: center.x - extent, y: center.y + extent }),
+                    ],
+                    fill: Color::TRANSPARENT,
+                    stroke: self.glyph,
+                    thickness: 1.5f32,
+                }
+                .draw(renderer);
+            }
+            if self.focused && self.selected_index == Some(index) {
+                visuals::Outline {
+                    x: bounds.x + 0.5f32,
+                    y: bounds.y + 0.5f32,
+                    width: bounds.width - 1f32,
+                    height: bounds.height - 1f32,
+                    stroke: self.focused_border,
+                    thickness: 1f32,
+                }
+                .draw(renderer);
+            }
+        }
+        renderer.pop_clip();
+
+        if let Some((decrease, increase)) = self.scroll_buttons {
+            self.render_scroll_button(renderer, decrease, -1f32);
+            self.render_scroll_button(renderer, increase, 1f32);
+        }
+
+        let inner = self.content_inner_bounds();
+        if let Some(content) = self.selected_content() {
+            renderer.push_clip(inner.x, inner.y, inner.width, inner.height);
+            renderer.translate(inner.x, inner.y);
+            content.render(renderer);
+            renderer.translate(-inner.x, -inner.y);
+            renderer.pop_clip();
+        }
+        visuals::Outline {
+            x: self.content_bounds.x + 0.5f32,
+            y: self.content_bounds.y + 0.5f32,
+            width: self.content_bounds.width - 1f32,
+            height: self.content_bounds.height - 1f32,
+            stroke: self.border,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                true
+            }
+            InputEvent::PointerMoved { position } => {
+                self.hovered = self.tab_at(*position);
+                false
+            }
+            InputEvent::PointerLeft => {
+                self.hovered = None;
+                false
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left } if self.enabled => {
+                if let Some((decrease, increase)) = self.scroll_buttons {
+                    if decrease.contains(*position) {
+                        self.scroll(-self.tab_step());
+                        return true;
+                    }
+                    if increase.contains(*position) {
+                        self.scroll(self.tab_step());
+                        return true;
+                    }
+                }
+                match self.tab_at(*position) {
+                    Some(index) if self.tabs[index].is_closable
+                        && self.close_bounds(self.tab_bounds[index]).contains(*position) =>
+                    {
+                        self.close(index);
+                        true
+                    }
+                    Some(index) => {
+                        self.select(index);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Middle } if self.enabled => {
+                match self.tab_at(*position) {
+                    Some(index) if self.tabs[index].is_closable => {
+                        self.close(index);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            InputEvent::PointerWheel { position, delta } if self.strip.contains(*position) => {
+                let lines = if delta.x != 0f32 { delta.x } else { delta.y };
+                self.scroll(-lines * PIXELS_PER_WHEEL_LINE);
+                true
+            }
+            InputEvent::KeyPressed { key, modifiers, .. } if self.enabled => {
+                let count = self.tabs.len();
+                if count == 0 {
+                    return false;
+                }
+                let current = self.selected_index;
+                let next = current.map_or(0, |d| (d + 1) % count);
+                let previous = current.map_or(count - 1, |d| (d + count - 1) % count);
+                let horizontal = self.is_horizontal();
+                let target = match key {
+                    Key::Named(NamedKey::Tab) if modifiers.control_key() => {
+                        if modifiers.shift_key() { previous } else { next }
+                    }
+                    Key::Named(NamedKey::PageDown) if modifiers.control_key() => next,
+                    Key::Named(NamedKey::PageUp) if modifiers.control_key() => previous,
+                    Key::Named(NamedKey::ArrowRight) if self.focused && horizontal => next,
+                    Key::Named(NamedKey::ArrowLeft) if self.focused && horizontal => previous,
+                    Key::Named(NamedKey::ArrowDown) if self.focused && !horizontal => next,
+                    Key::Named(NamedKey::ArrowUp) if self.focused && !horizontal => previous,
+                    Key::Named(NamedKey::Home) if self.focused => 0,
+                    Key::Named(NamedKey::End) if self.focused => count - 1,
+                    _ => return false,
+                };
+                // The focused component may belong to the content being switched away from
+                if !self.focused {
+                    context.request_focus(self.id);
+                }
+                self.select(target);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        let bounds = self.content_inner_bounds();
+        if let Some(content) = self.selected_content() {
+            visitor(bounds, content);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.content_inner_bounds();
+        let content = self.selected_index.and_then(|d| self.tabs.get_mut(d)).and_then(|d| d.content.as_mut());
+        if let Some(content) = content {
+            visitor(bounds, &mut **content);
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled && !self.tabs.is_empty() {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+}
+
+impl TabControl {
+    pub fn new() -> TabControl {
+        TabControl {
+            id: ComponentId::new(),
+            tabs: vec![],
+            selected_index: None,
+            placement: TabStripPlacement::Top,
+            text_style: TextStyle::new(),
+            padding: Thickness::uniform(6f32),
+            enabled: true,
+            background: Color::WHITE,
+            strip_background: Color::WHITE,
+            tab_background: Color::GAINSBORO,
+            hover_tab_background: Color::LIGHT_GRAY,
+            selected_tab_background: Color::WHITE,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            glyph: Color::DIM_GRAY,
+            on_selection_changed: None,
+            on_tab_closed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            focused: false,
+            hovered: None,
+            baseline: 0f32,
+            strip: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+            tabs_area: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+            tab_bounds: vec![],
+            scroll_buttons: None,
+            scroll_offset: 0f32,
+            scroll_to_selected: false,
+            content_bounds: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+        }
+    }
+
+    /// Selects the tab at *index*, notifying about the changed selection.
+    fn select(&mut self, index: usize) {
+        self.scroll_to_selected = true;
+        if self.selected_index == Some(index) {
+            return;
+        }
+        self.selected_index = Some(index);
+        if let Some(callback) = self.on_selection_changed.as_mut() {
+            callback(Some(index));
+        }
+    }
+
+    /// Removes the tab at *index*, selecting a neighbour if it was selected.
+    fn close(&mut self, index: usize) {
+        self.tabs.remove(index);
+        self.hovered = None;
+        let selected = index_after_close(self.selected_index, index, self.tabs.len());
+        // Closing another tab only shifts the index of the selected one
+        let changed = self.selected_index == Some(index);
+        self.selected_index = selected;
+        self.scroll_to_selected = true;
+        if let Some(callback) = self.on_tab_closed.as_mut() {
+            callback(index);
+        }
+        if changed {
+            if let Some(callback) = self.on_selection_changed.as_mut() {
+                callback(selected);
+            }
+        }
+    }
+
+    fn scroll(&mut self, delta: f32) {
+        // Clamped during the next layout pass
+        self.scroll_offset = f32::max(0f32, self.scroll_offset + delta);
+    }
+
+    /// The distance a scroll button scrolls the strip by.
+    fn tab_step(&self) -> f32 {
+        if self.is_horizontal() {
+            self.tabs_area.width / 2f32
+        } else {
+            self.tab_bounds.first().map_or(0f32, |d| d.height)
+        }
+    }
+
+    fn is_horizontal(&self) -> bool {
+        match self.placement {
+            TabStripPlacement::Top | TabStripPlacement::Bottom => true,
+            TabStripPlacement::Left | TabStripPlacement::Right => false,
+        }
+    }
+
+    fn tab_at(&self, position: Point<f32>) -> Option<usize> {
+        if !self.tabs_area.contains(position) {
+            return None;
+        }
+        self.tab_bounds.iter().position(|d| d.contains(position))
+    }
+
+    fn close_bounds(&self, tab: Rectangle<f32>) -> Rectangle<f32> {
+        Rectangle {
+            x: tab.x + tab.width - self.padding.right - CLOSE_SIZE,
+            y: tab.y + (tab.height - CLOSE_SIZE) / 2f32,
+            width: CLOSE_SIZE,
+            height: CLOSE_SIZE,
+        }
+    }
+
+    fn selected_content(&self) -> Option<&dyn Component> {
+        self.selected_index
+            .and_then(|d| self.tabs.get(d))
+            .and_then(|d| d.content())
+    }
+
+    fn content_inner_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: self.content_bounds.x + 1f32,
+            y: self.content_bounds.y + 1f32,
+            width: f32::max(0f32, self.content_bounds.width - 2f32),
+            height: f32::max(0f32, self.content_bounds.height - 2f32),
+        }
+    }
+
+    fn render_scroll_button(&self, renderer: &mut dyn Renderer, bounds: Rectangle<f32>, direction: f32) {
+        let center = Point { x: bounds.x + bounds.width / 2f32, y: bounds.y + bounds.height / 2f32 };
+        let extent = 3f32 * direction;
+        // A chevron pointing along the strip, towards the direction the button scrolls to
+        let commands = if self.is_horizontal() {
+            [
+                PathCommand::MoveTo(Point { x: center.x - extent / 2f32, y: center.y - extent }),
+                PathCommand::LineTo(Point { x: center.x + extent / 2f32, y: center.y }),
+                PathCommand::LineTo(Point { x: center.x - extent / 2f32, y: center.y + extent }),
+            ]
+        } else {
+            [
+                PathCommand::MoveTo(Point { x: center.x - extent, y: center.y - extent / 2f32 }),
+                PathCommand::LineTo(Point { x: center.x, y: center.y + extent / 2f32 }),
+                PathCommand::LineTo(Point { x: center.x + extent, y: center.y - extent / 2f32 }),
+            ]
+        };
+        visuals::Path {
+            commands: &commands,
+            fill: Color::TRANSPARENT,
+            stroke: self.glyph,
+            thickness: 1.5f32,
+        }
+        .draw(renderer);
+    }
+}
+
+/// The selected index after closing the tab at *closed*, leaving *count* tabs.
+fn index_after_close(selected: Option<usize>, closed: usize, count: usize) -> Option<usize> {
+    match selected {
+        _ if count == 0 => None,
+        Some(d) if d == closed => Some(usize::min(closed, count - 1)),
+        Some(d) if d > closed => Some(d - 1),
+        d => d,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_after_close() {
+        assert_eq!(index_after_close(Some(2), 2, 4), Some(2));
+        assert_eq!(index_after_close(Some(3), 3, 3), Some(2));
+        assert_eq!(index_after_close(Some(3), 1, 3), Some(2));
+        assert_eq!(index_after_close(Some(0), 1, 3), Some(0));
+        assert_eq!(index_after_close(Some(0), 0, 0), None);
+        assert_eq!(index_after_close(None, 0, 2), None);
+    }
+}
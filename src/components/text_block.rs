@@ -0,0 +1,465 @@
+use crate::abstraction::{Component, Renderer, TextMetrics, Visual};
+use crate::data::{ComponentId, Size};
+use crate::text::{self, TextAlignment, TextSpan, TextStyle, TextTrimming, TextWrapping};
+use crate::visuals;
+
+const ELLIPSIS: &str = "…";
+
+/// A part of a line drawn in the style of a single span.
+struct TextRun {
+    span: usize,
+    /// The offset of the run from the start of the line.
+    x: f32,
+    text: String,
+}
+
+struct TextLine {
+    runs: Vec<TextRun>,
+    /// The span the line started in, providing the font metrics of empty lines.
+    span: usize,
+    /// The width of the line, not counting trailing whitespace.
+    width: f32,
+    /// The offset the next run of the line starts at, counting trailing whitespace.
+    end: f32,
+    baseline: f32,
+}
+
+impl TextLine {
+    fn new(span: usize) -> TextLine {
+        TextLine {
+            runs: vec![],
+            span,
+            width: 0f32,
+            end: 0f32,
+            baseline: 0f32,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    fn append(&mut self, span: usize, text: &str, width: f32) {
+        match self.runs.last_mut() {
+            Some(run) if run.span == span => run.text.push_str(text),
+            _ => self.runs.push(TextRun {
+                span,
+                x: self.end,
+                text: text.to_string(),
+            }),
+        }
+        self.end += width;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SegmentKind {
+    Word,
+    Space,
+    Break,
+}
+
+/// # Description
+/// Displays read-only text, optionally made up of multiple spans in different styles.
+pub struct TextBlock {
+    /// The text displayed if there are no *spans*.
+    // #[property]
+    pub text: String,
+    /// The style *text* is drawn in.
+    // #[property]
+    pub text_style: TextStyle,
+    /// The spans displayed instead of *text*, in order.
+    // #[property]
+    pub spans: Vec<TextSpan>,
+    // #[property]
+    pub wrapping: TextWrapping,
+    // #[property]
+    pub trimming: TextTrimming,
+    // #[property]
+    pub alignment: TextAlignment,
+    /// The maximum number of lines displayed. Further lines are trimmed.
+    // #[property]
+    pub max_lines: Option<usize>,
+    /// The height of every line. If not set, lines are as high as their largest font requires.
+    // #[property]
+    pub line_height: Option<f32>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    lines: Vec<TextLine>,
+}
+
+impl Component for TextBlock {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let (_, size) = self.layout(available);
+        size
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        let (lines, _) = self.layout(given);
+        self.lines = lines;
+        self.size = given;
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        for line in self.lines.iter() {
+            let x = self.alignment.offset(line.width, self.size.width);
+            for run in line.runs.iter() {
+                visuals::Text {
+                    x: x + run.x,
+                    y: line.baseline,
+                    text: &run.text,
+                    style: self.style(run.span),
+                }
+                .draw(renderer);
+            }
+        }
+    }
+}
+
+impl TextBlock {
+    pub fn new() -> TextBlock {
+        TextBlock {
+            id: ComponentId::new(),
+            text: String::new(),
+            text_style: TextStyle::new(),
+            spans: vec![],
+            wrapping: TextWrapping::NoWrap,
+            trimming: TextTrimming::None,
+            alignment: TextAlignment::Left,
+            max_lines: None,
+            line_height: None,
+            size: Size { width: 0f32, height: 0f32 },
+            lines: vec![],
+        }
+    }
+
+    fn span_count(&self) -> usize {
+        if self.spans.is_empty() {
+            1
+        } else {
+            self.spans.len()
+        }
+    }
+
+    fn span_text(&self, span: usize) -> &str {
+        if self.spans.is_empty() {
+            &self.text
+        } else {
+            &self.spans[span].text
+        }
+    }
+
+    fn style(&self, span: usize) -> &TextStyle {
+        if self.spans.is_empty() {
+            &self.text_style
+        } else {
+            &self.spans[span].style
+        }
+    }
+
+    /// # Description
+    /// Breaks the text into lines fitting *available*.
+    ///
+    /// # Returns
+    /// The lines, with their baselines set, and the size required to display them.
+    fn layout(&self, available: Size<f32>) -> (Vec<TextLine>, Size<f32>) {
+        let metrics = text::text_metrics();
+        let metrics = metrics.as_ref();
+        let mut lines = self.break_lines(metrics, available.width);
+
+        // Lines exceeding max_lines or the available height are trimmed.
+        // The first line is always kept.
+        let max_lines = self.max_lines.unwrap_or(usize::MAX).max(1);
+        let mut trimmed = lines.len() > max_lines;
+        lines.truncate(max_lines);
+        let mut top = 0f32;
+        for index in 0..lines.len() {
+            let (height, ascent, descent) = self.line_metrics(metrics, &lines[index]);
+            if index > 0 && top + height > available.height {
+                trimmed = true;
+                lines.truncate(index);
+                break;
+            }
+            lines[index].baseline = top + (height - ascent - descent) / 2f32 + ascent;
+            top += height;
+        }
+
+        if self.trimming == TextTrimming::Ellipsis {
+            let count = lines.len();
+            for (index, line) in lines.iter_mut().enumerate() {
+                if line.width > available.width || (trimmed && index == count - 1) {
+                    self.ellipsize(metrics, line, available.width);
+                }
+            }
+        }
+
+        let width = lines.iter().map(|line| line.width).fold(0f32, f32::max);
+        (lines, Size { width, height: top })
+    }
+
+    /// Splits the spans into words, whitespace and line breaks and fills them into lines.
+    fn break_lines(&self, metrics: &dyn TextMetrics, width: f32) -> Vec<TextLine> {
+        let wrapping = if width.is_finite() {
+            self.wrapping
+        } else {
+            TextWrapping::NoWrap
+        };
+        let mut lines = vec![];
+        let mut line = TextLine::new(0);
+        for span in 0..self.span_count() {
+            let style = self.style(span);
+            for (kind, segment) in segments(self.span_text(span)) {
+                match kind {
+                    SegmentKind::Break => {
+                        lines.push(line);
+                        line = TextLine::new(span);
+                    }
+                    SegmentKind::Space => {
+                        if line.is_empty() {
+                            line.span = span;
+                        }
+                        line.append(span, segment, metrics.measure_text(segment, style));
+                    }
+                    SegmentKind::Word => {
+                        if line.is_empty() {
+                            line.span = span;
+                        }
+                        let segment_width = metrics.measure_text(segment, style);
+                        if wrapping == TextWrapping::NoWrap || line.end + segment_width <= width {
+                            line.append(span, segment, segment_width);
+                            line.width = line.end;
+                            continue;
+                        }
+                        if wrapping == TextWrapping::Word && line.width > 0f32 {
+                            lines.push(line);
+                            line = TextLine::new(span);
+                            if segment_width <= width {
+                                line.append(span, segment, segment_width);
+                                line.width = line.end;
+                                continue;
+                            }
+                        }
+                        // Break between characters, as the word does not fit a line by itself
+                        // or character wrapping is used
+                        for (index, character) in segment.char_indices() {
+                            let character = &segment[index..index + character.len_utf8()];
+                            let character_width = metrics.measure_text(character, style);
+                            if line.width > 0f32 && line.end + character_width > width {
+                                lines.push(line);
+                                line = TextLine::new(span);
+                            }
+                            line.append(span, character, character_width);
+                            line.width = line.end;
+                        }
+                    }
+                }
+            }
+        }
+        lines.push(line);
+        lines
+    }
+
+    /// # Returns
+    /// The height, the ascent and the descent of *line*.
+    fn line_metrics(&self, metrics: &dyn TextMetrics, line: &TextLine) -> (f32, f32, f32) {
+        let mut height = 0f32;
+        let mut ascent = 0f32;
+        let mut descent = 0f32;
+        let spans = line.runs.iter().map(|run| run.span);
+        let spans: Vec<usize> = if line.is_empty() {
+            vec![line.span]
+        } else {
+            spans.collect()
+        };
+        for span in spans {
+            let font = metrics.font_metrics(self.style(span));
+            height = f32::max(height, font.line_height);
+            ascent = f32::max(ascent, font.ascent);
+            descent = f32::max(descent, font.descent);
+        }
+        (self.line_height.unwrap_or(height), ascent, descent)
+    }
+
+    /// Removes characters from the end of *line* until an ellipsis fits into *width*,
+    /// then appends the ellipsis.
+    fn ellipsize(&self, metrics: &dyn TextMetrics, line: &mut TextLine, width: f32) {
+        let span = line.runs.last().map_or(line.span, |run| run.span);
+        let ellipsis_width = metrics.measure_text(ELLIPSIS, self.style(span));
+        loop {
+            while let Some(run) = line.runs.last_mut() {
+                let trimmed = run.text.trim_end().len();
+                run.text.truncate(trimmed);
+                if !run.text.is_empty() {
+                    break;
+                }
+                line.runs.pop();
+            }
+            line.width = match line.runs.last() {
+                None => 0f32,
+                Some(run) => run.x + metrics.measure_text(&run.text, self.style(run.span)),
+            };
+            if line.width + ellipsis_width <= width {
+                break;
+            }
+            match line.runs.last_mut() {
+                None => break,
+                Some(run) => run.text.pop(),
+            };
+        }
+        line.end = line.width;
+        line.append(span, ELLIPSIS, ellipsis_width);
+        line.width = line.end;
+    }
+}
+
+/// # Description
+/// Splits *text* into words, whitespace and line breaks.
+fn segments(text: &str) -> Vec<(SegmentKind, &str)> {
+    let mut segments = vec![];
+    let mut start = 0;
+    let mut current: Option<SegmentKind> = None;
+    for (index, character) in text.char_indices() {
+        let kind = match character {
+            '\n' => SegmentKind::Break,
+            // The line feed following a carriage return breaks the line
+            '\r' => SegmentKind::Space,
+            d if d.is_whitespace() => SegmentKind::Space,
+            _ => SegmentKind::Word,
+        };
+        if let Some(d) = current {
+            if d != kind || d == SegmentKind::Break {
+                segments.push((d, &text[start..index]));
+                start = index;
+            }
+        }
+        current = Some(kind);
+    }
+    if let Some(d) = current {
+        segments.push((d, &text[start..]));
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// At a font size of 10, the estimated metrics make every character 5 pixels wide and
+    /// every line 12 pixels high.
+    fn style(font_size: f32) -> TextStyle {
+        let mut style = TextStyle::new();
+        style.font_size = font_size;
+        style
+    }
+
+    fn text_block(text: &str, wrapping: TextWrapping) -> TextBlock {
+        let mut text_block = TextBlock::new();
+        text_block.text = text.to_string();
+        text_block.text_style = style(10f32);
+        text_block.wrapping = wrapping;
+        text_block
+    }
+
+    fn lines(text_block: &TextBlock, width: f32, height: f32) -> Vec<String> {
+        let (lines, _) = text_block.layout(Size { width, height });
+        lines
+            .iter()
+            .map(|line| line.runs.iter().map(|run| run.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            segments("ab  c\n\nd"),
+            vec![
+                (SegmentKind::Word, "ab"),
+                (SegmentKind::Space, "  "),
+                (SegmentKind::Word, "c"),
+                (SegmentKind::Break, "\n"),
+                (SegmentKind::Break, "\n"),
+                (SegmentKind::Word, "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_wrapping() {
+        let text_block = text_block("aaa bb cccc", TextWrapping::Word);
+        assert_eq!(lines(&text_block, 35f32, f32::INFINITY), vec!["aaa bb ", "cccc"]);
+        // Trailing whitespace does not count towards the width of a line
+        let (layout, size) = text_block.layout(Size { width: 35f32, height: f32::INFINITY });
+        assert_eq!(layout[0].width, 30f32);
+        assert_eq!((size.width, size.height), (30f32, 24f32));
+        // Words longer than a line are broken between characters
+        let text_block = self::text_block("ab abcdefghij", TextWrapping::Word);
+        assert_eq!(lines(&text_block, 20f32, f32::INFINITY), vec!["ab ", "abcd", "efgh", "ij"]);
+        // Without a width limit, text is only broken at line breaks
+        let text_block = self::text_block("aaa bb\n\ncccc", TextWrapping::Word);
+        assert_eq!(lines(&text_block, f32::INFINITY, f32::INFINITY), vec!["aaa bb", "", "cccc"]);
+    }
+
+    #[test]
+    fn test_character_wrapping() {
+        let text_block = text_block("ab cdefg", TextWrapping::Character);
+        assert_eq!(lines(&text_block, 15f32, f32::INFINITY), vec!["ab ", "cde", "fg"]);
+        let text_block = self::text_block("abcdefg", TextWrapping::NoWrap);
+        assert_eq!(lines(&text_block, 15f32, f32::INFINITY), vec!["abcdefg"]);
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let mut text_block = text_block("a b c d", TextWrapping::Word);
+        text_block.max_lines = Some(2);
+        assert_eq!(lines(&text_block, 10f32, f32::INFINITY), vec!["a ", "b "]);
+        text_block.trimming = TextTrimming::Ellipsis;
+        assert_eq!(lines(&text_block, 10f32, f32::INFINITY), vec!["a ", "b…"]);
+        // Lines not fitting the available height are cut off, but the first one is kept
+        text_block.max_lines = None;
+        assert_eq!(lines(&text_block, 10f32, 30f32), vec!["a ", "b…"]);
+        assert_eq!(lines(&text_block, 10f32, 5f32), vec!["a…"]);
+        text_block.trimming = TextTrimming::None;
+        assert_eq!(lines(&text_block, 10f32, 30f32), vec!["a ", "b "]);
+    }
+
+    #[test]
+    fn test_ellipsis() {
+        let mut text_block = text_block("abcdefgh", TextWrapping::NoWrap);
+        text_block.trimming = TextTrimming::Ellipsis;
+        assert_eq!(lines(&text_block, 22f32, f32::INFINITY), vec!["abc…"]);
+        assert_eq!(lines(&text_block, 40f32, f32::INFINITY), vec!["abcdefgh"]);
+        // Whitespace before the ellipsis is removed
+        text_block.text = "abc defgh".to_string();
+        assert_eq!(lines(&text_block, 25f32, f32::INFINITY), vec!["abc…"]);
+        // The ellipsis is drawn even if no character fits
+        assert_eq!(lines(&text_block, 3f32, f32::INFINITY), vec!["…"]);
+    }
+
+    #[test]
+    fn test_span_line_metrics() {
+        let mut text_block = TextBlock::new();
+        text_block.spans = vec![
+            TextSpan::new("small ", style(10f32)),
+            TextSpan::new("BIG\n\nx", style(20f32)),
+            TextSpan::new("\ny", style(10f32)),
+        ];
+        let (layout, size) = text_block.layout(Size { width: f32::INFINITY, height: f32::INFINITY });
+        assert_eq!(layout.len(), 4);
+        assert_eq!(layout[0].runs[1].x, 30f32);
+        // Lines are as high as their largest font, empty lines as the span they are in
+        let baselines: Vec<f32> = layout.iter().map(|line| line.baseline).collect();
+        assert_eq!(baselines, vec![18f32, 42f32, 66f32, 81f32]);
+        assert_eq!(size.height, 84f32);
+
+        text_block.line_height = Some(30f32);
+        let (layout, size) = text_block.layout(Size { width: f32::INFINITY, height: f32::INFINITY });
+        assert_eq!(layout[0].baseline, 21f32);
+        assert_eq!(size.height, 120f32);
+    }
+}
@@ -0,0 +1,1407 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::clipboard::{self, ClipboardKind};
+use crate::color::Color;
+use crate::components::{ContextMenu, ScrollBar};
+use crate::data::{ComponentId, Orientation, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::{self, TextStyle};
+use crate::visuals;
+
+/// The time the caret is shown or hidden while blinking.
+const CARET_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+/// The maximum number of edits which can be undone.
+const UNDO_LIMIT: usize = 100;
+const SCROLL_BAR_THICKNESS: f32 = 14f32;
+const CARET_WIDTH: f32 = 1f32;
+/// The distance between the baseline and the underline of text being composed.
+const UNDERLINE_OFFSET: f32 = 2f32;
+
+/// Consecutive edits of the same kind are undone at once, eg. a typed word along with the
+/// whitespace following it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EditKind {
+    Typing,
+    Deleting,
+    Other,
+}
+
+#[derive(Clone, Debug)]
+struct UndoState {
+    text: String,
+    caret: usize,
+    anchor: usize,
+}
+
+/// A line of the text, as byte offsets not including the line break.
+#[derive(Clone, Copy, Debug)]
+struct LineRange {
+    start: usize,
+    end: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum CharacterClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+fn classify(character: char) -> CharacterClass {
+    if character.is_whitespace() {
+        CharacterClass::Whitespace
+    } else if character.is_alphanumeric() || character == '_' {
+        CharacterClass::Word
+    } else {
+        CharacterClass::Punctuation
+    }
+}
+
+/// # Description
+/// An editable text field, either single-line or multi-line.
+///
+/// # Remarks
+/// Multi-line text boxes do not wrap text, but scroll horizontally and vertically using
+/// scroll bars shown whenever the text does not fit.
+pub struct TextBox {
+    // #[property]
+    pub text: String,
+    // #[property]
+    pub text_style: TextStyle,
+    /// The text displayed while *text* is empty.
+    // #[property]
+    pub placeholder: String,
+    // #[property]
+    pub placeholder_color: Color,
+    /// The maximum number of characters the user can enter.
+    // #[property]
+    pub max_length: Option<usize>,
+    /// Whether the text can be selected and copied, but not edited.
+    // #[property]
+    pub read_only: bool,
+    /// Whether the text is masked using *password_char*.
+    /// Password text boxes do not allow copying their text.
+    // #[property]
+    pub password: bool,
+    // #[property]
+    pub password_char: char,
+    /// Whether the text box accepts line breaks.
+    // #[property]
+    pub multi_line: bool,
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub corner_radius: f32,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub selection_background: Color,
+    // #[property]
+    pub caret_color: Color,
+    /// Called with the new text whenever the user changed the text.
+    // #[event]
+    pub on_text_changed: Option<Box<dyn FnMut(&str)>>,
+    /// Creates the context menu opened by right-clicking the text box.
+    // #[property]
+    pub context_menu: Option<Rc<dyn Fn() -> ContextMenu>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    /// The byte offset of the caret.
+    caret: usize,
+    /// The byte offset the selection started at. The selection spans from here to the caret.
+    anchor: usize,
+    /// The horizontal position kept while moving the caret up and down.
+    preferred_x: Option<f32>,
+    lines: Vec<LineRange>,
+    line_height: f32,
+    /// The distance from the top of a line to its baseline.
+    baseline: f32,
+    content_width: f32,
+    /// The area the text is displayed in.
+    viewport: Rectangle<f32>,
+    /// The horizontal scroll offset of single-line text boxes.
+    scroll_x: f32,
+    horizontal_bar: ScrollBar,
+    vertical_bar: ScrollBar,
+    horizontal_bar_visible: bool,
+    vertical_bar_visible: bool,
+    scroll_to_caret: bool,
+    focused: bool,
+    caret_visible: bool,
+    /// The text an input method is composing, displayed at the caret.
+    preedit: String,
+    /// The byte range of *preedit* the cursor of the input method spans.
+    preedit_cursor: Option<(usize, usize)>,
+    next_blink: Option<Instant>,
+    selecting: bool,
+    undo_stack: Vec<UndoState>,
+    redo_stack: Vec<UndoState>,
+    last_edit: EditKind,
+}
+
+impl Component for TextBox {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let metrics = text::text_metrics();
+        let line_height = metrics.font_metrics(&self.text_style).line_height;
+        let height = if self.multi_line {
+            let lines = self.text.split('\n').count() as f32;
+            f32::min(lines * line_height + self.padding.vertical(), available.height)
+        } else {
+            line_height + self.padding.vertical()
+        };
+        let width = if available.width.is_finite() {
+            available.width
+        } else {
+            let width = self
+                .text
+                .split('\n')
+                .map(|line| metrics.measure_text(&self.display(line), &self.text_style))
+                .fold(0f32, f32::max);
+            width + CARET_WIDTH + self.padding.horizontal()
+        };
+        Size { width, height }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        // The text may have been replaced since the last layout pass
+        self.caret = self.clamp_offset(self.caret);
+        self.anchor = self.clamp_offset(self.anchor);
+        self.update_lines();
+
+        let inner = Rectangle {
+            x: self.padding.left,
+            y: self.padding.top,
+            width: f32::max(0f32, given.width - self.padding.horizontal()),
+            height: f32::max(0f32, given.height - self.padding.vertical()),
+        };
+        let content_width = self.content_width + CARET_WIDTH;
+        let content_height = self.lines.len() as f32 * self.line_height;
+        self.vertical_bar_visible = false;
+        self.horizontal_bar_visible = false;
+        if self.multi_line {
+            self.vertical_bar_visible = content_height > inner.height;
+            self.horizontal_bar_visible = content_width
+                > inner.width - if self.vertical_bar_visible { SCROLL_BAR_THICKNESS } else { 0f32 };
+            if self.horizontal_bar_visible && !self.vertical_bar_visible {
+                self.vertical_bar_visible = content_height > inner.height - SCROLL_BAR_THICKNESS;
+            }
+        }
+        self.viewport = Rectangle {
+            x: inner.x,
+            y: inner.y,
+            width: f32::max(
+                0f32,
+                inner.width - if self.vertical_bar_visible { SCROLL_BAR_THICKNESS } else { 0f32 },
+            ),
+            height: f32::max(
+                0f32,
+                inner.height - if self.horizontal_bar_visible { SCROLL_BAR_THICKNESS } else { 0f32 },
+            ),
+        };
+
+        self.vertical_bar.range.max_value = f32::max(0f32, content_height - self.viewport.height);
+        self.vertical_bar.bar_value = self.viewport.height;
+        self.vertical_bar.range.small_change = self.line_height;
+        self.vertical_bar.set_value(self.vertical_bar.range.value);
+        self.vertical_bar.arrange(Size {
+            width: SCROLL_BAR_THICKNESS,
+            height: self.viewport.height,
+        });
+        self.horizontal_bar.range.max_value = f32::max(0f32, content_width - self.viewport.width);
+        self.horizontal_bar.bar_value = self.viewport.width;
+        self.horizontal_bar.range.small_change = self.line_height;
+        self.horizontal_bar.set_value(self.horizontal_bar.range.value);
+        self.horizontal_bar.arrange(Size {
+            width: self.viewport.width,
+            height: SCROLL_BAR_THICKNESS,
+        });
+        self.scroll_x = f32::max(0f32, f32::min(self.scroll_x, content_width - self.viewport.width));
+
+        if self.scroll_to_caret {
+            self.scroll_to_caret = false;
+            let caret = self.caret_position();
+            let mut scroll = self.scroll_offset();
+            if caret.x < scroll.x {
+                scroll.x = caret.x;
+            } else if caret.x + CARET_WIDTH > scroll.x + self.viewport.width {
+                scroll.x = caret.x + CARET_WIDTH - self.viewport.width;
+            }
+            if caret.y < scroll.y {
+                scroll.y = caret.y;
+            } else if caret.y + self.line_height > scroll.y + self.viewport.height {
+                scroll.y = caret.y + self.line_height - self.viewport.height;
+            }
+            self.set_scroll_offset(scroll);
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::RoundedRectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            radius: self.corner_radius,
+            fill: self.background,
+            stroke: if self.focused { self.focused_border } else { self.border },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        renderer.push_clip(
+            self.viewport.x,
+            self.viewport.y,
+            self.viewport.width,
+            self.viewport.height,
+        );
+        let scroll = self.scroll_offset();
+        let origin = Point {
+            x: self.viewport.x - scroll.x,
+            y: self.viewport.y - scroll.y,
+        };
+        let first = (scroll.y / self.line_height).floor().max(0f32) as usize;
+        let visible = (self.viewport.height / self.line_height).ceil() as usize + 1;
+        let (selection_start, selection_end) = self.selection();
+
+        if self.text.is_empty() && !self.placeholder.is_empty() {
+            let mut style = self.text_style.clone();
+            style.color = self.placeholder_color;
+            visuals::Text {
+                x: origin.x,
+                y: origin.y + self.baseline,
+                text: &self.placeholder,
+                style: &style,
+            }
+            .draw(renderer);
+        }
+        for (index, line) in self.lines.iter().enumerate().skip(first).take(visible) {
+            let top = origin.y + index as f32 * self.line_height;
+            if self.focused && selection_start < selection_end {
+                let start = usize::max(selection_start, line.start);
+                let end = usize::min(selection_end, line.end);
+                if start <= end && selection_start <= line.end && selection_end >= line.start {
+                    let x = self.offset_to_x(*line, start);
+                    let mut width = self.offset_to_x(*line, end) - x;
+                    // Selected line breaks are shown as a small extra selection
+                    if selection_end > line.end {
+                        width += self.line_height / 4f32;
+                    }
+                    renderer.draw_rectangle(
+                        origin.x + x,
+                        top,
+                        width,
+                        self.line_height,
+                        self.selection_background,
+                    );
+                }
+            }
+            if !self.preedit.is_empty() && line.start <= self.caret && self.caret <= line.end {
+                self.render_composition(renderer, *line, Point { x: origin.x, y: top });
+                continue;
+            }
+            let display = self.display(&self.text[line.start..line.end]);
+            visuals::Text {
+                x: origin.x,
+                y: top + self.baseline,
+                text: &display,
+                style: &self.text_style,
+            }
+            .draw(renderer);
+        }
+        let caret_hidden = !self.preedit.is_empty() && self.preedit_cursor.is_none();
+        if self.focused && self.caret_visible && !caret_hidden {
+            let caret = self.caret_position();
+            renderer.draw_rectangle(
+                origin.x + caret.x,
+                origin.y + caret.y,
+                CARET_WIDTH,
+                self.line_height,
+                self.caret_color,
+            );
+        }
+        renderer.pop_clip();
+
+        if self.vertical_bar_visible {
+            let bounds = self.vertical_bar_bounds();
+            renderer.translate(bounds.x, bounds.y);
+            self.vertical_bar.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+        if self.horizontal_bar_visible {
+            let bounds = self.horizontal_bar_bounds();
+            renderer.translate(bounds.x, bounds.y);
+            self.horizontal_bar.render(renderer);
+            renderer.translate(-bounds.x, -bounds.y);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        let selection = self.selection();
+        let selecting = self.selecting;
+        let handled = match event {
+            InputEvent::Tick => {
+                match self.next_blink {
+                    Some(at) if self.focused && at <= context.now() => {
+                        self.caret_visible = !self.caret_visible;
+                        let next = at + CARET_BLINK_INTERVAL;
+                        self.next_blink = Some(next);
+                        context.request_wake_up(next);
+                    }
+                    Some(at) if self.focused => context.request_wake_up(at),
+                    _ => {}
+                }
+                return false;
+            }
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                self.selecting = false;
+                self.preedit.clear();
+                self.preedit_cursor = None;
+                self.next_blink = None;
+                return true;
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left } => {
+                let offset = self.hit_test(*position);
+                match context.click_count() {
+                    2 => {
+                        let (start, end) = self.word_at(offset);
+                        self.select(start, end);
+                    }
+                    3 => {
+                        let line = self.lines[self.line_of(offset)];
+                        self.select(line.start, line.end);
+                    }
+                    // A fourth click starts over, placing the caret
+                    _ => self.move_caret(offset, context.modifiers().shift_key()),
+                }
+                self.selecting = true;
+                context.capture_pointer();
+                true
+            }
+            // Middle clicks paste the primary selection, as usual on Linux
+            InputEvent::PointerPressed { position, button: MouseButton::Middle } => {
+                let offset = self.hit_test(*position);
+                self.move_caret(offset, false);
+                self.paste_from(ClipboardKind::Primary);
+                true
+            }
+            InputEvent::PointerMoved { position } => {
+                if !self.selecting {
+                    return false;
+                }
+                let offset = self.hit_test(*position);
+                self.move_caret(offset, true);
+                true
+            }
+            InputEvent::PointerReleased { button: MouseButton::Left, .. } => {
+                if !self.selecting {
+                    return false;
+                }
+                self.selecting = false;
+                context.release_pointer_capture();
+                true
+            }
+            InputEvent::PointerCaptureLost => {
+                self.selecting = false;
+                return true;
+            }
+            InputEvent::PointerWheel { delta, .. } => {
+                if !self.multi_line {
+                    return false;
+                }
+                if self.vertical_bar_visible && delta.y != 0f32 {
+                    let value = self.vertical_bar.range.value - delta.y * self.vertical_bar.range.small_change;
+                    self.vertical_bar.set_value(value);
+                }
+                if self.horizontal_bar_visible && delta.x != 0f32 {
+                    let value =
+                        self.horizontal_bar.range.value - delta.x * self.horizontal_bar.range.small_change;
+                    self.horizontal_bar.set_value(value);
+                }
+                return true;
+            }
+            // Keys are consumed by the input method while it composes text
+            InputEvent::KeyPressed { .. } if !self.preedit.is_empty() => true,
+            InputEvent::KeyPressed { key, text, modifiers, .. } => {
+                self.handle_key(key, text.as_deref(), *modifiers)
+            }
+            InputEvent::ImePreedit { text, cursor } => {
+                if self.read_only {
+                    return false;
+                }
+                // Composing replaces the selection, just like typing does
+                let (start, end) = self.selection();
+                if self.preedit.is_empty() && !text.is_empty() && start != end {
+                    self.insert("", EditKind::Typing);
+                }
+                self.preedit = text.clone();
+                self.preedit_cursor = *cursor;
+                self.scroll_to_caret = true;
+                true
+            }
+            InputEvent::ImeCommit { text } => {
+                self.preedit.clear();
+                self.preedit_cursor = None;
+                self.insert(text, EditKind::Typing);
+                true
+            }
+            _ => false,
+        };
+        // Selections are published once the user finished selecting
+        let (start, end) = self.selection();
+        let changed = self.selection() != selection || selecting;
+        if handled && changed && !self.selecting && !self.password && start != end {
+            let _ = clipboard::clipboard().set_text(ClipboardKind::Primary, &self.text[start..end]);
+        }
+        if handled && self.focused {
+            // The caret stays visible while the user interacts with the text box
+            self.caret_visible = true;
+            let next = context.now() + CARET_BLINK_INTERVAL;
+            self.next_blink = Some(next);
+            context.request_wake_up(next);
+        }
+        handled
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        if self.vertical_bar_visible {
+            visitor(self.vertical_bar_bounds(), &self.vertical_bar);
+        }
+        if self.horizontal_bar_visible {
+            visitor(self.horizontal_bar_bounds(), &self.horizontal_bar);
+        }
+    }
+
+    fn visit_children_mut(&mut self, visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component)) {
+        if self.vertical_bar_visible {
+            visitor(self.vertical_bar_bounds(), &mut self.vertical_bar);
+        }
+        if self.horizontal_bar_visible {
+            visitor(self.horizontal_bar_bounds(), &mut self.horizontal_bar);
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        Some(Focusable::new())
+    }
+
+    fn text_input_area(&self) -> Option<Rectangle<f32>> {
+        // Input methods are not used for passwords, as they would reveal the composed text
+        if self.read_only || self.password {
+            return None;
+        }
+        let scroll = self.scroll_offset();
+        let line = self.line_of(self.caret);
+        let width = text::text_metrics().measure_text(&self.preedit, &self.text_style);
+        Some(Rectangle {
+            x: self.viewport.x - scroll.x + self.offset_to_x(self.lines[line], self.caret),
+            y: self.viewport.y - scroll.y + line as f32 * self.line_height,
+            width: f32::max(width, CARET_WIDTH),
+            height: self.line_height,
+        })
+    }
+
+    fn context_menu(&self) -> Option<ContextMenu> {
+        self.context_menu.as_ref().map(|create| create())
+    }
+}
+
+impl TextBox {
+    pub fn new() -> TextBox {
+        let mut vertical_bar = ScrollBar::new();
+        vertical_bar.mode = Orientation::Vertical;
+        vertical_bar.thickness = SCROLL_BAR_THICKNESS;
+        let mut horizontal_bar = ScrollBar::new();
+        horizontal_bar.mode = Orientation::Horizontal;
+        horizontal_bar.thickness = SCROLL_BAR_THICKNESS;
+        TextBox {
+            id: ComponentId::new(),
+            text: String::new(),
+            text_style: TextStyle::new(),
+            placeholder: String::new(),
+            placeholder_color: Color::GRAY,
+            max_length: None,
+            read_only: false,
+            password: false,
+            password_char: '●',
+            multi_line: false,
+            padding: Thickness { left: 4f32, top: 3f32, right: 4f32, bottom: 3f32 },
+            corner_radius: 2f32,
+            background: Color::WHITE,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            selection_background: Color::LIGHT_SKY_BLUE,
+            caret_color: Color::BLACK,
+            on_text_changed: None,
+            context_menu: None,
+            size: Size { width: 0f32, height: 0f32 },
+            caret: 0,
+            anchor: 0,
+            preferred_x: None,
+            lines: vec![LineRange { start: 0, end: 0 }],
+            line_height: 0f32,
+            baseline: 0f32,
+            content_width: 0f32,
+            viewport: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+            scroll_x: 0f32,
+            horizontal_bar,
+            vertical_bar,
+            horizontal_bar_visible: false,
+            vertical_bar_visible: false,
+            scroll_to_caret: false,
+            focused: false,
+            caret_visible: false,
+            preedit: String::new(),
+            preedit_cursor: None,
+            next_blink: None,
+            selecting: false,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            last_edit: EditKind::Other,
+        }
+    }
+
+    /// # Description
+    /// The selected range of the text, as byte offsets.
+    ///
+    /// # Returns
+    /// The start and the end of the selection, which are equal if nothing is selected.
+    pub fn selection(&self) -> (usize, usize) {
+        (usize::min(self.caret, self.anchor), usize::max(self.caret, self.anchor))
+    }
+
+    /// # Description
+    /// The selected text.
+    pub fn selected_text(&self) -> &str {
+        let (start, end) = self.selection();
+        &self.text[start..end]
+    }
+
+    /// # Description
+    /// Selects the text between the byte offsets *start* and *end*, placing the caret at *end*.
+    pub fn select(&mut self, start: usize, end: usize) {
+        self.anchor = self.clamp_offset(start);
+        self.caret = self.clamp_offset(end);
+        self.preferred_x = None;
+        self.last_edit = EditKind::Other;
+        self.scroll_to_caret = true;
+    }
+
+    pub fn select_all(&mut self) {
+        self.select(0, self.text.len());
+    }
+
+    /// # Description
+    /// Whether the text box has the keyboard focus.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.read_only && !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.read_only && !self.redo_stack.is_empty()
+    }
+
+    /// # Description
+    /// Reverts the last edit, or the last sequence of consecutive typing or deleting.
+    pub fn undo(&mut self) {
+        if !self.can_undo() {
+            return;
+        }
+        if let Some(state) = self.undo_stack.pop() {
+            let current = self.restore(state);
+            self.redo_stack.push(current);
+        }
+    }
+
+    /// # Description
+    /// Applies the last edit reverted by *undo* again.
+    pub fn redo(&mut self) {
+        if !self.can_redo() {
+            return;
+        }
+        if let Some(state) = self.redo_stack.pop() {
+            let current = self.restore(state);
+            self.undo_stack.push(current);
+        }
+    }
+
+    /// # Description
+    /// Copies the selected text to the clipboard.
+    ///
+    /// # Remarks
+    /// Does nothing for password text boxes.
+    pub fn copy(&self) {
+        let (start, end) = self.selection();
+        if self.password || start == end {
+            return;
+        }
+        let _ = clipboard::clipboard().set_text(ClipboardKind::Clipboard, &self.text[start..end]);
+    }
+
+    /// # Description
+    /// Copies the selected text to the clipboard and removes it.
+    pub fn cut(&mut self) {
+        if self.password || self.read_only {
+            return;
+        }
+        self.copy();
+        self.insert("", EditKind::Other);
+    }
+
+    /// # Description
+    /// Replaces the selection with the text on the clipboard.
+    pub fn paste(&mut self) {
+        self.paste_from(ClipboardKind::Clipboard);
+    }
+
+    fn paste_from(&mut self, kind: ClipboardKind) {
+        if let Ok(text) = clipboard::clipboard().text(kind) {
+            self.insert(&text, EditKind::Other);
+        }
+    }
+
+    fn handle_key(&mut self, key: &Key, text: Option<&str>, modifiers: ModifiersState) -> bool {
+        let control = modifiers.control_key();
+        let shift = modifiers.shift_key();
+        match key {
+            Key::Named(NamedKey::ArrowLeft) => {
+                let (start, end) = self.selection();
+                if !shift && start != end {
+                    self.move_caret(start, false);
+                } else if control {
+                    self.move_caret(self.previous_word(self.caret), shift);
+                } else {
+                    self.move_caret(self.previous_character(self.caret), shift);
+                }
+                true
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                let (start, end) = self.selection();
+                if !shift && start != end {
+                    self.move_caret(end, false);
+                } else if control {
+                    self.move_caret(self.next_word(self.caret), shift);
+                } else {
+                    self.move_caret(self.next_character(self.caret), shift);
+                }
+                true
+            }
+            Key::Named(NamedKey::ArrowUp) if self.multi_line => {
+                self.move_lines(-1, shift);
+                true
+            }
+            Key::Named(NamedKey::ArrowDown) if self.multi_line => {
+                self.move_lines(1, shift);
+                true
+            }
+            Key::Named(NamedKey::PageUp) if self.multi_line => {
+                self.move_lines(-self.page_lines(), shift);
+                true
+            }
+            Key::Named(NamedKey::PageDown) if self.multi_line => {
+                self.move_lines(self.page_lines(), shift);
+                true
+            }
+            Key::Named(NamedKey::Home) => {
+                let offset = if control {
+                    0
+                } else {
+                    self.lines[self.line_of(self.caret)].start
+                };
+                self.move_caret(offset, shift);
+                true
+            }
+            Key::Named(NamedKey::End) => {
+                let offset = if control {
+                    self.text.len()
+                } else {
+                    self.lines[self.line_of(self.caret)].end
+                };
+                self.move_caret(offset, shift);
+                true
+            }
+            // Unhandled on read-only text boxes, leaving the key to the parents
+            Key::Named(NamedKey::Backspace) if !self.read_only => {
+                let (start, end) = self.selection();
+                if start == end {
+                    self.anchor = if control {
+                        self.previous_word(self.caret)
+                    } else {
+                        self.previous_character(self.caret)
+                    };
+                }
+                self.insert("", EditKind::Deleting);
+                true
+            }
+            Key::Named(NamedKey::Delete) if !self.read_only => {
+                let (start, end) = self.selection();
+                if start == end {
+                    self.anchor = if control {
+                        self.next_word(self.caret)
+                    } else {
+                        self.next_character(self.caret)
+                    };
+                }
+                self.insert("", EditKind::Deleting);
+                true
+            }
+            Key::Named(NamedKey::Enter) if self.multi_line => {
+                self.insert("\n", EditKind::Other);
+                true
+            }
+            // AltGr is reported as Ctrl+Alt on some platforms and produces text
+            Key::Character(character) if control && !modifiers.alt_key() => {
+                match character.to_lowercase().as_str() {
+                    "a" => self.select_all(),
+                    "c" => self.copy(),
+                    "x" => self.cut(),
+                    "v" => self.paste(),
+                    "z" if shift => self.redo(),
+                    "z" => self.undo(),
+                    "y" => self.redo(),
+                    _ => return false,
+                }
+                true
+            }
+            _ => {
+                let text: String = match text {
+                    None => return false,
+                    Some(d) => d.chars().filter(|d| !d.is_control()).collect(),
+                };
+                if text.is_empty() {
+                    return false;
+                }
+                self.insert(&text, EditKind::Typing);
+                true
+            }
+        }
+    }
+
+    /// Replaces the selection with *text*, respecting *read_only*, *multi_line* and
+    /// *max_length*.
+    fn insert(&mut self, text: &str, kind: EditKind) {
+        if self.read_only {
+            return;
+        }
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let mut text = if self.multi_line {
+            text
+        } else {
+            text.split('\n').next().unwrap_or("").to_string()
+        };
+        let (start, end) = self.selection();
+        if let Some(max_length) = self.max_length {
+            let remaining = self.text.chars().count() - self.text[start..end].chars().count();
+            let allowed = max_length.saturating_sub(remaining);
+            if let Some((index, _)) = text.char_indices().nth(allowed) {
+                text.truncate(index);
+            }
+        }
+        if text.is_empty() && start == end {
+            return;
+        }
+        // Typing a word after whitespace starts a new undo step, so every word is undone alone
+        let new_word = kind == EditKind::Typing
+            && text.chars().next().map_or(false, |d| !d.is_whitespace())
+            && self.text[..start].chars().next_back().map_or(false, char::is_whitespace);
+        if kind == EditKind::Other || kind != self.last_edit || new_word {
+            self.undo_stack.push(UndoState {
+                text: self.text.clone(),
+                caret: self.caret,
+                anchor: self.anchor,
+            });
+            if self.undo_stack.len() > UNDO_LIMIT {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.last_edit = kind;
+        self.text.replace_range(start..end, &text);
+        self.caret = start + text.len();
+        self.anchor = self.caret;
+        self.text_changed();
+    }
+
+    /// Replaces text, caret and selection with *state*.
+    ///
+    /// # Returns
+    /// The state replaced.
+    fn restore(&mut self, state: UndoState) -> UndoState {
+        let current = UndoState {
+            text: std::mem::replace(&mut self.text, state.text),
+            caret: self.caret,
+            anchor: self.anchor,
+        };
+        self.caret = self.clamp_offset(state.caret);
+        self.anchor = self.clamp_offset(state.anchor);
+        self.last_edit = EditKind::Other;
+        self.text_changed();
+        current
+    }
+
+    fn text_changed(&mut self) {
+        self.preferred_x = None;
+        self.scroll_to_caret = true;
+        self.update_lines();
+        if let Some(callback) = self.on_text_changed.as_mut() {
+            callback(&self.text);
+        }
+    }
+
+    fn move_caret(&mut self, offset: usize, extend: bool) {
+        self.caret = self.clamp_offset(offset);
+        if !extend {
+            self.anchor = self.caret;
+        }
+        self.preferred_x = None;
+        self.last_edit = EditKind::Other;
+        self.scroll_to_caret = true;
+    }
+
+    /// Moves the caret by *count* lines, keeping its horizontal position.
+    fn move_lines(&mut self, count: isize, extend: bool) {
+        let line = self.line_of(self.caret);
+        let x = match self.preferred_x {
+            Some(d) => d,
+            None => self.offset_to_x(self.lines[line], self.caret),
+        };
+        let target = line as isize + count;
+        let offset = if target < 0 {
+            0
+        } else if target as usize >= self.lines.len() {
+            self.text.len()
+        } else {
+            self.x_to_offset(self.lines[target as usize], x)
+        };
+        self.move_caret(offset, extend);
+        self.preferred_x = Some(x);
+    }
+
+    fn page_lines(&self) -> isize {
+        isize::max(1, (self.viewport.height / self.line_height).floor() as isize)
+    }
+
+    /// The text as displayed, masking it for password text boxes.
+    fn display<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.password {
+            Cow::Owned(std::iter::repeat(self.password_char).take(text.chars().count()).collect())
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    fn update_lines(&mut self) {
+        let metrics = text::text_metrics();
+        let font = metrics.font_metrics(&self.text_style);
+        self.line_height = font.line_height;
+        self.baseline = (font.line_height - font.ascent - font.descent) / 2f32 + font.ascent;
+        self.lines.clear();
+        let mut start = 0;
+        for line in self.text.split('\n') {
+            self.lines.push(LineRange {
+                start,
+                end: start + line.len(),
+            });
+            start += line.len() + 1;
+        }
+        self.content_width = self
+            .lines
+            .iter()
+            .map(|line| {
+                metrics.measure_text(&self.display(&self.text[line.start..line.end]), &self.text_style)
+            })
+            .fold(0f32, f32::max);
+    }
+
+    fn clamp_offset(&self, offset: usize) -> usize {
+        let mut offset = usize::min(offset, self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    fn line_of(&self, offset: usize) -> usize {
+        self.lines
+            .iter()
+            .position(|line| offset <= line.end)
+            .unwrap_or(self.lines.len() - 1)
+    }
+
+    /// The position of the caret, relative to the scrolled content.
+    ///
+    /// # Remarks
+    /// While text is composed, this is the position of the cursor of the input method.
+    fn caret_position(&self) -> Point<f32> {
+        let line = self.line_of(self.caret);
+        let mut x = self.offset_to_x(self.lines[line], self.caret);
+        if let Some((cursor, _)) = self.preedit_cursor {
+            let cursor = usize::min(cursor, self.preedit.len());
+            if self.preedit.is_char_boundary(cursor) {
+                x += text::text_metrics()
+                    .measure_text(&self.display(&self.preedit[..cursor]), &self.text_style);
+            }
+        }
+        Point {
+            x,
+            y: line as f32 * self.line_height,
+        }
+    }
+
+    /// Draws *line* with the text being composed inserted at the caret and underlined.
+    fn render_composition(&self, renderer: &mut dyn Renderer, line: LineRange, origin: Point<f32>) {
+        let metrics = text::text_metrics();
+        let before = self.display(&self.text[line.start..self.caret]);
+        let preedit = self.display(&self.preedit);
+        let after = self.display(&self.text[self.caret..line.end]);
+        let x = metrics.measure_text(&before, &self.text_style);
+        let width = metrics.measure_text(&preedit, &self.text_style);
+        let baseline = origin.y + self.baseline;
+        for (offset, text) in [(0f32, &before), (x, &preedit), (x + width, &after)] {
+            visuals::Text {
+                x: origin.x + offset,
+                y: baseline,
+                text,
+                style: &self.text_style,
+            }
+            .draw(renderer);
+        }
+        renderer.draw_rectangle(
+            origin.x + x,
+            baseline + UNDERLINE_OFFSET,
+            width,
+            1f32,
+            self.text_style.color,
+        );
+        // The part of the composition the input method currently converts is emphasized
+        if let Some((start, end)) = self.preedit_cursor {
+            let valid = start < end
+                && end <= self.preedit.len()
+                && self.preedit.is_char_boundary(start)
+                && self.preedit.is_char_boundary(end);
+            if valid {
+                let start_x = metrics.measure_text(&self.display(&self.preedit[..start]), &self.text_style);
+                let end_x = metrics.measure_text(&self.display(&self.preedit[..end]), &self.text_style);
+                renderer.draw_rectangle(
+                    origin.x + x + start_x,
+                    baseline + UNDERLINE_OFFSET,
+                    end_x - start_x,
+                    2f32,
+                    self.text_style.color,
+                );
+            }
+        }
+    }
+
+    fn offset_to_x(&self, line: LineRange, offset: usize) -> f32 {
+        let offset = usize::max(line.start, usize::min(offset, line.end));
+        text::text_metrics().measure_text(&self.display(&self.text[line.start..offset]), &self.text_style)
+    }
+
+    /// The offset in *line* closest to *x*.
+    fn x_to_offset(&self, line: LineRange, x: f32) -> usize {
+        let metrics = text::text_metrics();
+        let text = &self.text[line.start..line.end];
+        let mut previous = 0f32;
+        for (index, character) in text.char_indices() {
+            let end = index + character.len_utf8();
+            let width = metrics.measure_text(&self.display(&text[..end]), &self.text_style);
+            if x < (previous + width) / 2f32 {
+                return line.start + index;
+            }
+            previous = width;
+        }
+        line.end
+    }
+
+    /// The offset closest to *position*, relative to the text box.
+    fn hit_test(&self, position: Point<f32>) -> usize {
+        let scroll = self.scroll_offset();
+        let y = position.y - self.viewport.y + scroll.y;
+        let line = if y < 0f32 {
+            0
+        } else {
+            usize::min((y / self.line_height) as usize, self.lines.len() - 1)
+        };
+        self.x_to_offset(self.lines[line], position.x - self.viewport.x + scroll.x)
+    }
+
+    fn scroll_offset(&self) -> Point<f32> {
+        if self.multi_line {
+            Point {
+                x: self.horizontal_bar.range.value,
+                y: self.vertical_bar.range.value,
+            }
+        } else {
+            Point { x: self.scroll_x, y: 0f32 }
+        }
+    }
+
+    fn set_scroll_offset(&mut self, scroll: Point<f32>) {
+        if self.multi_line {
+            self.horizontal_bar.set_value(scroll.x);
+            self.vertical_bar.set_value(scroll.y);
+        } else {
+            self.scroll_x = f32::max(0f32, scroll.x);
+        }
+    }
+
+    fn vertical_bar_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: self.viewport.x + self.viewport.width,
+            y: self.viewport.y,
+            width: SCROLL_BAR_THICKNESS,
+            height: self.viewport.height,
+        }
+    }
+
+    fn horizontal_bar_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: self.viewport.x,
+            y: self.viewport.y + self.viewport.height,
+            width: self.viewport.width,
+            height: SCROLL_BAR_THICKNESS,
+        }
+    }
+
+    fn previous_character(&self, offset: usize) -> usize {
+        self.text[..offset]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_character(&self, offset: usize) -> usize {
+        self.text[offset..]
+            .chars()
+            .next()
+            .map_or(offset, |d| offset + d.len_utf8())
+    }
+
+    /// The start of the word before *offset*.
+    fn previous_word(&self, offset: usize) -> usize {
+        let mut characters = self.text[..offset].char_indices().rev().peekable();
+        while let Some((_, d)) = characters.peek() {
+            if classify(*d) != CharacterClass::Whitespace {
+                break;
+            }
+            characters.next();
+        }
+        let class = match characters.peek() {
+            None => return 0,
+            Some((_, d)) => classify(*d),
+        };
+        let mut start = offset;
+        for (index, d) in characters {
+            if classify(d) != class {
+                break;
+            }
+            start = index;
+        }
+        start
+    }
+
+    /// The start of the word after *offset*.
+    fn next_word(&self, offset: usize) -> usize {
+        let text = &self.text[offset..];
+        let class = match text.chars().next() {
+            None => return offset,
+            Some(d) => classify(d),
+        };
+        let mut skipping_word = class != CharacterClass::Whitespace;
+        for (index, d) in text.char_indices() {
+            let current = classify(d);
+            if skipping_word && current != class {
+                skipping_word = false;
+            }
+            if !skipping_word && current != CharacterClass::Whitespace {
+                return offset + index;
+            }
+        }
+        self.text.len()
+    }
+
+    /// The range of the word (or whitespace) around *offset*.
+    fn word_at(&self, offset: usize) -> (usize, usize) {
+        let class = match self.text[offset..].chars().next() {
+            Some(d) if d != '\n' => classify(d),
+            _ => return (offset, offset),
+        };
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, d)| *d != '\n' && classify(*d) == class)
+            .last()
+            .map_or(offset, |(index, _)| index);
+        let end = self.text[offset..]
+            .char_indices()
+            .find(|(_, d)| *d == '\n' || classify(*d) != class)
+            .map_or(self.text.len(), |(index, _)| offset + index);
+        (start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::{Clipboard, MemoryClipboard};
+    use crate::pointer::PointerState;
+    use std::rc::Rc;
+
+    fn text_box(text: &str) -> TextBox {
+        let mut text_box = TextBox::new();
+        text_box.text = text.to_string();
+        text_box.arrange(Size { width: 200f32, height: 24f32 });
+        text_box
+    }
+
+    fn memory_clipboard() -> Rc<MemoryClipboard> {
+        let clipboard = Rc::new(MemoryClipboard::new());
+        clipboard::set_clipboard(clipboard.clone());
+        clipboard
+    }
+
+    fn press(text_box: &mut TextBox, key: Key, modifiers: ModifiersState) -> bool {
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, modifiers);
+        let event = InputEvent::KeyPressed { key, text: None, modifiers, repeat: false };
+        text_box.handle_event(&event, &mut context)
+    }
+
+    fn control(character: &str) -> (Key, ModifiersState) {
+        (Key::Character(character.into()), ModifiersState::CONTROL)
+    }
+
+    fn type_text(text_box: &mut TextBox, text: &str) {
+        for d in text.chars() {
+            let d = d.to_string();
+            text_box.handle_key(&Key::Character(d.as_str().into()), Some(&d), ModifiersState::empty());
+        }
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut text_box = text_box("foo bar.baz  qux");
+        let right = Key::Named(NamedKey::ArrowRight);
+        let left = Key::Named(NamedKey::ArrowLeft);
+        let word = ModifiersState::CONTROL;
+        press(&mut text_box, right.clone(), word);
+        assert_eq!(text_box.selection(), (4, 4));
+        press(&mut text_box, right.clone(), word);
+        assert_eq!(text_box.selection(), (7, 7));
+        press(&mut text_box, right.clone(), word);
+        assert_eq!(text_box.selection(), (8, 8));
+        press(&mut text_box, right.clone(), ModifiersState::empty());
+        assert_eq!(text_box.selection(), (9, 9));
+        press(&mut text_box, Key::Named(NamedKey::End), ModifiersState::empty());
+        assert_eq!(text_box.selection(), (16, 16));
+        press(&mut text_box, left.clone(), word);
+        assert_eq!(text_box.selection(), (13, 13));
+        press(&mut text_box, left.clone(), word);
+        assert_eq!(text_box.selection(), (8, 8));
+        press(&mut text_box, left, ModifiersState::empty());
+        assert_eq!(text_box.selection(), (7, 7));
+        press(&mut text_box, Key::Named(NamedKey::Home), ModifiersState::empty());
+        assert_eq!(text_box.selection(), (0, 0));
+        assert_eq!(text_box.word_at(5), (4, 7));
+    }
+
+    #[test]
+    fn test_shift_selection() {
+        let mut text_box = text_box("foo bar baz");
+        let right = Key::Named(NamedKey::ArrowRight);
+        press(&mut text_box, right.clone(), ModifiersState::SHIFT | ModifiersState::CONTROL);
+        assert_eq!(text_box.selected_text(), "foo ");
+        press(&mut text_box, right.clone(), ModifiersState::SHIFT);
+        assert_eq!(text_box.selected_text(), "foo b");
+        press(&mut text_box, Key::Named(NamedKey::Home), ModifiersState::SHIFT);
+        assert_eq!(text_box.selection(), (0, 0));
+        press(&mut text_box, Key::Named(NamedKey::End), ModifiersState::SHIFT);
+        assert_eq!(text_box.selected_text(), "foo bar baz");
+        // Moving without Shift collapses the selection to its edge
+        press(&mut text_box, Key::Named(NamedKey::ArrowLeft), ModifiersState::empty());
+        assert_eq!(text_box.selection(), (0, 0));
+        text_box.select(4, 7);
+        press(&mut text_box, right, ModifiersState::empty());
+        assert_eq!(text_box.selection(), (7, 7));
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut text_box = text_box("");
+        type_text(&mut text_box, "Hello World");
+        text_box.undo();
+        assert_eq!(text_box.text, "Hello ");
+        text_box.undo();
+        assert_eq!(text_box.text, "");
+        assert!(!text_box.can_undo());
+        text_box.redo();
+        text_box.redo();
+        assert_eq!(text_box.text, "Hello World");
+        assert_eq!(text_box.selection(), (11, 11));
+
+        let backspace = Key::Named(NamedKey::Backspace);
+        press(&mut text_box, backspace.clone(), ModifiersState::empty());
+        press(&mut text_box, backspace, ModifiersState::empty());
+        assert_eq!(text_box.text, "Hello Wor");
+        let (key, modifiers) = control("z");
+        press(&mut text_box, key, modifiers);
+        assert_eq!(text_box.text, "Hello World");
+        assert!(text_box.can_redo());
+        // A new edit discards the reverted ones
+        type_text(&mut text_box, "!");
+        assert!(!text_box.can_redo());
+        text_box.undo();
+        assert_eq!(text_box.text, "Hello World");
+    }
+
+    #[test]
+    fn test_read_only() {
+        let mut text_box = text_box("Hello");
+        text_box.read_only = true;
+        text_box.select(2, 2);
+        assert!(!press(&mut text_box, Key::Named(NamedKey::Backspace), ModifiersState::empty()));
+        assert!(!press(&mut text_box, Key::Named(NamedKey::Delete), ModifiersState::CONTROL));
+        type_text(&mut text_box, "x");
+        assert_eq!(text_box.text, "Hello");
+        // Moving the caret still works
+        assert!(press(&mut text_box, Key::Named(NamedKey::End), ModifiersState::empty()));
+        assert_eq!(text_box.selection(), (5, 5));
+    }
+
+    #[test]
+    fn test_max_length() {
+        let mut text_box = text_box("abc");
+        text_box.max_length = Some(5);
+        text_box.select(3, 3);
+        type_text(&mut text_box, "defg");
+        assert_eq!(text_box.text, "abcde");
+        // Replacing the selection frees its characters
+        text_box.select(0, 2);
+        text_box.insert("xyz12", EditKind::Other);
+        assert_eq!(text_box.text, "xycde");
+        text_box.max_length = None;
+        text_box.select_all();
+        text_box.insert("äö\nü", EditKind::Other);
+        assert_eq!(text_box.text, "äö");
+    }
+
+    #[test]
+    fn test_password() {
+        let mut text_box = text_box("abc");
+        text_box.password = true;
+        text_box.arrange(Size { width: 200f32, height: 24f32 });
+        let width = text_box.text_style.font_size * 0.5f32;
+        assert_eq!(text_box.display("abc"), "●●●");
+        assert_eq!(text_box.content_width, 3f32 * width);
+        assert_eq!(text_box.offset_to_x(text_box.lines[0], 2), 2f32 * width);
+        assert_eq!(text_box.display("äb"), "●●");
+        assert!(text_box.text_input_area().is_none());
+    }
+
+    #[test]
+    fn test_click_selection() {
+        let mut text_box = text_box("foo bar\nbaz");
+        text_box.multi_line = true;
+        text_box.arrange(Size { width: 200f32, height: 40f32 });
+        // Clicks onto the start of "bar"
+        let click = |text_box: &mut TextBox, count| {
+            let modifiers = ModifiersState::empty();
+            let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, modifiers);
+            context.click_count = count;
+            let position = Point { x: text_box.viewport.x + 27f32, y: text_box.viewport.y + 1f32 };
+            let event = InputEvent::PointerPressed { position, button: MouseButton::Left };
+            text_box.handle_event(&event, &mut context);
+        };
+        click(&mut text_box, 1);
+        assert_eq!(text_box.selection(), (4, 4));
+        click(&mut text_box, 2);
+        assert_eq!(text_box.selected_text(), "bar");
+        click(&mut text_box, 3);
+        assert_eq!(text_box.selected_text(), "foo bar");
+        click(&mut text_box, 4);
+        assert_eq!(text_box.selection(), (4, 4));
+    }
+
+    #[test]
+    fn test_copy_cut_paste() {
+        let clipboard = memory_clipboard();
+        let mut text_box = text_box("Hello World");
+        text_box.select(6, 11);
+        let (key, modifiers) = control("c");
+        assert!(press(&mut text_box, key, modifiers));
+        assert_eq!(clipboard.text(ClipboardKind::Clipboard).unwrap(), "World");
+        assert_eq!(text_box.text, "Hello World");
+
+        text_box.select(0, 6);
+        let (key, modifiers) = control("x");
+        press(&mut text_box, key, modifiers);
+        assert_eq!(clipboard.text(ClipboardKind::Clipboard).unwrap(), "Hello ");
+        assert_eq!(text_box.text, "World");
+
+        let (key, modifiers) = control("v");
+        press(&mut text_box, key, modifiers);
+        assert_eq!(text_box.text, "Hello World");
+        assert_eq!(text_box.selection(), (6, 6));
+    }
+
+    #[test]
+    fn test_copy_password() {
+        let clipboard = memory_clipboard();
+        clipboard.set_text(ClipboardKind::Clipboard, "Unchanged").unwrap();
+        let mut text_box = text_box("secret");
+        text_box.password = true;
+        text_box.select_all();
+        text_box.copy();
+        text_box.cut();
+        assert_eq!(clipboard.text(ClipboardKind::Clipboard).unwrap(), "Unchanged");
+        assert_eq!(text_box.text, "secret");
+    }
+
+    #[test]
+    fn test_primary_selection() {
+        let clipboard = memory_clipboard();
+        let mut text_box = text_box("Hello World");
+        press(&mut text_box, Key::Named(NamedKey::ArrowRight), ModifiersState::empty());
+        assert!(matches!(clipboard.text(ClipboardKind::Primary), Err(_)));
+        press(&mut text_box, Key::Named(NamedKey::End), ModifiersState::SHIFT);
+        assert_eq!(clipboard.text(ClipboardKind::Primary).unwrap(), "ello World");
+        assert!(matches!(clipboard.text(ClipboardKind::Clipboard), Err(_)));
+        // Collapsing the selection keeps the primary selection
+        press(&mut text_box, Key::Named(NamedKey::Home), ModifiersState::empty());
+        assert_eq!(clipboard.text(ClipboardKind::Primary).unwrap(), "ello World");
+
+        // Middle clicks paste the primary selection
+        clipboard.set_text(ClipboardKind::Primary, "Hi ").unwrap();
+        let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+        let event = InputEvent::PointerPressed {
+            position: Point { x: 0f32, y: 5f32 },
+            button: MouseButton::Middle,
+        };
+        text_box.handle_event(&event, &mut context);
+        assert_eq!(text_box.text, "Hi Hello World");
+    }
+}
@@ -0,0 +1,233 @@
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::glyph_label::GlyphLabel;
+use crate::components::press_state::{PressResult, PressState};
+use crate::data::{ComponentId, Rectangle, Size};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::TextStyle;
+use crate::tool_tip::ToolTip;
+use crate::visuals;
+
+/// # Description
+/// A switch turning a setting on or off, with the change taking effect immediately.
+pub struct ToggleSwitch {
+    /// The text displayed next to the switch.
+    // #[property]
+    pub text: String,
+    // #[property]
+    pub text_style: TextStyle,
+    // #[property]
+    pub is_on: bool,
+    // #[property]
+    pub enabled: bool,
+    /// The size of the track the thumb moves on.
+    // #[property]
+    pub track_size: Size<f32>,
+    /// The space between the track and the text.
+    // #[property]
+    pub spacing: f32,
+    /// The background of the track while the switch is on.
+    // #[property]
+    pub on_background: Color,
+    /// The background of the track while the switch is off.
+    // #[property]
+    pub off_background: Color,
+    // #[property]
+    pub hover_background: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub thumb: Color,
+    // #[property]
+    pub disabled_foreground: Color,
+    /// Called with the new value of *is_on* whenever the user toggled the switch.
+    // #[event]
+    pub on_toggled: Option<Box<dyn FnMut(bool)>>,
+    /// The tool tip shown while the pointer hovers the toggle switch.
+    // #[property]
+    pub tool_tip: Option<ToolTip>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    layout: GlyphLabel,
+    render_style: TextStyle,
+    press: PressState,
+}
+
+impl Component for ToggleSwitch {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, _available: Size<f32>) -> Size<f32> {
+        GlyphLabel::measure(self.track_size, self.spacing, &self.text, &self.text_style)
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.layout = GlyphLabel::arrange(self.track_size, self.spacing, &self.text_style, given);
+        self.render_style = self.text_style.clone();
+        if !self.enabled {
+            self.render_style.color = self.disabled_foreground;
+        }
+        self.size = given;
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let track = self.layout.glyph;
+        let background = if !self.enabled {
+            self.off_background
+        } else if self.is_on {
+            self.on_background
+        } else if self.press.hovered {
+            self.hover_background
+        } else {
+            self.off_background
+        };
+        visuals::RoundedRectangle {
+            x: track.x,
+            y: track.y,
+            width: track.width,
+            height: track.height,
+            radius: track.height / 2f32,
+            fill: background,
+            stroke: if self.enabled && self.press.focused { self.focused_border } else { Color::TRANSPARENT },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+
+        // The thumb is a circle inset by a tenth of the track height, at the end of the track
+        // matching the state
+        let inset = track.height / 10f32;
+        let diameter = track.height - inset * 2f32;
+        let x = if self.is_on {
+            track.x + track.width - inset - diameter
+        } else {
+            track.x + inset
+        };
+        visuals::RoundedRectangle {
+            x,
+            y: track.y + inset,
+            width: diameter,
+            height: diameter,
+            radius: diameter / 2f32,
+            fill: if self.enabled { self.thumb } else { self.disabled_foreground },
+            stroke: Color::TRANSPARENT,
+            thickness: 0f32,
+        }
+        .draw(renderer);
+
+        if !self.text.is_empty() {
+            visuals::Text {
+                x: self.layout.text_origin.x,
+                y: self.layout.text_origin.y,
+                text: &self.text,
+                style: &self.render_style,
+            }
+            .draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        let bounds = Rectangle { x: 0f32, y: 0f32, width: self.size.width, height: self.size.height };
+        match self.press.handle_event(event, context, bounds, self.enabled) {
+            PressResult::Unhandled => false,
+            PressResult::Handled => true,
+            PressResult::Clicked => {
+                self.toggle();
+                true
+            }
+        }
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+
+    fn tool_tip(&self) -> Option<&ToolTip> {
+        self.tool_tip.as_ref()
+    }
+}
+
+impl ToggleSwitch {
+    pub fn new() -> ToggleSwitch {
+        ToggleSwitch {
+            id: ComponentId::new(),
+            text: String::new(),
+            text_style: TextStyle::new(),
+            is_on: false,
+            enabled: true,
+            track_size: Size { width: 36f32, height: 18f32 },
+            spacing: 6f32,
+            on_background: Color::CORNFLOWER_BLUE,
+            off_background: Color::LIGHT_GRAY,
+            hover_background: Color::DARK_GRAY,
+            focused_border: Color::MIDNIGHT_BLUE,
+            thumb: Color::WHITE,
+            disabled_foreground: Color::GRAY,
+            on_toggled: None,
+            tool_tip: None,
+            size: Size { width: 0f32, height: 0f32 },
+            layout: GlyphLabel::new(),
+            render_style: TextStyle::new(),
+            press: PressState::new(),
+        }
+    }
+
+    /// # Description
+    /// Turns the switch on if it is off and vice versa, as if the user clicked it.
+    pub fn toggle(&mut self) {
+        self.is_on = !self.is_on;
+        if let Some(callback) = self.on_toggled.as_mut() {
+            callback(self.is_on);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Point;
+    use crate::pointer::PointerState;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Instant;
+    use winit::event::MouseButton;
+    use winit::keyboard::ModifiersState;
+
+    #[test]
+    fn test_toggle() {
+        let toggled = Rc::new(Cell::new(None));
+        let mut toggle_switch = ToggleSwitch::new();
+        let callback = toggled.clone();
+        toggle_switch.on_toggled = Some(Box::new(move |d| callback.set(Some(d))));
+        toggle_switch.arrange(Size { width: 100f32, height: 20f32 });
+        let click = |toggle_switch: &mut ToggleSwitch, release: Point<f32>| {
+            let mut context = EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+            let position = Point { x: 10f32, y: 10f32 };
+            toggle_switch.handle_event(&InputEvent::PointerPressed { position, button: MouseButton::Left }, &mut context);
+            toggle_switch.handle_event(&InputEvent::PointerReleased { position: release, button: MouseButton::Left }, &mut context);
+        };
+
+        click(&mut toggle_switch, Point { x: 10f32, y: 10f32 });
+        assert!(toggle_switch.is_on);
+        assert_eq!(toggled.get(), Some(true));
+        click(&mut toggle_switch, Point { x: 10f32, y: 10f32 });
+        assert!(!toggle_switch.is_on);
+        assert_eq!(toggled.get(), Some(false));
+
+        // Releasing outside of the switch leaves it as it is
+        toggled.set(None);
+        click(&mut toggle_switch, Point { x: 10f32, y: 50f32 });
+        assert!(!toggle_switch.is_on);
+        toggle_switch.enabled = false;
+        click(&mut toggle_switch, Point { x: 10f32, y: 10f32 });
+        assert!(!toggle_switch.is_on);
+        assert_eq!(toggled.get(), None);
+    }
+}
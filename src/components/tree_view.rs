@@ -0,0 +1,713 @@
+use std::rc::Rc;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::list_view::ListViewItem;
+use crate::components::{ScrollBarVisibility, ScrollViewer, TextBlock, VirtualizingPanel};
+use crate::data::{ComponentId, Point, Rectangle, Size, Thickness};
+use crate::focus::Focusable;
+use crate::input::{EventContext, InputEvent};
+use crate::text::{TextStyle, TextTrimming};
+use crate::visuals::{self, PathCommand};
+
+/// The width of the area left of the text of a node showing its chevron.
+const CHEVRON_WIDTH: f32 = 16f32;
+
+/// # Description
+/// A node of a [`TreeView`].
+///
+/// # Remarks
+/// The children of a node can be loaded lazily by *load_children*, called the first time the
+/// node is expanded. Until then, the node is displayed as having children.
+pub struct TreeViewNode {
+    // #[property]
+    pub text: String,
+    // #[property]
+    pub children: Vec<TreeViewNode>,
+    // #[property]
+    pub is_expanded: bool,
+    /// Loads the children of the node the first time it is expanded.
+    // #[property]
+    pub load_children: Option<Box<dyn FnOnce() -> Vec<TreeViewNode>>>,
+}
+
+impl TreeViewNode {
+    pub fn new(text: &str) -> TreeViewNode {
+        TreeViewNode {
+            text: text.to_string(),
+            children: vec![],
+            is_expanded: false,
+            load_children: None,
+        }
+    }
+
+    /// # Description
+    /// Creates a node whose children are loaded by *load_children* the first time it is
+    /// expanded.
+    pub fn lazy(text: &str, load_children: impl FnOnce() -> Vec<TreeViewNode> + 'static) -> TreeViewNode {
+        let mut node = TreeViewNode::new(text);
+        node.load_children = Some(Box::new(load_children));
+        node
+    }
+
+    pub fn with_children(mut self, children: Vec<TreeViewNode>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// # Description
+    /// Whether the node has children or may have children not loaded yet.
+    pub fn has_children(&self) -> bool {
+        !self.children.is_empty() || self.load_children.is_some()
+    }
+
+    /// # Description
+    /// Expands the node, loading its children if not done yet.
+    pub fn expand(&mut self) {
+        if let Some(load_children) = self.load_children.take() {
+            self.children = load_children();
+        }
+        self.is_expanded = true;
+    }
+
+    /// # Description
+    /// The descendant at *path*, the indices of the children leading to it.
+    pub fn descendant(&self, path: &[usize]) -> Option<&TreeViewNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((index, rest)) => self.children.get(*index)?.descendant(rest),
+        }
+    }
+
+    /// # Description
+    /// The descendant at *path*, the indices of the children leading to it.
+    pub fn descendant_mut(&mut self, path: &[usize]) -> Option<&mut TreeViewNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((index, rest)) => self.children.get_mut(*index)?.descendant_mut(rest),
+        }
+    }
+}
+
+/// A node visible in a [`TreeView`], as one row of its virtualizing panel.
+#[derive(Clone, Debug, PartialEq)]
+struct TreeRow {
+    /// The indices of the nodes leading to this node, starting at the roots.
+    path: Vec<usize>,
+    text: String,
+    expandable: bool,
+    expanded: bool,
+}
+
+impl TreeRow {
+    fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+/// Collects the rows of *nodes* and their expanded descendants, in display order.
+fn flatten(nodes: &[TreeViewNode], path: &mut Vec<usize>, rows: &mut Vec<TreeRow>) {
+    for (index, node) in nodes.iter().enumerate() {
+        path.push(index);
+        rows.push(TreeRow {
+            path: path.clone(),
+            text: node.text.clone(),
+            expandable: node.has_children(),
+            expanded: node.is_expanded,
+        });
+        if node.is_expanded {
+            flatten(&node.children, path, rows);
+        }
+        path.pop();
+    }
+}
+
+/// # Description
+/// The chevron and text of a node, displayed within its row.
+struct TreeViewHeader {
+    id: ComponentId,
+    expandable: bool,
+    expanded: bool,
+    chevron: Color,
+    size: Size<f32>,
+    text_block: TextBlock,
+}
+
+impl Component for TreeViewHeader {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        available
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let text = Size { width: f32::max(0f32, given.width - CHEVRON_WIDTH), height: given.height };
+        self.text_block.measure(text);
+        self.text_block.arrange(text);
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        if self.expandable {
+            let center = Point { x: CHEVRON_WIDTH / 2f32, y: self.size.height / 2f32 };
+            let extent = 3f32;
+            // A chevron pointing right, or down while expanded
+            let commands = if self.expanded {
+                [
+                    PathCommand::MoveTo(Point { x: center.x - extent, y: center.y - extent / 2f32 }),
+                    PathCommand::LineTo(Point { x: center.x, y: center.y + extent / 2f32 }),
+                    PathCommand::LineTo(Point { x: center.x + extent, y: center.y - extent / 2f32 }),
+                ]
+            } else {
+                [
+                    PathCommand::MoveTo(Point { x: center.x - extent / 2f32, y: center.y - extent }),
+                    PathCommand::LineTo(Point { x: center.x + extent / 2f32, y: center.y }),
+                    PathCommand::LineTo(Point { x: center.x - extent / 2f32, y: center.y + extent }),
+                ]
+            };
+            visuals::Path {
+                commands: &commands,
+                fill: Color::TRANSPARENT,
+                stroke: self.chevron,
+                thickness: 1.5f32,
+            }
+            .draw(renderer);
+        }
+        renderer.translate(CHEVRON_WIDTH, 0f32);
+        self.text_block.render(renderer);
+        renderer.translate(-CHEVRON_WIDTH, 0f32);
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.text_bounds(), &self.text_block);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.text_bounds();
+        visitor(bounds, &mut self.text_block);
+    }
+}
+
+impl TreeViewHeader {
+    fn text_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: CHEVRON_WIDTH,
+            y: 0f32,
+            width: f32::max(0f32, self.size.width - CHEVRON_WIDTH),
+            height: self.size.height,
+        }
+    }
+}
+
+/// # Description
+/// A scrollable hierarchy of nodes, which can be expanded and collapsed to show or hide their
+/// children.
+///
+/// # Remarks
+/// Only the rows of visible nodes are created, allowing nodes with thousands of children.
+/// Clicking the chevron of a node or double-clicking it toggles its expansion.
+/// The arrow keys move the selection, Right expands the selected node or moves to its first
+/// child, Left collapses it or moves to its parent and Enter toggles its expansion.
+pub struct TreeView {
+    /// The top level nodes. Call *refresh* after changing them or their descendants.
+    // #[property]
+    pub roots: Vec<TreeViewNode>,
+    // #[property]
+    pub item_height: f32,
+    /// The indentation of every level of the hierarchy.
+    // #[property]
+    pub indent: f32,
+    // #[property]
+    pub enabled: bool,
+    // #[property]
+    pub padding: Thickness,
+    // #[property]
+    pub text_style: TextStyle,
+    // #[property]
+    pub background: Color,
+    // #[property]
+    pub border: Color,
+    // #[property]
+    pub focused_border: Color,
+    // #[property]
+    pub hover_background: Color,
+    /// The background of the selected node while the tree view has the keyboard focus.
+    // #[property]
+    pub selection_background: Color,
+    /// The background of the selected node while the tree view does not have the keyboard focus.
+    // #[property]
+    pub inactive_selection_background: Color,
+    /// The color of the chevrons of nodes having children.
+    // #[property]
+    pub chevron: Color,
+    /// Called with the path of the selected node whenever the user changed the selection.
+    // #[event]
+    pub on_selection_changed: Option<Box<dyn FnMut(Option<&[usize]>)>>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    focused: bool,
+    viewer: ScrollViewer<VirtualizingPanel<ListViewItem>>,
+    rows: Rc<Vec<TreeRow>>,
+    /// Whether the rows have to be collected from the nodes during the next layout pass.
+    dirty: bool,
+    selected: Option<Vec<usize>>,
+    /// The row pressed last and the click count of that press.
+    last_click: Option<(usize, usize)>,
+}
+
+impl Component for TreeView {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        Size {
+            width: available.width,
+            height: f32::min(self.rows.len() as f32 * self.item_height + 2f32, available.height),
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        if self.dirty {
+            self.rebuild();
+        }
+        let rows = self.rows.clone();
+        let padding = self.padding;
+        let indent = self.indent;
+        let text_style = self.text_style.clone();
+        let chevron = self.chevron;
+        let panel = &mut self.viewer.content;
+        panel.item_count = rows.len();
+        panel.item_height = self.item_height;
+        panel.create_item = Box::new(move |index| {
+            let row = &rows[index];
+            let mut text_block = TextBlock::new();
+            text_block.text = row.text.clone();
+            text_block.text_style = text_style.clone();
+            text_block.trimming = TextTrimming::Ellipsis;
+            let header = TreeViewHeader {
+                id: ComponentId::new(),
+                expandable: row.expandable,
+                expanded: row.expanded,
+                chevron,
+                size: Size { width: 0f32, height: 0f32 },
+                text_block,
+            };
+            let mut padding = padding;
+            padding.left += row.depth() as f32 * indent;
+            ListViewItem::new(Box::new(header), padding)
+        });
+
+        let inner = self.inner_bounds();
+        self.viewer.arrange(Size { width: inner.width, height: inner.height });
+        let selection = if self.focused { self.selection_background } else { self.inactive_selection_background };
+        let selected = self.selected_row();
+        for (index, item) in self.viewer.content.realized_mut() {
+            item.selection = Some(selection).filter(|_| selected == Some(index));
+            item.hover_background = if self.enabled { self.hover_background } else { Color::TRANSPARENT };
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::Rectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            fill: self.background,
+        }
+        .draw(renderer);
+        let inner = self.inner_bounds();
+        renderer.translate(inner.x, inner.y);
+        self.viewer.render(renderer);
+        renderer.translate(-inner.x, -inner.y);
+        visuals::Outline {
+            x: 0.5f32,
+            y: 0.5f32,
+            width: self.size.width - 1f32,
+            height: self.size.height - 1f32,
+            stroke: if self.focused { self.focused_border } else { self.border },
+            thickness: 1f32,
+        }
+        .draw(renderer);
+    }
+
+    fn handle_event(&mut self, event: &InputEvent, context: &mut EventContext) -> bool {
+        match event {
+            InputEvent::FocusGained => {
+                self.focused = true;
+                true
+            }
+            InputEvent::FocusLost => {
+                self.focused = false;
+                true
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Left } if self.enabled => {
+                let (index, x) = match self.row_at(*position) {
+                    None => return true,
+                    Some(d) => d,
+                };
+                let row = &self.rows[index];
+                let chevron = self.padding.left + row.depth() as f32 * self.indent;
+                let on_chevron = x >= chevron && x < chevron + CHEVRON_WIDTH;
+                // The window counts clicks on the whole tree view, so the row has to match too
+                let count = context.click_count();
+                let double_click = self
+                    .last_click
+                    .map_or(false, |(last, last_count)| last == index && last_count + 1 == count);
+                self.last_click = if double_click { None } else { Some((index, count)) };
+                if on_chevron {
+                    self.toggle(index);
+                } else {
+                    self.select_row(index);
+                    if double_click {
+                        self.toggle(index);
+                    }
+                }
+                true
+            }
+            InputEvent::PointerPressed { position, button: MouseButton::Right } if self.enabled => {
+                // Right-clicking a node selects it for its context menu
+                if let Some((index, _)) = self.row_at(*position) {
+                    self.select_row(index);
+                }
+                true
+            }
+            InputEvent::KeyPressed { key, .. } if self.enabled => self.handle_key(key),
+            _ => false,
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.inner_bounds(), &self.viewer);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds = self.inner_bounds();
+        visitor(bounds, &mut self.viewer);
+    }
+
+    fn focusable(&self) -> Option<Focusable> {
+        if self.enabled {
+            Some(Focusable::new())
+        } else {
+            None
+        }
+    }
+}
+
+impl TreeView {
+    pub fn new() -> TreeView {
+        let mut viewer = ScrollViewer::new(VirtualizingPanel::new(Box::new(|_| {
+            ListViewItem::new(Box::new(TextBlock::new()), Thickness::uniform(0f32))
+        })));
+        viewer.horizontal_scroll_bar_visibility = ScrollBarVisibility::Disabled;
+        TreeView {
+            id: ComponentId::new(),
+            roots: vec![],
+            item_height: 24f32,
+            indent: 16f32,
+            enabled: true,
+            padding: Thickness::uniform(4f32),
+            text_style: TextStyle::new(),
+            background: Color::WHITE,
+            border: Color::GRAY,
+            focused_border: Color::CORNFLOWER_BLUE,
+            hover_background: Color::GAINSBORO,
+            selection_background: Color::LIGHT_SKY_BLUE,
+            inactive_selection_background: Color::LIGHT_GRAY,
+            chevron: Color::DIM_GRAY,
+            on_selection_changed: None,
+            size: Size { width: 0f32, height: 0f32 },
+            focused: false,
+            viewer,
+            rows: Rc::new(vec![]),
+            dirty: true,
+            selected: None,
+            last_click: None,
+        }
+    }
+
+    /// # Description
+    /// The node at *path*, the indices of the nodes leading to it starting at the roots.
+    pub fn node(&self, path: &[usize]) -> Option<&TreeViewNode> {
+        let (index, rest) = path.split_first()?;
+        self.roots.get(*index)?.descendant(rest)
+    }
+
+    /// # Description
+    /// The node at *path*, the indices of the nodes leading to it starting at the roots.
+    ///
+    /// # Remarks
+    /// Call *refresh* after changing the node.
+    pub fn node_mut(&mut self, path: &[usize]) -> Option<&mut TreeViewNode> {
+        let (index, rest) = path.split_first()?;
+        self.roots.get_mut(*index)?.descendant_mut(rest)
+    }
+
+    /// # Description
+    /// The path of the selected node, if any.
+    pub fn selected_path(&self) -> Option<&[usize]> {
+        self.selected.as_deref()
+    }
+
+    /// # Description
+    /// Selects the node at *path*, or clears the selection if `None`.
+    ///
+    /// # Remarks
+    /// Does not call *on_selection_changed*, which is reserved for changes made by the user.
+    /// The ancestors of the node are expanded to make it visible.
+    pub fn select(&mut self, path: Option<&[usize]>) {
+        if let Some(path) = path {
+            for length in 1..path.len() {
+                if let Some(node) = self.node_mut(&path[..length]) {
+                    node.expand();
+                }
+            }
+            self.rebuild();
+        }
+        self.selected = path.map(|d| d.to_vec());
+        if let Some(index) = self.selected_row() {
+            self.scroll_into_view(index);
+        }
+    }
+
+    /// # Description
+    /// Creates the rows of the visible nodes again, after the nodes changed.
+    pub fn refresh(&mut self) {
+        self.rebuild();
+        if self.selected.is_some() && self.selected_row().is_none() {
+            self.selected = None;
+        }
+    }
+
+    fn handle_key(&mut self, key: &Key) -> bool {
+        let count = self.rows.len();
+        if count == 0 {
+            return false;
+        }
+        let current = self.selected_row();
+        let page = self.viewer.content.page_size();
+        let target = match key {
+            Key::Named(NamedKey::ArrowUp) => current.map_or(0, |d| d.saturating_sub(1)),
+            Key::Named(NamedKey::ArrowDown) => current.map_or(0, |d| usize::min(d + 1, count - 1)),
+            Key::Named(NamedKey::PageUp) => current.map_or(0, |d| d.saturating_sub(page)),
+            Key::Named(NamedKey::PageDown) => current.map_or(0, |d| usize::min(d + page, count - 1)),
+            Key::Named(NamedKey::Home) => 0,
+            Key::Named(NamedKey::End) => count - 1,
+            Key::Named(NamedKey::ArrowRight) => {
+                let index = match current {
+                    None => return false,
+                    Some(d) => d,
+                };
+                let row = self.rows[index].clone();
+                if row.expandable && !row.expanded {
+                    self.toggle(index);
+                    return true;
+                }
+                // Move to the first child, if the node had any after loading them
+                match self.rows.get(index + 1) {
+                    Some(child) if row.expanded && child.path.len() > row.path.len() => index + 1,
+                    _ => return true,
+                }
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                let index = match current {
+                    None => return false,
+                    Some(d) => d,
+                };
+                let row = self.rows[index].clone();
+                if row.expanded {
+                    self.toggle(index);
+                    return true;
+                }
+                let parent = &row.path[..row.path.len() - 1];
+                match self.rows.iter().position(|d| d.path == parent) {
+                    Some(d) => d,
+                    None => return true,
+                }
+            }
+            Key::Named(NamedKey::Enter) => {
+                match current {
+                    Some(index) if self.rows[index].expandable => self.toggle(index),
+                    _ => return false,
+                }
+                return true;
+            }
+            _ => return false,
+        };
+        self.select_row(target);
+        true
+    }
+
+    /// Expands the node of the row at *index* or collapses it, selecting it if the selected
+    /// node is hidden by collapsing it.
+    fn toggle(&mut self, index: usize) {
+        let path = self.rows[index].path.clone();
+        let node = match self.node_mut(&path) {
+            None => return,
+            Some(d) => d,
+        };
+        if node.is_expanded {
+            node.is_expanded = false;
+        } else {
+            node.expand();
+        }
+        self.rebuild();
+        let hidden = self
+            .selected
+            .as_ref()
+            .map_or(false, |d| d.len() > path.len() && d.starts_with(&path));
+        if hidden {
+            if let Some(index) = self.rows.iter().position(|d| d.path == path) {
+                self.select_row(index);
+            }
+        }
+    }
+
+    /// Selects the node of the row at *index*, notifying about the changed selection.
+    fn select_row(&mut self, index: usize) {
+        self.scroll_into_view(index);
+        let path = &self.rows[index].path;
+        if self.selected.as_ref() == Some(path) {
+            return;
+        }
+        self.selected = Some(path.clone());
+        if let Some(callback) = self.on_selection_changed.as_mut() {
+            callback(self.selected.as_deref());
+        }
+    }
+
+    fn selected_row(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.rows.iter().position(|d| d.path == *selected)
+    }
+
+    fn scroll_into_view(&mut self, index: usize) {
+        let bounds = self.viewer.content.item_bounds(index);
+        self.viewer.scroll_into_view(bounds);
+    }
+
+    /// Collects the rows of the visible nodes and drops the components of the previous rows.
+    fn rebuild(&mut self) {
+        let mut rows = vec![];
+        flatten(&self.roots, &mut vec![], &mut rows);
+        self.rows = Rc::new(rows);
+        self.viewer.content.refresh();
+        self.dirty = false;
+    }
+
+    /// The row at *position* and the horizontal position within it.
+    fn row_at(&self, position: Point<f32>) -> Option<(usize, f32)> {
+        let inner = self.inner_bounds();
+        let viewport = self.viewer.viewport();
+        let x = position.x - inner.x;
+        let y = position.y - inner.y;
+        if x < 0f32 || y < 0f32 || x >= viewport.width || y >= viewport.height {
+            return None;
+        }
+        let index = self.viewer.content.index_at(y + self.viewer.offset().y)?;
+        if index < self.rows.len() {
+            Some((index, x))
+        } else {
+            None
+        }
+    }
+
+    fn inner_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: 1f32,
+            y: 1f32,
+            width: f32::max(0f32, self.size.width - 2f32),
+            height: f32::max(0f32, self.size.height - 2f32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatten() {
+        let mut roots = vec![
+            TreeViewNode::new("a").with_children(vec![TreeViewNode::new("b"), TreeViewNode::new("c")]),
+            TreeViewNode::lazy("d", || vec![TreeViewNode::new("e")]),
+        ];
+        let mut rows = vec![];
+        flatten(&roots, &mut vec![], &mut rows);
+        assert_eq!(rows.iter().map(|d| d.text.as_str()).collect::<Vec<_>>(), vec!["a", "d"]);
+        assert!(rows[1].expandable);
+
+        roots[0].is_expanded = true;
+        roots[1].expand();
+        rows.clear();
+        flatten(&roots, &mut vec![], &mut rows);
+        assert_eq!(
+            rows.iter().map(|d| d.text.as_str()).collect::<Vec<_>>(),
+            vec!["a", "b", "c", "d", "e"]
+        );
+        assert_eq!(rows[4].path, vec![1, 0]);
+        assert_eq!(rows[4].depth(), 1);
+    }
+
+    #[test]
+    fn test_lazy_loading() {
+        let mut node = TreeViewNode::lazy("a", || vec![]);
+        assert!(node.has_children());
+        node.expand();
+        assert!(node.is_expanded);
+        assert!(!node.has_children());
+        assert!(node.load_children.is_none());
+    }
+
+    #[test]
+    fn test_double_click() {
+        use crate::pointer::PointerState;
+        use std::time::Instant;
+        use winit::keyboard::ModifiersState;
+
+        let mut tree_view = TreeView::new();
+        tree_view.roots = vec![
+            TreeViewNode::new("a").with_children(vec![TreeViewNode::new("b")]),
+            TreeViewNode::new("c").with_children(vec![TreeViewNode::new("d")]),
+        ];
+        tree_view.arrange(Size { width: 200f32, height: 200f32 });
+        let click = |tree_view: &mut TreeView, row: usize, count: usize| {
+            let mut context =
+                EventContext::new(Instant::now(), &PointerState::new(), None, ModifiersState::empty());
+            context.click_count = count;
+            let position = Point { x: 100f32, y: 1f32 + row as f32 * 24f32 + 12f32 };
+            tree_view.handle_event(&InputEvent::PointerPressed { position, button: MouseButton::Left }, &mut context);
+        };
+
+        // Two quick clicks on different rows toggle neither
+        click(&mut tree_view, 0, 1);
+        click(&mut tree_view, 1, 2);
+        assert!(!tree_view.roots[0].is_expanded);
+        assert!(!tree_view.roots[1].is_expanded);
+        assert_eq!(tree_view.selected, Some(vec![1]));
+
+        click(&mut tree_view, 0, 1);
+        click(&mut tree_view, 0, 2);
+        assert!(tree_view.roots[0].is_expanded);
+        assert_eq!(tree_view.rows.len(), 3);
+
+        // A third click does not toggle the node back
+        click(&mut tree_view, 0, 3);
+        assert!(tree_view.roots[0].is_expanded);
+    }
+}
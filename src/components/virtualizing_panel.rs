@@ -0,0 +1,161 @@
+use crate::abstraction::{Component, Renderer};
+use crate::data::{ComponentId, Rectangle, Size};
+
+/// # Description
+/// Stacks a number of items of the same height vertically, only creating the components of the
+/// items visible in its viewport.
+///
+/// # Remarks
+/// Used as the content of a [`super::ScrollViewer`], which reports the visible area through
+/// [`Component::set_viewport`]. Components of items scrolled out of view are dropped and created
+/// again by *create_item* once they become visible.
+pub struct VirtualizingPanel<T: Component> {
+    /// The number of items.
+    // #[property]
+    pub item_count: usize,
+    /// The height of every item.
+    // #[property]
+    pub item_height: f32,
+    /// Creates the component of the item at the given index.
+    // #[property]
+    pub create_item: Box<dyn Fn(usize) -> T>,
+
+    id: ComponentId,
+    size: Size<f32>,
+    viewport: Rectangle<f32>,
+    /// The components of the visible items, ordered by index.
+    realized: Vec<(usize, T)>,
+}
+
+impl<T: Component> Component for VirtualizingPanel<T> {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        Size {
+            width: if available.width.is_finite() { available.width } else { 0f32 },
+            height: self.item_count as f32 * self.item_height,
+        }
+    }
+
+    fn set_viewport(&mut self, viewport: Rectangle<f32>) {
+        self.viewport = viewport;
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.size = given;
+        let (first, last) = self.visible_range();
+        self.realized.retain(|(index, _)| *index >= first && *index < last);
+        for index in first..last {
+            match self.realized.binary_search_by_key(&index, |(d, _)| *d) {
+                Ok(_) => {}
+                Err(position) => self.realized.insert(position, (index, (self.create_item)(index))),
+            }
+        }
+        let size = Size { width: given.width, height: self.item_height };
+        for (_, item) in self.realized.iter_mut() {
+            item.measure(size);
+            item.arrange(size);
+        }
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        for (index, item) in self.realized.iter() {
+            let y = *index as f32 * self.item_height;
+            renderer.translate(0f32, y);
+            item.render(renderer);
+            renderer.translate(0f32, -y);
+        }
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        for (index, item) in self.realized.iter() {
+            visitor(self.item_bounds(*index), item);
+        }
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        let bounds: Vec<_> = self.realized.iter().map(|(d, _)| self.item_bounds(*d)).collect();
+        for ((_, item), bounds) in self.realized.iter_mut().zip(bounds) {
+            visitor(bounds, item);
+        }
+    }
+}
+
+impl<T: Component> VirtualizingPanel<T> {
+    pub fn new(create_item: Box<dyn Fn(usize) -> T>) -> VirtualizingPanel<T> {
+        VirtualizingPanel {
+            id: ComponentId::new(),
+            item_count: 0,
+            item_height: 24f32,
+            create_item,
+            size: Size { width: 0f32, height: 0f32 },
+            viewport: Rectangle { x: 0f32, y: 0f32, width: 0f32, height: 0f32 },
+            realized: vec![],
+        }
+    }
+
+    /// # Description
+    /// Drops the components of all items, creating them again during the next layout pass.
+    ///
+    /// # Remarks
+    /// Call after the items changed, as the components of visible items are kept otherwise.
+    pub fn refresh(&mut self) {
+        self.realized.clear();
+    }
+
+    /// # Description
+    /// The components of the visible items, together with their index.
+    pub fn realized_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.realized.iter_mut().map(|(index, item)| (*index, item))
+    }
+
+    /// # Description
+    /// The area of the item at *index*, relative to the panel.
+    pub fn item_bounds(&self, index: usize) -> Rectangle<f32> {
+        Rectangle {
+            x: 0f32,
+            y: index as f32 * self.item_height,
+            width: self.size.width,
+            height: self.item_height,
+        }
+    }
+
+    /// # Description
+    /// The index of the item at the vertical position *y*, relative to the panel.
+    pub fn index_at(&self, y: f32) -> Option<usize> {
+        if y < 0f32 || self.item_height <= 0f32 {
+            return None;
+        }
+        let index = (y / self.item_height) as usize;
+        if index < self.item_count {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    /// # Description
+    /// The number of items fully fitting into the viewport, at least one.
+    pub fn page_size(&self) -> usize {
+        if self.item_height <= 0f32 {
+            return 1;
+        }
+        usize::max(1, (self.viewport.height / self.item_height) as usize)
+    }
+
+    /// The first visible item and the item after the last visible one.
+    fn visible_range(&self) -> (usize, usize) {
+        if self.item_height <= 0f32 {
+            return (0, 0);
+        }
+        let first = (f32::max(0f32, self.viewport.y) / self.item_height) as usize;
+        let last = ((self.viewport.y + self.viewport.height) / self.item_height).ceil() as usize;
+        (usize::min(first, self.item_count), usize::min(last, self.item_count))
+    }
+}
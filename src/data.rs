@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Orientation {
     /// # Description
@@ -21,15 +23,132 @@ pub enum Orientation {
     /// ```
     Vertical,
 }
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Rectangle<T> {
     pub x: T,
     pub y: T,
     pub width: T,
     pub height: T,
 }
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Size<T> {
     pub width: T,
     pub height: T,
-}
\ No newline at end of file
+}
+impl Rectangle<f32> {
+    /// # Description
+    /// Checks whether the given point lies within this rectangle.
+    ///
+    /// # Remarks
+    /// The left and top edges are inclusive, the right and bottom edges are exclusive.
+    pub fn contains(&self, point: Point<f32>) -> bool {
+        point.x >= self.x
+            && point.y >= self.y
+            && point.x < self.x + self.width
+            && point.y < self.y + self.height
+    }
+}
+/// # Description
+/// The thickness of a frame around a rectangle, eg. a padding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thickness {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Thickness {
+    pub fn uniform(value: f32) -> Thickness {
+        Thickness {
+            left: value,
+            top: value,
+            right: value,
+            bottom: value,
+        }
+    }
+
+    /// # Description
+    /// The sum of the left and right thickness.
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    /// # Description
+    /// The sum of the top and bottom thickness.
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// # Description
+/// An affine transformation of points, eg. a translation, a scaling or a combination of both.
+///
+/// # Remarks
+/// A point `(x, y)` is transformed to `(m11 * x + m21 * y + dx, m12 * x + m22 * y + dy)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub m11: f32,
+    pub m12: f32,
+    pub m21: f32,
+    pub m22: f32,
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Transform {
+    pub fn identity() -> Transform {
+        Transform { m11: 1f32, m12: 0f32, m21: 0f32, m22: 1f32, dx: 0f32, dy: 0f32 }
+    }
+
+    pub fn translation(x: f32, y: f32) -> Transform {
+        Transform { dx: x, dy: y, ..Transform::identity() }
+    }
+
+    pub fn scaling(x: f32, y: f32) -> Transform {
+        Transform { m11: x, m22: y, ..Transform::identity() }
+    }
+
+    /// # Description
+    /// The transformation applying this transformation first and *next* afterwards.
+    pub fn then(&self, next: &Transform) -> Transform {
+        Transform {
+            m11: self.m11 * next.m11 + self.m12 * next.m21,
+            m12: self.m11 * next.m12 + self.m12 * next.m22,
+            m21: self.m21 * next.m11 + self.m22 * next.m21,
+            m22: self.m21 * next.m12 + self.m22 * next.m22,
+            dx: self.dx * next.m11 + self.dy * next.m21 + next.dx,
+            dy: self.dx * next.m12 + self.dy * next.m22 + next.dy,
+        }
+    }
+
+    pub fn apply(&self, point: Point<f32>) -> Point<f32> {
+        Point {
+            x: self.m11 * point.x + self.m21 * point.y + self.dx,
+            y: self.m12 * point.x + self.m22 * point.y + self.dy,
+        }
+    }
+}
+
+/// # Description
+/// Uniquely identifies a component instance for the lifetime of the application.
+///
+/// # Remarks
+/// Used by windows to keep track of eg. the focused component across layout passes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ComponentId(u64);
+
+impl ComponentId {
+    /// # Description
+    /// Creates a new, never before issued, id.
+    pub fn new() -> ComponentId {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        ComponentId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
@@ -0,0 +1,412 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug)]
+pub enum DateErrors {
+    InvalidFormat(String),
+    InvalidDate(i32, u32, u32),
+}
+
+impl Display for DateErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateErrors::InvalidFormat(d) => write!(f, "Invalid date format ({})", d),
+            DateErrors::InvalidDate(year, month, day) => {
+                write!(f, "Invalid date (year {}, month {}, day {})", year, month, day)
+            }
+        }
+    }
+}
+
+impl Error for DateErrors {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// # Description
+    /// The number of days since the last monday, from `0` for monday to `6` for sunday.
+    pub fn index(&self) -> u32 {
+        *self as u32
+    }
+
+    /// # Description
+    /// The weekday *index* days after monday, wrapping around after sunday.
+    pub fn from_index(index: u32) -> Weekday {
+        Weekday::ALL[(index % 7) as usize]
+    }
+
+    /// # Description
+    /// The number of days from the last *start* up to this weekday, from `0` to `6`.
+    ///
+    /// # Remarks
+    /// This is the column of the weekday in a week starting with *start*.
+    pub fn days_since(&self, start: Weekday) -> u32 {
+        (self.index() + 7 - start.index()) % 7
+    }
+}
+
+/// # Description
+/// A day of the proleptic gregorian calendar, without any time or time zone.
+///
+/// # Remarks
+/// Dates order chronologically.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Date {
+    /// # Description
+    /// Creates the date *day* of *month* (`1` to `12`) in *year*.
+    ///
+    /// # Returns
+    /// [`DateErrors::InvalidDate`] if the month or the day does not exist.
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Date, DateErrors> {
+        if month < 1 || month > 12 || day < 1 || day > Date::days_in_month(year, month) {
+            return Err(DateErrors::InvalidDate(year, month, day));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// # Description
+    /// The current date.
+    ///
+    /// # Remarks
+    /// The date is determined in UTC, as there is no time zone information available.
+    pub fn today() -> Date {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(d) => -(d.duration().as_secs() as i64),
+        };
+        Date::from_days(seconds.div_euclid(86400))
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn is_leap_year(year: i32) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    pub fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// # Description
+    /// The date *days* days after the 1st of January 1970.
+    pub fn from_days(days: i64) -> Date {
+        // Counts in eras of 400 years starting with the 1st of March, so leap days end a year
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date { year: year as i32, month, day }
+    }
+
+    /// # Description
+    /// The number of days since the 1st of January 1970, negative for earlier dates.
+    pub fn days(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // The 1st of January 1970 was a thursday
+        Weekday::from_index((self.days() + 3).rem_euclid(7) as u32)
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days(self.days() + days)
+    }
+
+    /// # Description
+    /// The same day *months* months later (or earlier, if negative).
+    ///
+    /// # Remarks
+    /// Days not existing in the target month are clamped to its last day.
+    pub fn add_months(&self, months: i32) -> Date {
+        let index = self.year as i64 * 12 + (self.month - 1) as i64 + months as i64;
+        let year = index.div_euclid(12) as i32;
+        let month = index.rem_euclid(12) as u32 + 1;
+        let day = u32::min(self.day, Date::days_in_month(year, month));
+        Date { year, month, day }
+    }
+
+    pub fn first_of_month(&self) -> Date {
+        Date { day: 1, ..*self }
+    }
+
+    pub fn last_of_month(&self) -> Date {
+        Date { day: Date::days_in_month(self.year, self.month), ..*self }
+    }
+}
+
+/// # Description
+/// The order of the day, the month and the year in a formatted date.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+const ENGLISH_DAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+const GERMAN_MONTHS: [&str; 12] = [
+    "Januar", "Februar", "März", "April", "Mai", "Juni",
+    "Juli", "August", "September", "Oktober", "November", "Dezember",
+];
+const GERMAN_DAYS: [&str; 7] = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+const FRENCH_MONTHS: [&str; 12] = [
+    "janvier", "février", "mars", "avril", "mai", "juin",
+    "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+];
+const FRENCH_DAYS: [&str; 7] = ["lu", "ma", "me", "je", "ve", "sa", "di"];
+
+/// # Description
+/// How dates are written and named in a locale.
+///
+/// # Remarks
+/// Formatted dates always consist of the numeric day, month and year, zero padded and
+/// separated by *separator*. The names are used by components displaying a calendar.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DateFormat {
+    pub order: DateOrder,
+    pub separator: char,
+    /// The day each week starts with.
+    pub first_day_of_week: Weekday,
+    /// The names of the months, starting with january.
+    pub month_names: [&'static str; 12],
+    /// The abbreviated names of the days of the week, starting with monday.
+    pub day_names: [&'static str; 7],
+}
+
+impl DateFormat {
+    /// # Description
+    /// The ISO 8601 format (eg. `2024-03-31`), with weeks starting on monday.
+    pub fn iso() -> DateFormat {
+        DateFormat {
+            order: DateOrder::YearMonthDay,
+            separator: '-',
+            first_day_of_week: Weekday::Monday,
+            month_names: ENGLISH_MONTHS,
+            day_names: ENGLISH_DAYS,
+        }
+    }
+
+    /// # Description
+    /// The format used in *locale*, given as a language tag (eg. `de-DE`) or as a POSIX locale
+    /// name (eg. `de_DE.UTF-8`).
+    ///
+    /// # Remarks
+    /// The formats are not read from the platform but built in for the English, German, French,
+    /// Japanese and Chinese languages. Any other locale falls back to [`DateFormat::iso`], whose
+    /// dates can be read regardless of the user's language, instead of guessing an order that
+    /// might swap the day and the month.
+    pub fn from_locale(locale: &str) -> DateFormat {
+        let locale = locale.split(|d| d == '.' || d == '@').next().unwrap_or("");
+        let mut parts = locale.split(|d| d == '_' || d == '-');
+        let language = parts.next().unwrap_or("").to_lowercase();
+        let region = parts.next().unwrap_or("").to_uppercase();
+        let iso = DateFormat::iso();
+        match (language.as_str(), region.as_str()) {
+            ("en", "US") => DateFormat {
+                order: DateOrder::MonthDayYear,
+                separator: '/',
+                first_day_of_week: Weekday::Sunday,
+                ..iso
+            },
+            ("en", "CA") => DateFormat { first_day_of_week: Weekday::Sunday, ..iso },
+            ("en", _) => DateFormat { order: DateOrder::DayMonthYear, separator: '/', ..iso },
+            ("de", _) => DateFormat {
+                order: DateOrder::DayMonthYear,
+                separator: '.',
+                month_names: GERMAN_MONTHS,
+                day_names: GERMAN_DAYS,
+                ..iso
+            },
+            ("fr", _) => DateFormat {
+                order: DateOrder::DayMonthYear,
+                separator: '/',
+                month_names: FRENCH_MONTHS,
+                day_names: FRENCH_DAYS,
+                ..iso
+            },
+            ("ja", _) | ("zh", _) => DateFormat {
+                separator: '/',
+                first_day_of_week: Weekday::Sunday,
+                ..iso
+            },
+            _ => iso,
+        }
+    }
+
+    /// # Description
+    /// The format of the locale configured for the process using the `LC_ALL`, `LC_TIME` or
+    /// `LANG` environment variables.
+    pub fn system() -> DateFormat {
+        ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|d| std::env::var(d).ok())
+            .find(|d| !d.is_empty())
+            .map_or_else(DateFormat::iso, |d| DateFormat::from_locale(&d))
+    }
+
+    pub fn format(&self, date: Date) -> String {
+        let (day, month, year) = (
+            format!("{:02}", date.day),
+            format!("{:02}", date.month),
+            format!("{:04}", date.year),
+        );
+        let parts = match self.order {
+            DateOrder::DayMonthYear => [day, month, year],
+            DateOrder::MonthDayYear => [month, day, year],
+            DateOrder::YearMonthDay => [year, month, day],
+        };
+        parts.join(&*self.separator.to_string())
+    }
+
+    /// # Description
+    /// Parses a date written in this format.
+    ///
+    /// # Remarks
+    /// Any non-digit separates the parts, so `31.3.24` and `31/03/2024` are both accepted in a
+    /// day-month-year format. Years of one or two digits are taken as years after 2000, other
+    /// years must have four digits. Dates starting with a four digit year are always read as
+    /// ISO 8601 dates.
+    ///
+    /// # Returns
+    /// [`DateErrors::InvalidFormat`] if *text* does not consist of three numbers of valid lengths
+    /// and [`DateErrors::InvalidDate`] if the date does not exist.
+    pub fn parse(&self, text: &str) -> Result<Date, DateErrors> {
+        let invalid = || DateErrors::InvalidFormat(text.to_string());
+        let parts: Vec<&str> = text
+            .split(|d: char| !d.is_ascii_digit())
+            .filter(|d| !d.is_empty())
+            .collect();
+        if parts.len() != 3 || parts.iter().any(|d| d.len() > 4) {
+            return Err(invalid());
+        }
+        let order = if parts[0].len() == 4 { DateOrder::YearMonthDay } else { self.order };
+        let (day, month, year) = match order {
+            DateOrder::DayMonthYear => (parts[0], parts[1], parts[2]),
+            DateOrder::MonthDayYear => (parts[1], parts[0], parts[2]),
+            DateOrder::YearMonthDay => (parts[2], parts[1], parts[0]),
+        };
+        // Three digit years are most likely mistyped, so they are not guessed at
+        if day.len() > 2 || month.len() > 2 || year.len() == 3 {
+            return Err(invalid());
+        }
+        let number = |d: &str| d.parse::<u32>().map_err(|_| invalid());
+        let mut full_year = number(year)? as i32;
+        if year.len() <= 2 {
+            full_year += 2000;
+        }
+        Date::new(full_year, number(month)?, number(day)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days() {
+        let date = |y, m, d| Date::new(y, m, d).unwrap();
+        assert_eq!(0, date(1970, 1, 1).days());
+        assert_eq!(date(1970, 1, 1), Date::from_days(0));
+        assert_eq!(-1, date(1969, 12, 31).days());
+        assert_eq!(date(2000, 2, 29), Date::from_days(11016));
+        for days in -1000000..1000000 {
+            assert_eq!(days, Date::from_days(days).days());
+        }
+        assert_eq!(Weekday::Thursday, date(1970, 1, 1).weekday());
+        assert_eq!(Weekday::Sunday, date(2024, 3, 31).weekday());
+        assert_eq!(date(2024, 3, 1), date(2024, 2, 28).add_days(2));
+        assert_eq!(date(2023, 2, 28), date(2023, 1, 31).add_months(1));
+        assert_eq!(date(2023, 12, 15), date(2024, 1, 15).add_months(-1));
+        assert!(Date::new(2023, 2, 29).is_err());
+        assert_eq!(6, Weekday::Sunday.days_since(Weekday::Monday));
+        assert_eq!(0, Weekday::Sunday.days_since(Weekday::Sunday));
+    }
+
+    #[test]
+    fn test_format() {
+        let date = Date::new(2024, 3, 5).unwrap();
+        assert_eq!("2024-03-05", DateFormat::iso().format(date));
+        assert_eq!("05.03.2024", DateFormat::from_locale("de_DE.UTF-8").format(date));
+        assert_eq!("03/05/2024", DateFormat::from_locale("en-US").format(date));
+        assert_eq!("05/03/2024", DateFormat::from_locale("en_GB").format(date));
+    }
+
+    #[test]
+    fn test_parse() {
+        let date = Date::new(2024, 3, 5).unwrap();
+        let german = DateFormat::from_locale("de-DE");
+        assert_eq!(date, german.parse("05.03.2024").unwrap());
+        assert_eq!(date, german.parse(" 5.3.24 ").unwrap());
+        assert_eq!(date, german.parse("2024-03-05").unwrap());
+        assert_eq!(date, DateFormat::from_locale("en-US").parse("3/5/2024").unwrap());
+        assert!(matches!(german.parse("31.02.2024"), Err(DateErrors::InvalidDate(2024, 2, 31))));
+        assert!(matches!(german.parse("05.03"), Err(DateErrors::InvalidFormat(_))));
+        assert!(matches!(german.parse(""), Err(DateErrors::InvalidFormat(_))));
+        assert!(matches!(german.parse("05.03.202"), Err(DateErrors::InvalidFormat(_))));
+        assert!(matches!(german.parse("005.03.2024"), Err(DateErrors::InvalidFormat(_))));
+        assert!(matches!(german.parse("2024-003-05"), Err(DateErrors::InvalidFormat(_))));
+        assert_eq!(DateFormat::iso(), DateFormat::from_locale("nl_NL.UTF-8"));
+    }
+}
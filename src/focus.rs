@@ -0,0 +1,475 @@
+use crate::data::ComponentId;
+use crate::tree::TreeSnapshot;
+use std::collections::HashMap;
+
+/// # Description
+/// Describes how a component takes part in keyboard focus.
+#[derive(Clone, Copy, Debug)]
+pub struct Focusable {
+    /// The position of the component in the tab order of its focus scope.
+    /// Components with a lower tab index come first, components with an equal tab index
+    /// are ordered as they appear in the tree.
+    pub tab_index: i32,
+    /// Whether the component can be reached using Tab and Shift+Tab.
+    /// Components which are not a tab stop may still be focused by clicking them.
+    pub tab_stop: bool,
+    /// Whether the window draws a focus visual around the component while it has the focus.
+    /// Components indicating the focus themselves, eg. menus, turn it off.
+    pub focus_visual: bool,
+}
+
+impl Focusable {
+    pub fn new() -> Focusable {
+        Focusable {
+            tab_index: 0,
+            tab_stop: true,
+            focus_visual: true,
+        }
+    }
+}
+
+/// # Description
+/// Describes how Tab and Shift+Tab behave inside a focus scope.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TabNavigation {
+    /// The content of the scope is part of the surrounding tab order.
+    Continue,
+    /// Once inside the scope, the focus wraps around at the end of the scope.
+    Cycle,
+    /// Once inside the scope, the focus stops at the end of the scope.
+    Contained,
+    /// The scope is only visited once, focusing the last focused component of the scope.
+    Once,
+    /// The content of the scope is not part of the tab order.
+    None,
+}
+
+/// # Description
+/// Describes a component grouping the focusable components below it.
+#[derive(Clone, Copy, Debug)]
+pub struct FocusScope {
+    /// The position of the scope in the tab order of its parent scope.
+    pub tab_index: i32,
+    /// How Tab and Shift+Tab behave inside the scope.
+    pub tab_navigation: TabNavigation,
+    /// Whether the arrow keys move the focus between the components of the scope.
+    pub directional_navigation: bool,
+}
+
+impl FocusScope {
+    pub fn new() -> FocusScope {
+        FocusScope {
+            tab_index: 0,
+            tab_navigation: TabNavigation::Continue,
+            directional_navigation: false,
+        }
+    }
+}
+
+/// # Description
+/// A direction for directional (arrow key) focus navigation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// # Description
+/// Keeps track of the focused component of a window.
+///
+/// # Remarks
+/// The focus manager does not own any component. All navigation is resolved against a
+/// [`TreeSnapshot`] of the window content, taken right before the navigation happens.
+pub struct FocusManager {
+    focused: Option<ComponentId>,
+    focus_visible: bool,
+    window_focused: bool,
+    /// The last focused component per focus scope, used by [`TabNavigation::Once`].
+    remembered: HashMap<ComponentId, ComponentId>,
+}
+
+impl FocusManager {
+    pub(crate) fn new() -> FocusManager {
+        FocusManager {
+            focused: None,
+            focus_visible: false,
+            window_focused: true,
+            remembered: HashMap::new(),
+        }
+    }
+
+    /// # Description
+    /// The currently focused component.
+    pub fn focused(&self) -> Option<ComponentId> {
+        self.focused
+    }
+
+    /// # Description
+    /// Whether the window has the keyboard focus of the operating system.
+    pub fn is_window_focused(&self) -> bool {
+        self.window_focused
+    }
+
+    /// # Description
+    /// Whether a focus visual should be drawn around the focused component.
+    ///
+    /// # Remarks
+    /// Focus visuals are only shown if the focus was moved using the keyboard.
+    pub fn is_focus_visible(&self) -> bool {
+        self.focused.is_some() && self.focus_visible && self.window_focused
+    }
+
+    pub(crate) fn set_window_focused(&mut self, focused: bool) {
+        self.window_focused = focused;
+    }
+
+    /// # Description
+    /// Moves the focus to *id*.
+    ///
+    /// # Parameters
+    /// - **tree**: The current tree of the window.
+    /// - **id**: The component to focus or `None` to clear the focus.
+    /// - **visible**: Whether the focus was moved using the keyboard.
+    ///
+    /// # Returns
+    /// The previously focused component and the newly focused component if the focus changed.
+    pub(crate) fn set_focus(
+        &mut self,
+        tree: &TreeSnapshot,
+        id: Option<ComponentId>,
+        visible: bool,
+    ) -> Option<(Option<ComponentId>, Option<ComponentId>)> {
+        self.focus_visible = visible;
+        if id == self.focused {
+            return None;
+        }
+        if let Some(index) = id.and_then(|id| tree.index_of(id)) {
+            let focused = tree.nodes[index].id;
+            for ancestor in tree.path(index) {
+                if tree.nodes[ancestor].focus_scope.is_some() && ancestor != index {
+                    self.remembered.insert(tree.nodes[ancestor].id, focused);
+                }
+            }
+        }
+        let previous = self.focused;
+        self.focused = id;
+        Some((previous, id))
+    }
+
+    /// # Description
+    /// Finds the component receiving the focus when pressing Tab (or Shift+Tab if *reverse*).
+    pub(crate) fn next_tab_stop(&self, tree: &TreeSnapshot, reverse: bool) -> Option<ComponentId> {
+        let current = self.focused.and_then(|id| tree.index_of(id));
+
+        // The innermost scope keeping the focus inside, the window cycles otherwise
+        let mut scope = None;
+        let mut mode = TabNavigation::Cycle;
+        let mut parent = current.and_then(|index| tree.nodes[index].parent);
+        while let Some(index) = parent {
+            match tree.nodes[index].focus_scope {
+                Some(d)
+                    if d.tab_navigation == TabNavigation::Cycle
+                        || d.tab_navigation == TabNavigation::Contained =>
+                {
+                    scope = Some(index);
+                    mode = d.tab_navigation;
+                    break;
+                }
+                _ => parent = tree.nodes[index].parent,
+            }
+        }
+
+        let order = self.tab_order(tree, scope);
+        if order.is_empty() {
+            return None;
+        }
+        let last = order.len() - 1;
+        let next = match (
+            current,
+            current.and_then(|c| order.iter().position(|i| *i == c)),
+        ) {
+            (_, Some(position)) if !reverse => {
+                if position < last {
+                    position + 1
+                } else if mode == TabNavigation::Contained {
+                    position
+                } else {
+                    0
+                }
+            }
+            (_, Some(position)) => {
+                if position > 0 {
+                    position - 1
+                } else if mode == TabNavigation::Contained {
+                    position
+                } else {
+                    last
+                }
+            }
+            // The focused component is no tab stop, continue from its place in the tree
+            (Some(current), None) if !reverse => {
+                order.iter().position(|i| *i > current).unwrap_or(0)
+            }
+            (Some(current), None) => order.iter().rposition(|i| *i < current).unwrap_or(last),
+            (None, None) if !reverse => 0,
+            (None, None) => last,
+        };
+        Some(tree.nodes[order[next]].id)
+    }
+
+    /// # Description
+    /// Finds the component receiving the focus when pressing an arrow key.
+    ///
+    /// # Remarks
+    /// Directional navigation only happens inside the innermost focus scope which has
+    /// *directional_navigation* enabled. The nearest focusable component in *direction*,
+    /// measured from the centers of the components, is chosen.
+    pub(crate) fn next_in_direction(
+        &self,
+        tree: &TreeSnapshot,
+        direction: FocusDirection,
+    ) -> Option<ComponentId> {
+        let current = self.focused.and_then(|id| tree.index_of(id))?;
+        let mut scope = tree.nodes[current].parent;
+        while let Some(index) = scope {
+            match tree.nodes[index].focus_scope {
+                Some(d) if d.directional_navigation => break,
+                _ => scope = tree.nodes[index].parent,
+            }
+        }
+        let scope = scope?;
+
+        let from = tree.nodes[current].bounds;
+        let (from_x, from_y) = (from.x + from.width / 2f32, from.y + from.height / 2f32);
+        let mut best: Option<(f32, usize)> = None;
+        for (index, node) in tree.nodes.iter().enumerate() {
+            if index == current || node.focusable.is_none() || !tree.is_ancestor(scope, index) {
+                continue;
+            }
+            let dx = node.bounds.x + node.bounds.width / 2f32 - from_x;
+            let dy = node.bounds.y + node.bounds.height / 2f32 - from_y;
+            let (primary, secondary) = match direction {
+                FocusDirection::Left => (-dx, dy),
+                FocusDirection::Right => (dx, dy),
+                FocusDirection::Up => (-dy, dx),
+                FocusDirection::Down => (dy, dx),
+            };
+            if primary <= 0f32 {
+                continue;
+            }
+            // Components aligned with the current one are preferred over closer, offset ones
+            let score = primary + secondary.abs() * 2f32;
+            match best {
+                Some((best_score, _)) if best_score <= score => {}
+                _ => best = Some((score, index)),
+            }
+        }
+        best.map(|(_, index)| tree.nodes[index].id)
+    }
+
+    /// # Description
+    /// Builds the tab order of the scope at *scope* (or of the whole window if `None`),
+    /// expanding nested focus scopes according to their [`TabNavigation`].
+    fn tab_order(&self, tree: &TreeSnapshot, scope: Option<usize>) -> Vec<usize> {
+        let mut items: Vec<(i32, usize)> = vec![];
+        for (index, node) in tree.nodes.iter().enumerate() {
+            if tree.scope_of(index) != scope {
+                continue;
+            }
+            match (node.focus_scope, node.focusable) {
+                (Some(d), _) => items.push((d.tab_index, index)),
+                (None, Some(d)) if d.tab_stop => items.push((d.tab_index, index)),
+                _ => {}
+            }
+        }
+        items.sort_by_key(|(tab_index, _)| *tab_index);
+
+        let mut order = vec![];
+        for (_, index) in items {
+            let node = &tree.nodes[index];
+            let focus_scope = match node.focus_scope {
+                None => {
+                    order.push(index);
+                    continue;
+                }
+                Some(d) => d,
+            };
+            // A focusable scope comes before its content
+            if node.focusable.map_or(false, |d| d.tab_stop) {
+                order.push(index);
+            }
+            match focus_scope.tab_navigation {
+                TabNavigation::None => {}
+                TabNavigation::Once => {
+                    let inner = self.tab_order(tree, Some(index));
+                    let remembered = self
+                        .remembered
+                        .get(&node.id)
+                        .and_then(|id| inner.iter().find(|i| tree.nodes[**i].id == *id));
+                    if let Some(index) = remembered.or(inner.first()) {
+                        order.push(*index);
+                    }
+                }
+                _ => order.extend(self.tab_order(tree, Some(index))),
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Rectangle;
+    use crate::tree::TreeNode;
+
+    fn node(parent: Option<usize>, x: f32, y: f32, focusable: Option<Focusable>) -> TreeNode {
+        TreeNode {
+            id: ComponentId::new(),
+            parent,
+            bounds: Rectangle { x, y, width: 50f32, height: 30f32 },
+            focusable,
+            focus_scope: None,
+            animating: false,
+            popup: false,
+            hit_test_visible: true,
+        }
+    }
+
+    /// Builds a window with the tab stops `A`, `C`, `D`, `G`, `F` and `B` (in tab order),
+    /// where `C`, `D` and `G` are inside *scope* and `E` is no tab stop.
+    fn tree(scope: FocusScope) -> TreeSnapshot {
+        let mut late = Focusable::new();
+        late.tab_index = 1;
+        let mut no_tab_stop = Focusable::new();
+        no_tab_stop.tab_stop = false;
+        let mut nodes = vec![
+            node(None, 0f32, 0f32, None),
+            node(Some(0), 0f32, 0f32, Some(Focusable::new())),
+            node(Some(0), 100f32, 0f32, Some(late)),
+            node(Some(0), 0f32, 100f32, None),
+            node(Some(3), 0f32, 100f32, Some(Focusable::new())),
+            node(Some(3), 100f32, 100f32, Some(Focusable::new())),
+            node(Some(3), 0f32, 150f32, Some(Focusable::new())),
+            node(Some(0), 200f32, 0f32, Some(no_tab_stop)),
+            node(Some(0), 0f32, 200f32, Some(Focusable::new())),
+        ];
+        nodes[3].focus_scope = Some(scope);
+        TreeSnapshot { nodes }
+    }
+
+    const A: usize = 1;
+    const B: usize = 2;
+    const C: usize = 4;
+    const D: usize = 5;
+    const G: usize = 6;
+    const E: usize = 7;
+    const F: usize = 8;
+
+    fn focus(manager: &mut FocusManager, tree: &TreeSnapshot, index: usize) {
+        manager.set_focus(tree, Some(tree.nodes[index].id), true);
+    }
+
+    /// Presses Tab (or Shift+Tab) and returns the index of the newly focused node.
+    fn tab(manager: &mut FocusManager, tree: &TreeSnapshot, reverse: bool) -> Option<usize> {
+        let next = manager.next_tab_stop(tree, reverse)?;
+        manager.set_focus(tree, Some(next), true);
+        tree.index_of(next)
+    }
+
+    #[test]
+    fn test_tab_order() {
+        let tree = tree(FocusScope::new());
+        let manager = FocusManager::new();
+        assert_eq!(manager.tab_order(&tree, None), vec![A, C, D, G, F, B]);
+
+        let mut manager = FocusManager::new();
+        assert_eq!(tab(&mut manager, &tree, false), Some(A));
+        assert_eq!(tab(&mut manager, &tree, true), Some(B));
+        assert_eq!(tab(&mut manager, &tree, true), Some(F));
+        // Components which are no tab stop continue from their place in the tree
+        focus(&mut manager, &tree, E);
+        assert_eq!(tab(&mut manager, &tree, false), Some(F));
+    }
+
+    #[test]
+    fn test_tab_navigation_cycle() {
+        let mut scope = FocusScope::new();
+        scope.tab_navigation = TabNavigation::Cycle;
+        let tree = tree(scope);
+        let mut manager = FocusManager::new();
+        focus(&mut manager, &tree, A);
+        assert_eq!(tab(&mut manager, &tree, false), Some(C));
+        assert_eq!(tab(&mut manager, &tree, false), Some(D));
+        assert_eq!(tab(&mut manager, &tree, false), Some(G));
+        assert_eq!(tab(&mut manager, &tree, false), Some(C));
+        assert_eq!(tab(&mut manager, &tree, true), Some(G));
+    }
+
+    #[test]
+    fn test_tab_navigation_contained() {
+        let mut scope = FocusScope::new();
+        scope.tab_navigation = TabNavigation::Contained;
+        let tree = tree(scope);
+        let mut manager = FocusManager::new();
+        focus(&mut manager, &tree, G);
+        assert_eq!(tab(&mut manager, &tree, false), Some(G));
+        focus(&mut manager, &tree, C);
+        assert_eq!(tab(&mut manager, &tree, true), Some(C));
+    }
+
+    #[test]
+    fn test_tab_navigation_once() {
+        let mut scope = FocusScope::new();
+        scope.tab_navigation = TabNavigation::Once;
+        let tree = tree(scope);
+        let mut manager = FocusManager::new();
+        focus(&mut manager, &tree, A);
+        assert_eq!(tab(&mut manager, &tree, false), Some(C));
+        assert_eq!(tab(&mut manager, &tree, false), Some(F));
+        // The scope is entered at the last focused component
+        focus(&mut manager, &tree, D);
+        focus(&mut manager, &tree, A);
+        assert_eq!(tab(&mut manager, &tree, false), Some(D));
+        assert_eq!(tab(&mut manager, &tree, true), Some(A));
+    }
+
+    #[test]
+    fn test_tab_navigation_none() {
+        let mut scope = FocusScope::new();
+        scope.tab_navigation = TabNavigation::None;
+        let tree = tree(scope);
+        let mut manager = FocusManager::new();
+        assert_eq!(manager.tab_order(&tree, None), vec![A, F, B]);
+        focus(&mut manager, &tree, A);
+        assert_eq!(tab(&mut manager, &tree, false), Some(F));
+    }
+
+    #[test]
+    fn test_next_in_direction() {
+        let mut scope = FocusScope::new();
+        scope.directional_navigation = true;
+        let tree = tree(scope);
+        let mut manager = FocusManager::new();
+        let next = |manager: &FocusManager, direction| {
+            manager
+                .next_in_direction(&tree, direction)
+                .and_then(|id| tree.index_of(id))
+        };
+        focus(&mut manager, &tree, C);
+        assert_eq!(next(&manager, FocusDirection::Right), Some(D));
+        assert_eq!(next(&manager, FocusDirection::Down), Some(G));
+        // Components outside the scope are not reached
+        assert_eq!(next(&manager, FocusDirection::Up), None);
+        assert_eq!(next(&manager, FocusDirection::Left), None);
+        focus(&mut manager, &tree, D);
+        assert_eq!(next(&manager, FocusDirection::Left), Some(C));
+        // Aligned components are preferred over closer, offset ones
+        assert_eq!(next(&manager, FocusDirection::Down), Some(G));
+        // Only scopes with directional navigation take part
+        focus(&mut manager, &tree, A);
+        assert_eq!(next(&manager, FocusDirection::Down), None);
+    }
+}
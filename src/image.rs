@@ -0,0 +1,98 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::clipboard::ClipboardImage;
+
+#[derive(Debug)]
+pub enum ImageErrors {
+    /// The number of bytes does not match the dimensions.
+    InvalidSize { width: usize, height: usize, bytes: usize },
+}
+
+impl Display for ImageErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageErrors::InvalidSize { width, height, bytes } => write!(
+                f,
+                "Invalid image size ({} bytes for {}x{} pixels)",
+                bytes, width, height
+            ),
+        }
+    }
+}
+
+impl Error for ImageErrors {}
+
+/// # Description
+/// How an image is sampled when drawn at a size other than its own.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageInterpolation {
+    /// Every pixel is drawn as a sharp square, eg. to inspect single pixels.
+    NearestNeighbor,
+    /// The pixels are blended smoothly.
+    Linear,
+}
+
+/// # Description
+/// A bitmap, which can be drawn using [`crate::abstraction::Renderer::draw_image`].
+///
+/// # Remarks
+/// Clones share the pixels, which cannot be changed once the image was created. Renderers
+/// may use this to keep the image uploaded for as long as it is drawn.
+#[derive(Clone, Debug)]
+pub struct Image {
+    id: u64,
+    width: usize,
+    height: usize,
+    bytes: Rc<[u8]>,
+}
+
+impl PartialEq for Image {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Image {
+    /// # Description
+    /// Creates an image from its pixels, given row by row with 4 bytes (red, green, blue,
+    /// alpha) per pixel.
+    ///
+    /// # Returns
+    /// [`ImageErrors::InvalidSize`] if *bytes* does not hold exactly *width* times *height*
+    /// pixels.
+    pub fn from_rgba(width: usize, height: usize, bytes: Vec<u8>) -> Result<Image, ImageErrors> {
+        if width.checked_mul(height).and_then(|d| d.checked_mul(4)) != Some(bytes.len()) {
+            return Err(ImageErrors::InvalidSize { width, height, bytes: bytes.len() });
+        }
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        Ok(Image {
+            id: NEXT.fetch_add(1, Ordering::Relaxed),
+            width,
+            height,
+            bytes: bytes.into(),
+        })
+    }
+
+    pub fn from_clipboard(image: &ClipboardImage) -> Result<Image, ImageErrors> {
+        Image::from_rgba(image.width, image.height, image.bytes.clone())
+    }
+
+    /// # Description
+    /// Identifies the pixels of the image, which are shared by all its clones.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// # Description
+    /// The pixels of the image, row by row, 4 bytes (red, green, blue, alpha) per pixel.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
@@ -0,0 +1,313 @@
+use crate::data::{ComponentId, Point};
+use crate::overlay::{Overlay, OverlayRequest, PopupPlacement};
+use crate::pointer::PointerState;
+use std::time::Instant;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, ModifiersState, SmolStr};
+
+/// # Description
+/// The amount of pixels a single line of a mouse wheel scroll represents.
+///
+/// # Remarks
+/// Used to normalize pixel based wheel deltas (eg. touchpads) to line based ones.
+pub const PIXELS_PER_WHEEL_LINE: f32 = 20f32;
+
+/// # Description
+/// An input event, dispatched to a component.
+///
+/// # Remarks
+/// All positions carried by an event are relative to the component receiving it.
+/// Events are translated by the window before being dispatched, using
+/// [`InputEvent::translated`].
+#[derive(Clone, Debug)]
+pub enum InputEvent {
+    /// The pointer moved to *position*.
+    PointerMoved { position: Point<f32> },
+    /// The *button* was pressed while the pointer was at *position*.
+    PointerPressed {
+        position: Point<f32>,
+        button: MouseButton,
+    },
+    /// The *button* was released while the pointer was at *position*.
+    PointerReleased {
+        position: Point<f32>,
+        button: MouseButton,
+    },
+    /// The mouse wheel was scrolled by *delta* lines while the pointer was at *position*.
+    /// Positive values indicate that the content should move right and down.
+    PointerWheel {
+        position: Point<f32>,
+        delta: Point<f32>,
+    },
+    /// The pointer entered the component or one of its children.
+    PointerEntered,
+    /// The pointer left the component and all of its children.
+    PointerLeft,
+    /// The component lost a pointer capture it did not release itself, eg. because the
+    /// window lost the focus during a drag.
+    PointerCaptureLost,
+    /// A key was pressed while the component had the keyboard focus.
+    KeyPressed {
+        /// The logical key, respecting the keyboard layout.
+        key: Key,
+        /// The text produced by the key press, if any.
+        text: Option<SmolStr>,
+        modifiers: ModifiersState,
+        /// Whether the event was caused by the key being held down.
+        repeat: bool,
+    },
+    /// A key was released while the component had the keyboard focus.
+    KeyReleased { key: Key, modifiers: ModifiersState },
+    /// An input method started, updated or ended (if *text* is empty) composing text while
+    /// the component had the keyboard focus.
+    ImePreedit {
+        /// The text being composed, which is not yet part of the content.
+        text: String,
+        /// The byte range of *text* the cursor or selection of the input method spans,
+        /// or `None` if the cursor should be hidden.
+        cursor: Option<(usize, usize)>,
+    },
+    /// An input method committed *text* while the component had the keyboard focus.
+    ImeCommit { text: String },
+    /// The component received the keyboard focus.
+    FocusGained,
+    /// The component lost the keyboard focus.
+    FocusLost,
+    /// The component *id* of the mutually exclusive *group* was checked, eg. a radio button.
+    /// Other members of the group uncheck themselves.
+    /// Broadcast using [`EventContext::broadcast`], the window dispatches it to the members of
+    /// the group only. An empty *group* consists of the siblings of *id*, a named group spans
+    /// the whole window.
+    GroupChecked { group: String, id: ComponentId },
+    /// An arrow key was pressed on the component *id* of the mutually exclusive *group*,
+    /// moving the check to the next member, or to the previous one unless *forward*.
+    /// Broadcast like [`InputEvent::GroupChecked`], but dispatched to one member after another
+    /// in tree order, starting next to *id* and wrapping around, until a member handled it.
+    GroupStepped { group: String, id: ComponentId, forward: bool },
+    /// The pointer was pressed outside of the component and its popups, which should be
+    /// closed. See [`crate::abstraction::Component::visit_popups`].
+    LightDismiss,
+    /// The overlay *id* shown by the component was closed by the window, eg. because the
+    /// pointer was pressed outside of it.
+    OverlayClosed { id: ComponentId },
+    /// Alt was pressed and released without another key (*key* is `None`), or pressed
+    /// together with *key*, and the focused component did not handle it.
+    /// Broadcast by the window, eg. activating a menu bar.
+    AccessKey { key: Option<char> },
+    /// A previously requested wake-up time was reached.
+    /// See [`EventContext::request_wake_up`].
+    Tick,
+}
+
+impl InputEvent {
+    /// # Description
+    /// The position of the pointer, if this event is a pointer event.
+    pub fn position(&self) -> Option<Point<f32>> {
+        match self {
+            InputEvent::PointerMoved { position }
+            | InputEvent::PointerPressed { position, .. }
+            | InputEvent::PointerReleased { position, .. }
+            | InputEvent::PointerWheel { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// # Description
+    /// Creates a copy of this event, with all positions moved by *-x* and *-y*.
+    ///
+    /// # Parameters
+    /// - **x**: The horizontal offset of the receiving component in the current space.
+    /// - **y**: The vertical offset of the receiving component in the current space.
+    pub fn translated(&self, x: f32, y: f32) -> InputEvent {
+        let translate = |position: &Point<f32>| Point {
+            x: position.x - x,
+            y: position.y - y,
+        };
+        match self {
+            InputEvent::PointerMoved { position } => InputEvent::PointerMoved {
+                position: translate(position),
+            },
+            InputEvent::PointerPressed { position, button } => InputEvent::PointerPressed {
+                position: translate(position),
+                button: *button,
+            },
+            InputEvent::PointerReleased { position, button } => InputEvent::PointerReleased {
+                position: translate(position),
+                button: *button,
+            },
+            InputEvent::PointerWheel { position, delta } => InputEvent::PointerWheel {
+                position: translate(position),
+                delta: *delta,
+            },
+            event => event.clone(),
+        }
+    }
+}
+
+/// # Description
+/// Context passed along with an [`InputEvent`], allowing components to request
+/// services from the window they are hosted in.
+pub struct EventContext {
+    now: Instant,
+    /// The component the event currently is dispatched to.
+    pub(crate) current: Option<ComponentId>,
+    pub(crate) pointer_capture: Option<ComponentId>,
+    pub(crate) hovered: Vec<ComponentId>,
+    pressed: Vec<(MouseButton, ComponentId)>,
+    pub(crate) click_count: usize,
+    pub(crate) focused: Option<ComponentId>,
+    pub(crate) focus_request: Option<ComponentId>,
+    pub(crate) wake_up: Option<Instant>,
+    pub(crate) broadcasts: Vec<InputEvent>,
+    pub(crate) overlays: Vec<OverlayRequest>,
+    modifiers: ModifiersState,
+}
+
+impl EventContext {
+    pub(crate) fn new(
+        now: Instant,
+        pointer: &PointerState,
+        focused: Option<ComponentId>,
+        modifiers: ModifiersState,
+    ) -> EventContext {
+        EventContext {
+            now,
+            current: None,
+            pointer_capture: pointer.captured(),
+            hovered: pointer.hovered_path().to_vec(),
+            pressed: pointer.pressed_buttons().to_vec(),
+            click_count: pointer.click_count(),
+            focused,
+            focus_request: None,
+            wake_up: None,
+            broadcasts: vec![],
+            overlays: vec![],
+            modifiers,
+        }
+    }
+
+    /// # Description
+    /// The point in time the event is dispatched at.
+    pub fn now(&self) -> Instant {
+        self.now
+    }
+
+    /// # Description
+    /// The modifier keys held while the event is dispatched.
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// # Description
+    /// Requests all following pointer events to be dispatched to the current component,
+    /// regardless of where the pointer is, until [`EventContext::release_pointer_capture`]
+    /// is called.
+    pub fn capture_pointer(&mut self) {
+        self.pointer_capture = self.current;
+    }
+
+    /// # Description
+    /// Releases a pointer capture previously acquired with [`EventContext::capture_pointer`].
+    ///
+    /// # Remarks
+    /// Does nothing if the current component does not hold the capture.
+    pub fn release_pointer_capture(&mut self) {
+        if self.pointer_capture == self.current {
+            self.pointer_capture = None;
+        }
+    }
+
+    /// # Description
+    /// Whether the current component holds the pointer capture.
+    pub fn has_pointer_capture(&self) -> bool {
+        self.current.is_some() && self.pointer_capture == self.current
+    }
+
+    /// # Description
+    /// Whether the pointer hovers the current component or one of its children.
+    pub fn is_hovered(&self) -> bool {
+        self.current.map_or(false, |id| self.hovered.contains(&id))
+    }
+
+    /// # Description
+    /// Whether *button* was pressed on the current component and still is held.
+    pub fn is_pressed(&self, button: MouseButton) -> bool {
+        self.current
+            .map_or(false, |id| self.pressed.contains(&(button, id)))
+    }
+
+    /// # Description
+    /// How many presses in quick succession the last [`InputEvent::PointerPressed`] completed,
+    /// eg. `2` for a double click.
+    ///
+    /// # Remarks
+    /// Presses only count together when made with the same button on the same component at
+    /// about the same position. Components telling apart several items, eg. the rows of a
+    /// list, still have to check the second press hit the same item.
+    pub fn click_count(&self) -> usize {
+        self.click_count
+    }
+
+    /// # Description
+    /// Whether the current component has the keyboard focus.
+    pub fn has_focus(&self) -> bool {
+        self.current.is_some() && self.focused == self.current
+    }
+
+    /// # Description
+    /// The component having the keyboard focus.
+    pub fn focused(&self) -> Option<ComponentId> {
+        self.focused
+    }
+
+    /// # Description
+    /// Requests the keyboard focus to be moved to *id* once the event was dispatched.
+    pub fn request_focus(&mut self, id: ComponentId) {
+        self.focus_request = Some(id);
+    }
+
+    /// # Description
+    /// Requests an [`InputEvent::Tick`] to be dispatched once *at* is reached.
+    ///
+    /// # Remarks
+    /// Wake-ups are one-shot. Components that need to be woken up repeatedly (eg. for an
+    /// auto-repeat) have to request a new wake-up every time they receive a tick.
+    /// Ticks are dispatched to every component, so a component has to check for itself
+    /// whether it actually is due.
+    pub fn request_wake_up(&mut self, at: Instant) {
+        self.wake_up = match self.wake_up {
+            Some(existing) if existing <= at => Some(existing),
+            _ => Some(at),
+        };
+    }
+
+    /// # Description
+    /// Requests *event* to be dispatched to every component of the window once the current
+    /// event was dispatched.
+    pub fn broadcast(&mut self, event: InputEvent) {
+        self.broadcasts.push(event);
+    }
+
+    /// # Description
+    /// Shows *overlay* on top of the content of the window once the current event was
+    /// dispatched, belonging to the component the event is dispatched to.
+    ///
+    /// # Returns
+    /// The id of the content of the overlay, identifying the overlay.
+    pub fn show_overlay(&mut self, mut overlay: Overlay) -> ComponentId {
+        let id = overlay.content.id();
+        overlay.owner = self.current;
+        self.overlays.push(OverlayRequest::Show(overlay));
+        id
+    }
+
+    /// # Description
+    /// Moves the overlay *id* to *placement*, eg. to keep a drag preview at the pointer.
+    pub fn move_overlay(&mut self, id: ComponentId, placement: PopupPlacement) {
+        self.overlays.push(OverlayRequest::Move(id, placement));
+    }
+
+    pub fn close_overlay(&mut self, id: ComponentId) {
+        self.overlays.push(OverlayRequest::Close(id));
+    }
+}
@@ -1,14 +1,39 @@
-use crate::abstraction::{Component, RenderSurface, Renderer};
+use crate::abstraction::{ContainerComponent, RenderSurface, Renderer};
 use crate::application::{AppBuilder, AppErrors};
+use crate::color::Color;
+use crate::commands::{Command, CommandBinding, DelegateCommand, KeyGesture};
+use crate::components::{
+    ContextMenu, DataGridColumn, DataGridSource, MenuItem, MenuItemKind, SplitPane, TabItem,
+    TreeViewNode,
+};
 use crate::data::Size;
+use crate::date::Date;
+use crate::image::Image;
 use crate::renderers::SkiaRenderer;
+use crate::text::{TextTrimming, TextWrapping};
+use crate::tool_tip::ToolTip;
+use crate::window::AppWindow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 mod abstraction;
 mod application;
+mod clipboard;
 mod color;
+mod commands;
 mod components;
 mod data;
+mod date;
+mod focus;
+mod image;
+mod input;
+mod overlay;
+mod pointer;
 mod renderers;
+mod text;
+mod tool_tip;
+mod tree;
 mod visuals;
 mod window;
 
@@ -21,7 +46,7 @@ fn main() {
         .expect("Failed to initialize logger.");
 
     // ToDo: Add Post-Init for testing etc.
-    let result = AppBuilder::new().run(|event_loop| {
+    let result = AppBuilder::new().with_content(demo).run(|event_loop| {
         SkiaRenderer::create(event_loop).map(|renderer| renderer as Box<dyn RenderSurface>)
     }).expect("UI application error");
 
@@ -90,3 +115,210 @@ fn main() {
 
      */
 }
+
+/// # Description
+/// Fills *window* with one of each component, to try them out.
+fn demo(window: &mut AppWindow) {
+    fn menu_item(text: &str, gesture: Option<&str>) -> MenuItem {
+        let mut item = MenuItem::new();
+        item.text = text.to_string();
+        item.gesture = gesture.and_then(|d| KeyGesture::parse(d).ok());
+        item
+    }
+    let new_command: Rc<dyn Command> = Rc::new(DelegateCommand::new("New", || {
+        log::info!("New executed");
+    }));
+    let mut new = menu_item("_New", Some("Ctrl+N"));
+    new.command = Some(new_command.clone());
+    let mut recent = menu_item("_Recent", None);
+    recent.submenu.items = vec![menu_item("_1 notes.txt", None), menu_item("_2 todo.txt", None)];
+    let mut file = menu_item("_File", None);
+    file.submenu.items = vec![
+        new,
+        menu_item("_Open...", Some("Ctrl+O")),
+        recent,
+        MenuItem::separator(),
+        menu_item("E_xit", Some("Alt+F4")),
+    ];
+    let mut status_bar = menu_item("_Status Bar", None);
+    status_bar.kind = MenuItemKind::Check;
+    status_bar.is_checked = true;
+    let mut view = menu_item("_View", None);
+    view.submenu.items = vec![status_bar, MenuItem::separator()];
+    for (text, checked) in [("S_mall", false), ("_Large", true)].iter() {
+        let mut size = menu_item(text, None);
+        size.kind = MenuItemKind::Radio { group: "size".to_string() };
+        size.is_checked = *checked;
+        view.submenu.items.push(size);
+    }
+    let mut menu_bar = components::MenuBar::new();
+    menu_bar.items = vec![file, view];
+
+    window.add_content(Box::new(menu_bar));
+    window.command_bindings.push(CommandBinding::new(
+        KeyGesture::parse("Ctrl+N").unwrap(),
+        new_command,
+    ));
+    for value in [0f32, 25f32, 50f32, 75f32].iter() {
+        let mut scroll_bar = components::ScrollBar::new();
+        scroll_bar.range.max_value = 100f32;
+        scroll_bar.range.value = *value;
+        scroll_bar.bar_value = 40f32;
+        scroll_bar.thickness = 14f32;
+        window.add_content(Box::new(scroll_bar));
+    }
+    let mut button = components::Button::new();
+    button.text = "Button".to_string();
+    button.tool_tip = Some(ToolTip::text("Does nothing, but shows this tool tip"));
+    button.context_menu = Some(Rc::new(|| {
+        ContextMenu::new(vec![menu_item("_Copy", Some("Ctrl+C")), menu_item("_Paste", Some("Ctrl+V"))])
+    }));
+    window.add_content(Box::new(button));
+    let mut text_block = components::TextBlock::new();
+    text_block.text = "The quick brown fox jumps over the lazy dog".to_string();
+    text_block.wrapping = TextWrapping::Word;
+    text_block.trimming = TextTrimming::Ellipsis;
+    text_block.max_lines = Some(2);
+    window.add_content(Box::new(text_block));
+    let mut text_box = components::TextBox::new();
+    text_box.placeholder = "Text".to_string();
+    window.add_content(Box::new(text_box));
+    let mut check_box = components::CheckBox::new();
+    check_box.text = "Check".to_string();
+    check_box.three_state = true;
+    window.add_content(Box::new(check_box));
+    for text in ["One", "Two"].iter() {
+        let mut radio_button = components::RadioButton::new();
+        radio_button.text = text.to_string();
+        window.add_content(Box::new(radio_button));
+    }
+    let mut toggle_switch = components::ToggleSwitch::new();
+    toggle_switch.text = "Toggle".to_string();
+    window.add_content(Box::new(toggle_switch));
+    let mut combo_box = components::ComboBox::new();
+    combo_box.placeholder = "Choose".to_string();
+    combo_box.items = (1..=100).map(|d| format!("Item {}", d)).collect();
+    window.add_content(Box::new(combo_box));
+    let mut list_view = components::ListView::new();
+    list_view.items = Rc::new((1..=10000).map(|d| format!("Entry {}", d)).collect::<Vec<_>>());
+    list_view.selection_mode = components::SelectionMode::Extended;
+    window.add_content(Box::new(list_view));
+    let mut tree_view = components::TreeView::new();
+    tree_view.roots = (1..=3)
+        .map(|d| {
+            TreeViewNode::lazy(&format!("Folder {}", d), || {
+                (1..=1000).map(|d| TreeViewNode::new(&format!("File {}", d))).collect()
+            })
+        })
+        .collect();
+    window.add_content(Box::new(tree_view));
+    let mut data_grid = components::DataGrid::new();
+    data_grid.source = Rc::new(DemoRows { count: 100000, edits: RefCell::new(HashMap::new()) });
+    data_grid.columns = vec![
+        DataGridColumn::new("Id", 0).with_width(40f32),
+        DataGridColumn::new("Name", 1),
+        DataGridColumn::new("Score", 2),
+    ];
+    data_grid.columns[0].is_read_only = true;
+    data_grid.frozen_columns = 1;
+    window.add_content(Box::new(data_grid));
+    let mut tab_control = components::TabControl::new();
+    tab_control.tabs = (1..=12)
+        .map(|d| {
+            TabItem::new(&format!("Document {}", d), || Box::new(components::TextBox::new()))
+                .with_closable(d > 1)
+        })
+        .collect();
+    tab_control.selected_index = Some(0);
+    window.add_content(Box::new(tab_control));
+    let split_panel = components::SplitPanel::new()
+        .with_pane(SplitPane::new(Box::new(components::TextBox::new())).with_min_size(20f32))
+        .with_pane(SplitPane::new(Box::new(components::TextBox::new())).with_weight(2f32))
+        .with_pane(SplitPane::new(Box::new(components::TextBox::new())).with_max_size(60f32));
+    window.add_content(Box::new(split_panel));
+    let mut group_box = components::GroupBox::new();
+    group_box.header = "Group".to_string();
+    let border = components::Border::new().with_child(Box::new(components::TextBox::new()));
+    group_box.add_child(Box::new(border));
+    window.add_content(Box::new(group_box));
+    let mut expander = components::Expander::new();
+    expander.header = "Details".to_string();
+    expander.add_child(Box::new(components::TextBox::new()));
+    window.add_content(Box::new(expander));
+    let mut numeric_up_down = components::NumericUpDown::new();
+    numeric_up_down.range.max_value = 1000000f32;
+    numeric_up_down.range.small_change = 0.5f32;
+    numeric_up_down.format.decimals = 1;
+    numeric_up_down.format.thousands_separator = Some(',');
+    numeric_up_down.format.suffix = " px".to_string();
+    window.add_content(Box::new(numeric_up_down));
+    let mut color_picker = components::ColorPicker::new();
+    color_picker.color = Color::CORNFLOWER_BLUE;
+    window.add_content(Box::new(color_picker));
+    let mut date_picker = components::DatePicker::new();
+    let today = Date::today();
+    date_picker.min_date = Some(today.add_months(-6));
+    date_picker.max_date = Some(today.add_months(6));
+    date_picker.blackout_dates = vec![today.add_days(1), today.add_days(2)];
+    date_picker.placeholder = date_picker.format.format(today);
+    window.add_content(Box::new(date_picker));
+    let (width, height) = (256, 192);
+    let mut bytes = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            // A gradient overlaid by a checkerboard, to tell single pixels apart when zoomed in
+            let dark = (x / 16 + y / 16) % 2 == 0;
+            let shade = if dark { 0.6f32 } else { 1f32 };
+            let red = (x * 255 / width) as f32 * shade;
+            let green = (y * 255 / height) as f32 * shade;
+            bytes.extend_from_slice(&[red as u8, green as u8, (160f32 * shade) as u8, 255]);
+        }
+    }
+    if let Ok(image) = Image::from_rgba(width, height, bytes) {
+        let mut image_view = components::ImageView::new().with_image(image);
+        image_view.background = Color::GAINSBORO;
+        window.add_content(Box::new(image_view));
+    }
+    let mut slider = components::Slider::new();
+    slider.tick_frequency = 10f32;
+    slider.snap_to_ticks = true;
+    slider.tool_tip = Some(ToolTip::text("Snaps to multiples of ten"));
+    window.add_content(Box::new(slider));
+    let mut progress_bar = components::ProgressBar::new();
+    progress_bar.range.value = 40f32;
+    window.add_content(Box::new(progress_bar));
+    let mut progress_bar = components::ProgressBar::new();
+    progress_bar.is_indeterminate = true;
+    window.add_content(Box::new(progress_bar));
+}
+
+/// # Description
+/// The rows of the demo data grid, generated when displayed instead of being stored.
+struct DemoRows {
+    count: usize,
+    /// The texts entered by editing cells, by row and field.
+    edits: RefCell<HashMap<(usize, usize), String>>,
+}
+
+impl DataGridSource for DemoRows {
+    fn row_count(&self) -> usize {
+        self.count
+    }
+
+    fn cell_text(&self, row: usize, field: usize) -> String {
+        if let Some(text) = self.edits.borrow().get(&(row, field)) {
+            return text.clone();
+        }
+        let id = row + 1;
+        match field {
+            0 => id.to_string(),
+            1 => format!("Name {}", id),
+            _ => (id * 7 % 100).to_string(),
+        }
+    }
+
+    fn set_cell_text(&self, row: usize, field: usize, text: &str) -> bool {
+        self.edits.borrow_mut().insert((row, field), text.to_string());
+        true
+    }
+}
@@ -0,0 +1,213 @@
+use crate::abstraction::Component;
+use crate::data::{ComponentId, Rectangle, Size};
+
+/// # Description
+/// The side of its anchor a popup is placed at.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Placement {
+    /// Below the anchor, aligned with its left edge.
+    Bottom,
+    /// Above the anchor, aligned with its left edge.
+    Top,
+    /// Right of the anchor, aligned with its top edge.
+    Right,
+    /// Left of the anchor, aligned with its top edge.
+    Left,
+}
+
+impl Placement {
+    fn flipped(self) -> Placement {
+        match self {
+            Placement::Bottom => Placement::Top,
+            Placement::Top => Placement::Bottom,
+            Placement::Right => Placement::Left,
+            Placement::Left => Placement::Right,
+        }
+    }
+}
+
+/// # Description
+/// Where a popup or overlay is placed.
+///
+/// # Remarks
+/// Popups overflowing the window at the preferred side are flipped to the opposite side if
+/// they fit there, and moved along the side to stay inside the window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PopupPlacement {
+    /// The area the popup is placed next to, relative to the component showing it.
+    pub anchor: Rectangle<f32>,
+    /// The preferred side of the anchor.
+    pub placement: Placement,
+}
+
+impl PopupPlacement {
+    pub fn new(anchor: Rectangle<f32>, placement: Placement) -> PopupPlacement {
+        PopupPlacement { anchor, placement }
+    }
+}
+
+/// # Description
+/// Content shown by a window on top of its components and their popups, eg. a tooltip, a
+/// context menu or a drag preview.
+///
+/// # Remarks
+/// An overlay belongs to the component which showed it, if any, and is closed together with
+/// it. Events not handled by the overlay bubble up to that component.
+pub struct Overlay {
+    pub content: Box<dyn Component>,
+    /// Where the overlay is placed. The anchor is relative to the component showing the
+    /// overlay, or to the window.
+    pub placement: PopupPlacement,
+    /// Whether the overlay is closed by pressing the pointer outside of it or by pressing
+    /// escape.
+    pub light_dismiss: bool,
+    /// Whether the overlay receives pointer input. Tooltips and drag previews do not, letting
+    /// the pointer reach the components below them.
+    pub hit_test_visible: bool,
+
+    pub(crate) owner: Option<ComponentId>,
+    /// The component to return the keyboard focus to if the overlay is closed while it
+    /// contains the focus.
+    pub(crate) restore_focus: Option<ComponentId>,
+}
+
+impl Overlay {
+    pub fn new(content: Box<dyn Component>, placement: PopupPlacement) -> Overlay {
+        Overlay {
+            content,
+            placement,
+            light_dismiss: true,
+            hit_test_visible: true,
+            owner: None,
+            restore_focus: None,
+        }
+    }
+
+    pub fn with_light_dismiss(mut self, light_dismiss: bool) -> Self {
+        self.light_dismiss = light_dismiss;
+        self
+    }
+
+    pub fn with_hit_test_visible(mut self, hit_test_visible: bool) -> Self {
+        self.hit_test_visible = hit_test_visible;
+        self
+    }
+}
+
+/// # Description
+/// A change of the overlays of a window, requested by a component.
+pub(crate) enum OverlayRequest {
+    Show(Overlay),
+    Move(ComponentId, PopupPlacement),
+    Close(ComponentId),
+}
+
+/// # Description
+/// The bounds of a popup or overlay, as of the last layout pass.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PopupLayout {
+    pub(crate) id: ComponentId,
+    /// The bounds of the popup, relative to the window.
+    pub(crate) bounds: Rectangle<f32>,
+}
+
+/// # Description
+/// Places a popup of *size* next to *anchor*, within a window of *window* size.
+///
+/// # Parameters
+/// - **anchor**: The area to place the popup next to, relative to the window.
+///
+/// # Returns
+/// The bounds of the popup, relative to the window.
+pub(crate) fn place(
+    anchor: Rectangle<f32>,
+    placement: Placement,
+    size: Size<f32>,
+    window: Size<f32>,
+) -> Rectangle<f32> {
+    let at = |placement: Placement| match placement {
+        Placement::Bottom => Rectangle {
+            x: anchor.x,
+            y: anchor.y + anchor.height,
+            width: size.width,
+            height: size.height,
+        },
+        Placement::Top => Rectangle {
+            x: anchor.x,
+            y: anchor.y - size.height,
+            width: size.width,
+            height: size.height,
+        },
+        Placement::Right => Rectangle {
+            x: anchor.x + anchor.width,
+            y: anchor.y,
+            width: size.width,
+            height: size.height,
+        },
+        Placement::Left => Rectangle {
+            x: anchor.x - size.width,
+            y: anchor.y,
+            width: size.width,
+            height: size.height,
+        },
+    };
+    let fits = |bounds: &Rectangle<f32>| {
+        bounds.x >= 0f32
+            && bounds.y >= 0f32
+            && bounds.x + bounds.width <= window.width
+            && bounds.y + bounds.height <= window.height
+    };
+    // Only the side matters for flipping, the popup is moved along the side below
+    let along = |bounds: Rectangle<f32>| match placement {
+        Placement::Bottom | Placement::Top => Rectangle { x: 0f32, ..bounds },
+        Placement::Right | Placement::Left => Rectangle { y: 0f32, ..bounds },
+    };
+    let preferred = at(placement);
+    let flipped = at(placement.flipped());
+    let mut bounds = if !fits(&along(preferred)) && fits(&along(flipped)) {
+        flipped
+    } else {
+        preferred
+    };
+    bounds.x = f32::max(0f32, f32::min(bounds.x, window.width - bounds.width));
+    bounds.y = f32::max(0f32, f32::min(bounds.y, window.height - bounds.height));
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Size<f32> = Size { width: 200f32, height: 100f32 };
+    const SIZE: Size<f32> = Size { width: 50f32, height: 40f32 };
+
+    fn anchor(x: f32, y: f32) -> Rectangle<f32> {
+        Rectangle { x, y, width: 20f32, height: 10f32 }
+    }
+
+    #[test]
+    fn test_place() {
+        let bounds = place(anchor(10f32, 10f32), Placement::Bottom, SIZE, WINDOW);
+        assert_eq!(bounds, Rectangle { x: 10f32, y: 20f32, width: 50f32, height: 40f32 });
+        let bounds = place(anchor(10f32, 10f32), Placement::Right, SIZE, WINDOW);
+        assert_eq!(bounds, Rectangle { x: 30f32, y: 10f32, width: 50f32, height: 40f32 });
+    }
+
+    #[test]
+    fn test_place_flipped() {
+        let bounds = place(anchor(10f32, 70f32), Placement::Bottom, SIZE, WINDOW);
+        assert_eq!(bounds, Rectangle { x: 10f32, y: 30f32, width: 50f32, height: 40f32 });
+        let bounds = place(anchor(160f32, 10f32), Placement::Right, SIZE, WINDOW);
+        assert_eq!(bounds, Rectangle { x: 110f32, y: 10f32, width: 50f32, height: 40f32 });
+    }
+
+    #[test]
+    fn test_place_moved_inside() {
+        let bounds = place(anchor(170f32, 30f32), Placement::Bottom, SIZE, WINDOW);
+        assert_eq!(bounds, Rectangle { x: 150f32, y: 40f32, width: 50f32, height: 40f32 });
+        // Neither side fits, the popup stays at the preferred side
+        let size = Size { width: 50f32, height: 60f32 };
+        let bounds = place(anchor(10f32, 45f32), Placement::Bottom, size, WINDOW);
+        assert_eq!(bounds, Rectangle { x: 10f32, y: 40f32, width: 50f32, height: 60f32 });
+    }
+}
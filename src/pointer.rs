@@ -0,0 +1,218 @@
+use crate::data::{ComponentId, Point};
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+
+/// The longest time between two presses counting as a double click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+/// How far the pointer may move between two presses counting as a double click.
+const DOUBLE_CLICK_DISTANCE: f32 = 4f32;
+
+/// A press of a pointer button, continued by the next press if it is close enough.
+#[derive(Clone, Copy, Debug)]
+struct Click {
+    button: MouseButton,
+    target: Option<ComponentId>,
+    position: Point<f32>,
+    at: Instant,
+}
+
+/// # Description
+/// Keeps track of the pointer of a window: where it is, which components it hovers, which
+/// component captured it, which buttons are pressed on which component and how many clicks
+/// were made in quick succession.
+pub struct PointerState {
+    position: Point<f32>,
+    inside: bool,
+    /// The hovered components, from the root down to the innermost one.
+    hovered: Vec<ComponentId>,
+    capture: Option<ComponentId>,
+    pressed: Vec<(MouseButton, ComponentId)>,
+    last_click: Option<Click>,
+    click_count: usize,
+}
+
+impl PointerState {
+    pub(crate) fn new() -> PointerState {
+        PointerState {
+            position: Point { x: 0f32, y: 0f32 },
+            inside: false,
+            hovered: vec![],
+            capture: None,
+            pressed: vec![],
+            last_click: None,
+            click_count: 0,
+        }
+    }
+
+    /// # Description
+    /// The last known position of the pointer, relative to the window.
+    pub fn position(&self) -> Point<f32> {
+        self.position
+    }
+
+    /// # Description
+    /// Whether the pointer currently is inside the window.
+    pub fn is_inside(&self) -> bool {
+        self.inside
+    }
+
+    /// # Description
+    /// The innermost hovered component.
+    pub fn hovered(&self) -> Option<ComponentId> {
+        self.hovered.last().copied()
+    }
+
+    /// # Description
+    /// Whether *id* is hovered, either directly or through one of its children.
+    ///
+    /// # Remarks
+    /// While the pointer is captured, only the capturing component and its parents can be
+    /// hovered.
+    pub fn is_hovered(&self, id: ComponentId) -> bool {
+        self.hovered.contains(&id)
+    }
+
+    /// # Description
+    /// The component which captured the pointer.
+    pub fn captured(&self) -> Option<ComponentId> {
+        self.capture
+    }
+
+    /// # Description
+    /// The component *button* was pressed on, if it still is held.
+    pub fn pressed(&self, button: MouseButton) -> Option<ComponentId> {
+        self.pressed
+            .iter()
+            .find(|(d, _)| *d == button)
+            .map(|(_, id)| *id)
+    }
+
+    /// # Description
+    /// How many presses of the same button on the same component were made in quick
+    /// succession and at about the same position up to the last one, eg. `2` for a double
+    /// click.
+    pub fn click_count(&self) -> usize {
+        self.click_count
+    }
+
+    pub(crate) fn hovered_path(&self) -> &[ComponentId] {
+        &self.hovered
+    }
+
+    pub(crate) fn pressed_buttons(&self) -> &[(MouseButton, ComponentId)] {
+        &self.pressed
+    }
+
+    pub(crate) fn moved(&mut self, position: Point<f32>) {
+        self.position = position;
+        self.inside = true;
+    }
+
+    pub(crate) fn left(&mut self) {
+        self.inside = false;
+    }
+
+    pub(crate) fn set_capture(&mut self, capture: Option<ComponentId>) {
+        self.capture = capture;
+    }
+
+    /// # Description
+    /// Replaces the hovered components.
+    ///
+    /// # Parameters
+    /// - **path**: The newly hovered components, from the root down to the innermost one.
+    ///
+    /// # Returns
+    /// The components no longer hovered (innermost first) and the newly hovered components
+    /// (outermost first).
+    pub(crate) fn set_hovered(
+        &mut self,
+        path: Vec<ComponentId>,
+    ) -> (Vec<ComponentId>, Vec<ComponentId>) {
+        let left = self
+            .hovered
+            .iter()
+            .rev()
+            .filter(|id| !path.contains(id))
+            .copied()
+            .collect();
+        let entered = path
+            .iter()
+            .filter(|id| !self.hovered.contains(id))
+            .copied()
+            .collect();
+        self.hovered = path;
+        (left, entered)
+    }
+
+    pub(crate) fn press(&mut self, button: MouseButton, id: ComponentId) {
+        self.release(button);
+        self.pressed.push((button, id));
+    }
+
+    /// # Description
+    /// Counts a press of *button* on *target* at *position*, continuing the click count of
+    /// the previous press if it was close enough in time and space and hit the same target.
+    pub(crate) fn click(
+        &mut self,
+        button: MouseButton,
+        target: Option<ComponentId>,
+        position: Point<f32>,
+        now: Instant,
+    ) {
+        self.click_count = match self.last_click {
+            Some(d)
+                if d.button == button
+                    && d.target == target
+                    && now - d.at <= DOUBLE_CLICK_TIME
+                    && (position.x - d.position.x).abs() <= DOUBLE_CLICK_DISTANCE
+                    && (position.y - d.position.y).abs() <= DOUBLE_CLICK_DISTANCE =>
+            {
+                self.click_count + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some(Click { button, target, position, at: now });
+    }
+
+    pub(crate) fn release(&mut self, button: MouseButton) {
+        self.pressed.retain(|(d, _)| *d != button);
+    }
+
+    pub(crate) fn release_all(&mut self) {
+        self.pressed.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click_count() {
+        let mut pointer = PointerState::new();
+        let target = Some(ComponentId::new());
+        let now = Instant::now();
+        let at = Point { x: 10f32, y: 10f32 };
+        let later = |millis| now + Duration::from_millis(millis);
+        pointer.click(MouseButton::Left, target, at, now);
+        assert_eq!(pointer.click_count(), 1);
+        pointer.click(MouseButton::Left, target, Point { x: 12f32, y: 9f32 }, later(200));
+        assert_eq!(pointer.click_count(), 2);
+        pointer.click(MouseButton::Left, target, at, later(400));
+        assert_eq!(pointer.click_count(), 3);
+        // Too late
+        pointer.click(MouseButton::Left, target, at, later(1000));
+        assert_eq!(pointer.click_count(), 1);
+        // Too far away
+        pointer.click(MouseButton::Left, target, Point { x: 20f32, y: 10f32 }, later(1100));
+        assert_eq!(pointer.click_count(), 1);
+        // Another button
+        pointer.click(MouseButton::Right, target, Point { x: 20f32, y: 10f32 }, later(1200));
+        assert_eq!(pointer.click_count(), 1);
+        // Another component
+        let other = Some(ComponentId::new());
+        pointer.click(MouseButton::Right, other, Point { x: 20f32, y: 10f32 }, later(1300));
+        assert_eq!(pointer.click_count(), 1);
+    }
+}
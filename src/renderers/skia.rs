@@ -1,3 +1,5 @@
 mod renderer;
+mod text_metrics;
 
-pub use self::renderer::*;
\ No newline at end of file
+pub use self::renderer::*;
+pub use self::text_metrics::*;
\ No newline at end of file
@@ -1,5 +1,10 @@
-use crate::abstraction::{RenderSurface, Renderer};
+use crate::abstraction::{RenderSurface, Renderer, TextMetrics};
 use crate::color::Color;
+use crate::data::{Orientation, Transform};
+use crate::image::{Image, ImageInterpolation};
+use crate::renderers::skia::SkiaTextMetrics;
+use crate::text::TextStyle;
+use crate::visuals::PathCommand;
 use glutin::config::{ConfigTemplateBuilder, GlConfig};
 use glutin::context::{
     ContextApi, ContextAttributesBuilder, NotCurrentGlContext, PossiblyCurrentContext,
@@ -11,11 +16,13 @@ use skia_safe::gpu::ganesh::gl::backend_render_targets;
 use skia_safe::gpu::gl::FramebufferInfo;
 use skia_safe::gpu::SurfaceOrigin;
 use skia_safe::{gpu, Canvas, ColorType, Surface};
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::error::Error;
 use std::ffi::CString;
 use std::fmt::{Display, Formatter};
 use std::num::NonZeroU32;
+use std::rc::Rc;
 use skia_safe::colors::WHITE;
 use winit::dpi::{LogicalSize, Size};
 use winit::event_loop::{ActiveEventLoop, EventLoop};
@@ -57,28 +64,178 @@ pub struct SkiaRenderer {
     gr_context: skia_safe::gpu::DirectContext,
     gl_context: PossiblyCurrentContext,
     window: Window,
+    text_metrics: Rc<SkiaTextMetrics>,
+    /// The converted images by their id, along with whether they were drawn in the current frame.
+    images: HashMap<u64, (sb::Image, bool)>,
 }
 #[cfg(feature = "disable")]
 pub struct SkiaRenderer {
     pub surface: Surface,
 }
 
+fn to_path(commands: &[PathCommand]) -> sb::Path {
+    let mut path = sb::Path::new();
+    for command in commands {
+        match *command {
+            PathCommand::MoveTo(p) => path.move_to((p.x, p.y)),
+            PathCommand::LineTo(p) => path.line_to((p.x, p.y)),
+            PathCommand::QuadTo(c, p) => path.quad_to((c.x, c.y), (p.x, p.y)),
+            PathCommand::CubicTo(c1, c2, p) => path.cubic_to((c1.x, c1.y), (c2.x, c2.y), (p.x, p.y)),
+            PathCommand::Close => path.close(),
+        };
+    }
+    path
+}
+
+fn to_color4f(color: Color) -> sb::Color4f {
+    sb::Color4f::new(
+        color.red() as f32 / 255f32,
+        color.green() as f32 / 255f32,
+        color.blue() as f32 / 255f32,
+        color.alpha() as f32 / 255f32,
+    )
+}
+
 impl Renderer for SkiaRenderer {
     fn draw_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, fill: Color) {
         let rect = sb::Rect::new(x, y, width + x, height + y);
-        let color = sb::Color4f::new(
-            fill.red() as f32 / 255f32,
-            fill.green() as f32 / 255f32,
-            fill.blue() as f32 / 255f32,
-            fill.alpha() as f32 / 255f32,
+        let paint = sb::Paint::new(&to_color4f(fill), None);
+        self.canvas().draw_rect(&rect, &paint);
+    }
+    fn stroke_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, stroke: Color, thickness: f32) {
+        let rect = sb::Rect::new(x, y, width + x, height + y);
+        let mut paint = sb::Paint::new(&to_color4f(stroke), None);
+        paint.set_style(sb::PaintStyle::Stroke);
+        paint.set_stroke_width(thickness);
+        self.canvas().draw_rect(&rect, &paint);
+    }
+    fn draw_rounded_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32, fill: Color) {
+        let rect = sb::RRect::new_rect_xy(sb::Rect::new(x, y, width + x, height + y), radius, radius);
+        let mut paint = sb::Paint::new(&to_color4f(fill), None);
+        paint.set_anti_alias(true);
+        self.canvas().draw_rrect(rect, &paint);
+    }
+    fn stroke_rounded_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32, stroke: Color, thickness: f32) {
+        let rect = sb::RRect::new_rect_xy(sb::Rect::new(x, y, width + x, height + y), radius, radius);
+        let mut paint = sb::Paint::new(&to_color4f(stroke), None);
+        paint.set_anti_alias(true);
+        paint.set_style(sb::PaintStyle::Stroke);
+        paint.set_stroke_width(thickness);
+        self.canvas().draw_rrect(rect, &paint);
+    }
+    fn draw_text(&mut self, x: f32, y: f32, text: &str, style: &TextStyle) {
+        let font = self.text_metrics.font(style);
+        let mut paint = sb::Paint::new(&to_color4f(style.color), None);
+        paint.set_anti_alias(true);
+        self.canvas().draw_str(text, (x, y), &font, &paint);
+    }
+    fn fill_path(&mut self, commands: &[PathCommand], fill: Color) {
+        let mut paint = sb::Paint::new(&to_color4f(fill), None);
+        paint.set_anti_alias(true);
+        self.canvas().draw_path(&to_path(commands), &paint);
+    }
+    fn stroke_path(&mut self, commands: &[PathCommand], stroke: Color, thickness: f32) {
+        let mut paint = sb::Paint::new(&to_color4f(stroke), None);
+        paint.set_anti_alias(true);
+        paint.set_style(sb::PaintStyle::Stroke);
+        paint.set_stroke_width(thickness);
+        self.canvas().draw_path(&to_path(commands), &paint);
+    }
+    fn draw_linear_gradient(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        orientation: Orientation,
+        colors: &[Color],
+    ) {
+        let end = match orientation {
+            Orientation::Horizontal => (x + width, y),
+            Orientation::Vertical => (x, y + height),
+        };
+        let colors: Vec<sb::Color4f> = colors.iter().map(|d| to_color4f(*d)).collect();
+        let shader = sb::Shader::linear_gradient(
+            ((x, y), end),
+            colors.as_slice(),
+            None,
+            sb::TileMode::Clamp,
+            None,
+            None,
         );
-        let paint = sb::Paint::new(&color, None);
+        let mut paint = sb::Paint::default();
+        paint.set_shader(shader);
+        let rect = sb::Rect::new(x, y, width + x, height + y);
         self.canvas().draw_rect(&rect, &paint);
     }
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        interpolation: ImageInterpolation,
+    ) {
+        if !self.images.contains_key(&image.id()) {
+            let info = sb::ImageInfo::new(
+                (image.width() as i32, image.height() as i32),
+                ColorType::RGBA8888,
+                sb::AlphaType::Unpremul,
+                None,
+            );
+            let data = sb::Data::new_copy(image.bytes());
+            match sb::images::raster_from_data(&info, data, image.width() * 4) {
+                None => return,
+                Some(d) => self.images.insert(image.id(), (d, true)),
+            };
+        }
+        let filter = match interpolation {
+            ImageInterpolation::NearestNeighbor => sb::FilterMode::Nearest,
+            ImageInterpolation::Linear => sb::FilterMode::Linear,
+        };
+        let sampling = sb::SamplingOptions::new(filter, sb::MipmapMode::None);
+        let rect = sb::Rect::new(x, y, width + x, height + y);
+        let paint = sb::Paint::default();
+        let surface_image = match self.images.get_mut(&image.id()) {
+            None => return,
+            Some(d) => {
+                d.1 = true;
+                d.0.clone()
+            }
+        };
+        self.canvas().draw_image_rect_with_sampling_options(surface_image, None, rect, sampling, &paint);
+    }
     fn translate(&mut self, x: f32, y: f32) {
         let rect = sb::Vector::new(x, y);
         self.canvas().translate(rect);
     }
+    fn push_transform(&mut self, transform: Transform) {
+        let matrix = sb::Matrix::new_all(
+            transform.m11,
+            transform.m21,
+            transform.dx,
+            transform.m12,
+            transform.m22,
+            transform.dy,
+            0f32,
+            0f32,
+            1f32,
+        );
+        self.canvas().save();
+        self.canvas().concat(&matrix);
+    }
+    fn pop_transform(&mut self) {
+        self.canvas().restore();
+    }
+    fn push_clip(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let rect = sb::Rect::from_xywh(x, y, width, height);
+        self.canvas().save();
+        self.canvas().clip_rect(rect, sb::ClipOp::Intersect, true);
+    }
+    fn pop_clip(&mut self) {
+        self.canvas().restore();
+    }
 }
 
 impl RenderSurface for SkiaRenderer {
@@ -223,6 +380,8 @@ impl RenderSurface for SkiaRenderer {
             gr_context,
             gl_context,
             window,
+            text_metrics: Rc::new(SkiaTextMetrics::new()),
+            images: HashMap::new(),
         }))
     }
 
@@ -230,6 +389,14 @@ impl RenderSurface for SkiaRenderer {
         self.window.id()
     }
 
+    fn window(&self) -> &Window {
+        &self.window
+    }
+
+    fn text_metrics(&self) -> Rc<dyn TextMetrics> {
+        self.text_metrics.clone()
+    }
+
     fn start_rendering(&mut self) {
         self.canvas().reset_matrix();
         self.canvas().clear(WHITE);
@@ -240,6 +407,8 @@ impl RenderSurface for SkiaRenderer {
     }
 
     fn finish_rendering(&mut self) {
+        // Images not drawn in this frame are released
+        self.images.retain(|_, d| std::mem::replace(&mut d.1, false));
         self.gr_context.flush_and_submit();
         self.gl_surface.swap_buffers(&self.gl_context).unwrap();
     }
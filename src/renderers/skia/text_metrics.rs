@@ -0,0 +1,67 @@
+use crate::abstraction::TextMetrics;
+use crate::text::{FontMetrics, FontWeight, TextStyle};
+use skia_safe as sb;
+use skia_safe::font_style::{Slant, Weight, Width};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// # Description
+/// Text metrics backed by skia fonts, matching the text drawn by the skia renderer.
+pub struct SkiaTextMetrics {
+    font_mgr: sb::FontMgr,
+    /// Resolved typefaces, keyed by family, weight and whether they are italic.
+    typefaces: RefCell<HashMap<(String, FontWeight, bool), Option<sb::Typeface>>>,
+}
+
+impl SkiaTextMetrics {
+    pub fn new() -> SkiaTextMetrics {
+        SkiaTextMetrics {
+            font_mgr: sb::FontMgr::new(),
+            typefaces: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// # Description
+    /// Creates the skia font used to draw text in *style*.
+    pub fn font(&self, style: &TextStyle) -> sb::Font {
+        let key = (style.font_family.clone(), style.weight, style.italic);
+        let mut typefaces = self.typefaces.borrow_mut();
+        let typeface = typefaces.entry(key).or_insert_with(|| {
+            let font_style = sb::FontStyle::new(
+                Weight::from(style.weight.value()),
+                Width::NORMAL,
+                if style.italic {
+                    Slant::Italic
+                } else {
+                    Slant::Upright
+                },
+            );
+            self.font_mgr
+                .match_family_style(&style.font_family, font_style)
+                .or_else(|| self.font_mgr.legacy_make_typeface(None, font_style))
+        });
+        match typeface {
+            Some(d) => sb::Font::from_typeface(d.clone(), style.font_size),
+            None => {
+                let mut font = sb::Font::default();
+                font.set_size(style.font_size);
+                font
+            }
+        }
+    }
+}
+
+impl TextMetrics for SkiaTextMetrics {
+    fn font_metrics(&self, style: &TextStyle) -> FontMetrics {
+        let (line_spacing, metrics) = self.font(style).metrics();
+        FontMetrics {
+            ascent: -metrics.ascent,
+            descent: metrics.descent,
+            line_height: line_spacing,
+        }
+    }
+
+    fn measure_text(&self, text: &str, style: &TextStyle) -> f32 {
+        self.font(style).measure_str(text, None).0
+    }
+}
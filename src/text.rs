@@ -0,0 +1,160 @@
+use crate::abstraction::TextMetrics;
+use crate::color::Color;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum FontWeight {
+    Light,
+    Normal,
+    SemiBold,
+    Bold,
+}
+
+impl FontWeight {
+    /// # Description
+    /// The numeric weight, as used by OpenType (`400` being normal, `700` being bold).
+    pub fn value(&self) -> i32 {
+        match self {
+            FontWeight::Light => 300,
+            FontWeight::Normal => 400,
+            FontWeight::SemiBold => 600,
+            FontWeight::Bold => 700,
+        }
+    }
+}
+
+/// # Description
+/// Describes how text is drawn.
+#[derive(Clone, Debug)]
+pub struct TextStyle {
+    /// The name of the font family. Unknown families fall back to the default font.
+    pub font_family: String,
+    /// The size of the font in pixels.
+    pub font_size: f32,
+    pub weight: FontWeight,
+    pub italic: bool,
+    pub color: Color,
+}
+
+impl TextStyle {
+    pub fn new() -> TextStyle {
+        TextStyle {
+            font_family: "sans-serif".to_string(),
+            font_size: 14f32,
+            weight: FontWeight::Normal,
+            italic: false,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// # Description
+/// A piece of text drawn in a single style, eg. a bold word in a sentence.
+#[derive(Clone, Debug)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+impl TextSpan {
+    pub fn new(text: &str, style: TextStyle) -> TextSpan {
+        TextSpan {
+            text: text.to_string(),
+            style,
+        }
+    }
+}
+
+/// # Description
+/// How text is broken into lines if it does not fit the available width.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextWrapping {
+    /// Text is only broken at explicit line breaks.
+    NoWrap,
+    /// Text is broken between words. Words longer than a line are broken between characters.
+    Word,
+    /// Text is broken between any two characters.
+    Character,
+}
+
+/// # Description
+/// How text is shortened if it does not fit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextTrimming {
+    /// Text overflowing is cut off.
+    None,
+    /// Text overflowing is replaced by an ellipsis (`…`).
+    Ellipsis,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl TextAlignment {
+    /// # Description
+    /// The offset of a line of *width* in a space of *available* width.
+    pub fn offset(&self, width: f32, available: f32) -> f32 {
+        match self {
+            TextAlignment::Left => 0f32,
+            TextAlignment::Center => (available - width) / 2f32,
+            TextAlignment::Right => available - width,
+        }
+    }
+}
+
+/// # Description
+/// Vertical metrics of a font.
+#[derive(Clone, Copy, Debug)]
+pub struct FontMetrics {
+    /// The distance from the top of a line to the baseline.
+    pub ascent: f32,
+    /// The distance from the baseline to the bottom of a line.
+    pub descent: f32,
+    /// The recommended distance between the baselines of two lines.
+    pub line_height: f32,
+}
+
+/// # Description
+/// Text metrics estimating the size of text from the font size alone.
+///
+/// # Remarks
+/// Used until a render surface provided the actual metrics, eg. when laying out components
+/// before any window was created.
+pub struct EstimatedTextMetrics {}
+
+impl TextMetrics for EstimatedTextMetrics {
+    fn font_metrics(&self, style: &TextStyle) -> FontMetrics {
+        FontMetrics {
+            ascent: style.font_size * 0.8f32,
+            descent: style.font_size * 0.2f32,
+            line_height: style.font_size * 1.2f32,
+        }
+    }
+
+    fn measure_text(&self, text: &str, style: &TextStyle) -> f32 {
+        text.chars().count() as f32 * style.font_size * 0.5f32
+    }
+}
+
+thread_local! {
+    static TEXT_METRICS: RefCell<Rc<dyn TextMetrics>> = RefCell::new(Rc::new(EstimatedTextMetrics {}));
+}
+
+/// # Description
+/// The text metrics used to measure text during layout.
+///
+/// # Remarks
+/// Components have no access to a renderer while being measured. Instead, windows install the
+/// metrics of their render surface before every layout pass.
+pub fn text_metrics() -> Rc<dyn TextMetrics> {
+    TEXT_METRICS.with(|metrics| metrics.borrow().clone())
+}
+
+pub(crate) fn set_text_metrics(metrics: Rc<dyn TextMetrics>) {
+    TEXT_METRICS.with(|d| *d.borrow_mut() = metrics);
+}
@@ -0,0 +1,318 @@
+use crate::abstraction::{Component, Renderer, Visual};
+use crate::color::Color;
+use crate::components::TextBlock;
+use crate::data::{ComponentId, Rectangle, Size, Thickness};
+use crate::text::TextWrapping;
+use crate::visuals;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// The maximum width of a text tool tip, wider text is wrapped.
+const MAX_TEXT_WIDTH: f32 = 320f32;
+
+/// # Description
+/// What a tool tip displays.
+#[derive(Clone)]
+pub enum ToolTipContent {
+    /// Text, wrapped if it is too long.
+    Text(String),
+    /// A component, created every time the tool tip is shown.
+    Component(Rc<dyn Fn() -> Box<dyn Component>>),
+}
+
+/// # Description
+/// A tool tip of a component, shown next to the pointer after the component was hovered for
+/// a while.
+///
+/// # Remarks
+/// The tool tip is hidden when the pointer leaves the component, when the pointer is pressed
+/// and once *show_duration* elapsed. It is not shown again until the pointer left the
+/// component. See [`crate::abstraction::Component::tool_tip`].
+#[derive(Clone)]
+pub struct ToolTip {
+    pub content: ToolTipContent,
+    /// The time the component has to be hovered for until the tool tip is shown.
+    pub initial_delay: Duration,
+    /// The time the tool tip is shown for.
+    pub show_duration: Duration,
+}
+
+impl ToolTip {
+    pub fn new(content: ToolTipContent) -> ToolTip {
+        ToolTip {
+            content,
+            initial_delay: Duration::from_millis(500),
+            show_duration: Duration::from_secs(5),
+        }
+    }
+
+    /// # Description
+    /// Creates a tool tip displaying *text*.
+    pub fn text(text: &str) -> ToolTip {
+        ToolTip::new(ToolTipContent::Text(text.to_string()))
+    }
+
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn with_show_duration(mut self, show_duration: Duration) -> Self {
+        self.show_duration = show_duration;
+        self
+    }
+
+    /// # Description
+    /// Creates the component displaying the content of this tool tip.
+    pub(crate) fn create_content(&self) -> Box<dyn Component> {
+        match &self.content {
+            ToolTipContent::Text(text) => Box::new(TextToolTip::new(text)),
+            ToolTipContent::Component(create) => create(),
+        }
+    }
+}
+
+/// # Description
+/// Displays the text of a tool tip on a bordered background.
+struct TextToolTip {
+    id: ComponentId,
+    text: TextBlock,
+    padding: Thickness,
+    background: Color,
+    border: Color,
+    size: Size<f32>,
+}
+
+impl Component for TextToolTip {
+    fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn measure(&self, available: Size<f32>) -> Size<f32> {
+        let text = self.text.measure(Size {
+            width: f32::min(available.width, MAX_TEXT_WIDTH) - self.padding.horizontal(),
+            height: f32::INFINITY,
+        });
+        Size {
+            width: text.width + self.padding.horizontal(),
+            height: text.height + self.padding.vertical(),
+        }
+    }
+
+    fn arrange(&mut self, given: Size<f32>) -> Size<f32> {
+        self.text.arrange(Size {
+            width: f32::max(0f32, given.width - self.padding.horizontal()),
+            height: f32::max(0f32, given.height - self.padding.vertical()),
+        });
+        self.size = given;
+        self.size
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) {
+        visuals::RoundedRectangle {
+            x: 0f32,
+            y: 0f32,
+            width: self.size.width,
+            height: self.size.height,
+            radius: 3f32,
+            fill: self.background,
+            stroke: self.border,
+            thickness: 1f32,
+        }
+        .draw(renderer);
+        renderer.translate(self.padding.left, self.padding.top);
+        self.text.render(renderer);
+        renderer.translate(-self.padding.left, -self.padding.top);
+    }
+
+    fn visit_children(&self, visitor: &mut dyn FnMut(Rectangle<f32>, &dyn Component)) {
+        visitor(self.text_bounds(), &self.text);
+    }
+
+    fn visit_children_mut(
+        &mut self,
+        visitor: &mut dyn FnMut(Rectangle<f32>, &mut dyn Component),
+    ) {
+        visitor(self.text_bounds(), &mut self.text);
+    }
+}
+
+impl TextToolTip {
+    fn new(text: &str) -> TextToolTip {
+        let mut text_block = TextBlock::new();
+        text_block.text = text.to_string();
+        text_block.wrapping = TextWrapping::Word;
+        TextToolTip {
+            id: ComponentId::new(),
+            text: text_block,
+            padding: Thickness { left: 6f32, top: 3f32, right: 6f32, bottom: 3f32 },
+            background: Color::WHITE,
+            border: Color::GRAY,
+            size: Size { width: 0f32, height: 0f32 },
+        }
+    }
+
+    fn text_bounds(&self) -> Rectangle<f32> {
+        Rectangle {
+            x: self.padding.left,
+            y: self.padding.top,
+            width: f32::max(0f32, self.size.width - self.padding.horizontal()),
+            height: f32::max(0f32, self.size.height - self.padding.vertical()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ToolTipState {
+    Idle,
+    /// The tool tip of *owner* is shown at *at*.
+    Pending { owner: ComponentId, at: Instant },
+    /// The tool tip of *owner* is shown in the overlay *overlay* until *until*.
+    Shown { owner: ComponentId, overlay: ComponentId, until: Instant },
+    /// The tool tip of *owner* was hidden and is not shown again while *owner* is hovered.
+    Hidden { owner: ComponentId },
+}
+
+/// # Description
+/// Keeps track of when to show and hide the tool tip of the hovered component of a window.
+pub(crate) struct ToolTipService {
+    state: ToolTipState,
+}
+
+impl ToolTipService {
+    pub(crate) fn new() -> ToolTipService {
+        ToolTipService { state: ToolTipState::Idle }
+    }
+
+    /// # Description
+    /// The component whose tool tip is pending, shown or hidden.
+    pub(crate) fn owner(&self) -> Option<ComponentId> {
+        match self.state {
+            ToolTipState::Idle => None,
+            ToolTipState::Pending { owner, .. }
+            | ToolTipState::Shown { owner, .. }
+            | ToolTipState::Hidden { owner } => Some(owner),
+        }
+    }
+
+    /// # Description
+    /// Updates the hovered component with a tool tip.
+    ///
+    /// # Parameters
+    /// - **owner**: The innermost hovered component with a tool tip, along with the time to
+    ///              show it at.
+    ///
+    /// # Returns
+    /// The overlay of the tool tip to close, if the tool tip of another component was shown.
+    pub(crate) fn hover(&mut self, owner: Option<(ComponentId, Instant)>) -> Option<ComponentId> {
+        if owner.map(|d| d.0) == self.owner() {
+            return None;
+        }
+        let closed = self.shown();
+        self.state = match owner {
+            None => ToolTipState::Idle,
+            Some((owner, at)) => ToolTipState::Pending { owner, at },
+        };
+        closed
+    }
+
+    /// # Description
+    /// Hides the tool tip, eg. because the pointer was pressed.
+    ///
+    /// # Returns
+    /// The overlay of the tool tip to close, if it was shown.
+    pub(crate) fn hide(&mut self) -> Option<ComponentId> {
+        let closed = self.shown();
+        if let Some(owner) = self.owner() {
+            self.state = ToolTipState::Hidden { owner };
+        }
+        closed
+    }
+
+    /// # Description
+    /// The component whose tool tip is due to be shown at *now*.
+    pub(crate) fn due(&self, now: Instant) -> Option<ComponentId> {
+        match self.state {
+            ToolTipState::Pending { owner, at } if at <= now => Some(owner),
+            _ => None,
+        }
+    }
+
+    /// # Description
+    /// Records that the pending tool tip is shown in *overlay* until *until*.
+    pub(crate) fn show(&mut self, overlay: ComponentId, until: Instant) {
+        if let ToolTipState::Pending { owner, .. } = self.state {
+            self.state = ToolTipState::Shown { owner, overlay, until };
+        }
+    }
+
+    /// # Description
+    /// Hides the tool tip if it was shown for long enough at *now*.
+    ///
+    /// # Returns
+    /// The overlay of the tool tip to close.
+    pub(crate) fn expire(&mut self, now: Instant) -> Option<ComponentId> {
+        match self.state {
+            ToolTipState::Shown { until, .. } if until <= now => self.hide(),
+            _ => None,
+        }
+    }
+
+    /// # Description
+    /// The next point in time the tool tip is due to be shown or hidden at.
+    pub(crate) fn next_wake_up(&self) -> Option<Instant> {
+        match self.state {
+            ToolTipState::Pending { at, .. } => Some(at),
+            ToolTipState::Shown { until, .. } => Some(until),
+            _ => None,
+        }
+    }
+
+    fn shown(&self) -> Option<ComponentId> {
+        match self.state {
+            ToolTipState::Shown { overlay, .. } => Some(overlay),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_tip_service() {
+        let now = Instant::now();
+        let (owner, overlay) = (ComponentId::new(), ComponentId::new());
+        let mut service = ToolTipService::new();
+        assert_eq!(service.hover(Some((owner, now + Duration::from_millis(500)))), None);
+        assert_eq!(service.due(now), None);
+        assert_eq!(service.next_wake_up(), Some(now + Duration::from_millis(500)));
+        let now = now + Duration::from_millis(500);
+        assert_eq!(service.due(now), Some(owner));
+        service.show(overlay, now + Duration::from_secs(5));
+        assert_eq!(service.due(now), None);
+        assert_eq!(service.expire(now), None);
+        assert_eq!(service.expire(now + Duration::from_secs(5)), Some(overlay));
+        assert_eq!(service.next_wake_up(), None);
+        // Hovering the same component does not show the tool tip again
+        assert_eq!(service.hover(Some((owner, now))), None);
+        assert_eq!(service.due(now), None);
+        assert_eq!(service.hover(None), None);
+        assert_eq!(service.owner(), None);
+    }
+
+    #[test]
+    fn test_tool_tip_service_hide() {
+        let now = Instant::now();
+        let (first, second, overlay) = (ComponentId::new(), ComponentId::new(), ComponentId::new());
+        let mut service = ToolTipService::new();
+        service.hover(Some((first, now)));
+        service.show(overlay, now + Duration::from_secs(5));
+        assert_eq!(service.hover(Some((second, now))), Some(overlay));
+        assert_eq!(service.due(now), Some(second));
+        assert_eq!(service.hide(), None);
+        assert_eq!(service.due(now), None);
+        assert_eq!(service.owner(), Some(second));
+    }
+}
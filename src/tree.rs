@@ -0,0 +1,267 @@
+use crate::abstraction::Component;
+use crate::data::{ComponentId, Point, Rectangle};
+use crate::focus::{FocusScope, Focusable};
+use crate::overlay::PopupPlacement;
+
+/// # Description
+/// A single component of a [`TreeSnapshot`].
+pub(crate) struct TreeNode {
+    pub(crate) id: ComponentId,
+    pub(crate) parent: Option<usize>,
+    /// The bounds of the component, relative to the window.
+    pub(crate) bounds: Rectangle<f32>,
+    pub(crate) focusable: Option<Focusable>,
+    pub(crate) focus_scope: Option<FocusScope>,
+    pub(crate) animating: bool,
+    /// Whether the component is a popup or an overlay, shown on top of the main tree.
+    pub(crate) popup: bool,
+    /// Whether the component can be hit by the pointer.
+    pub(crate) hit_test_visible: bool,
+}
+
+/// # Description
+/// A flattened copy of the layout of a component tree, in tree order (parents before their
+/// children, children in the order they are visited), followed by the popups of the tree.
+///
+/// # Remarks
+/// Snapshots are cheap to take and must be taken again after every layout pass, as they do
+/// not follow changes of the tree.
+pub(crate) struct TreeSnapshot {
+    pub(crate) nodes: Vec<TreeNode>,
+}
+
+impl TreeSnapshot {
+    /// # Description
+    /// Takes a snapshot of the trees below *roots*.
+    ///
+    /// # Parameters
+    /// - **roots**: The root components, paired with their bounds relative to the window.
+    pub(crate) fn new<'a, I>(roots: I) -> TreeSnapshot
+    where
+        I: Iterator<Item = (Rectangle<f32>, &'a dyn Component)>,
+    {
+        let mut nodes = vec![];
+        for (bounds, root) in roots {
+            collect(&mut nodes, None, bounds, root);
+        }
+        TreeSnapshot { nodes }
+    }
+
+    /// # Description
+    /// Adds a popup or an overlay to the snapshot, on top of all nodes added before.
+    ///
+    /// # Parameters
+    /// - **owner**: The component showing the popup, which becomes its parent.
+    /// - **bounds**: The bounds of the popup, relative to the window.
+    /// - **hit_test_visible**: Whether the popup and its children can be hit by the pointer.
+    pub(crate) fn push_popup(
+        &mut self,
+        owner: Option<ComponentId>,
+        bounds: Rectangle<f32>,
+        popup: &dyn Component,
+        hit_test_visible: bool,
+    ) {
+        let parent = owner.and_then(|id| self.index_of(id));
+        let index = self.nodes.len();
+        collect(&mut self.nodes, parent, bounds, popup);
+        self.nodes[index].popup = true;
+        for node in self.nodes[index..].iter_mut() {
+            node.hit_test_visible = hit_test_visible;
+        }
+    }
+
+    pub(crate) fn index_of(&self, id: ComponentId) -> Option<usize> {
+        self.nodes.iter().position(|node| node.id == id)
+    }
+
+    /// # Description
+    /// The indices of all nodes from the root down to (and including) *index*.
+    pub(crate) fn path(&self, index: usize) -> Vec<usize> {
+        let mut path = vec![index];
+        let mut parent = self.nodes[index].parent;
+        while let Some(d) = parent {
+            path.push(d);
+            parent = self.nodes[d].parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// # Description
+    /// Whether *ancestor* is a (direct or indirect) parent of *index*.
+    pub(crate) fn is_ancestor(&self, ancestor: usize, index: usize) -> bool {
+        let mut parent = self.nodes[index].parent;
+        while let Some(d) = parent {
+            if d == ancestor {
+                return true;
+            }
+            parent = self.nodes[d].parent;
+        }
+        false
+    }
+
+    /// # Description
+    /// The innermost focus scope containing *index*, not counting *index* itself.
+    pub(crate) fn scope_of(&self, index: usize) -> Option<usize> {
+        let mut parent = self.nodes[index].parent;
+        while let Some(d) = parent {
+            if self.nodes[d].focus_scope.is_some() {
+                return Some(d);
+            }
+            parent = self.nodes[d].parent;
+        }
+        None
+    }
+
+    /// # Description
+    /// Finds the topmost, deepest component at *point*.
+    ///
+    /// # Remarks
+    /// A component is only hit if all of its parents are hit as well, so content moved out
+    /// of the bounds of its parent (eg. by scrolling) cannot be hit. Popups are hit regardless
+    /// of their parent, unless they are not hit test visible.
+    pub(crate) fn hit_test(&self, point: Point<f32>) -> Option<usize> {
+        let mut hit = vec![false; self.nodes.len()];
+        for (index, node) in self.nodes.iter().enumerate() {
+            hit[index] = node.hit_test_visible
+                && node.bounds.contains(point)
+                && (node.popup || node.parent.map_or(true, |d| hit[d]));
+        }
+        hit.iter().rposition(|d| *d)
+    }
+}
+
+fn collect(
+    nodes: &mut Vec<TreeNode>,
+    parent: Option<usize>,
+    bounds: Rectangle<f32>,
+    component: &dyn Component,
+) {
+    let index = nodes.len();
+    nodes.push(TreeNode {
+        id: component.id(),
+        parent,
+        bounds,
+        focusable: component.focusable(),
+        focus_scope: component.focus_scope(),
+        animating: component.is_animating(),
+        popup: false,
+        hit_test_visible: true,
+    });
+    component.visit_children(&mut |child_bounds, child| {
+        collect(nodes, Some(index), offset(bounds, child_bounds), child);
+    });
+}
+
+/// Moves *bounds*, relative to *parent*, into the space *parent* is relative to.
+fn offset(parent: Rectangle<f32>, bounds: Rectangle<f32>) -> Rectangle<f32> {
+    Rectangle {
+        x: parent.x + bounds.x,
+        y: parent.y + bounds.y,
+        width: bounds.width,
+        height: bounds.height,
+    }
+}
+
+/// # Description
+/// Invokes *f* with every popup shown by the tree below (and including) *component*, in the
+/// order they are displayed in. Popups shown by popups follow the popup showing them.
+///
+/// # Parameters
+/// - **f**: Invoked with the component showing the popup, the placement of the popup and
+///          the popup.
+pub(crate) fn visit_popups(
+    component: &dyn Component,
+    f: &mut dyn FnMut(&dyn Component, PopupPlacement, &dyn Component),
+) {
+    component.visit_children(&mut |_, child| visit_popups(child, &mut *f));
+    component.visit_popups(&mut |placement, popup| {
+        f(component, placement, popup);
+        visit_popups(popup, &mut *f);
+    });
+}
+
+/// # Description
+/// Mutable version of [`visit_popups`], passing the bounds of the component showing the
+/// popup along.
+///
+/// # Parameters
+/// - **bounds**: The bounds of *component*, relative to the window.
+/// - **f**: Invoked with the bounds of the component showing the popup, the placement of
+///          the popup and the popup. Returns the bounds of the popup, relative to the window.
+pub(crate) fn visit_popups_mut(
+    component: &mut dyn Component,
+    bounds: Rectangle<f32>,
+    f: &mut dyn FnMut(Rectangle<f32>, PopupPlacement, &mut dyn Component) -> Rectangle<f32>,
+) {
+    component.visit_children_mut(&mut |child_bounds, child| {
+        visit_popups_mut(child, offset(bounds, child_bounds), &mut *f);
+    });
+    component.visit_popups_mut(&mut |placement, popup| {
+        let popup_bounds = f(bounds, placement, &mut *popup);
+        visit_popups_mut(popup, popup_bounds, &mut *f);
+    });
+}
+
+/// # Description
+/// Invokes *f* with the component identified by *id*, if it is part of the tree below
+/// *component*.
+///
+/// # Returns
+/// Whether the component was found.
+pub(crate) fn find(
+    component: &dyn Component,
+    id: ComponentId,
+    f: &mut dyn FnMut(&dyn Component),
+) -> bool {
+    if component.id() == id {
+        f(component);
+        return true;
+    }
+    let mut found = false;
+    component.visit_children(&mut |_, child| {
+        if !found {
+            found = find(child, id, &mut *f);
+        }
+    });
+    component.visit_popups(&mut |_, popup| {
+        if !found {
+            found = find(popup, id, &mut *f);
+        }
+    });
+    found
+}
+
+/// # Description
+/// Mutable version of [`find`].
+pub(crate) fn find_mut(
+    component: &mut dyn Component,
+    id: ComponentId,
+    f: &mut dyn FnMut(&mut dyn Component),
+) -> bool {
+    if component.id() == id {
+        f(component);
+        return true;
+    }
+    let mut found = false;
+    component.visit_children_mut(&mut |_, child| {
+        if !found {
+            found = find_mut(child, id, &mut *f);
+        }
+    });
+    component.visit_popups_mut(&mut |_, popup| {
+        if !found {
+            found = find_mut(popup, id, &mut *f);
+        }
+    });
+    found
+}
+
+/// # Description
+/// Invokes *f* with every component of the tree below (and including) *component*,
+/// in tree order, including popups.
+pub(crate) fn visit_all_mut(component: &mut dyn Component, f: &mut dyn FnMut(&mut dyn Component)) {
+    f(component);
+    component.visit_children_mut(&mut |_, child| visit_all_mut(child, &mut *f));
+    component.visit_popups_mut(&mut |_, popup| visit_all_mut(popup, &mut *f));
+}
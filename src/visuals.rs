@@ -1,3 +1,15 @@
+mod image;
+mod linear_gradient;
+mod outline;
+mod path;
 mod rectangle;
+mod rounded_rectangle;
+mod text;
 
-pub use self::rectangle::*;
\ No newline at end of file
+pub use self::image::*;
+pub use self::linear_gradient::*;
+pub use self::outline::*;
+pub use self::path::*;
+pub use self::rectangle::*;
+pub use self::rounded_rectangle::*;
+pub use self::text::*;
\ No newline at end of file
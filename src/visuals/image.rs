@@ -0,0 +1,20 @@
+use crate::abstraction::{Renderer, Visual};
+use crate::image::{self, ImageInterpolation};
+
+// #[visual]
+pub struct Image<'a> {
+    pub x: f32,
+    pub y: f32,
+    /// The width the image is stretched to.
+    pub width: f32,
+    /// The height the image is stretched to.
+    pub height: f32,
+    pub image: &'a image::Image,
+    pub interpolation: ImageInterpolation,
+}
+
+impl Visual for Image<'_> {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.draw_image(self.image, self.x, self.y, self.width, self.height, self.interpolation);
+    }
+}
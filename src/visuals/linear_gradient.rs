@@ -0,0 +1,28 @@
+use crate::abstraction::{Renderer, Visual};
+use crate::color::Color;
+use crate::data::Orientation;
+
+// #[visual]
+pub struct LinearGradient<'a> {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Whether the gradient runs from left to right or from top to bottom.
+    pub orientation: Orientation,
+    /// The colors of the gradient, spaced evenly along it.
+    pub colors: &'a [Color],
+}
+
+impl Visual for LinearGradient<'_> {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.draw_linear_gradient(
+            self.x,
+            self.y,
+            self.width,
+            self.height,
+            self.orientation,
+            self.colors,
+        );
+    }
+}
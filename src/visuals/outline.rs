@@ -0,0 +1,18 @@
+use crate::abstraction::{Renderer, Visual};
+use crate::color::Color;
+
+// #[visual]
+pub struct Outline {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub stroke: Color,
+    pub thickness: f32,
+}
+
+impl Visual for Outline {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.stroke_rectangle(self.x, self.y, self.width, self.height, self.stroke, self.thickness);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::abstraction::{Renderer, Visual};
+use crate::color::Color;
+use crate::data::Point;
+
+/// # Description
+/// A single command of a path, eg. a line from the current point to another point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathCommand {
+    /// Starts a new figure at the point.
+    MoveTo(Point<f32>),
+    LineTo(Point<f32>),
+    /// A quadratic bezier curve, using the first point as control point.
+    QuadTo(Point<f32>, Point<f32>),
+    /// A cubic bezier curve, using the first two points as control points.
+    CubicTo(Point<f32>, Point<f32>, Point<f32>),
+    /// Closes the current figure with a line to its start.
+    Close,
+}
+
+// #[visual]
+pub struct Path<'a> {
+    /// The commands of the path, relative to the current translation.
+    pub commands: &'a [PathCommand],
+    pub fill: Color,
+    pub stroke: Color,
+    /// The thickness of the stroke. No stroke is drawn if this is `0`.
+    pub thickness: f32,
+}
+
+impl Visual for Path<'_> {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.fill_path(self.commands, self.fill);
+        if self.thickness > 0f32 {
+            renderer.stroke_path(self.commands, self.stroke, self.thickness);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::abstraction::{Renderer, Visual};
+use crate::color::Color;
+
+// #[visual]
+pub struct RoundedRectangle {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub radius: f32,
+    pub fill: Color,
+    pub stroke: Color,
+    /// The thickness of the stroke. No stroke is drawn if this is `0`.
+    pub thickness: f32,
+}
+
+impl Visual for RoundedRectangle {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.draw_rounded_rectangle(self.x, self.y, self.width, self.height, self.radius, self.fill);
+        if self.thickness > 0f32 {
+            renderer.stroke_rounded_rectangle(self.x, self.y, self.width, self.height, self.radius, self.stroke, self.thickness);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::abstraction::{Renderer, Visual};
+use crate::text::TextStyle;
+
+// #[visual]
+pub struct Text<'a> {
+    pub x: f32,
+    /// The baseline of the text.
+    pub y: f32,
+    pub text: &'a str,
+    pub style: &'a TextStyle,
+}
+
+impl<'a> Visual for Text<'a> {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.draw_text(self.x, self.y, self.text, self.style);
+    }
+}
@@ -1,12 +1,55 @@
-use crate::abstraction::{Component, RenderSurface};
+use crate::abstraction::{Component, RenderSurface, Visual};
+use crate::color::Color;
+use crate::commands::{Command, CommandBinding};
+use crate::data::{ComponentId, Rectangle, Size};
+use crate::focus::{FocusDirection, FocusManager};
+use crate::input::{EventContext, InputEvent};
+use crate::overlay::{self, Overlay, OverlayRequest, Placement, PopupLayout, PopupPlacement};
+use crate::pointer::PointerState;
+use crate::text;
+use crate::tool_tip::{ToolTip, ToolTipService};
+use crate::tree::{self, TreeSnapshot};
+use crate::visuals;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::MouseButton;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
 use winit::window::WindowId;
-use crate::components;
-use crate::data::Size;
+
+/// The color of the focus visual drawn around the focused component.
+const FOCUS_VISUAL_COLOR: Color = Color::CORNFLOWER_BLUE;
+/// The time between two frames while a component is animating.
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+/// The distance between the pointer position and a tool tip below it, leaving room for the
+/// pointer itself.
+const TOOL_TIP_OFFSET: f32 = 20f32;
 
 pub struct AppWindow {
     pub(crate) id: Option<WindowId>,
     pub(crate) generation: usize,
     pub(crate) render_surface: Option<Box<dyn RenderSurface>>,
+    /// The currently pressed modifier keys.
+    pub(crate) modifiers: ModifiersState,
+    /// The earliest point in time a component requested to be woken up at.
+    pub(crate) next_wake_up: Option<Instant>,
+    pub focus_manager: FocusManager,
+    pub pointer: PointerState,
+    /// The key gestures bound to commands at the scope of this window.
+    pub command_bindings: Vec<CommandBinding>,
+    content: Vec<Box<dyn Component>>,
+    layout: Vec<Rectangle<f32>>,
+    /// The overlays shown on top of the content, in the order they are displayed in.
+    overlays: Vec<Overlay>,
+    /// The bounds of the popups of the content and of the overlays.
+    popup_layout: Vec<PopupLayout>,
+    tool_tips: ToolTipService,
+    /// The component to return the keyboard focus to, as an overlay containing it was closed.
+    restore_focus: Option<ComponentId>,
+    /// Whether Alt was pressed without another key since, see [`InputEvent::AccessKey`].
+    alt_pressed: bool,
+    /// The text input area last reported to the input method.
+    text_input_area: Option<Rectangle<f32>>,
 }
 
 impl AppWindow {
@@ -19,59 +62,1133 @@ impl AppWindow {
             id,
             render_surface,
             generation,
+            modifiers: ModifiersState::empty(),
+            next_wake_up: None,
+            focus_manager: FocusManager::new(),
+            pointer: PointerState::new(),
+            command_bindings: vec![],
+            content: vec![],
+            layout: vec![],
+            overlays: vec![],
+            popup_layout: vec![],
+            tool_tips: ToolTipService::new(),
+            restore_focus: None,
+            alt_pressed: false,
+            text_input_area: None,
         }
     }
 
-    pub fn render(&mut self) {
+    /// # Description
+    /// Adds *component* below the content of this window.
+    pub fn add_content(&mut self, component: Box<dyn Component>) {
+        self.content.push(component);
+    }
+
+    /// # Description
+    /// Measures and arranges the content of this window, stacking it vertically.
+    fn layout(&mut self) {
         const width: f32 = 100f32;
         const height: f32 = 100f32;
-        let render_surface = self.render_surface.as_mut().unwrap();
-        render_surface.start_rendering();
+        const spacing: f32 = 3f32;
+        self.layout.clear();
+        let mut y = 0f32;
+        if let Some(render_surface) = self.render_surface.as_ref() {
+            text::set_text_metrics(render_surface.text_metrics());
+        }
+        for component in self.content.iter_mut() {
+            y += spacing;
+            let desired = component.measure(Size { width, height });
+            let size = component.arrange(Size {
+                width,
+                height: f32::min(desired.height, height),
+            });
+            self.layout.push(Rectangle {
+                x: 0f32,
+                y,
+                width: size.width,
+                height: size.height,
+            });
+            y += size.height + spacing;
+        }
+        self.layout_popups();
+    }
+
+    /// # Description
+    /// Measures, arranges and places the popups of the content and the overlays, after the
+    /// content was arranged.
+    fn layout_popups(&mut self) {
+        let window = self.size();
+        let mut popups = vec![];
+        for (component, bounds) in self.content.iter_mut().zip(self.layout.iter()) {
+            tree::visit_popups_mut(&mut **component, *bounds, &mut |owner, placement, popup| {
+                place_popup(&mut popups, window, owner, placement, popup)
+            });
+        }
+        self.popup_layout = popups;
+
+        // Overlays are placed relative to the component showing them, which may be a popup
+        let tree = self.snapshot();
+        self.overlays.retain(|overlay| {
+            overlay.owner.map_or(true, |id| tree.index_of(id).is_some())
+        });
+        let mut popups = vec![];
+        for overlay in self.overlays.iter_mut() {
+            let owner = match overlay.owner.and_then(|id| tree.index_of(id)) {
+                Some(index) => tree.nodes[index].bounds,
+                None => Rectangle { x: 0f32, y: 0f32, width: window.width, height: window.height },
+            };
+            let bounds = place_popup(&mut popups, window, owner, overlay.placement, &mut *overlay.content);
+            tree::visit_popups_mut(&mut *overlay.content, bounds, &mut |owner, placement, popup| {
+                place_popup(&mut popups, window, owner, placement, popup)
+            });
+        }
+        self.popup_layout.extend(popups);
+    }
+
+    /// # Description
+    /// The size of the client area of this window.
+    fn size(&self) -> Size<f32> {
+        match self.render_surface.as_ref() {
+            None => Size { width: f32::INFINITY, height: f32::INFINITY },
+            Some(d) => {
+                let size = d.window().inner_size();
+                Size { width: size.width as f32, height: size.height as f32 }
+            }
+        }
+    }
+
+    /// # Description
+    /// Takes a snapshot of the component tree of this window, including popups and overlays,
+    /// as of the last layout pass.
+    fn snapshot(&self) -> TreeSnapshot {
+        let mut tree = TreeSnapshot::new(
+            self.layout
+                .iter()
+                .copied()
+                .zip(self.content.iter().map(|component| &**component)),
+        );
+        self.visit_popups(&mut |owner, bounds, popup, hit_test_visible| {
+            tree.push_popup(owner, bounds, popup, hit_test_visible);
+        });
+        tree
+    }
 
-        render_surface.translate(0f32, 3f32);
-        let mut scroll_bar = components::ScrollBar::new();
-        scroll_bar.max_value = 100f32;
-        scroll_bar.value = 0f32;
-        scroll_bar.bar_value = 40f32;
-        scroll_bar.thickness = 14f32;
-        scroll_bar.measure(Size { width, height });
-        scroll_bar.arrange(Size { width, height });
-        scroll_bar.render(render_surface.renderer());
-        render_surface.translate(0f32, 17f32);
-
-        render_surface.translate(0f32, 3f32);
-        let mut scroll_bar = components::ScrollBar::new();
-        scroll_bar.max_value = 100f32;
-        scroll_bar.value = 25f32;
-        scroll_bar.bar_value = 40f32;
-        scroll_bar.thickness = 14f32;
-        scroll_bar.measure(Size { width, height });
-        scroll_bar.arrange(Size { width, height });
-        scroll_bar.render(render_surface.renderer());
-        render_surface.translate(0f32, 17f32);
-
-        render_surface.translate(0f32, 3f32);
-        let mut scroll_bar = components::ScrollBar::new();
-        scroll_bar.max_value = 100f32;
-        scroll_bar.value = 50f32;
-        scroll_bar.bar_value = 40f32;
-        scroll_bar.thickness = 14f32;
-        scroll_bar.measure(Size { width, height });
-        scroll_bar.arrange(Size { width, height });
-        scroll_bar.render(render_surface.renderer());
-        render_surface.translate(0f32, 17f32);
-
-        render_surface.translate(0f32, 3f32);
-        let mut scroll_bar = components::ScrollBar::new();
-        scroll_bar.max_value = 100f32;
-        scroll_bar.value = 75f32;
-        scroll_bar.bar_value = 40f32;
-        scroll_bar.thickness = 14f32;
-        scroll_bar.measure(Size { width, height });
-        scroll_bar.arrange(Size { width, height });
-        scroll_bar.render(render_surface.renderer());
-        render_surface.translate(0f32, 17f32);
+    /// # Description
+    /// Invokes *f* with every popup of the content and every overlay, in the order they are
+    /// displayed in, along with the component showing them, their bounds and whether they
+    /// can be hit by the pointer.
+    ///
+    /// # Remarks
+    /// Popups shown after the last layout pass are skipped.
+    fn visit_popups(&self, f: &mut dyn FnMut(Option<ComponentId>, Rectangle<f32>, &dyn Component, bool)) {
+        let bounds_of = |id: ComponentId| {
+            self.popup_layout
+                .iter()
+                .find(|d| d.id == id)
+                .map(|d| d.bounds)
+        };
+        for component in self.content.iter() {
+            tree::visit_popups(&**component, &mut |owner, _, popup| {
+                if let Some(bounds) = bounds_of(popup.id()) {
+                    f(Some(owner.id()), bounds, popup, true);
+                }
+            });
+        }
+        for overlay in self.overlays.iter() {
+            let content = &*overlay.content;
+            let bounds = match bounds_of(content.id()) {
+                None => continue,
+                Some(d) => d,
+            };
+            f(overlay.owner, bounds, content, overlay.hit_test_visible);
+            tree::visit_popups(content, &mut |owner, _, popup| {
+                if let Some(bounds) = bounds_of(popup.id()) {
+                    f(Some(owner.id()), bounds, popup, overlay.hit_test_visible);
+                }
+            });
+        }
+    }
 
+    /// # Description
+    /// Invokes *f* with the component identified by *id*, searching the content, its popups
+    /// and the overlays.
+    ///
+    /// # Returns
+    /// Whether the component was found.
+    fn find(&self, id: ComponentId, f: &mut dyn FnMut(&dyn Component)) -> bool {
+        self.content
+            .iter()
+            .map(|component| &**component)
+            .chain(self.overlays.iter().map(|overlay| &*overlay.content))
+            .any(|component| tree::find(component, id, &mut *f))
+    }
+
+    /// # Description
+    /// Mutable version of *find*.
+    fn find_mut(&mut self, id: ComponentId, f: &mut dyn FnMut(&mut dyn Component)) -> bool {
+        self.content
+            .iter_mut()
+            .map(|component| &mut **component)
+            .chain(self.overlays.iter_mut().map(|overlay| &mut *overlay.content))
+            .any(|component| tree::find_mut(component, id, &mut *f))
+    }
+
+    pub fn render(&mut self) {
+        self.layout();
+        let tree = self.snapshot();
+        self.update_text_input(&tree);
+        if tree.nodes.iter().any(|d| d.animating) {
+            self.schedule_wake_up(Instant::now() + FRAME_INTERVAL);
+        }
+        let focus_visual = if self.focus_manager.is_focus_visible() {
+            self.focus_manager
+                .focused()
+                .and_then(|id| tree.index_of(id))
+                .filter(|index| tree.nodes[*index].focusable.map_or(true, |d| d.focus_visual))
+                .map(|index| tree.nodes[index].bounds)
+        } else {
+            None
+        };
+        let mut render_surface = match self.render_surface.take() {
+            None => return,
+            Some(d) => d,
+        };
+        render_surface.start_rendering();
+        for (component, bounds) in self.content.iter().zip(self.layout.iter()) {
+            render_surface.translate(bounds.x, bounds.y);
+            component.render(render_surface.renderer());
+            render_surface.translate(-bounds.x, -bounds.y);
+        }
+        self.visit_popups(&mut |_, bounds, popup, _| {
+            render_surface.translate(bounds.x, bounds.y);
+            popup.render(render_surface.renderer());
+            render_surface.translate(-bounds.x, -bounds.y);
+        });
+        if let Some(bounds) = focus_visual {
+            visuals::Outline {
+                x: bounds.x - 1f32,
+                y: bounds.y - 1f32,
+                width: bounds.width + 2f32,
+                height: bounds.height + 2f32,
+                stroke: FOCUS_VISUAL_COLOR,
+                thickness: 2f32,
+            }
+            .draw(render_surface.renderer());
+        }
         render_surface.finish_rendering();
+        self.render_surface = Some(render_surface);
+    }
+
+    /// # Description
+    /// Enables input methods while the focused component accepts text input and keeps their
+    /// candidate window next to the text being inserted.
+    fn update_text_input(&mut self, tree: &TreeSnapshot) {
+        let mut area = None;
+        if let Some(index) = self.focus_manager.focused().and_then(|id| tree.index_of(id)) {
+            let node = &tree.nodes[index];
+            self.find(node.id, &mut |d| area = d.text_input_area());
+            area = area.map(|d: Rectangle<f32>| Rectangle {
+                x: node.bounds.x + d.x,
+                y: node.bounds.y + d.y,
+                width: d.width,
+                height: d.height,
+            });
+        }
+        if area == self.text_input_area {
+            return;
+        }
+        let window = match self.render_surface.as_ref() {
+            None => return,
+            Some(d) => d.window(),
+        };
+        if area.is_some() != self.text_input_area.is_some() {
+            window.set_ime_allowed(area.is_some());
+        }
+        if let Some(d) = area {
+            window.set_ime_cursor_area(
+                PhysicalPosition::new(d.x, d.y),
+                PhysicalSize::new(d.width, d.height),
+            );
+        }
+        self.text_input_area = area;
+    }
+
+    /// # Description
+    /// Dispatches an input event to the content of this window.
+    ///
+    /// # Remarks
+    /// Pointer events are dispatched to the topmost component under the pointer, or, if a
+    /// component captured the pointer, to that component. After a pointer event, the hovered
+    /// components are updated, dispatching [`InputEvent::PointerLeft`] and
+    /// [`InputEvent::PointerEntered`]. Keyboard events are dispatched to
+    /// the focused component. Both bubble up to the parents of the component until one of
+    /// them handled the event. Unhandled keyboard events are used for focus navigation.
+    /// Ticks are dispatched to every component.
+    pub(crate) fn dispatch(&mut self, event: InputEvent) {
+        let tree = self.snapshot();
+        if let Some(id) = self.pointer.captured() {
+            if tree.index_of(id).is_none() {
+                self.pointer.set_capture(None);
+            }
+        }
+        if let Some(position) = event.position() {
+            self.pointer.moved(position);
+        }
+        let mut context = EventContext::new(
+            Instant::now(),
+            &self.pointer,
+            self.focus_manager.focused(),
+            self.modifiers,
+        );
+        let focused = self
+            .focus_manager
+            .focused()
+            .and_then(|id| tree.index_of(id));
+        let keyboard = match &event {
+            InputEvent::Tick => {
+                self.broadcast(&event, &mut context);
+                self.update_tool_tip(context.now());
+                false
+            }
+            InputEvent::FocusGained
+            | InputEvent::FocusLost
+            | InputEvent::LightDismiss
+            | InputEvent::OverlayClosed { .. } => false,
+            InputEvent::KeyPressed { key, modifiers, .. } => {
+                let mut handled = match focused {
+                    Some(index) => self.route(&tree, index, &event, &mut context).is_some(),
+                    None => false,
+                };
+                if !handled {
+                    handled = self.execute_gesture(&tree, focused, key, *modifiers);
+                }
+                if !handled && *key == Key::Named(NamedKey::Escape) {
+                    // Escape closes the topmost light-dismiss overlay
+                    let topmost = self.overlays.iter().rev().find(|d| d.light_dismiss);
+                    if let Some(id) = topmost.map(|d| d.content.id()) {
+                        self.dismiss_overlay(&tree, id, &mut context);
+                        handled = true;
+                    }
+                }
+                let context_menu_key = match key {
+                    Key::Named(NamedKey::ContextMenu) => true,
+                    Key::Named(NamedKey::F10) => modifiers.shift_key(),
+                    _ => false,
+                };
+                if let (false, true, Some(index)) = (handled, context_menu_key, focused) {
+                    let anchor = tree.nodes[index].bounds;
+                    handled = self.open_context_menu(&tree, index, anchor, true, &mut context);
+                }
+                let alt = *key == Key::Named(NamedKey::Alt);
+                if let (false, false, Key::Character(text)) = (handled, alt, key) {
+                    let mnemonic = text.chars().next().and_then(|d| d.to_lowercase().next());
+                    if let (true, Some(mnemonic)) = (modifiers.alt_key(), mnemonic) {
+                        context.broadcast(InputEvent::AccessKey { key: Some(mnemonic) });
+                        handled = true;
+                    }
+                }
+                self.alt_pressed = alt && !handled;
+                if !handled {
+                    self.navigate(&tree, key, *modifiers, &mut context);
+                }
+                true
+            }
+            InputEvent::KeyReleased { key, .. } => {
+                let handled = match focused {
+                    Some(index) => self.route(&tree, index, &event, &mut context).is_some(),
+                    None => false,
+                };
+                let alt = *key == Key::Named(NamedKey::Alt);
+                if alt && !handled && std::mem::take(&mut self.alt_pressed) {
+                    context.broadcast(InputEvent::AccessKey { key: None });
+                }
+                true
+            }
+            InputEvent::ImePreedit { .. } | InputEvent::ImeCommit { .. } => {
+                if let Some(index) = focused {
+                    self.route(&tree, index, &event, &mut context);
+                }
+                true
+            }
+            _ => {
+                let target = match context.pointer_capture.and_then(|id| tree.index_of(id)) {
+                    Some(index) => Some(index),
+                    None => event
+                        .position()
+                        .and_then(|position| tree.hit_test(position)),
+                };
+                if let InputEvent::PointerPressed { position, button } = &event {
+                    let id = target.map(|index| tree.nodes[index].id);
+                    self.pointer.click(*button, id, *position, context.now());
+                    context.click_count = self.pointer.click_count();
+                    self.alt_pressed = false;
+                    if let Some(id) = self.tool_tips.hide() {
+                        self.close_overlay(id);
+                    }
+                    self.light_dismiss(&tree, target, &mut context);
+                }
+                if let (Some(index), InputEvent::PointerPressed { .. }) = (target, &event) {
+                    // Clicking focuses the deepest focusable component under the pointer
+                    let focusable = tree
+                        .path(index)
+                        .into_iter()
+                        .rev()
+                        .find(|d| tree.nodes[*d].focusable.is_some());
+                    if let Some(focusable) = focusable {
+                        self.change_focus(
+                            &tree,
+                            Some(tree.nodes[focusable].id),
+                            false,
+                            &mut context,
+                        );
+                    }
+                }
+                let handled = match target {
+                    Some(index) => self.route(&tree, index, &event, &mut context),
+                    None => None,
+                };
+                if let (None, Some(index), InputEvent::PointerReleased { position, button: MouseButton::Right }) =
+                    (handled, target, &event)
+                {
+                    let anchor = Rectangle { x: position.x, y: position.y, width: 0f32, height: 0f32 };
+                    self.open_context_menu(&tree, index, anchor, false, &mut context);
+                }
+                let handler = handled.or(target);
+                match (&event, handler) {
+                    (InputEvent::PointerPressed { button, .. }, Some(index)) => {
+                        self.pointer.press(*button, tree.nodes[index].id)
+                    }
+                    (InputEvent::PointerReleased { button, .. }, _) => {
+                        self.pointer.release(*button)
+                    }
+                    _ => {}
+                }
+                self.update_hover(&tree, &mut context);
+                false
+            }
+        };
+        if let Some(id) = context.focus_request.take() {
+            self.change_focus(&tree, Some(id), keyboard, &mut context);
+        }
+        self.finish(context);
+    }
+
+    /// # Description
+    /// Dispatches an [`InputEvent::Tick`] if the requested wake-up time was reached.
+    pub(crate) fn wake_up(&mut self, now: Instant) {
+        match self.next_wake_up {
+            Some(at) if at <= now => {
+                self.next_wake_up = None;
+                self.dispatch(InputEvent::Tick);
+            }
+            _ => {}
+        }
+    }
+
+    /// # Description
+    /// Updates whether this window has the keyboard focus of the operating system,
+    /// notifying the focused component.
+    pub(crate) fn set_window_focused(&mut self, focused: bool) {
+        self.focus_manager.set_window_focused(focused);
+        let tree = self.snapshot();
+        let mut context = EventContext::new(
+            Instant::now(),
+            &self.pointer,
+            self.focus_manager.focused(),
+            self.modifiers,
+        );
+        if let Some(index) = self
+            .focus_manager
+            .focused()
+            .and_then(|id| tree.index_of(id))
+        {
+            let event = if focused {
+                InputEvent::FocusGained
+            } else {
+                InputEvent::FocusLost
+            };
+            self.deliver(&tree, index, &event, &mut context);
+        }
+        if !focused {
+            // Buttons released while the window is not focused are never reported
+            self.pointer.release_all();
+            if let Some(index) = context.pointer_capture.and_then(|id| tree.index_of(id)) {
+                context.pointer_capture = None;
+                self.deliver(&tree, index, &InputEvent::PointerCaptureLost, &mut context);
+            }
+        }
+        self.finish(context);
+    }
+
+    /// # Description
+    /// Notifies this window that the pointer left it.
+    ///
+    /// # Remarks
+    /// A pointer capture is kept, so drags continue to work outside of the window.
+    pub(crate) fn pointer_left(&mut self) {
+        self.pointer.left();
+        let tree = self.snapshot();
+        let mut context = EventContext::new(
+            Instant::now(),
+            &self.pointer,
+            self.focus_manager.focused(),
+            self.modifiers,
+        );
+        self.update_hover(&tree, &mut context);
+        self.finish(context);
+    }
+
+    /// # Description
+    /// Updates the hovered components after the pointer moved or the capture changed.
+    fn update_hover(&mut self, tree: &TreeSnapshot, context: &mut EventContext) {
+        let position = self.pointer.position();
+        let hovered = if !self.pointer.is_inside() {
+            None
+        } else {
+            match context.pointer_capture.and_then(|id| tree.index_of(id)) {
+                // While captured, only the capturing component can be hovered
+                Some(index) => Some(index).filter(|d| tree.nodes[*d].bounds.contains(position)),
+                None => tree.hit_test(position),
+            }
+        };
+        let path = hovered
+            .map(|index| {
+                tree.path(index)
+                    .into_iter()
+                    .map(|d| tree.nodes[d].id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let (left, entered) = self.pointer.set_hovered(path);
+        context.hovered = self.pointer.hovered_path().to_vec();
+        for index in left.into_iter().filter_map(|id| tree.index_of(id)) {
+            self.deliver(tree, index, &InputEvent::PointerLeft, context);
+        }
+        for index in entered.into_iter().filter_map(|id| tree.index_of(id)) {
+            self.deliver(tree, index, &InputEvent::PointerEntered, context);
+        }
+        self.update_tool_tip(context.now());
+    }
+
+    /// # Description
+    /// Schedules, shows or hides the tool tip of the innermost hovered component having one.
+    ///
+    /// # Remarks
+    /// Tool tips are shown below the pointer as an overlay not receiving pointer input.
+    fn update_tool_tip(&mut self, now: Instant) {
+        let mut owner = None;
+        for id in self.pointer.hovered_path().iter().rev() {
+            self.find(*id, &mut |d| {
+                owner = d.tool_tip().map(|tool_tip| (*id, now + tool_tip.initial_delay))
+            });
+            if owner.is_some() {
+                break;
+            }
+        }
+        if let Some(id) = self.tool_tips.hover(owner) {
+            self.close_overlay(id);
+        }
+        if let Some(id) = self.tool_tips.expire(now) {
+            self.close_overlay(id);
+        }
+        if let Some(owner) = self.tool_tips.due(now) {
+            let mut tool_tip: Option<ToolTip> = None;
+            self.find(owner, &mut |d| tool_tip = d.tool_tip().cloned());
+            match tool_tip {
+                // The component no longer has a tool tip
+                None => {
+                    self.tool_tips.hide();
+                }
+                Some(tool_tip) => {
+                    let position = self.pointer.position();
+                    let anchor = Rectangle {
+                        x: position.x,
+                        y: position.y,
+                        width: 0f32,
+                        height: TOOL_TIP_OFFSET,
+                    };
+                    let overlay = Overlay::new(
+                        tool_tip.create_content(),
+                        PopupPlacement::new(anchor, Placement::Bottom),
+                    )
+                    .with_light_dismiss(false)
+                    .with_hit_test_visible(false);
+                    let id = self.show_overlay(overlay);
+                    self.tool_tips.show(id, now + tool_tip.show_duration);
+                }
+            }
+        }
+        if let Some(at) = self.tool_tips.next_wake_up() {
+            self.schedule_wake_up(at);
+        }
+    }
+
+    /// # Description
+    /// Closes the light-dismiss overlays and dispatches [`InputEvent::LightDismiss`] to the
+    /// components showing popups, unless the pointer was pressed on *target* within them.
+    fn light_dismiss(
+        &mut self,
+        tree: &TreeSnapshot,
+        target: Option<usize>,
+        context: &mut EventContext,
+    ) {
+        let outside = |index: usize| target.map_or(true, |d| d != index && !tree.is_ancestor(index, d));
+        let dismissed: Vec<ComponentId> = self
+            .overlays
+            .iter()
+            .filter(|overlay| overlay.light_dismiss)
+            .map(|overlay| overlay.content.id())
+            .filter(|id| tree.index_of(*id).map_or(false, outside))
+            .collect();
+        for id in dismissed {
+            self.dismiss_overlay(tree, id, context);
+        }
+        let mut owners: Vec<usize> = tree
+            .nodes
+            .iter()
+            .filter(|node| node.popup && !self.overlays.iter().any(|d| d.content.id() == node.id))
+            .filter_map(|node| node.parent)
+            .filter(|owner| outside(*owner))
+            .collect();
+        owners.dedup();
+        for owner in owners {
+            self.deliver(tree, owner, &InputEvent::LightDismiss, context);
+        }
+    }
+
+    /// # Description
+    /// Closes the overlay showing *id*, dispatching [`InputEvent::OverlayClosed`] to the
+    /// component which showed it.
+    fn dismiss_overlay(&mut self, tree: &TreeSnapshot, id: ComponentId, context: &mut EventContext) {
+        let position = match self.overlays.iter().position(|d| d.content.id() == id) {
+            None => return,
+            Some(d) => d,
+        };
+        let overlay = self.remove_overlay(position);
+        if let Some(owner) = overlay.owner.and_then(|d| tree.index_of(d)) {
+            self.deliver(tree, owner, &InputEvent::OverlayClosed { id }, context);
+        }
+    }
+
+    /// # Description
+    /// Shows *overlay* on top of the content of this window.
+    ///
+    /// # Returns
+    /// The id of the content of the overlay, identifying the overlay.
+    pub fn show_overlay(&mut self, overlay: Overlay) -> ComponentId {
+        let id = overlay.content.id();
+        self.overlays.push(overlay);
+        id
+    }
+
+    pub fn close_overlay(&mut self, id: ComponentId) {
+        if let Some(position) = self.overlays.iter().position(|d| d.content.id() == id) {
+            self.remove_overlay(position);
+        }
+        if self.restore_focus.is_some() {
+            let context = EventContext::new(
+                Instant::now(),
+                &self.pointer,
+                self.focus_manager.focused(),
+                self.modifiers,
+            );
+            self.finish(context);
+        }
+    }
+
+    /// # Description
+    /// Removes the overlay at *position*, remembering where to return the focus to if the
+    /// overlay was focused.
+    fn remove_overlay(&mut self, position: usize) -> Overlay {
+        let overlay = self.overlays.remove(position);
+        if overlay.restore_focus.is_some() {
+            self.restore_focus = overlay.restore_focus;
+        }
+        overlay
+    }
+
+    /// # Description
+    /// Opens the context menu of the component at *index* or of its innermost parent having
+    /// one, moving the keyboard focus to it.
+    ///
+    /// # Parameters
+    /// - **anchor**: The area to open the context menu next to, relative to the window.
+    /// - **keyboard**: Whether the context menu was opened using the keyboard.
+    ///
+    /// # Returns
+    /// Whether a context menu was opened.
+    fn open_context_menu(
+        &mut self,
+        tree: &TreeSnapshot,
+        index: usize,
+        anchor: Rectangle<f32>,
+        keyboard: bool,
+        context: &mut EventContext,
+    ) -> bool {
+        for index in tree.path(index).into_iter().rev() {
+            let node = &tree.nodes[index];
+            let mut menu = None;
+            self.find(node.id, &mut |d| menu = d.context_menu());
+            let mut menu = match menu {
+                None => continue,
+                Some(d) => d,
+            };
+            if keyboard {
+                menu.highlight_first();
+            }
+            let anchor = Rectangle {
+                x: anchor.x - node.bounds.x,
+                y: anchor.y - node.bounds.y,
+                ..anchor
+            };
+            let mut overlay = Overlay::new(Box::new(menu), PopupPlacement::new(anchor, Placement::Bottom));
+            overlay.owner = Some(node.id);
+            overlay.restore_focus = self.focus_manager.focused();
+            let id = self.show_overlay(overlay);
+            // The menu is focused before being laid out, receiving key presses afterwards
+            self.change_focus(tree, Some(id), keyboard, context);
+            return true;
+        }
+        false
+    }
+
+    /// # Description
+    /// Moves the keyboard focus to *id*, notifying the components losing and gaining it.
+    pub fn focus(&mut self, id: Option<ComponentId>) {
+        let tree = self.snapshot();
+        let mut context = EventContext::new(
+            Instant::now(),
+            &self.pointer,
+            self.focus_manager.focused(),
+            self.modifiers,
+        );
+        self.change_focus(&tree, id, true, &mut context);
+        self.finish(context);
+    }
+
+    fn change_focus(
+        &mut self,
+        tree: &TreeSnapshot,
+        id: Option<ComponentId>,
+        visible: bool,
+        context: &mut EventContext,
+    ) {
+        let (lost, gained) = match self.focus_manager.set_focus(tree, id, visible) {
+            None => return,
+            Some(d) => d,
+        };
+        context.focused = gained;
+        if let Some(index) = lost.and_then(|id| tree.index_of(id)) {
+            self.deliver(tree, index, &InputEvent::FocusLost, context);
+        }
+        if let Some(index) = gained.and_then(|id| tree.index_of(id)) {
+            self.deliver(tree, index, &InputEvent::FocusGained, context);
+        }
+    }
+
+    /// # Description
+    /// Executes the command bound to a key gesture.
+    ///
+    /// # Remarks
+    /// The bindings of the focused component and its parents are searched first, innermost
+    /// first, followed by the bindings of the window. The first matching command which can be
+    /// executed is executed.
+    ///
+    /// # Returns
+    /// Whether a command was executed.
+    fn execute_gesture(
+        &self,
+        tree: &TreeSnapshot,
+        focused: Option<usize>,
+        key: &Key,
+        modifiers: ModifiersState,
+    ) -> bool {
+        let mut candidates: Vec<Rc<dyn Command>> = vec![];
+        let path = focused.map(|index| tree.path(index)).unwrap_or_default();
+        for index in path.into_iter().rev() {
+            let id = tree.nodes[index].id;
+            self.find(id, &mut |target| {
+                candidates.extend(
+                    target
+                        .command_bindings()
+                        .iter()
+                        .filter(|binding| binding.gesture.matches(key, modifiers))
+                        .map(|binding| binding.command.clone()),
+                );
+            });
+        }
+        candidates.extend(
+            self.command_bindings
+                .iter()
+                .filter(|binding| binding.gesture.matches(key, modifiers))
+                .map(|binding| binding.command.clone()),
+        );
+        match candidates.into_iter().find(|command| command.can_execute()) {
+            None => false,
+            Some(command) => {
+                command.execute();
+                true
+            }
+        }
+    }
+
+    /// # Description
+    /// Moves the focus according to an unhandled key press.
+    fn navigate(
+        &mut self,
+        tree: &TreeSnapshot,
+        key: &Key,
+        modifiers: ModifiersState,
+        context: &mut EventContext,
+    ) {
+        if modifiers.control_key() || modifiers.alt_key() || modifiers.super_key() {
+            return;
+        }
+        let next = match key {
+            Key::Named(NamedKey::Tab) => self
+                .focus_manager
+                .next_tab_stop(tree, modifiers.shift_key()),
+            Key::Named(NamedKey::ArrowUp) => self
+                .focus_manager
+                .next_in_direction(tree, FocusDirection::Up),
+            Key::Named(NamedKey::ArrowDown) => self
+                .focus_manager
+                .next_in_direction(tree, FocusDirection::Down),
+            Key::Named(NamedKey::ArrowLeft) => self
+                .focus_manager
+                .next_in_direction(tree, FocusDirection::Left),
+            Key::Named(NamedKey::ArrowRight) => self
+                .focus_manager
+                .next_in_direction(tree, FocusDirection::Right),
+            _ => return,
+        };
+        if next.is_some() {
+            self.change_focus(tree, next, true, context);
+        }
+    }
+
+    /// # Description
+    /// Dispatches *event* to the component at *index* and its parents, until it was handled.
+    ///
+    /// # Returns
+    /// The index of the component which handled the event.
+    fn route(
+        &mut self,
+        tree: &TreeSnapshot,
+        index: usize,
+        event: &InputEvent,
+        context: &mut EventContext,
+    ) -> Option<usize> {
+        for index in tree.path(index).into_iter().rev() {
+            if self.deliver(tree, index, event, context) {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// # Description
+    /// Dispatches *event* to the component at *index* only.
+    fn deliver(
+        &mut self,
+        tree: &TreeSnapshot,
+        index: usize,
+        event: &InputEvent,
+        context: &mut EventContext,
+    ) -> bool {
+        let node = &tree.nodes[index];
+        let id = node.id;
+        let event = event.translated(node.bounds.x, node.bounds.y);
+        let mut handled = false;
+        context.current = Some(id);
+        self.find_mut(id, &mut |target| {
+            handled = target.handle_event(&event, context);
+        });
+        context.current = None;
+        handled
+    }
+
+    /// # Description
+    /// Dispatches *event* to every component of this window.
+    fn broadcast(&mut self, event: &InputEvent, context: &mut EventContext) {
+        let components = self
+            .content
+            .iter_mut()
+            .map(|component| &mut **component)
+            .chain(self.overlays.iter_mut().map(|overlay| &mut *overlay.content));
+        for component in components {
+            tree::visit_all_mut(component, &mut |target| {
+                context.current = Some(target.id());
+                target.handle_event(event, context);
+            });
+        }
+        context.current = None;
+    }
+
+    /// # Description
+    /// Dispatches [`InputEvent::GroupChecked`] or [`InputEvent::GroupStepped`] to the members
+    /// of the group of the component sending it.
+    ///
+    /// # Remarks
+    /// Components of an unnamed group are grouped with their siblings, so separate containers
+    /// hold separate groups. Named groups span the whole window.
+    fn dispatch_to_group(&mut self, event: &InputEvent, context: &mut EventContext) {
+        let (group, id, forward) = match event {
+            InputEvent::GroupChecked { group, id } => (group, *id, None),
+            InputEvent::GroupStepped { group, id, forward } => (group, *id, Some(*forward)),
+            _ => return,
+        };
+        let tree = self.snapshot();
+        let sender = match tree.index_of(id) {
+            None => return,
+            Some(d) => d,
+        };
+        let parent = tree.nodes[sender].parent;
+        let mut members: Vec<usize> = (0..tree.nodes.len())
+            .filter(|d| !group.is_empty() || tree.nodes[*d].parent == parent)
+            .collect();
+        let forward = match forward {
+            None => {
+                for index in members {
+                    self.deliver(&tree, index, event, context);
+                }
+                return;
+            }
+            Some(d) => d,
+        };
+        // The members following the sender, wrapping around
+        let position = members.iter().position(|d| *d == sender).unwrap_or(0);
+        members.rotate_left(position + 1);
+        members.pop();
+        if !forward {
+            members.reverse();
+        }
+        for index in members {
+            if self.deliver(&tree, index, event, context) {
+                break;
+            }
+        }
+    }
+
+    /// # Description
+    /// Applies the requests components made during a dispatch.
+    fn finish(&mut self, mut context: EventContext) {
+        // Broadcast events may request further broadcasts, which are dispatched in turn
+        while !context.broadcasts.is_empty() {
+            let events = std::mem::take(&mut context.broadcasts);
+            for event in events.iter() {
+                match event {
+                    InputEvent::GroupChecked { .. } | InputEvent::GroupStepped { .. } => {
+                        self.dispatch_to_group(event, &mut context)
+                    }
+                    _ => self.broadcast(event, &mut context),
+                }
+            }
+        }
+        // Members of a group take the focus as an arrow key stepped to them
+        if let Some(id) = context.focus_request.take() {
+            let tree = self.snapshot();
+            let visible = self.focus_manager.is_focus_visible();
+            self.change_focus(&tree, Some(id), visible, &mut context);
+        }
+        for request in std::mem::take(&mut context.overlays) {
+            match request {
+                OverlayRequest::Show(overlay) => self.overlays.push(overlay),
+                OverlayRequest::Move(id, placement) => {
+                    for overlay in self.overlays.iter_mut().filter(|d| d.content.id() == id) {
+                        overlay.placement = placement;
+                    }
+                }
+                OverlayRequest::Close(id) => {
+                    if let Some(position) = self.overlays.iter().position(|d| d.content.id() == id) {
+                        self.remove_overlay(position);
+                    }
+                }
+            }
+        }
+        if let Some(id) = self.restore_focus.take() {
+            // Only if the focus was inside of the closed overlay, rather than moved elsewhere
+            let focused = self.focus_manager.focused();
+            if focused.map_or(true, |d| !self.find(d, &mut |_| {})) {
+                let tree = self.snapshot();
+                let visible = self.focus_manager.is_focus_visible();
+                self.change_focus(&tree, Some(id), visible, &mut context);
+            }
+        }
+        self.pointer.set_capture(context.pointer_capture);
+        if let Some(wake_up) = context.wake_up {
+            self.schedule_wake_up(wake_up);
+        }
+    }
+
+    /// # Description
+    /// Requests an [`InputEvent::Tick`] at *at*, unless one was requested earlier.
+    fn schedule_wake_up(&mut self, at: Instant) {
+        self.next_wake_up = match self.next_wake_up {
+            Some(existing) if existing <= at => Some(existing),
+            _ => Some(at),
+        };
+    }
+}
+
+/// # Description
+/// Measures, arranges and places *popup* next to its anchor.
+///
+/// # Parameters
+/// - **owner**: The bounds of the component showing the popup, relative to the window.
+///
+/// # Returns
+/// The bounds of the popup, relative to the window.
+fn place_popup(
+    popups: &mut Vec<PopupLayout>,
+    window: Size<f32>,
+    owner: Rectangle<f32>,
+    placement: PopupPlacement,
+    popup: &mut dyn Component,
+) -> Rectangle<f32> {
+    let desired = popup.measure(window);
+    let size = popup.arrange(Size {
+        width: f32::min(desired.width, window.width),
+        height: f32::min(desired.height, window.height),
+    });
+    let anchor = Rectangle {
+        x: owner.x + placement.anchor.x,
+        y: owner.y + placement.anchor.y,
+        width: placement.anchor.width,
+        height: placement.anchor.height,
+    };
+    let bounds = overlay::place(anchor, placement.placement, size, window);
+    popups.push(PopupLayout { id: popup.id(), bounds });
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{self, ContextMenu, MenuItem, SplitPane};
+
+    #[test]
+    fn test_wake_up() {
+        let mut window = AppWindow::new(None, None, 0);
+        let mut progress_bar = components::ProgressBar::new();
+        progress_bar.range.value = 40f32;
+        window.content = vec![Box::new(components::TextBlock::new()), Box::new(progress_bar)];
+        window.render();
+        window.dispatch(InputEvent::Tick);
+        assert_eq!(window.next_wake_up, None);
+
+        // Animations hidden in a collapsed expander do not wake the window up
+        let mut progress_bar = components::ProgressBar::new();
+        progress_bar.is_indeterminate = true;
+        let mut expander = components::Expander::new().with_child(Box::new(progress_bar));
+        expander.is_expanded = false;
+        window.content.push(Box::new(expander));
+        window.render();
+        assert_eq!(window.next_wake_up, None);
+
+        let mut progress_bar = components::ProgressBar::new();
+        progress_bar.is_indeterminate = true;
+        window.content.push(Box::new(progress_bar));
+        window.render();
+        assert!(window.next_wake_up.is_some());
+    }
+
+    #[test]
+    fn test_radio_groups() {
+        use std::cell::Cell;
+
+        let checked: Vec<Rc<Cell<bool>>> = (0..4).map(|_| Rc::new(Cell::new(false))).collect();
+        let radio_buttons: Vec<components::RadioButton> = checked
+            .iter()
+            .map(|checked| {
+                let mut radio_button = components::RadioButton::new();
+                let checked = checked.clone();
+                radio_button.on_checked_changed = Some(Box::new(move |d| checked.set(d)));
+                radio_button
+            })
+            .collect();
+        let ids: Vec<ComponentId> = radio_buttons.iter().map(|d| d.id()).collect();
+        let mut radio_buttons = radio_buttons.into_iter().map(|d| Box::new(d) as Box<dyn Component>);
+        let mut window = AppWindow::new(None, None, 0);
+        window.content = vec![radio_buttons.next().unwrap(), radio_buttons.next().unwrap()];
+        let mut split_panel = components::SplitPanel::new();
+        for radio_button in radio_buttons {
+            split_panel = split_panel.with_pane(SplitPane::new(radio_button));
+        }
+        window.content.push(Box::new(split_panel));
+        window.render();
+        let state = || checked.iter().map(|d| d.get()).collect::<Vec<_>>();
+        let press = |window: &mut AppWindow, key: NamedKey| {
+            window.dispatch(InputEvent::KeyPressed {
+                key: Key::Named(key),
+                text: None,
+                modifiers: ModifiersState::empty(),
+                repeat: false,
+            });
+            window.dispatch(InputEvent::KeyReleased { key: Key::Named(key), modifiers: ModifiersState::empty() });
+        };
+
+        window.focus(Some(ids[0]));
+        press(&mut window, NamedKey::Space);
+        assert_eq!(state(), vec![true, false, false, false]);
+        press(&mut window, NamedKey::ArrowDown);
+        assert_eq!(state(), vec![false, true, false, false]);
+        assert_eq!(window.focus_manager.focused(), Some(ids[1]));
+        // The last member wraps around to the first
+        press(&mut window, NamedKey::ArrowRight);
+        assert_eq!(state(), vec![true, false, false, false]);
+
+        // The radio buttons in the split panel form a group of their own
+        window.focus(Some(ids[2]));
+        press(&mut window, NamedKey::Space);
+        assert_eq!(state(), vec![true, false, true, false]);
+        press(&mut window, NamedKey::ArrowUp);
+        assert_eq!(state(), vec![true, false, false, true]);
+        assert_eq!(window.focus_manager.focused(), Some(ids[3]));
+    }
+
+    #[test]
+    fn test_light_dismiss() {
+        let item = |text: &str| {
+            let mut item = MenuItem::new();
+            item.text = text.to_string();
+            item
+        };
+        let mut file = item("_File");
+        file.submenu.items = vec![item("_New"), item("E_xit")];
+        let mut menu_bar = components::MenuBar::new();
+        menu_bar.items = vec![file];
+        let mut window = AppWindow::new(None, None, 0);
+        window.content = vec![Box::new(menu_bar), Box::new(components::TextBlock::new())];
+        window.render();
+        let click = |window: &mut AppWindow, x: f32, y: f32| {
+            let position = crate::data::Point { x, y };
+            window.dispatch(InputEvent::PointerPressed { position, button: MouseButton::Left });
+            window.dispatch(InputEvent::PointerReleased { position, button: MouseButton::Left });
+            window.render();
+        };
+        let popups = |window: &AppWindow| window.snapshot().nodes.iter().filter(|d| d.popup).count();
+
+        let header = window.layout[0];
+        click(&mut window, header.x + 5f32, header.y + 5f32);
+        assert_eq!(popups(&window), 1);
+        // Pressing within the submenu keeps it open
+        let submenu = window.popup_layout[0].bounds;
+        window.dispatch(InputEvent::PointerPressed {
+            position: crate::data::Point { x: submenu.x + 1f32, y: submenu.y + 1f32 },
+            button: MouseButton::Left,
+        });
+        assert_eq!(popups(&window), 1);
+        let below = window.layout[1];
+        click(&mut window, below.x + 5f32, below.y + below.height + 50f32);
+        assert_eq!(popups(&window), 0);
+
+        let anchor = Rectangle { x: 10f32, y: 60f32, width: 0f32, height: 0f32 };
+        let menu = ContextMenu::new(vec![item("_Copy"), item("_Paste")]);
+        window.show_overlay(Overlay::new(Box::new(menu), PopupPlacement::new(anchor, Placement::Bottom)));
+        window.render();
+        assert_eq!(window.overlays.len(), 1);
+        click(&mut window, 90f32, 5f32);
+        assert!(window.overlays.is_empty());
     }
 }